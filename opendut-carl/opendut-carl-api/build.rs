@@ -4,10 +4,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     std::env::set_var("PROTOC", protobuf_src::protoc());
 
     let protos = [
+        "proto/opendut/carl/services/audit-log.proto",
+        "proto/opendut/carl/services/backup.proto",
         "proto/opendut/carl/services/cluster-manager.proto",
         "proto/opendut/carl/services/metadata-provider.proto",
         "proto/opendut/carl/services/peer-manager.proto",
         "proto/opendut/carl/services/peer-messaging-broker.proto",
+        "proto/opendut/carl/services/reservation-manager.proto",
+        "proto/opendut/carl/services/service-account-manager.proto",
+        "proto/opendut/carl/services/webhook-manager.proto",
     ];
 
     let includes = [
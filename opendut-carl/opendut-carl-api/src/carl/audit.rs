@@ -0,0 +1,96 @@
+use std::time::SystemTime;
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::resources::Id;
+
+/// A record of a mutating operation, which was performed on CARL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub timestamp: SystemTime,
+    pub user: String,
+    pub operation: String,
+    pub resource_id: Id,
+    /// Summary of the resource before the operation, if it existed.
+    pub before: Option<String>,
+    /// Summary of the resource after the operation, if it still exists.
+    pub after: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    pub user: Option<String>,
+    pub resource_id: Option<Id>,
+    /// If set, only the latest entries up to this number are returned.
+    pub limit: Option<u32>,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct ListAuditEntriesError {
+    message: String,
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use crate::carl::audit::{AuditEntry, AuditQuery, ListAuditEntriesError};
+    use crate::proto::services::audit_log;
+    use crate::proto::services::audit_log::audit_log_client::AuditLogClient;
+
+    #[derive(Clone, Debug)]
+    pub struct AuditLog<T> {
+        inner: AuditLogClient<T>,
+    }
+
+    impl<T> AuditLog<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: AuditLogClient<T>) -> AuditLog<T> {
+            AuditLog { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> AuditLog<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = AuditLogClient::new(InterceptedService::new(inner, interceptor));
+            AuditLog {
+                inner: inner_client
+            }
+        }
+
+        pub async fn list_audit_entries(&mut self, query: AuditQuery) -> Result<Vec<AuditEntry>, ListAuditEntriesError> {
+            let request = tonic::Request::new(audit_log::ListAuditEntriesRequest::from(query));
+
+            match self.inner.list_audit_entries(request).await {
+                Ok(response) => {
+                    response.into_inner().entries.into_iter()
+                        .map(AuditEntry::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|cause| ListAuditEntriesError { message: format!("Conversion failed for list of audit entries: {cause}") })
+                },
+                Err(status) => {
+                    Err(ListAuditEntriesError { message: format!("gRPC failure: {status}") })
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use prost::Message;
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName};
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+use opendut_types::proto::ConversionError;
+use opendut_types::reservation::Reservation;
+use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccount, ServiceAccountId, ServiceAccountName};
+use opendut_types::webhook::Webhook;
+
+use crate::proto::services::backup;
+
+/// Version of the archive format, which is written and can be restored by this version of CARL.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 2;
+
+/// Snapshot of all resources of a CARL instance.
+/// Devices and executors are contained in the descriptors of their peers.
+///
+/// Issued setup-strings and peer certificates are deliberately not contained, since they are bound to the
+/// certificate authority and OIDC clients of the instance, which issued them. Peers have to be set up anew
+/// with setup-strings of the instance, the archive is restored into.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourcesArchive {
+    pub format_version: u32,
+    pub peer_descriptors: Vec<PeerDescriptor>,
+    pub cluster_configurations: Vec<ClusterConfiguration>,
+    pub cluster_deployments: Vec<ClusterDeployment>,
+    pub reservations: Vec<Reservation>,
+    pub service_accounts: Vec<ServiceAccount>,
+    /// Contain only the hashes of the tokens' secrets, so restored tokens remain valid.
+    pub api_tokens: Vec<ApiToken>,
+    pub webhooks: Vec<Webhook>,
+}
+
+impl ResourcesArchive {
+    /// Encodes the archive for writing it to a file.
+    pub fn encode(self) -> Vec<u8> {
+        backup::ResourcesArchive::from(self).encode_to_vec()
+    }
+
+    /// Decodes an archive, which was previously encoded with [`ResourcesArchive::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeResourcesArchiveError> {
+        let archive = backup::ResourcesArchive::decode(bytes)?;
+        Ok(Self::try_from(archive)?)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeResourcesArchiveError {
+    #[error("Archive is not a valid protobuf message: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+    #[error("Archive contains invalid resources: {0}")]
+    Conversion(#[from] ConversionError),
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct CreateBackupError {
+    message: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RestoreBackupError {
+    #[error("Archive in format version {actual_version} cannot be restored! Supported format version: {supported_version}")]
+    UnsupportedFormatVersion {
+        actual_version: u32,
+        supported_version: u32,
+    },
+    #[error("PeerDescriptor '{peer_name}' <{peer_id}> could not be restored:\n  {cause}")]
+    PeerDescriptor {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cause: String,
+    },
+    #[error("ClusterConfiguration '{cluster_name}' <{cluster_id}> could not be restored:\n  {cause}")]
+    ClusterConfiguration {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        cause: String,
+    },
+    #[error("ClusterDeployment <{cluster_id}> could not be restored:\n  {cause}")]
+    ClusterDeployment {
+        cluster_id: ClusterId,
+        cause: String,
+    },
+    #[error("ServiceAccount '{service_account_name}' <{service_account_id}> could not be restored:\n  {cause}")]
+    ServiceAccount {
+        service_account_id: ServiceAccountId,
+        service_account_name: ServiceAccountName,
+        cause: String,
+    },
+    #[error("ApiToken <{api_token_id}> could not be restored:\n  {cause}")]
+    ApiToken {
+        api_token_id: ApiTokenId,
+        cause: String,
+    },
+    #[error("Backup could not be restored, due to an internal error:\n  {cause}")]
+    Internal {
+        cause: String,
+    },
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::backup::{CreateBackupError, ResourcesArchive, RestoreBackupError};
+    use crate::proto::services::backup;
+    use crate::proto::services::backup::backup_client::BackupClient;
+
+    #[derive(Clone, Debug)]
+    pub struct Backup<T> {
+        inner: BackupClient<T>,
+    }
+
+    impl<T> Backup<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: BackupClient<T>) -> Backup<T> {
+            Backup { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> Backup<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = BackupClient::new(InterceptedService::new(inner, interceptor));
+            Backup {
+                inner: inner_client
+            }
+        }
+
+        pub async fn create_backup(&mut self) -> Result<ResourcesArchive, CreateBackupError> {
+            let request = tonic::Request::new(backup::CreateBackupRequest {});
+
+            match self.inner.create_backup(request).await {
+                Ok(response) => {
+                    let archive = response.into_inner().archive
+                        .ok_or_else(|| CreateBackupError { message: String::from("Response contains no archive.") })?;
+                    ResourcesArchive::try_from(archive)
+                        .map_err(|cause| CreateBackupError { message: format!("Conversion failed for archive: {cause}") })
+                },
+                Err(status) => {
+                    Err(CreateBackupError { message: format!("gRPC failure: {status}") })
+                },
+            }
+        }
+
+        pub async fn restore_backup(&mut self, archive: ResourcesArchive) -> Result<(), ClientError<RestoreBackupError>> {
+            let request = tonic::Request::new(backup::RestoreBackupRequest {
+                archive: Some(archive.into()),
+            });
+
+            let response = self.inner.restore_backup(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                backup::restore_backup_response::Reply::Failure(failure) => {
+                    let error = RestoreBackupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                backup::restore_backup_response::Reply::Success(_) => {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
@@ -91,6 +91,8 @@ mod client {
             use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
             use tonic::metadata::MetadataValue;
 
+            use opendut_types::peer::setup::PeerSetupNonce;
+
             pub type Downstream = tonic::Streaming<peer_messaging_broker::Downstream>;
             pub type Upstream = mpsc::Sender<peer_messaging_broker::Upstream>;
 
@@ -101,12 +103,14 @@ mod client {
                   <T::ResponseBody as Body>::Error: Into<StdError> + Send,
             {
 
-                pub async fn open_stream(&mut self, id: PeerId, remote_address: &IpAddr) -> Result<(Downstream, Upstream), error::OpenStream> {
+                /// Opens the stream of the peer. The nonce of its setup-string is consumed by CARL on the first connect.
+                pub async fn open_stream(&mut self, id: PeerId, setup_nonce: PeerSetupNonce, remote_address: &IpAddr) -> Result<(Downstream, Upstream), error::OpenStream> {
                     let (tx, rx) = mpsc::channel(1024);
 
                     let response = {
                         let mut request = tonic::Request::new(ReceiverStream::new(rx));
                         request.metadata_mut().insert("id", MetadataValue::from_str(&id.to_string()).unwrap());
+                        request.metadata_mut().insert("setup-nonce", MetadataValue::from_str(&setup_nonce.to_string()).unwrap());
                         request.metadata_mut().insert("remote-host", MetadataValue::from_str(&remote_address.to_string()).unwrap());
 
                         self.inner
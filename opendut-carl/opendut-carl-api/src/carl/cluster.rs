@@ -2,6 +2,11 @@
 pub use client::*;
 use opendut_types::cluster::{ClusterId, ClusterName};
 use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::PeerId;
+use opendut_types::project::ProjectName;
+use opendut_types::reservation::ReservationId;
+use opendut_types::resources::Version;
+use opendut_types::topology::DeviceId;
 use opendut_types::ShortName;
 
 #[derive(thiserror::Error, Debug)]
@@ -13,6 +18,19 @@ pub enum CreateClusterConfigurationError {
         other_id: ClusterId,
         other_name: ClusterName
     },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be stored, because it was expected in version {expected_version}, but is in version {actual_version}!")]
+    VersionConflict {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        expected_version: Version,
+        actual_version: Version,
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created, because it is illegal:\n  {}", errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  "))]
+    IllegalClusterConfiguration {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        errors: Vec<IllegalClusterConfigurationError>,
+    },
     #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created, due to internal errors:\n  {cause}")]
     Internal {
         cluster_id: ClusterId,
@@ -21,6 +39,73 @@ pub enum CreateClusterConfigurationError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum UpdateClusterConfigurationError {
+    #[error("ClusterConfiguration <{cluster_id}> could not be updated, because a ClusterConfiguration with that id does not exist!")]
+    ClusterConfigurationNotFound {
+        cluster_id: ClusterId
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be updated, because it is illegal:\n  {}", errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  "))]
+    IllegalClusterConfiguration {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        errors: Vec<IllegalClusterConfigurationError>,
+    },
+    #[error("ClusterConfiguration '{cluster_name}' <{cluster_id}> cannot be updated when cluster is in state '{}'! A ClusterConfiguration can be updated when cluster is in state: {}", actual_state.short_name(), ClusterState::short_names_joined(required_states))]
+    IllegalClusterState {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        actual_state: ClusterState,
+        required_states: Vec<ClusterState>,
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be updated, because it was expected in version {expected_version}, but is in version {actual_version}!")]
+    VersionConflict {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        expected_version: Version,
+        actual_version: Version,
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be updated, due to internal errors:\n  {cause}")]
+    Internal {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
+pub enum IllegalClusterConfigurationError {
+    #[error("Device <{device_id}> does not exist!")]
+    DeviceNotFound {
+        device_id: DeviceId,
+    },
+    #[error("Leader <{leader_id}> is not a registered peer!")]
+    LeaderNotFound {
+        leader_id: PeerId,
+    },
+    #[error("Device <{device_id}> is already used by the deployed cluster '{other_cluster_name}' <{other_cluster_id}>!")]
+    DeviceInUse {
+        device_id: DeviceId,
+        other_cluster_id: ClusterId,
+        other_cluster_name: ClusterName,
+    },
+    #[error("Leader <{leader_id}> belongs to the project '{leader_project}' instead of the cluster's project!")]
+    LeaderInOtherProject {
+        leader_id: PeerId,
+        leader_project: ProjectName,
+    },
+    #[error("Device <{device_id}> belongs to the project '{device_project}' instead of the cluster's project!")]
+    DeviceInOtherProject {
+        device_id: DeviceId,
+        device_project: ProjectName,
+    },
+    #[error("Name is already used by the cluster '{other_cluster_name}' <{other_cluster_id}> of the same project!")]
+    NameInUse {
+        other_cluster_id: ClusterId,
+        other_cluster_name: ClusterName,
+    },
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum DeleteClusterConfigurationError {
     #[error("ClusterConfiguration <{cluster_id}> could not be deleted, because a ClusterConfiguration with that id does not exist!")]
@@ -48,6 +133,12 @@ pub struct GetClusterConfigurationError {
     message: String,
 }
 
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct GetClusterStateError {
+    message: String,
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("{message}")]
 pub struct ListClusterConfigurationsError {
@@ -56,6 +147,10 @@ pub struct ListClusterConfigurationsError {
 
 #[derive(thiserror::Error, Debug)]
 pub enum StoreClusterDeploymentError {
+    #[error("ClusterDeployment for cluster <{cluster_id}> could not be stored, because a ClusterConfiguration with that id does not exist!")]
+    ClusterConfigurationNotFound {
+        cluster_id: ClusterId
+    },
     #[error("ClusterDeployment for cluster '{cluster_name}' <{cluster_id}> cannot be changed when cluster is in state '{}'! A cluster can be updated when: {}", actual_state.short_name(), ClusterState::short_names_joined(required_states))]
     IllegalClusterState {
         cluster_id: ClusterId,
@@ -63,6 +158,13 @@ pub enum StoreClusterDeploymentError {
         actual_state: ClusterState,
         required_states: Vec<ClusterState>,
     },
+    #[error("ClusterDeployment for cluster '{cluster_name}' <{cluster_id}> could not be stored, because its peers or devices are reserved by user '{user}' with reservation <{reservation_id}>!")]
+    Reserved {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        reservation_id: ReservationId,
+        user: String,
+    },
     #[error("ClusterDeployment for cluster '{cluster_name}' <{cluster_id}> could not be changed, due to internal errors:\n  {cause}")]
     Internal {
         cluster_id: ClusterId,
@@ -105,7 +207,7 @@ mod client {
 
     use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
 
-    use crate::carl::{ClientError, extract};
+    use crate::carl::{ClientError, extract, WatchError, WatchEvent, WatchStream};
     use crate::proto::services::cluster_manager;
     use crate::proto::services::cluster_manager::cluster_manager_client::ClusterManagerClient;
 
@@ -152,9 +254,19 @@ mod client {
         }
 
         pub async fn store_cluster_configuration(&mut self, configuration: ClusterConfiguration) -> Result<ClusterId, ClientError<CreateClusterConfigurationError>> {
+            self.store_cluster_configuration_with_expected_version(configuration, None).await
+        }
+
+        /// Stores the cluster configuration only, if it is currently in the given version, as returned by [`Self::get_cluster_configuration_with_version`].
+        pub async fn store_cluster_configuration_if_version(&mut self, configuration: ClusterConfiguration, expected_version: Version) -> Result<ClusterId, ClientError<CreateClusterConfigurationError>> {
+            self.store_cluster_configuration_with_expected_version(configuration, Some(expected_version)).await
+        }
+
+        async fn store_cluster_configuration_with_expected_version(&mut self, configuration: ClusterConfiguration, expected_version: Option<Version>) -> Result<ClusterId, ClientError<CreateClusterConfigurationError>> {
 
             let request = tonic::Request::new(cluster_manager::CreateClusterConfigurationRequest {
                 cluster_configuration: Some(configuration.into()),
+                expected_version: expected_version.map(u64::from),
             });
 
             let response = self.inner.create_cluster_configuration(request).await?
@@ -172,6 +284,38 @@ mod client {
             }
         }
 
+        /// Updates an existing cluster configuration. If the cluster is deployed, only the affected peers are reconfigured.
+        pub async fn update_cluster_configuration(&mut self, configuration: ClusterConfiguration) -> Result<ClusterId, ClientError<UpdateClusterConfigurationError>> {
+            self.update_cluster_configuration_with_expected_version(configuration, None).await
+        }
+
+        /// Updates the cluster configuration only, if it is currently in the given version, as returned by [`Self::get_cluster_configuration_with_version`].
+        pub async fn update_cluster_configuration_if_version(&mut self, configuration: ClusterConfiguration, expected_version: Version) -> Result<ClusterId, ClientError<UpdateClusterConfigurationError>> {
+            self.update_cluster_configuration_with_expected_version(configuration, Some(expected_version)).await
+        }
+
+        async fn update_cluster_configuration_with_expected_version(&mut self, configuration: ClusterConfiguration, expected_version: Option<Version>) -> Result<ClusterId, ClientError<UpdateClusterConfigurationError>> {
+
+            let request = tonic::Request::new(cluster_manager::UpdateClusterConfigurationRequest {
+                cluster_configuration: Some(configuration.into()),
+                expected_version: expected_version.map(u64::from),
+            });
+
+            let response = self.inner.update_cluster_configuration(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::update_cluster_configuration_response::Reply::Failure(failure) => {
+                    let error = UpdateClusterConfigurationError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::update_cluster_configuration_response::Reply::Success(success) => {
+                    let cluster_id = extract!(success.cluster_id)?;
+                    Ok(cluster_id)
+                }
+            }
+        }
+
         pub async fn delete_cluster_configuration(&mut self, cluster_id: ClusterId) -> Result<ClusterConfiguration, ClientError<DeleteClusterConfigurationError>> {
 
             let request = tonic::Request::new(cluster_manager::DeleteClusterConfigurationRequest {
@@ -194,6 +338,11 @@ mod client {
         }
 
         pub async fn get_cluster_configuration(&mut self, id: ClusterId) -> Result<ClusterConfiguration, GetClusterConfigurationError> {
+            self.get_cluster_configuration_with_version(id).await
+                .map(|(configuration, _)| configuration)
+        }
+
+        pub async fn get_cluster_configuration_with_version(&mut self, id: ClusterId) -> Result<(ClusterConfiguration, Version), GetClusterConfigurationError> {
             let request = tonic::Request::new(cluster_manager::GetClusterConfigurationRequest {
                 id: Some(id.into()),
             });
@@ -206,11 +355,12 @@ mod client {
                         cluster_manager::get_cluster_configuration_response::Result::Failure(_) => {
                             Err(GetClusterConfigurationError { message: String::from("Failed to get cluster configuration!") })
                         }
-                        cluster_manager::get_cluster_configuration_response::Result::Success(cluster_manager::GetClusterConfigurationSuccess { configuration }) => {
+                        cluster_manager::get_cluster_configuration_response::Result::Success(cluster_manager::GetClusterConfigurationSuccess { configuration, version }) => {
                             let configuration = configuration
                                 .ok_or(GetClusterConfigurationError { message: String::from("Response contains no cluster configuration!") })?;
-                            ClusterConfiguration::try_from(configuration)
-                                .map_err(|_| GetClusterConfigurationError { message: String::from("Conversion failed for cluster configurations!") })
+                            let configuration = ClusterConfiguration::try_from(configuration)
+                                .map_err(|_| GetClusterConfigurationError { message: String::from("Conversion failed for cluster configurations!") })?;
+                            Ok((configuration, Version::from(version)))
                         }
                     }
                 },
@@ -220,7 +370,58 @@ mod client {
             }
         }
 
+        pub async fn get_cluster_state(&mut self, id: ClusterId) -> Result<ClusterState, GetClusterStateError> {
+            let request = tonic::Request::new(cluster_manager::GetClusterStateRequest {
+                id: Some(id.into()),
+            });
+
+            match self.inner.get_cluster_state(request).await {
+                Ok(response) => {
+                    let result = response.into_inner().result
+                        .ok_or(GetClusterStateError { message: String::from("Response contains no result!") })?;
+                    match result {
+                        cluster_manager::get_cluster_state_response::Result::Failure(_) => {
+                            Err(GetClusterStateError { message: format!("Failed to get state of cluster <{id}>!") })
+                        }
+                        cluster_manager::get_cluster_state_response::Result::Success(cluster_manager::GetClusterStateSuccess { state }) => {
+                            let state = state
+                                .ok_or(GetClusterStateError { message: String::from("Response contains no cluster state!") })?;
+                            ClusterState::try_from(state)
+                                .map_err(|_| GetClusterStateError { message: String::from("Conversion failed for cluster state!") })
+                        }
+                    }
+                },
+                Err(status) => {
+                    Err(GetClusterStateError { message: format!("gRPC failure: {status}") })
+                }
+            }
+        }
+
         pub async fn list_cluster_configurations(&mut self) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
+            self.list_cluster_configurations_with_details().await
+                .map(|configurations| configurations.into_iter()
+                    .map(|(configuration, _, _)| configuration)
+                    .collect()
+                )
+        }
+
+        pub async fn list_cluster_configurations_with_versions(&mut self) -> Result<Vec<(ClusterConfiguration, Version)>, ListClusterConfigurationsError> {
+            self.list_cluster_configurations_with_details().await
+                .map(|configurations| configurations.into_iter()
+                    .map(|(configuration, version, _)| (configuration, version))
+                    .collect()
+                )
+        }
+
+        pub async fn list_cluster_configurations_with_states(&mut self) -> Result<Vec<(ClusterConfiguration, ClusterState)>, ListClusterConfigurationsError> {
+            self.list_cluster_configurations_with_details().await
+                .map(|configurations| configurations.into_iter()
+                    .map(|(configuration, _, state)| (configuration, state))
+                    .collect()
+                )
+        }
+
+        async fn list_cluster_configurations_with_details(&mut self) -> Result<Vec<(ClusterConfiguration, Version, ClusterState)>, ListClusterConfigurationsError> {
             let request = tonic::Request::new(cluster_manager::ListClusterConfigurationsRequest {});
 
             match self.inner.list_cluster_configurations(request).await {
@@ -231,11 +432,23 @@ mod client {
                         cluster_manager::list_cluster_configurations_response::Result::Failure(_) => {
                             Err(ListClusterConfigurationsError { message: String::from("Failed to list clusters!") })
                         }
-                        cluster_manager::list_cluster_configurations_response::Result::Success(cluster_manager::ListClusterConfigurationsSuccess { configurations }) => {
-                            configurations.into_iter()
+                        cluster_manager::list_cluster_configurations_response::Result::Success(cluster_manager::ListClusterConfigurationsSuccess { configurations, versions, states }) => {
+                            let configurations = configurations.into_iter()
                                 .map(ClusterConfiguration::try_from)
                                 .collect::<Result<Vec<ClusterConfiguration>, _>>()
-                                .map_err(|_| ListClusterConfigurationsError { message: String::from("Conversion failed for list of cluster configurations!") })
+                                .map_err(|_| ListClusterConfigurationsError { message: String::from("Conversion failed for list of cluster configurations!") })?;
+                            let versions = versions.into_iter()
+                                .map(Version::from)
+                                .chain(std::iter::repeat(Version::NONE)); //tolerate responses of CARL without versions
+                            let states = states.into_iter()
+                                .map(ClusterState::try_from)
+                                .collect::<Result<Vec<ClusterState>, _>>()
+                                .map_err(|_| ListClusterConfigurationsError { message: String::from("Conversion failed for list of cluster states!") })?
+                                .into_iter()
+                                .chain(std::iter::repeat(ClusterState::default())); //tolerate responses of CARL without states
+                            Ok(configurations.into_iter().zip(versions).zip(states)
+                                .map(|((configuration, version), state)| (configuration, version, state))
+                                .collect())
                         }
                     }
                 },
@@ -288,6 +501,14 @@ mod client {
         }
 
         pub async fn list_cluster_deployments(&mut self) -> Result<Vec<ClusterDeployment>, ListClusterDeploymentsError> {
+            self.list_cluster_deployments_with_states().await
+                .map(|deployments| deployments.into_iter()
+                    .map(|(deployment, _)| deployment)
+                    .collect()
+                )
+        }
+
+        pub async fn list_cluster_deployments_with_states(&mut self) -> Result<Vec<(ClusterDeployment, ClusterState)>, ListClusterDeploymentsError> {
             let request = tonic::Request::new(cluster_manager::ListClusterDeploymentsRequest {});
 
             match self.inner.list_cluster_deployments(request).await {
@@ -298,11 +519,18 @@ mod client {
                         cluster_manager::list_cluster_deployments_response::Result::Failure(_) => {
                             Err(ListClusterDeploymentsError { message: String::from("Failed to list clusters!") })
                         }
-                        cluster_manager::list_cluster_deployments_response::Result::Success(cluster_manager::ListClusterDeploymentsSuccess { deployments }) => {
-                            deployments.into_iter()
+                        cluster_manager::list_cluster_deployments_response::Result::Success(cluster_manager::ListClusterDeploymentsSuccess { deployments, states }) => {
+                            let deployments = deployments.into_iter()
                                 .map(ClusterDeployment::try_from)
                                 .collect::<Result<Vec<ClusterDeployment>, _>>()
-                                .map_err(|_| ListClusterDeploymentsError { message: String::from("Conversion failed for list of cluster deployments!") })
+                                .map_err(|_| ListClusterDeploymentsError { message: String::from("Conversion failed for list of cluster deployments!") })?;
+                            let states = states.into_iter()
+                                .map(ClusterState::try_from)
+                                .collect::<Result<Vec<ClusterState>, _>>()
+                                .map_err(|_| ListClusterDeploymentsError { message: String::from("Conversion failed for list of cluster states!") })?
+                                .into_iter()
+                                .chain(std::iter::repeat(ClusterState::default())); //tolerate responses of CARL without states
+                            Ok(deployments.into_iter().zip(states).collect())
                         }
                     }
                 },
@@ -311,5 +539,23 @@ mod client {
                 }
             }
         }
+
+        pub async fn watch_cluster_configurations(&mut self) -> Result<WatchStream<cluster_manager::WatchClusterConfigurationsResponse, WatchEvent<ClusterId, ClusterConfiguration>>, WatchError> {
+            let request = tonic::Request::new(cluster_manager::WatchClusterConfigurationsRequest {});
+
+            match self.inner.watch_cluster_configurations(request).await {
+                Ok(response) => Ok(WatchStream::new(response.into_inner())),
+                Err(status) => Err(WatchError { message: format!("gRPC failure: {status}") }),
+            }
+        }
+
+        pub async fn watch_cluster_deployments(&mut self) -> Result<WatchStream<cluster_manager::WatchClusterDeploymentsResponse, WatchEvent<ClusterId, ClusterDeployment>>, WatchError> {
+            let request = tonic::Request::new(cluster_manager::WatchClusterDeploymentsRequest {});
+
+            match self.inner.watch_cluster_deployments(request).await {
+                Ok(response) => Ok(WatchStream::new(response.into_inner())),
+                Err(status) => Err(WatchError { message: format!("gRPC failure: {status}") }),
+            }
+        }
     }
 }
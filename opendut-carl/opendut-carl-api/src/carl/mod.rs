@@ -1,16 +1,66 @@
 use cfg_if::cfg_if;
 
+pub mod audit;
+pub mod backup;
 pub mod broker;
 pub mod cluster;
 pub mod metadata;
 pub mod peer;
+pub mod reservation;
+pub mod service_account;
+pub mod webhook;
+
+/// A change of a resource in CARL, as received when watching resources.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchEvent<I, R> {
+    Created { id: I, resource: R },
+    Updated { id: I, resource: R },
+    Deleted { id: I },
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct WatchError {
+    pub message: String,
+}
 
 cfg_if! {
     if #[cfg(any(feature = "client", feature = "wasm-client"))] {
         use std::fmt::Display;
+        use std::marker::PhantomData;
         use tonic::codegen::http::uri::InvalidUri;
         use opendut_types::proto::ConversionError;
 
+        /// Stream of [`WatchEvent`]s, received from CARL.
+        ///
+        /// When the stream ends with an error, the client may have missed events and should list the resources again.
+        pub struct WatchStream<M, E> {
+            inner: tonic::Streaming<M>,
+            marker: PhantomData<E>,
+        }
+
+        impl <M, E> WatchStream<M, E>
+        where
+            E: TryFrom<M, Error=ConversionError>,
+        {
+            pub(crate) fn new(inner: tonic::Streaming<M>) -> Self {
+                Self { inner, marker: PhantomData }
+            }
+
+            /// Waits for the next event. Returns `None`, when CARL closed the stream.
+            pub async fn next(&mut self) -> Result<Option<E>, WatchError> {
+                match self.inner.message().await {
+                    Ok(Some(message)) => {
+                        E::try_from(message)
+                            .map(Some)
+                            .map_err(|cause| WatchError { message: format!("Received invalid event: {cause}") })
+                    }
+                    Ok(None) => Ok(None),
+                    Err(status) => Err(WatchError { message: format!("gRPC failure: {status}") }),
+                }
+            }
+        }
+
         #[derive(thiserror::Error, Debug)]
         pub enum ClientError<A>
         where
@@ -102,6 +152,7 @@ cfg_if! {
 
 cfg_if! {
     if #[cfg(feature = "client")] {
+        use std::ops::Not;
         use std::path::PathBuf;
         
         use tracing::{debug, info};
@@ -109,24 +160,39 @@ cfg_if! {
         use opendut_auth::confidential::client::ConfidentialClient;
         use opendut_auth::confidential::tonic_service::TonicAuthenticationService;
 
+        use crate::carl::audit::AuditLog;
+        use crate::carl::backup::Backup;
         use crate::carl::cluster::ClusterManager;
         use crate::carl::metadata::MetadataProvider;
         use crate::carl::peer::PeersRegistrar;
         use crate::carl::broker::PeerMessagingBroker;
+        use crate::carl::reservation::ReservationManager;
+        use crate::carl::service_account::ServiceAccountManager;
+        use crate::carl::webhook::WebhookManager;
 
+        use crate::proto::services::audit_log::audit_log_client::AuditLogClient;
+        use crate::proto::services::backup::backup_client::BackupClient;
         use crate::proto::services::cluster_manager::cluster_manager_client::ClusterManagerClient;
         use crate::proto::services::metadata_provider::metadata_provider_client::MetadataProviderClient;
         use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
         use crate::proto::services::peer_messaging_broker::peer_messaging_broker_client::PeerMessagingBrokerClient;
+        use crate::proto::services::reservation_manager::reservation_manager_client::ReservationManagerClient;
+        use crate::proto::services::service_account_manager::service_account_manager_client::ServiceAccountManagerClient;
+        use crate::proto::services::webhook_manager::webhook_manager_client::WebhookManagerClient;
 
         use tower::ServiceBuilder;
 
         #[derive(Debug, Clone)]
         pub struct CarlClient {
+            pub audit: AuditLog<TonicAuthenticationService>,
+            pub backup: Backup<TonicAuthenticationService>,
             pub broker: PeerMessagingBroker<TonicAuthenticationService>,
             pub cluster: ClusterManager<TonicAuthenticationService>,
             pub metadata: MetadataProvider<TonicAuthenticationService>,
             pub peers: PeersRegistrar<TonicAuthenticationService>,
+            pub reservations: ReservationManager<TonicAuthenticationService>,
+            pub service_accounts: ServiceAccountManager<TonicAuthenticationService>,
+            pub webhooks: WebhookManager<TonicAuthenticationService>,
         }
 
         pub enum CaCertInfo {
@@ -166,6 +232,28 @@ cfg_if! {
                         debug!("Using override for verified domain name of '{domain_name_override}'.");
                         config = config.domain_name(domain_name_override);
                     }
+
+                    let client_certificate_path = settings.get_string("network.tls.client.certificate").ok()
+                        .filter(|path| path.is_empty().not())
+                        .map(PathBuf::from);
+                    let client_key_path = settings.get_string("network.tls.client.key").ok()
+                        .filter(|path| path.is_empty().not())
+                        .map(PathBuf::from);
+
+                    match (client_certificate_path, client_key_path) {
+                        (Some(certificate_path), Some(key_path)) if certificate_path.exists() && key_path.exists() => {
+                            debug!("Using TLS client certificate: {}", certificate_path.display());
+                            let certificate = std::fs::read_to_string(&certificate_path)
+                                .map_err(|cause| InitializationError::TlsConfiguration { message: format!("Failed to read client certificate from path '{}'", certificate_path.display()), cause: cause.into() })?;
+                            let key = std::fs::read_to_string(&key_path)
+                                .map_err(|cause| InitializationError::TlsConfiguration { message: format!("Failed to read client key from path '{}'", key_path.display()), cause: cause.into() })?;
+                            config = config.identity(tonic::transport::Identity::from_pem(certificate, key));
+                        }
+                        (Some(certificate_path), _) => {
+                            debug!("Not using a TLS client certificate, since none was found at '{}'.", certificate_path.display());
+                        }
+                        _ => {}
+                    }
                     config
                 };
 
@@ -177,8 +265,17 @@ cfg_if! {
                     .tls_config(tls_config)
                     .map_err(|cause| InitializationError::TlsConfiguration { message: String::from("Failed to initialize secure channel with specified TLS configuration"), cause: cause.into() })?;
 
-                let oidc_client = ConfidentialClient::from_settings(settings).await
-                    .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?;
+                let api_token = settings.get_string("network.carl.api.token").ok()
+                    .filter(|api_token| api_token.is_empty().not());
+
+                let oidc_client = match api_token {
+                    Some(_) => {
+                        debug!("Using API token instead of OIDC for authentication.");
+                        None
+                    }
+                    None => ConfidentialClient::from_settings(settings).await
+                        .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?,
+                };
                 match oidc_client {
                     None => {}
                     Some(ref client) => {
@@ -193,14 +290,22 @@ cfg_if! {
                 info!("Connected to CARL at '{address}'.");
 
                 let auth_svc = ServiceBuilder::new()
-                    .layer_fn(|channel| TonicAuthenticationService::new(channel, oidc_client.clone()))
+                    .layer_fn(|channel| match &api_token {
+                        Some(api_token) => TonicAuthenticationService::with_api_token(channel, Clone::clone(api_token)),
+                        None => TonicAuthenticationService::new(channel, oidc_client.clone()),
+                    })
                     .service(channel);
 
                 Ok(CarlClient {
+                    audit: AuditLog::new(AuditLogClient::new(Clone::clone(&auth_svc))),
+                    backup: Backup::new(BackupClient::new(Clone::clone(&auth_svc))),
                     broker: PeerMessagingBroker::new(PeerMessagingBrokerClient::new(Clone::clone(&auth_svc))),
                     cluster: ClusterManager::new(ClusterManagerClient::new(Clone::clone(&auth_svc))),
                     metadata: MetadataProvider::new(MetadataProviderClient::new(Clone::clone(&auth_svc))),
                     peers: PeersRegistrar::new(PeerManagerClient::new(Clone::clone(&auth_svc))),
+                    reservations: ReservationManager::new(ReservationManagerClient::new(Clone::clone(&auth_svc))),
+                    service_accounts: ServiceAccountManager::new(ServiceAccountManagerClient::new(Clone::clone(&auth_svc))),
+                    webhooks: WebhookManager::new(WebhookManagerClient::new(Clone::clone(&auth_svc))),
                 })
             }
         }
@@ -214,18 +319,28 @@ pub mod wasm {
 
     use opendut_auth::public::{Auth, AuthInterceptor, OptionalAuthData};
 
+    use crate::carl::audit::AuditLog;
+    use crate::carl::backup::Backup;
     use crate::carl::broker::PeerMessagingBroker;
     use crate::carl::cluster::ClusterManager;
     use crate::carl::InitializationError;
     use crate::carl::metadata::MetadataProvider;
     use crate::carl::peer::PeersRegistrar;
+    use crate::carl::reservation::ReservationManager;
+    use crate::carl::service_account::ServiceAccountManager;
+    use crate::carl::webhook::WebhookManager;
 
     #[derive(Debug, Clone)]
     pub struct CarlClient {
+        pub audit: AuditLog<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub backup: Backup<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub broker: PeerMessagingBroker<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub cluster: ClusterManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub metadata: MetadataProvider<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub peers: PeersRegistrar<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub reservations: ReservationManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub service_accounts: ServiceAccountManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub webhooks: WebhookManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
     }
 
     impl CarlClient {
@@ -247,10 +362,15 @@ pub mod wasm {
             let auth_interceptor = AuthInterceptor::new(auth);
 
             Ok(CarlClient {
+                audit: AuditLog::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                backup: Backup::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 broker: PeerMessagingBroker::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 cluster: ClusterManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 metadata: MetadataProvider::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 peers: PeersRegistrar::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                reservations: ReservationManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                service_accounts: ServiceAccountManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                webhooks: WebhookManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
             })
         }
     }
@@ -1,7 +1,10 @@
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
+use opendut_types::cluster::ClusterId;
 use opendut_types::peer::{PeerId, PeerName};
+use opendut_types::peer::setup::PeerSetupNonce;
 use opendut_types::peer::state::PeerState;
+use opendut_types::resources::Version;
 use opendut_types::ShortName;
 use opendut_types::topology::DeviceId;
 
@@ -14,12 +17,25 @@ pub enum StorePeerDescriptorError {
         actual_state: PeerState,
         required_states: Vec<PeerState>,
     },
+    #[error("Peer '{peer_name}' <{peer_id}> cannot be updated, while it is assigned to cluster <{cluster_id}>, even if it is down! Undeploy the cluster first.")]
+    ClusterMember {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cluster_id: ClusterId,
+    },
     #[error("Peer '{peer_name}' <{peer_id}> could not be stored, due to illegal devices:\n  {error}")]
     IllegalDevices {
         peer_id: PeerId,
         peer_name: PeerName,
         error: IllegalDevicesError
     },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be stored, because it was expected in version {expected_version}, but is in version {actual_version}!")]
+    VersionConflict {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        expected_version: Version,
+        actual_version: Version,
+    },
     #[error("Peer '{peer_name}' <{peer_id}> could not be created, due to internal errors:\n  {cause}")]
     Internal {
         peer_id: PeerId,
@@ -41,6 +57,12 @@ pub enum DeletePeerDescriptorError {
         actual_state: PeerState,
         required_states: Vec<PeerState>,
     },
+    #[error("Peer '{peer_name}' <{peer_id}> cannot be deleted, while it is assigned to cluster <{cluster_id}>, even if it is down! Undeploy the cluster first.")]
+    ClusterMember {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cluster_id: ClusterId,
+    },
     #[error("Peer '{peer_name}' <{peer_id}> deleted with internal errors:\n  {cause}")]
     Internal {
         peer_id: PeerId,
@@ -49,6 +71,14 @@ pub enum DeletePeerDescriptorError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum GetPeerStatusReportsError {
+    #[error("A peer with id <{peer_id}> could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum GetPeerDescriptorError {
     #[error("A peer with id <{peer_id}> could not be found!")]
@@ -78,6 +108,27 @@ pub enum ListDevicesError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum ListPeerSetupsError {
+    #[error("Setup-strings of peer <{peer_id}> could not be listed, because a peer with that id does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokePeerSetupError {
+    #[error("Setup-string <{nonce}> could not be revoked, because no setup-string with that nonce was issued!")]
+    PeerSetupNotFound {
+        nonce: PeerSetupNonce
+    },
+    #[error("Setup-string <{nonce}> could not be revoked, due to internal errors:\n  {cause}")]
+    Internal {
+        nonce: PeerSetupNonce,
+        cause: String,
+    },
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("{message}")]
 pub struct  CreateSetupError {
@@ -99,10 +150,14 @@ mod client {
     use opendut_types::cleo::CleoSetup;
 
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerSetup};
+    use opendut_types::peer::configuration::ParameterStatusReport;
+    use opendut_types::peer::setup::{IssuedPeerSetup, PeerSetupNonce};
+    use opendut_types::peer::state::PeerState;
+    use opendut_types::resources::Version;
     use opendut_types::topology::DeviceDescriptor;
 
-    use crate::carl::{ClientError, extract};
-    use crate::carl::peer::{CreateSetupError, DeletePeerDescriptorError, GetPeerDescriptorError, ListDevicesError, ListPeerDescriptorsError, StorePeerDescriptorError};
+    use crate::carl::{ClientError, extract, WatchError, WatchEvent, WatchStream};
+    use crate::carl::peer::{CreateSetupError, DeletePeerDescriptorError, GetPeerDescriptorError, GetPeerStatusReportsError, ListDevicesError, ListPeerDescriptorsError, ListPeerSetupsError, RevokePeerSetupError, StorePeerDescriptorError};
     use crate::proto::services::peer_manager;
     use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
 
@@ -147,9 +202,19 @@ mod client {
         }
 
         pub async fn store_peer_descriptor(&mut self, descriptor: PeerDescriptor) -> Result<PeerId, ClientError<StorePeerDescriptorError>> {
+            self.store_peer_descriptor_with_expected_version(descriptor, None).await
+        }
+
+        /// Stores the peer descriptor only, if it is currently in the given version, as returned by [`Self::get_peer_descriptor_with_version`].
+        pub async fn store_peer_descriptor_if_version(&mut self, descriptor: PeerDescriptor, expected_version: Version) -> Result<PeerId, ClientError<StorePeerDescriptorError>> {
+            self.store_peer_descriptor_with_expected_version(descriptor, Some(expected_version)).await
+        }
+
+        async fn store_peer_descriptor_with_expected_version(&mut self, descriptor: PeerDescriptor, expected_version: Option<Version>) -> Result<PeerId, ClientError<StorePeerDescriptorError>> {
 
             let request = tonic::Request::new(peer_manager::StorePeerDescriptorRequest {
                 peer: Some(descriptor.into()),
+                expected_version: expected_version.map(u64::from),
             });
 
             let response = self.inner.store_peer_descriptor(request).await?
@@ -189,6 +254,11 @@ mod client {
         }
 
         pub async fn get_peer_descriptor(&mut self, peer_id: PeerId) -> Result<PeerDescriptor, ClientError<GetPeerDescriptorError>> {
+            self.get_peer_descriptor_with_version(peer_id).await
+                .map(|(peer_descriptor, _)| peer_descriptor)
+        }
+
+        pub async fn get_peer_descriptor_with_version(&mut self, peer_id: PeerId) -> Result<(PeerDescriptor, Version), ClientError<GetPeerDescriptorError>> {
 
             let request = tonic::Request::new(peer_manager::GetPeerDescriptorRequest {
                 peer_id: Some(peer_id.into()),
@@ -204,12 +274,44 @@ mod client {
                 }
                 peer_manager::get_peer_descriptor_response::Reply::Success(success) => {
                     let peer_descriptor = extract!(success.descriptor)?;
-                    Ok(peer_descriptor)
+                    Ok((peer_descriptor, Version::from(success.version)))
+                }
+            }
+        }
+
+        /// Returns the status of each parameter of the peer's configuration, as far as the peer reported it back.
+        pub async fn get_peer_status_reports(&mut self, peer_id: PeerId) -> Result<Vec<ParameterStatusReport>, ClientError<GetPeerStatusReportsError>> {
+
+            let request = tonic::Request::new(peer_manager::GetPeerStatusReportsRequest {
+                peer_id: Some(peer_id.into()),
+            });
+
+            let response = self.inner.get_peer_status_reports(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::get_peer_status_reports_response::Reply::Failure(failure) => {
+                    let error = GetPeerStatusReportsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::get_peer_status_reports_response::Reply::Success(success) => {
+                    let reports = success.reports.into_iter()
+                        .map(ParameterStatusReport::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(reports)
                 }
             }
         }
 
         pub async fn list_peer_descriptors(&mut self) -> Result<Vec<PeerDescriptor>, ClientError<ListPeerDescriptorsError>> {
+            self.list_peer_descriptors_with_versions().await
+                .map(|peers| peers.into_iter()
+                    .map(|(peer_descriptor, _)| peer_descriptor)
+                    .collect()
+                )
+        }
+
+        pub async fn list_peer_descriptors_with_versions(&mut self) -> Result<Vec<(PeerDescriptor, Version)>, ClientError<ListPeerDescriptorsError>> {
 
             let request = tonic::Request::new(peer_manager::ListPeerDescriptorsRequest {});
 
@@ -222,10 +324,13 @@ mod client {
                     Err(ClientError::UsageError(error))
                 }
                 peer_manager::list_peer_descriptors_response::Reply::Success(success) => {
-                    Ok(success.peers.into_iter()
+                    let peers = success.peers.into_iter()
                         .map(PeerDescriptor::try_from)
-                        .collect::<Result<Vec<_>, _>>()?
-                    )
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let versions = success.versions.into_iter()
+                        .map(Version::from)
+                        .chain(std::iter::repeat(Version::NONE)); //tolerate responses of CARL without versions
+                    Ok(peers.into_iter().zip(versions).collect())
                 }
             }
         }
@@ -261,6 +366,52 @@ mod client {
             }
         }
 
+        /// Lists the setup-strings, which were issued for the peer, without their secrets.
+        pub async fn list_peer_setups(&mut self, peer_id: PeerId) -> Result<Vec<IssuedPeerSetup>, ClientError<ListPeerSetupsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListPeerSetupsRequest {
+                peer_id: Some(peer_id.into()),
+            });
+
+            let response = self.inner.list_peer_setups(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_peer_setups_response::Reply::Failure(failure) => {
+                    let error = ListPeerSetupsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_peer_setups_response::Reply::Success(success) => {
+                    let setups = success.setups.into_iter()
+                        .map(IssuedPeerSetup::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(setups)
+                }
+            }
+        }
+
+        /// Revokes the setup-string, so that EDGAR cannot connect with it anymore.
+        pub async fn revoke_peer_setup(&mut self, nonce: PeerSetupNonce) -> Result<IssuedPeerSetup, ClientError<RevokePeerSetupError>> {
+
+            let request = tonic::Request::new(peer_manager::RevokePeerSetupRequest {
+                nonce: Some(nonce.into()),
+            });
+
+            let response = self.inner.revoke_peer_setup(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::revoke_peer_setup_response::Reply::Failure(failure) => {
+                    let error = RevokePeerSetupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::revoke_peer_setup_response::Reply::Success(success) => {
+                    let setup = extract!(success.setup)?;
+                    Ok(setup)
+                }
+            }
+        }
+
         pub async fn create_cleo_setup(&mut self, user_id: String) -> Result<CleoSetup, CreateSetupError> {
             let request = tonic::Request::new(
                 peer_manager::GenerateCleoSetupRequest {
@@ -307,5 +458,23 @@ mod client {
                 },
             }
         }
+
+        pub async fn watch_peer_descriptors(&mut self) -> Result<WatchStream<peer_manager::WatchPeerDescriptorsResponse, WatchEvent<PeerId, PeerDescriptor>>, WatchError> {
+            let request = tonic::Request::new(peer_manager::WatchPeerDescriptorsRequest {});
+
+            match self.inner.watch_peer_descriptors(request).await {
+                Ok(response) => Ok(WatchStream::new(response.into_inner())),
+                Err(status) => Err(WatchError { message: format!("gRPC failure: {status}") }),
+            }
+        }
+
+        pub async fn watch_peer_states(&mut self) -> Result<WatchStream<peer_manager::WatchPeerStatesResponse, WatchEvent<PeerId, PeerState>>, WatchError> {
+            let request = tonic::Request::new(peer_manager::WatchPeerStatesRequest {});
+
+            match self.inner.watch_peer_states(request).await {
+                Ok(response) => Ok(WatchStream::new(response.into_inner())),
+                Err(status) => Err(WatchError { message: format!("gRPC failure: {status}") }),
+            }
+        }
     }
 }
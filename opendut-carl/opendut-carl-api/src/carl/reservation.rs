@@ -0,0 +1,164 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::cluster::ClusterId;
+use opendut_types::reservation::ReservationId;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateReservationError {
+    #[error("Reservation <{reservation_id}> could not be created, because it is illegal: {message}")]
+    IllegalReservation {
+        reservation_id: ReservationId,
+        message: String,
+    },
+    #[error("Reservation <{reservation_id}> could not be created, because it conflicts with reservation <{other_reservation_id}> of user '{other_user}'!")]
+    ReservationConflict {
+        reservation_id: ReservationId,
+        other_reservation_id: ReservationId,
+        other_user: String,
+    },
+    #[error("Reservation <{reservation_id}> could not be created, because cluster <{cluster_id}> of user '{deployed_by}' is deployed on the claimed peers or devices!")]
+    ClusterDeployed {
+        reservation_id: ReservationId,
+        cluster_id: ClusterId,
+        deployed_by: String,
+    },
+    #[error("Reservation <{reservation_id}> could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        reservation_id: ReservationId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteReservationError {
+    #[error("Reservation <{reservation_id}> could not be deleted, because a reservation with that id does not exist!")]
+    ReservationNotFound {
+        reservation_id: ReservationId,
+    },
+    #[error("Reservation <{reservation_id}> could not be deleted, because it is held by user '{user}'!")]
+    ReservedByOtherUser {
+        reservation_id: ReservationId,
+        user: String,
+    },
+    #[error("Reservation <{reservation_id}> could not be deleted, due to internal errors:\n  {cause}")]
+    Internal {
+        reservation_id: ReservationId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct ListReservationsError {
+    message: String,
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::reservation::{Reservation, ReservationId};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::reservation::{CreateReservationError, DeleteReservationError, ListReservationsError};
+    use crate::proto::services::reservation_manager;
+    use crate::proto::services::reservation_manager::reservation_manager_client::ReservationManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ReservationManager<T> {
+        inner: ReservationManagerClient<T>,
+    }
+
+    impl<T> ReservationManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ReservationManagerClient<T>) -> ReservationManager<T> {
+            ReservationManager { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ReservationManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = ReservationManagerClient::new(InterceptedService::new(inner, interceptor));
+            ReservationManager {
+                inner: inner_client
+            }
+        }
+
+        /// Creates the reservation for the user sending the request. Returns the reservation as stored by CARL.
+        pub async fn create_reservation(&mut self, reservation: Reservation) -> Result<Reservation, ClientError<CreateReservationError>> {
+
+            let request = tonic::Request::new(reservation_manager::CreateReservationRequest {
+                reservation: Some(reservation.into()),
+            });
+
+            let response = self.inner.create_reservation(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                reservation_manager::create_reservation_response::Reply::Failure(failure) => {
+                    let error = CreateReservationError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                reservation_manager::create_reservation_response::Reply::Success(success) => {
+                    let reservation = extract!(success.reservation)?;
+                    Ok(reservation)
+                }
+            }
+        }
+
+        pub async fn delete_reservation(&mut self, reservation_id: ReservationId) -> Result<Reservation, ClientError<DeleteReservationError>> {
+
+            let request = tonic::Request::new(reservation_manager::DeleteReservationRequest {
+                reservation_id: Some(reservation_id.into()),
+            });
+
+            let response = self.inner.delete_reservation(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                reservation_manager::delete_reservation_response::Reply::Failure(failure) => {
+                    let error = DeleteReservationError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                reservation_manager::delete_reservation_response::Reply::Success(success) => {
+                    let reservation = extract!(success.reservation)?;
+                    Ok(reservation)
+                }
+            }
+        }
+
+        pub async fn list_reservations(&mut self) -> Result<Vec<Reservation>, ListReservationsError> {
+            let request = tonic::Request::new(reservation_manager::ListReservationsRequest {});
+
+            match self.inner.list_reservations(request).await {
+                Ok(response) => {
+                    response.into_inner().reservations.into_iter()
+                        .map(Reservation::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|cause| ListReservationsError { message: format!("Conversion failed for list of reservations: {cause}") })
+                }
+                Err(status) => {
+                    Err(ListReservationsError { message: format!("gRPC failure: {status}") })
+                }
+            }
+        }
+    }
+}
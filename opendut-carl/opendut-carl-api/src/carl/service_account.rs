@@ -0,0 +1,255 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccountId, ServiceAccountName};
+
+/// An [`ApiToken`] together with its secret, as returned once when the token is issued.
+#[derive(Clone, Debug)]
+pub struct IssuedApiToken {
+    pub api_token: ApiToken,
+    pub secret: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateServiceAccountError {
+    #[error("Service account '{name}' <{service_account_id}> could not be created, because a service account with that name already exists with id <{other_service_account_id}>!")]
+    ServiceAccountAlreadyExists {
+        service_account_id: ServiceAccountId,
+        name: ServiceAccountName,
+        other_service_account_id: ServiceAccountId,
+    },
+    #[error("Service account <{service_account_id}> could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteServiceAccountError {
+    #[error("Service account <{service_account_id}> could not be deleted, because a service account with that id does not exist!")]
+    ServiceAccountNotFound {
+        service_account_id: ServiceAccountId,
+    },
+    #[error("Service account <{service_account_id}> could not be deleted, due to internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct ListServiceAccountsError {
+    message: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IssueApiTokenError {
+    #[error("API token <{api_token_id}> could not be issued, because service account <{service_account_id}> does not exist!")]
+    ServiceAccountNotFound {
+        api_token_id: ApiTokenId,
+        service_account_id: ServiceAccountId,
+    },
+    #[error("API token <{api_token_id}> could not be issued, because it is illegal: {message}")]
+    IllegalApiToken {
+        api_token_id: ApiTokenId,
+        message: String,
+    },
+    #[error("API token <{api_token_id}> could not be issued, due to internal errors:\n  {cause}")]
+    Internal {
+        api_token_id: ApiTokenId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokeApiTokenError {
+    #[error("API token <{api_token_id}> could not be revoked, because an API token with that id does not exist!")]
+    ApiTokenNotFound {
+        api_token_id: ApiTokenId,
+    },
+    #[error("API token <{api_token_id}> could not be revoked, due to internal errors:\n  {cause}")]
+    Internal {
+        api_token_id: ApiTokenId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct ListApiTokensError {
+    message: String,
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccount, ServiceAccountId};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::service_account::{CreateServiceAccountError, DeleteServiceAccountError, IssueApiTokenError, IssuedApiToken, ListApiTokensError, ListServiceAccountsError, RevokeApiTokenError};
+    use crate::proto::services::service_account_manager;
+    use crate::proto::services::service_account_manager::service_account_manager_client::ServiceAccountManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ServiceAccountManager<T> {
+        inner: ServiceAccountManagerClient<T>,
+    }
+
+    impl<T> ServiceAccountManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ServiceAccountManagerClient<T>) -> ServiceAccountManager<T> {
+            ServiceAccountManager { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ServiceAccountManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = ServiceAccountManagerClient::new(InterceptedService::new(inner, interceptor));
+            ServiceAccountManager {
+                inner: inner_client
+            }
+        }
+
+        /// Creates the service account on behalf of the user sending the request. Returns the service account as stored by CARL.
+        pub async fn create_service_account(&mut self, service_account: ServiceAccount) -> Result<ServiceAccount, ClientError<CreateServiceAccountError>> {
+
+            let request = tonic::Request::new(service_account_manager::CreateServiceAccountRequest {
+                service_account: Some(service_account.into()),
+            });
+
+            let response = self.inner.create_service_account(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::create_service_account_response::Reply::Failure(failure) => {
+                    let error = CreateServiceAccountError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::create_service_account_response::Reply::Success(success) => {
+                    let service_account = extract!(success.service_account)?;
+                    Ok(service_account)
+                }
+            }
+        }
+
+        /// Deletes the service account and revokes all of its API tokens.
+        pub async fn delete_service_account(&mut self, service_account_id: ServiceAccountId) -> Result<ServiceAccount, ClientError<DeleteServiceAccountError>> {
+
+            let request = tonic::Request::new(service_account_manager::DeleteServiceAccountRequest {
+                service_account_id: Some(service_account_id.into()),
+            });
+
+            let response = self.inner.delete_service_account(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::delete_service_account_response::Reply::Failure(failure) => {
+                    let error = DeleteServiceAccountError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::delete_service_account_response::Reply::Success(success) => {
+                    let service_account = extract!(success.service_account)?;
+                    Ok(service_account)
+                }
+            }
+        }
+
+        pub async fn list_service_accounts(&mut self) -> Result<Vec<ServiceAccount>, ListServiceAccountsError> {
+            let request = tonic::Request::new(service_account_manager::ListServiceAccountsRequest {});
+
+            match self.inner.list_service_accounts(request).await {
+                Ok(response) => {
+                    response.into_inner().service_accounts.into_iter()
+                        .map(ServiceAccount::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|cause| ListServiceAccountsError { message: format!("Conversion failed for list of service accounts: {cause}") })
+                }
+                Err(status) => {
+                    Err(ListServiceAccountsError { message: format!("gRPC failure: {status}") })
+                }
+            }
+        }
+
+        /// Issues the API token. The secret of the token is generated by CARL and only returned once.
+        pub async fn issue_api_token(&mut self, api_token: ApiToken) -> Result<IssuedApiToken, ClientError<IssueApiTokenError>> {
+
+            let request = tonic::Request::new(service_account_manager::IssueApiTokenRequest {
+                api_token: Some(api_token.into()),
+            });
+
+            let response = self.inner.issue_api_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::issue_api_token_response::Reply::Failure(failure) => {
+                    let error = IssueApiTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::issue_api_token_response::Reply::Success(success) => {
+                    let api_token = extract!(success.api_token)?;
+                    Ok(IssuedApiToken { api_token, secret: success.secret })
+                }
+            }
+        }
+
+        pub async fn revoke_api_token(&mut self, api_token_id: ApiTokenId) -> Result<ApiToken, ClientError<RevokeApiTokenError>> {
+
+            let request = tonic::Request::new(service_account_manager::RevokeApiTokenRequest {
+                api_token_id: Some(api_token_id.into()),
+            });
+
+            let response = self.inner.revoke_api_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::revoke_api_token_response::Reply::Failure(failure) => {
+                    let error = RevokeApiTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::revoke_api_token_response::Reply::Success(success) => {
+                    let api_token = extract!(success.api_token)?;
+                    Ok(api_token)
+                }
+            }
+        }
+
+        /// Lists the API tokens of the given service account or of all service accounts, if `None`.
+        pub async fn list_api_tokens(&mut self, service_account_id: Option<ServiceAccountId>) -> Result<Vec<ApiToken>, ListApiTokensError> {
+            let request = tonic::Request::new(service_account_manager::ListApiTokensRequest {
+                service_account_id: service_account_id.map(Into::into),
+            });
+
+            match self.inner.list_api_tokens(request).await {
+                Ok(response) => {
+                    response.into_inner().api_tokens.into_iter()
+                        .map(ApiToken::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|cause| ListApiTokensError { message: format!("Conversion failed for list of API tokens: {cause}") })
+                }
+                Err(status) => {
+                    Err(ListApiTokensError { message: format!("gRPC failure: {status}") })
+                }
+            }
+        }
+    }
+}
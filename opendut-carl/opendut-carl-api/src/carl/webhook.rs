@@ -0,0 +1,184 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::webhook::WebhookId;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateWebhookError {
+    #[error("Webhook <{webhook_id}> could not be created, because a webhook with that id already exists!")]
+    WebhookAlreadyExists {
+        webhook_id: WebhookId,
+    },
+    #[error("Webhook <{webhook_id}> could not be created, because it is illegal: {message}")]
+    IllegalWebhook {
+        webhook_id: WebhookId,
+        message: String,
+    },
+    #[error("Webhook <{webhook_id}> could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        webhook_id: WebhookId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteWebhookError {
+    #[error("Webhook <{webhook_id}> could not be deleted, because a webhook with that id does not exist!")]
+    WebhookNotFound {
+        webhook_id: WebhookId,
+    },
+    #[error("Webhook <{webhook_id}> could not be deleted, due to internal errors:\n  {cause}")]
+    Internal {
+        webhook_id: WebhookId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("{message}")]
+pub struct ListWebhooksError {
+    message: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListWebhookDeliveriesError {
+    #[error("Deliveries of webhook <{webhook_id}> could not be listed, because a webhook with that id does not exist!")]
+    WebhookNotFound {
+        webhook_id: WebhookId,
+    },
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::webhook::{Webhook, WebhookDelivery, WebhookId};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::webhook::{CreateWebhookError, DeleteWebhookError, ListWebhookDeliveriesError, ListWebhooksError};
+    use crate::proto::services::webhook_manager;
+    use crate::proto::services::webhook_manager::webhook_manager_client::WebhookManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct WebhookManager<T> {
+        inner: WebhookManagerClient<T>,
+    }
+
+    impl<T> WebhookManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: WebhookManagerClient<T>) -> WebhookManager<T> {
+            WebhookManager { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> WebhookManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = WebhookManagerClient::new(InterceptedService::new(inner, interceptor));
+            WebhookManager {
+                inner: inner_client
+            }
+        }
+
+        /// Creates the webhook. Returns the webhook as stored by CARL, including the generated secret, which is not returned again.
+        pub async fn create_webhook(&mut self, webhook: Webhook) -> Result<Webhook, ClientError<CreateWebhookError>> {
+
+            let request = tonic::Request::new(webhook_manager::CreateWebhookRequest {
+                webhook: Some(webhook.into()),
+            });
+
+            let response = self.inner.create_webhook(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                webhook_manager::create_webhook_response::Reply::Failure(failure) => {
+                    let error = CreateWebhookError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                webhook_manager::create_webhook_response::Reply::Success(success) => {
+                    let webhook = extract!(success.webhook)?;
+                    Ok(webhook)
+                }
+            }
+        }
+
+        pub async fn delete_webhook(&mut self, webhook_id: WebhookId) -> Result<Webhook, ClientError<DeleteWebhookError>> {
+
+            let request = tonic::Request::new(webhook_manager::DeleteWebhookRequest {
+                webhook_id: Some(webhook_id.into()),
+            });
+
+            let response = self.inner.delete_webhook(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                webhook_manager::delete_webhook_response::Reply::Failure(failure) => {
+                    let error = DeleteWebhookError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                webhook_manager::delete_webhook_response::Reply::Success(success) => {
+                    let webhook = extract!(success.webhook)?;
+                    Ok(webhook)
+                }
+            }
+        }
+
+        /// Lists all webhooks. Their secrets are not included.
+        pub async fn list_webhooks(&mut self) -> Result<Vec<Webhook>, ListWebhooksError> {
+            let request = tonic::Request::new(webhook_manager::ListWebhooksRequest {});
+
+            match self.inner.list_webhooks(request).await {
+                Ok(response) => {
+                    response.into_inner().webhooks.into_iter()
+                        .map(Webhook::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|cause| ListWebhooksError { message: format!("Conversion failed for list of webhooks: {cause}") })
+                }
+                Err(status) => {
+                    Err(ListWebhooksError { message: format!("gRPC failure: {status}") })
+                }
+            }
+        }
+
+        /// Lists the latest deliveries to the webhook, ordered from oldest to latest.
+        pub async fn list_webhook_deliveries(&mut self, webhook_id: WebhookId, limit: Option<u32>) -> Result<Vec<WebhookDelivery>, ClientError<ListWebhookDeliveriesError>> {
+
+            let request = tonic::Request::new(webhook_manager::ListWebhookDeliveriesRequest {
+                webhook_id: Some(webhook_id.into()),
+                limit,
+            });
+
+            let response = self.inner.list_webhook_deliveries(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                webhook_manager::list_webhook_deliveries_response::Reply::Failure(failure) => {
+                    let error = ListWebhookDeliveriesError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                webhook_manager::list_webhook_deliveries_response::Reply::Success(success) => {
+                    let deliveries = success.deliveries.into_iter()
+                        .map(WebhookDelivery::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(deliveries)
+                }
+            }
+        }
+    }
+}
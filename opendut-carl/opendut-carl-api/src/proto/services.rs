@@ -1,13 +1,322 @@
+/// Implements the conversions between a `Watch*Response` and a [`WatchEvent`](crate::carl::WatchEvent).
+macro_rules! watch_event_conversions {
+    ($response:ident, $response_module:ident, $deleted:ident, $id_field:ident: $id:ty, $resource:ty) => {
+        impl From<crate::carl::WatchEvent<$id, $resource>> for $response {
+            fn from(event: crate::carl::WatchEvent<$id, $resource>) -> Self {
+                use crate::carl::WatchEvent;
+                let (id, event) = match event {
+                    WatchEvent::Created { id, resource } => (id, $response_module::Event::Created(resource.into())),
+                    WatchEvent::Updated { id, resource } => (id, $response_module::Event::Updated(resource.into())),
+                    WatchEvent::Deleted { id } => (id, $response_module::Event::Deleted($deleted {})),
+                };
+                $response {
+                    $id_field: Some(id.into()),
+                    event: Some(event),
+                }
+            }
+        }
+
+        impl TryFrom<$response> for crate::carl::WatchEvent<$id, $resource> {
+            type Error = opendut_types::proto::ConversionError;
+            fn try_from(response: $response) -> Result<Self, Self::Error> {
+                use crate::carl::WatchEvent;
+                type ErrorBuilder = opendut_types::proto::ConversionErrorBuilder<$response, WatchEvent<$id, $resource>>;
+                let id: $id = response.$id_field
+                    .ok_or_else(|| ErrorBuilder::field_not_set(stringify!($id_field)))?
+                    .try_into()?;
+                let event = response.event
+                    .ok_or_else(|| ErrorBuilder::field_not_set("event"))?;
+                let event = match event {
+                    $response_module::Event::Created(resource) => WatchEvent::Created { id, resource: resource.try_into()? },
+                    $response_module::Event::Updated(resource) => WatchEvent::Updated { id, resource: resource.try_into()? },
+                    $response_module::Event::Deleted(_) => WatchEvent::Deleted { id },
+                };
+                Ok(event)
+            }
+        }
+    };
+}
+
+pub mod audit_log {
+    use std::time::{Duration, SystemTime};
+
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::resources::Id;
+
+    use crate::carl::audit::{AuditEntry as AuditEntryModel, AuditQuery};
+
+    tonic::include_proto!("opendut.carl.services.audit_log");
+
+    impl From<AuditEntryModel> for AuditEntry {
+        fn from(entry: AuditEntryModel) -> Self {
+            let timestamp = entry.timestamp.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            Self {
+                timestamp: u64::try_from(timestamp).unwrap_or(u64::MAX),
+                user: entry.user,
+                operation: entry.operation,
+                resource_id: Some(entry.resource_id.into()),
+                before: entry.before,
+                after: entry.after,
+            }
+        }
+    }
+
+    impl TryFrom<AuditEntry> for AuditEntryModel {
+        type Error = ConversionError;
+        fn try_from(entry: AuditEntry) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<AuditEntry, AuditEntryModel>;
+            let resource_id: Id = entry.resource_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("resource_id"))?
+                .into();
+            Ok(Self {
+                timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(entry.timestamp),
+                user: entry.user,
+                operation: entry.operation,
+                resource_id,
+                before: entry.before,
+                after: entry.after,
+            })
+        }
+    }
+
+    impl From<AuditQuery> for ListAuditEntriesRequest {
+        fn from(query: AuditQuery) -> Self {
+            Self {
+                user: query.user,
+                resource_id: query.resource_id.map(Into::into),
+                limit: query.limit,
+            }
+        }
+    }
+
+    impl From<ListAuditEntriesRequest> for AuditQuery {
+        fn from(request: ListAuditEntriesRequest) -> Self {
+            Self {
+                user: request.user,
+                resource_id: request.resource_id.map(Into::into),
+                limit: request.limit,
+            }
+        }
+    }
+}
+
+pub mod backup {
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::service_account::{ApiTokenId, ServiceAccountId, ServiceAccountName};
+
+    use crate::carl::backup::{ResourcesArchive as ResourcesArchiveModel, RestoreBackupError};
+
+    tonic::include_proto!("opendut.carl.services.backup");
+
+    impl From<ResourcesArchiveModel> for ResourcesArchive {
+        fn from(archive: ResourcesArchiveModel) -> Self {
+            Self {
+                format_version: archive.format_version,
+                peer_descriptors: archive.peer_descriptors.into_iter().map(Into::into).collect(),
+                cluster_configurations: archive.cluster_configurations.into_iter().map(Into::into).collect(),
+                cluster_deployments: archive.cluster_deployments.into_iter().map(Into::into).collect(),
+                reservations: archive.reservations.into_iter().map(Into::into).collect(),
+                service_accounts: archive.service_accounts.into_iter().map(Into::into).collect(),
+                api_tokens: archive.api_tokens.into_iter().map(Into::into).collect(),
+                webhooks: archive.webhooks.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<ResourcesArchive> for ResourcesArchiveModel {
+        type Error = ConversionError;
+        fn try_from(archive: ResourcesArchive) -> Result<Self, Self::Error> {
+            Ok(Self {
+                format_version: archive.format_version,
+                peer_descriptors: archive.peer_descriptors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                cluster_configurations: archive.cluster_configurations.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                cluster_deployments: archive.cluster_deployments.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                reservations: archive.reservations.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                service_accounts: archive.service_accounts.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                api_tokens: archive.api_tokens.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                webhooks: archive.webhooks.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            })
+        }
+    }
+
+    impl From<RestoreBackupError> for RestoreBackupFailure {
+        fn from(error: RestoreBackupError) -> Self {
+            let proto_error = match error {
+                RestoreBackupError::UnsupportedFormatVersion { actual_version, supported_version } => {
+                    restore_backup_failure::Error::UnsupportedFormatVersion(RestoreBackupFailureUnsupportedFormatVersion {
+                        actual_version,
+                        supported_version,
+                    })
+                }
+                RestoreBackupError::PeerDescriptor { peer_id, peer_name, cause } => {
+                    restore_backup_failure::Error::PeerDescriptor(RestoreBackupFailurePeerDescriptor {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cause,
+                    })
+                }
+                RestoreBackupError::ClusterConfiguration { cluster_id, cluster_name, cause } => {
+                    restore_backup_failure::Error::ClusterConfiguration(RestoreBackupFailureClusterConfiguration {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        cause,
+                    })
+                }
+                RestoreBackupError::ClusterDeployment { cluster_id, cause } => {
+                    restore_backup_failure::Error::ClusterDeployment(RestoreBackupFailureClusterDeployment {
+                        cluster_id: Some(cluster_id.into()),
+                        cause,
+                    })
+                }
+                RestoreBackupError::ServiceAccount { service_account_id, service_account_name, cause } => {
+                    restore_backup_failure::Error::ServiceAccount(RestoreBackupFailureServiceAccount {
+                        service_account_id: Some(service_account_id.into()),
+                        service_account_name: Some(service_account_name.into()),
+                        cause,
+                    })
+                }
+                RestoreBackupError::ApiToken { api_token_id, cause } => {
+                    restore_backup_failure::Error::ApiToken(RestoreBackupFailureApiToken {
+                        api_token_id: Some(api_token_id.into()),
+                        cause,
+                    })
+                }
+                RestoreBackupError::Internal { cause } => {
+                    restore_backup_failure::Error::Internal(RestoreBackupFailureInternal {
+                        cause,
+                    })
+                }
+            };
+            RestoreBackupFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RestoreBackupFailure> for RestoreBackupError {
+        type Error = ConversionError;
+        fn try_from(failure: RestoreBackupFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreBackupFailure, RestoreBackupError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                restore_backup_failure::Error::UnsupportedFormatVersion(error) => {
+                    RestoreBackupError::UnsupportedFormatVersion {
+                        actual_version: error.actual_version,
+                        supported_version: error.supported_version,
+                    }
+                }
+                restore_backup_failure::Error::PeerDescriptor(error) => {
+                    error.try_into()?
+                }
+                restore_backup_failure::Error::ClusterConfiguration(error) => {
+                    error.try_into()?
+                }
+                restore_backup_failure::Error::ClusterDeployment(error) => {
+                    error.try_into()?
+                }
+                restore_backup_failure::Error::ServiceAccount(error) => {
+                    error.try_into()?
+                }
+                restore_backup_failure::Error::ApiToken(error) => {
+                    error.try_into()?
+                }
+                restore_backup_failure::Error::Internal(error) => {
+                    RestoreBackupError::Internal {
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<RestoreBackupFailurePeerDescriptor> for RestoreBackupError {
+        type Error = ConversionError;
+        fn try_from(failure: RestoreBackupFailurePeerDescriptor) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreBackupFailurePeerDescriptor, RestoreBackupError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            Ok(RestoreBackupError::PeerDescriptor { peer_id, peer_name, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<RestoreBackupFailureClusterConfiguration> for RestoreBackupError {
+        type Error = ConversionError;
+        fn try_from(failure: RestoreBackupFailureClusterConfiguration) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreBackupFailureClusterConfiguration, RestoreBackupError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            Ok(RestoreBackupError::ClusterConfiguration { cluster_id, cluster_name, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<RestoreBackupFailureClusterDeployment> for RestoreBackupError {
+        type Error = ConversionError;
+        fn try_from(failure: RestoreBackupFailureClusterDeployment) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreBackupFailureClusterDeployment, RestoreBackupError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(RestoreBackupError::ClusterDeployment { cluster_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<RestoreBackupFailureServiceAccount> for RestoreBackupError {
+        type Error = ConversionError;
+        fn try_from(failure: RestoreBackupFailureServiceAccount) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreBackupFailureServiceAccount, RestoreBackupError>;
+            let service_account_id: ServiceAccountId = failure.service_account_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                .try_into()?;
+            let service_account_name: ServiceAccountName = failure.service_account_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("service_account_name"))?
+                .try_into()?;
+            Ok(RestoreBackupError::ServiceAccount { service_account_id, service_account_name, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<RestoreBackupFailureApiToken> for RestoreBackupError {
+        type Error = ConversionError;
+        fn try_from(failure: RestoreBackupFailureApiToken) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreBackupFailureApiToken, RestoreBackupError>;
+            let api_token_id: ApiTokenId = failure.api_token_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("api_token_id"))?
+                .try_into()?;
+            Ok(RestoreBackupError::ApiToken { api_token_id, cause: failure.cause })
+        }
+    }
+}
+
 pub mod cluster_manager {
     use opendut_types::cluster::{ClusterId, ClusterName};
     use opendut_types::cluster::state::ClusterState;
+    use opendut_types::peer::PeerId;
+    use opendut_types::project::ProjectName;
     use opendut_types::proto;
+    use opendut_types::reservation::ReservationId;
     use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::topology::DeviceId;
 
-    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError};
+    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError, UpdateClusterConfigurationError};
 
     tonic::include_proto!("opendut.carl.services.cluster_manager");
 
+    watch_event_conversions!(WatchClusterConfigurationsResponse, watch_cluster_configurations_response, WatchClusterConfigurationsDeleted, cluster_id: ClusterId, opendut_types::cluster::ClusterConfiguration);
+    watch_event_conversions!(WatchClusterDeploymentsResponse, watch_cluster_deployments_response, WatchClusterDeploymentsDeleted, cluster_id: ClusterId, opendut_types::cluster::ClusterDeployment);
+
     impl From<CreateClusterConfigurationError> for CreateClusterConfigurationFailure {
         fn from(error: CreateClusterConfigurationError) -> Self {
             let proto_error = match error {
@@ -19,6 +328,21 @@ pub mod cluster_manager {
                         other_name: Some(other_name.into()),
                     })
                 }
+                CreateClusterConfigurationError::VersionConflict { cluster_id, cluster_name, expected_version, actual_version } => {
+                    create_cluster_configuration_failure::Error::VersionConflict(CreateClusterConfigurationFailureVersionConflict {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        expected_version: expected_version.into(),
+                        actual_version: actual_version.into(),
+                    })
+                }
+                CreateClusterConfigurationError::IllegalClusterConfiguration { cluster_id, cluster_name, errors } => {
+                    create_cluster_configuration_failure::Error::IllegalClusterConfiguration(CreateClusterConfigurationFailureIllegalClusterConfiguration {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        errors: errors.into_iter().map(IllegalClusterConfigurationError::from).collect(),
+                    })
+                }
                 CreateClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
                     create_cluster_configuration_failure::Error::Internal(CreateClusterConfigurationFailureInternal {
                         cluster_id: Some(cluster_id.into()),
@@ -43,6 +367,12 @@ pub mod cluster_manager {
                 create_cluster_configuration_failure::Error::ClusterConfigurationAlreadyExists(error) => {
                     error.try_into()?
                 }
+                create_cluster_configuration_failure::Error::VersionConflict(error) => {
+                    error.try_into()?
+                }
+                create_cluster_configuration_failure::Error::IllegalClusterConfiguration(error) => {
+                    error.try_into()?
+                }
                 create_cluster_configuration_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -71,6 +401,150 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<CreateClusterConfigurationFailureVersionConflict> for CreateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateClusterConfigurationFailureVersionConflict) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailureVersionConflict, CreateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            Ok(CreateClusterConfigurationError::VersionConflict {
+                cluster_id,
+                cluster_name,
+                expected_version: failure.expected_version.into(),
+                actual_version: failure.actual_version.into(),
+            })
+        }
+    }
+
+    impl TryFrom<CreateClusterConfigurationFailureIllegalClusterConfiguration> for CreateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateClusterConfigurationFailureIllegalClusterConfiguration) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailureIllegalClusterConfiguration, CreateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let errors = failure.errors.into_iter()
+                .map(crate::carl::cluster::IllegalClusterConfigurationError::try_from)
+                .collect::<Result<_, _>>()?;
+            Ok(CreateClusterConfigurationError::IllegalClusterConfiguration { cluster_id, cluster_name, errors })
+        }
+    }
+
+    impl From<crate::carl::cluster::IllegalClusterConfigurationError> for IllegalClusterConfigurationError {
+        fn from(error: crate::carl::cluster::IllegalClusterConfigurationError) -> Self {
+            let proto_error = match error {
+                crate::carl::cluster::IllegalClusterConfigurationError::DeviceNotFound { device_id } => {
+                    illegal_cluster_configuration_error::Error::DeviceNotFound(IllegalClusterConfigurationErrorDeviceNotFound {
+                        device_id: Some(device_id.into()),
+                    })
+                }
+                crate::carl::cluster::IllegalClusterConfigurationError::LeaderNotFound { leader_id } => {
+                    illegal_cluster_configuration_error::Error::LeaderNotFound(IllegalClusterConfigurationErrorLeaderNotFound {
+                        leader_id: Some(leader_id.into()),
+                    })
+                }
+                crate::carl::cluster::IllegalClusterConfigurationError::DeviceInUse { device_id, other_cluster_id, other_cluster_name } => {
+                    illegal_cluster_configuration_error::Error::DeviceInUse(IllegalClusterConfigurationErrorDeviceInUse {
+                        device_id: Some(device_id.into()),
+                        other_cluster_id: Some(other_cluster_id.into()),
+                        other_cluster_name: Some(other_cluster_name.into()),
+                    })
+                }
+                crate::carl::cluster::IllegalClusterConfigurationError::LeaderInOtherProject { leader_id, leader_project } => {
+                    illegal_cluster_configuration_error::Error::LeaderInOtherProject(IllegalClusterConfigurationErrorLeaderInOtherProject {
+                        leader_id: Some(leader_id.into()),
+                        leader_project: Some(leader_project.into()),
+                    })
+                }
+                crate::carl::cluster::IllegalClusterConfigurationError::DeviceInOtherProject { device_id, device_project } => {
+                    illegal_cluster_configuration_error::Error::DeviceInOtherProject(IllegalClusterConfigurationErrorDeviceInOtherProject {
+                        device_id: Some(device_id.into()),
+                        device_project: Some(device_project.into()),
+                    })
+                }
+                crate::carl::cluster::IllegalClusterConfigurationError::NameInUse { other_cluster_id, other_cluster_name } => {
+                    illegal_cluster_configuration_error::Error::NameInUse(IllegalClusterConfigurationErrorNameInUse {
+                        other_cluster_id: Some(other_cluster_id.into()),
+                        other_cluster_name: Some(other_cluster_name.into()),
+                    })
+                }
+            };
+            IllegalClusterConfigurationError {
+                error: Some(proto_error),
+            }
+        }
+    }
+
+    impl TryFrom<IllegalClusterConfigurationError> for crate::carl::cluster::IllegalClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(error: IllegalClusterConfigurationError) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<IllegalClusterConfigurationError, crate::carl::cluster::IllegalClusterConfigurationError>;
+            let inner = error.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match inner {
+                illegal_cluster_configuration_error::Error::DeviceNotFound(error) => {
+                    let device_id: DeviceId = error.device_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("device_id"))?
+                        .try_into()?;
+                    crate::carl::cluster::IllegalClusterConfigurationError::DeviceNotFound { device_id }
+                }
+                illegal_cluster_configuration_error::Error::LeaderNotFound(error) => {
+                    let leader_id: PeerId = error.leader_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("leader_id"))?
+                        .try_into()?;
+                    crate::carl::cluster::IllegalClusterConfigurationError::LeaderNotFound { leader_id }
+                }
+                illegal_cluster_configuration_error::Error::DeviceInUse(error) => {
+                    let device_id: DeviceId = error.device_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("device_id"))?
+                        .try_into()?;
+                    let other_cluster_id: ClusterId = error.other_cluster_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_cluster_id"))?
+                        .try_into()?;
+                    let other_cluster_name: ClusterName = error.other_cluster_name
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_cluster_name"))?
+                        .try_into()?;
+                    crate::carl::cluster::IllegalClusterConfigurationError::DeviceInUse { device_id, other_cluster_id, other_cluster_name }
+                }
+                illegal_cluster_configuration_error::Error::LeaderInOtherProject(error) => {
+                    let leader_id: PeerId = error.leader_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("leader_id"))?
+                        .try_into()?;
+                    let leader_project: ProjectName = error.leader_project
+                        .ok_or_else(|| ErrorBuilder::field_not_set("leader_project"))?
+                        .try_into()?;
+                    crate::carl::cluster::IllegalClusterConfigurationError::LeaderInOtherProject { leader_id, leader_project }
+                }
+                illegal_cluster_configuration_error::Error::DeviceInOtherProject(error) => {
+                    let device_id: DeviceId = error.device_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("device_id"))?
+                        .try_into()?;
+                    let device_project: ProjectName = error.device_project
+                        .ok_or_else(|| ErrorBuilder::field_not_set("device_project"))?
+                        .try_into()?;
+                    crate::carl::cluster::IllegalClusterConfigurationError::DeviceInOtherProject { device_id, device_project }
+                }
+                illegal_cluster_configuration_error::Error::NameInUse(error) => {
+                    let other_cluster_id: ClusterId = error.other_cluster_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_cluster_id"))?
+                        .try_into()?;
+                    let other_cluster_name: ClusterName = error.other_cluster_name
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_cluster_name"))?
+                        .try_into()?;
+                    crate::carl::cluster::IllegalClusterConfigurationError::NameInUse { other_cluster_id, other_cluster_name }
+                }
+            };
+            Ok(error)
+        }
+    }
+
     impl TryFrom<CreateClusterConfigurationFailureInternal> for CreateClusterConfigurationError {
         type Error = ConversionError;
         fn try_from(failure: CreateClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
@@ -85,50 +559,71 @@ pub mod cluster_manager {
         }
     }
 
-    impl From<DeleteClusterConfigurationError> for DeleteClusterConfigurationFailure {
-        fn from(error: DeleteClusterConfigurationError) -> Self {
+    impl From<UpdateClusterConfigurationError> for UpdateClusterConfigurationFailure {
+        fn from(error: UpdateClusterConfigurationError) -> Self {
             let proto_error = match error {
-                DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id } => {
-                    delete_cluster_configuration_failure::Error::ClusterConfigurationNotFound(DeleteClusterConfigurationFailureClusterConfigurationNotFound {
+                UpdateClusterConfigurationError::ClusterConfigurationNotFound { cluster_id } => {
+                    update_cluster_configuration_failure::Error::ClusterConfigurationNotFound(UpdateClusterConfigurationFailureClusterConfigurationNotFound {
                         cluster_id: Some(cluster_id.into())
                     })
                 }
-                DeleteClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } => {
-                    delete_cluster_configuration_failure::Error::IllegalClusterState(DeleteClusterConfigurationFailureIllegalClusterState {
+                UpdateClusterConfigurationError::IllegalClusterConfiguration { cluster_id, cluster_name, errors } => {
+                    update_cluster_configuration_failure::Error::IllegalClusterConfiguration(UpdateClusterConfigurationFailureIllegalClusterConfiguration {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        errors: errors.into_iter().map(IllegalClusterConfigurationError::from).collect(),
+                    })
+                }
+                UpdateClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } => {
+                    update_cluster_configuration_failure::Error::IllegalClusterState(UpdateClusterConfigurationFailureIllegalClusterState {
                         cluster_id: Some(cluster_id.into()),
                         cluster_name: Some(cluster_name.into()),
                         actual_state: Some(actual_state.into()),
                         required_states: required_states.into_iter().map(Into::into).collect(),
                     })
                 }
-                DeleteClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
-                    delete_cluster_configuration_failure::Error::Internal(DeleteClusterConfigurationFailureInternal {
+                UpdateClusterConfigurationError::VersionConflict { cluster_id, cluster_name, expected_version, actual_version } => {
+                    update_cluster_configuration_failure::Error::VersionConflict(UpdateClusterConfigurationFailureVersionConflict {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        expected_version: expected_version.into(),
+                        actual_version: actual_version.into(),
+                    })
+                }
+                UpdateClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
+                    update_cluster_configuration_failure::Error::Internal(UpdateClusterConfigurationFailureInternal {
                         cluster_id: Some(cluster_id.into()),
                         cluster_name: Some(cluster_name.into()),
                         cause
                     })
                 }
             };
-            DeleteClusterConfigurationFailure {
+            UpdateClusterConfigurationFailure {
                 error: Some(proto_error)
             }
         }
     }
 
-    impl TryFrom<DeleteClusterConfigurationFailure> for DeleteClusterConfigurationError {
+    impl TryFrom<UpdateClusterConfigurationFailure> for UpdateClusterConfigurationError {
         type Error = ConversionError;
-        fn try_from(failure: DeleteClusterConfigurationFailure) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailure, DeleteClusterConfigurationError>;
+        fn try_from(failure: UpdateClusterConfigurationFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UpdateClusterConfigurationFailure, UpdateClusterConfigurationError>;
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
-                delete_cluster_configuration_failure::Error::ClusterConfigurationNotFound(error) => {
+                update_cluster_configuration_failure::Error::ClusterConfigurationNotFound(error) => {
                     error.try_into()?
                 }
-                delete_cluster_configuration_failure::Error::IllegalClusterState(error) => {
+                update_cluster_configuration_failure::Error::IllegalClusterConfiguration(error) => {
                     error.try_into()?
                 }
-                delete_cluster_configuration_failure::Error::Internal(error) => {
+                update_cluster_configuration_failure::Error::IllegalClusterState(error) => {
+                    error.try_into()?
+                }
+                update_cluster_configuration_failure::Error::VersionConflict(error) => {
+                    error.try_into()?
+                }
+                update_cluster_configuration_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
             };
@@ -136,21 +631,38 @@ pub mod cluster_manager {
         }
     }
 
-    impl TryFrom<DeleteClusterConfigurationFailureClusterConfigurationNotFound> for DeleteClusterConfigurationError {
+    impl TryFrom<UpdateClusterConfigurationFailureClusterConfigurationNotFound> for UpdateClusterConfigurationError {
         type Error = ConversionError;
-        fn try_from(failure: DeleteClusterConfigurationFailureClusterConfigurationNotFound) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureClusterConfigurationNotFound, DeleteClusterConfigurationError>;
+        fn try_from(failure: UpdateClusterConfigurationFailureClusterConfigurationNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UpdateClusterConfigurationFailureClusterConfigurationNotFound, UpdateClusterConfigurationError>;
             let cluster_id: ClusterId = failure.cluster_id
                 .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
                 .try_into()?;
-            Ok(DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })
+            Ok(UpdateClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })
         }
     }
 
-    impl TryFrom<DeleteClusterConfigurationFailureIllegalClusterState> for DeleteClusterConfigurationError {
+    impl TryFrom<UpdateClusterConfigurationFailureIllegalClusterConfiguration> for UpdateClusterConfigurationError {
         type Error = ConversionError;
-        fn try_from(failure: DeleteClusterConfigurationFailureIllegalClusterState) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureIllegalClusterState, DeleteClusterConfigurationError>;
+        fn try_from(failure: UpdateClusterConfigurationFailureIllegalClusterConfiguration) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UpdateClusterConfigurationFailureIllegalClusterConfiguration, UpdateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let errors = failure.errors.into_iter()
+                .map(crate::carl::cluster::IllegalClusterConfigurationError::try_from)
+                .collect::<Result<_, _>>()?;
+            Ok(UpdateClusterConfigurationError::IllegalClusterConfiguration { cluster_id, cluster_name, errors })
+        }
+    }
+
+    impl TryFrom<UpdateClusterConfigurationFailureIllegalClusterState> for UpdateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: UpdateClusterConfigurationFailureIllegalClusterState) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UpdateClusterConfigurationFailureIllegalClusterState, UpdateClusterConfigurationError>;
             let cluster_id: ClusterId = failure.cluster_id
                 .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
                 .try_into()?;
@@ -163,35 +675,163 @@ pub mod cluster_manager {
             let required_states = failure.required_states.into_iter()
                 .map(proto::cluster::ClusterState::try_into)
                 .collect::<Result<_, _>>()?;
-            Ok(DeleteClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states })
+            Ok(UpdateClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states })
         }
     }
 
-    impl TryFrom<DeleteClusterConfigurationFailureInternal> for DeleteClusterConfigurationError {
+    impl TryFrom<UpdateClusterConfigurationFailureVersionConflict> for UpdateClusterConfigurationError {
         type Error = ConversionError;
-        fn try_from(failure: DeleteClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureInternal, DeleteClusterConfigurationError>;
+        fn try_from(failure: UpdateClusterConfigurationFailureVersionConflict) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UpdateClusterConfigurationFailureVersionConflict, UpdateClusterConfigurationError>;
             let cluster_id: ClusterId = failure.cluster_id
                 .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
                 .try_into()?;
             let cluster_name: ClusterName = failure.cluster_name
                 .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
                 .try_into()?;
-            Ok(DeleteClusterConfigurationError::Internal { cluster_id, cluster_name, cause: failure.cause })
+            Ok(UpdateClusterConfigurationError::VersionConflict {
+                cluster_id,
+                cluster_name,
+                expected_version: failure.expected_version.into(),
+                actual_version: failure.actual_version.into(),
+            })
         }
     }
 
-    impl From<StoreClusterDeploymentError> for StoreClusterDeploymentFailure {
-        fn from(error: StoreClusterDeploymentError) -> Self {
-            let proto_error = match error {
-                StoreClusterDeploymentError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } => {
-                    store_cluster_deployment_failure::Error::IllegalClusterState(StoreClusterDeploymentFailureIllegalClusterState {
-                        cluster_id: Some(cluster_id.into()),
-                        cluster_name: Some(cluster_name.into()),
+    impl TryFrom<UpdateClusterConfigurationFailureInternal> for UpdateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: UpdateClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UpdateClusterConfigurationFailureInternal, UpdateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            Ok(UpdateClusterConfigurationError::Internal { cluster_id, cluster_name, cause: failure.cause })
+        }
+    }
+
+    impl From<DeleteClusterConfigurationError> for DeleteClusterConfigurationFailure {
+        fn from(error: DeleteClusterConfigurationError) -> Self {
+            let proto_error = match error {
+                DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id } => {
+                    delete_cluster_configuration_failure::Error::ClusterConfigurationNotFound(DeleteClusterConfigurationFailureClusterConfigurationNotFound {
+                        cluster_id: Some(cluster_id.into())
+                    })
+                }
+                DeleteClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } => {
+                    delete_cluster_configuration_failure::Error::IllegalClusterState(DeleteClusterConfigurationFailureIllegalClusterState {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        actual_state: Some(actual_state.into()),
+                        required_states: required_states.into_iter().map(Into::into).collect(),
+                    })
+                }
+                DeleteClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
+                    delete_cluster_configuration_failure::Error::Internal(DeleteClusterConfigurationFailureInternal {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        cause
+                    })
+                }
+            };
+            DeleteClusterConfigurationFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteClusterConfigurationFailure> for DeleteClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteClusterConfigurationFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailure, DeleteClusterConfigurationError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_cluster_configuration_failure::Error::ClusterConfigurationNotFound(error) => {
+                    error.try_into()?
+                }
+                delete_cluster_configuration_failure::Error::IllegalClusterState(error) => {
+                    error.try_into()?
+                }
+                delete_cluster_configuration_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<DeleteClusterConfigurationFailureClusterConfigurationNotFound> for DeleteClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteClusterConfigurationFailureClusterConfigurationNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureClusterConfigurationNotFound, DeleteClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })
+        }
+    }
+
+    impl TryFrom<DeleteClusterConfigurationFailureIllegalClusterState> for DeleteClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteClusterConfigurationFailureIllegalClusterState) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureIllegalClusterState, DeleteClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let actual_state: ClusterState = failure.actual_state
+                .ok_or_else(|| ErrorBuilder::field_not_set("actual_state"))?
+                .try_into()?;
+            let required_states = failure.required_states.into_iter()
+                .map(proto::cluster::ClusterState::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeleteClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states })
+        }
+    }
+
+    impl TryFrom<DeleteClusterConfigurationFailureInternal> for DeleteClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureInternal, DeleteClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            Ok(DeleteClusterConfigurationError::Internal { cluster_id, cluster_name, cause: failure.cause })
+        }
+    }
+
+    impl From<StoreClusterDeploymentError> for StoreClusterDeploymentFailure {
+        fn from(error: StoreClusterDeploymentError) -> Self {
+            let proto_error = match error {
+                StoreClusterDeploymentError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } => {
+                    store_cluster_deployment_failure::Error::IllegalClusterState(StoreClusterDeploymentFailureIllegalClusterState {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
                         actual_state: Some(actual_state.into()),
                         required_states: required_states.into_iter().map(Into::into).collect(),
                     })
                 }
+                StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id } => {
+                    store_cluster_deployment_failure::Error::ClusterConfigurationNotFound(StoreClusterDeploymentFailureClusterConfigurationNotFound {
+                        cluster_id: Some(cluster_id.into())
+                    })
+                }
+                StoreClusterDeploymentError::Reserved { cluster_id, cluster_name, reservation_id, user } => {
+                    store_cluster_deployment_failure::Error::Reserved(StoreClusterDeploymentFailureReserved {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        reservation_id: Some(reservation_id.into()),
+                        user,
+                    })
+                }
                 StoreClusterDeploymentError::Internal { cluster_id, cluster_name, cause } => {
                     store_cluster_deployment_failure::Error::Internal(StoreClusterDeploymentFailureInternal {
                         cluster_id: Some(cluster_id.into()),
@@ -216,6 +856,12 @@ pub mod cluster_manager {
                 store_cluster_deployment_failure::Error::IllegalClusterState(error) => {
                     error.try_into()?
                 }
+                store_cluster_deployment_failure::Error::ClusterConfigurationNotFound(error) => {
+                    error.try_into()?
+                }
+                store_cluster_deployment_failure::Error::Reserved(error) => {
+                    error.try_into()?
+                }
                 store_cluster_deployment_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -224,6 +870,23 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<StoreClusterDeploymentFailureReserved> for StoreClusterDeploymentError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreClusterDeploymentFailureReserved) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreClusterDeploymentFailureReserved, StoreClusterDeploymentError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let reservation_id: ReservationId = failure.reservation_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                .try_into()?;
+            Ok(StoreClusterDeploymentError::Reserved { cluster_id, cluster_name, reservation_id, user: failure.user })
+        }
+    }
+
     impl TryFrom<StoreClusterDeploymentFailureIllegalClusterState> for StoreClusterDeploymentError {
         type Error = ConversionError;
         fn try_from(failure: StoreClusterDeploymentFailureIllegalClusterState) -> Result<Self, Self::Error> {
@@ -244,6 +907,17 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<StoreClusterDeploymentFailureClusterConfigurationNotFound> for StoreClusterDeploymentError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreClusterDeploymentFailureClusterConfigurationNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreClusterDeploymentFailureClusterConfigurationNotFound, StoreClusterDeploymentError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id })
+        }
+    }
+
     impl TryFrom<StoreClusterDeploymentFailureInternal> for StoreClusterDeploymentError {
         type Error = ConversionError;
         fn try_from(failure: StoreClusterDeploymentFailureInternal) -> Result<Self, Self::Error> {
@@ -362,16 +1036,21 @@ pub mod metadata_provider {
 
 #[allow(clippy::large_enum_variant)]
 pub mod peer_manager {
+    use opendut_types::cluster::ClusterId;
     use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::peer::setup::PeerSetupNonce;
     use opendut_types::peer::state::PeerState;
     use opendut_types::proto;
     use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
     use opendut_types::topology::DeviceId;
 
-    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError};
+    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, GetPeerStatusReportsError, ListPeerDescriptorsError, ListPeerSetupsError, RevokePeerSetupError};
 
     tonic::include_proto!("opendut.carl.services.peer_manager");
 
+    watch_event_conversions!(WatchPeerDescriptorsResponse, watch_peer_descriptors_response, WatchPeerDescriptorsDeleted, peer_id: PeerId, opendut_types::peer::PeerDescriptor);
+    watch_event_conversions!(WatchPeerStatesResponse, watch_peer_states_response, WatchPeerStatesDeleted, peer_id: PeerId, PeerState);
+
     impl From<StorePeerDescriptorError> for StorePeerDescriptorFailure {
         fn from(error: StorePeerDescriptorError) -> Self {
             let proto_error = match error {
@@ -383,6 +1062,13 @@ pub mod peer_manager {
                         required_states: required_states.into_iter().map(Into::into).collect(),
                     })
                 }
+                StorePeerDescriptorError::ClusterMember { peer_id, peer_name, cluster_id } => {
+                    store_peer_descriptor_failure::Error::ClusterMember(StorePeerDescriptorFailureClusterMember {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
                 StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error } => {
                     store_peer_descriptor_failure::Error::IllegalDevices(StorePeerDescriptorFailureIllegalDevices {
                         peer_id: Some(peer_id.into()),
@@ -390,6 +1076,14 @@ pub mod peer_manager {
                         error: Some(error.into()),
                     })
                 }
+                StorePeerDescriptorError::VersionConflict { peer_id, peer_name, expected_version, actual_version } => {
+                    store_peer_descriptor_failure::Error::VersionConflict(StorePeerDescriptorFailureVersionConflict {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        expected_version: expected_version.into(),
+                        actual_version: actual_version.into(),
+                    })
+                }
                 StorePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
                     store_peer_descriptor_failure::Error::Internal(StorePeerDescriptorFailureInternal {
                         peer_id: Some(peer_id.into()),
@@ -414,9 +1108,15 @@ pub mod peer_manager {
                 store_peer_descriptor_failure::Error::IllegalPeerState(error) => {
                     error.try_into()?
                 }
+                store_peer_descriptor_failure::Error::ClusterMember(error) => {
+                    error.try_into()?
+                }
                 store_peer_descriptor_failure::Error::IllegalDevices(error) => {
                     error.try_into()?
                 }
+                store_peer_descriptor_failure::Error::VersionConflict(error) => {
+                    error.try_into()?
+                }
                 store_peer_descriptor_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -445,6 +1145,23 @@ pub mod peer_manager {
         }
     }
 
+    impl TryFrom<StorePeerDescriptorFailureClusterMember> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureClusterMember) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureClusterMember, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::ClusterMember { peer_id, peer_name, cluster_id })
+        }
+    }
+
     impl TryFrom<StorePeerDescriptorFailureIllegalDevices> for StorePeerDescriptorError {
         type Error = ConversionError;
         fn try_from(failure: StorePeerDescriptorFailureIllegalDevices) -> Result<Self, Self::Error> {
@@ -462,6 +1179,25 @@ pub mod peer_manager {
         }
     }
 
+    impl TryFrom<StorePeerDescriptorFailureVersionConflict> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureVersionConflict) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureVersionConflict, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::VersionConflict {
+                peer_id,
+                peer_name,
+                expected_version: failure.expected_version.into(),
+                actual_version: failure.actual_version.into(),
+            })
+        }
+    }
+
     impl TryFrom<StorePeerDescriptorFailureInternal> for StorePeerDescriptorError {
         type Error = ConversionError;
         fn try_from(failure: StorePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
@@ -492,6 +1228,13 @@ pub mod peer_manager {
                         required_states: required_states.into_iter().map(Into::into).collect(),
                     })
                 }
+                DeletePeerDescriptorError::ClusterMember { peer_id, peer_name, cluster_id } => {
+                    delete_peer_descriptor_failure::Error::ClusterMember(DeletePeerDescriptorFailureClusterMember {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
                 DeletePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
                     delete_peer_descriptor_failure::Error::Internal(DeletePeerDescriptorFailureInternal {
                         peer_id: Some(peer_id.into()),
@@ -519,6 +1262,9 @@ pub mod peer_manager {
                 delete_peer_descriptor_failure::Error::IllegalPeerState(error) => {
                     error.try_into()?
                 }
+                delete_peer_descriptor_failure::Error::ClusterMember(error) => {
+                    error.try_into()?
+                }
                 delete_peer_descriptor_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -558,6 +1304,23 @@ pub mod peer_manager {
         }
     }
 
+    impl TryFrom<DeletePeerDescriptorFailureClusterMember> for DeletePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerDescriptorFailureClusterMember) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailureClusterMember, DeletePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(DeletePeerDescriptorError::ClusterMember { peer_id, peer_name, cluster_id })
+        }
+    }
+
     impl TryFrom<DeletePeerDescriptorFailureInternal> for DeletePeerDescriptorError {
         type Error = ConversionError;
         fn try_from(failure: DeletePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
@@ -672,6 +1435,117 @@ pub mod peer_manager {
         }
     }
 
+    impl From<GetPeerStatusReportsError> for GetPeerStatusReportsFailure {
+        fn from(error: GetPeerStatusReportsError) -> Self {
+            let proto_error = match error {
+                GetPeerStatusReportsError::PeerNotFound { peer_id } => {
+                    get_peer_status_reports_failure::Error::PeerNotFound(GetPeerStatusReportsFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+            };
+            GetPeerStatusReportsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetPeerStatusReportsFailure> for GetPeerStatusReportsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerStatusReportsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerStatusReportsFailure, GetPeerStatusReportsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_peer_status_reports_failure::Error::PeerNotFound(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    GetPeerStatusReportsError::PeerNotFound { peer_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListPeerSetupsError> for ListPeerSetupsFailure {
+        fn from(error: ListPeerSetupsError) -> Self {
+            let proto_error = match error {
+                ListPeerSetupsError::PeerNotFound { peer_id } => {
+                    list_peer_setups_failure::Error::PeerNotFound(ListPeerSetupsFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+            };
+            ListPeerSetupsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListPeerSetupsFailure> for ListPeerSetupsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListPeerSetupsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListPeerSetupsFailure, ListPeerSetupsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_peer_setups_failure::Error::PeerNotFound(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    ListPeerSetupsError::PeerNotFound { peer_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<RevokePeerSetupError> for RevokePeerSetupFailure {
+        fn from(error: RevokePeerSetupError) -> Self {
+            let proto_error = match error {
+                RevokePeerSetupError::PeerSetupNotFound { nonce } => {
+                    revoke_peer_setup_failure::Error::PeerSetupNotFound(RevokePeerSetupFailurePeerSetupNotFound {
+                        nonce: Some(nonce.into()),
+                    })
+                }
+                RevokePeerSetupError::Internal { nonce, cause } => {
+                    revoke_peer_setup_failure::Error::Internal(RevokePeerSetupFailureInternal {
+                        nonce: Some(nonce.into()),
+                        cause,
+                    })
+                }
+            };
+            RevokePeerSetupFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RevokePeerSetupFailure> for RevokePeerSetupError {
+        type Error = ConversionError;
+        fn try_from(failure: RevokePeerSetupFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RevokePeerSetupFailure, RevokePeerSetupError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                revoke_peer_setup_failure::Error::PeerSetupNotFound(error) => {
+                    let nonce: PeerSetupNonce = error.nonce
+                        .ok_or_else(|| ErrorBuilder::field_not_set("nonce"))?
+                        .try_into()?;
+                    RevokePeerSetupError::PeerSetupNotFound { nonce }
+                }
+                revoke_peer_setup_failure::Error::Internal(error) => {
+                    let nonce: PeerSetupNonce = error.nonce
+                        .ok_or_else(|| ErrorBuilder::field_not_set("nonce"))?
+                        .try_into()?;
+                    RevokePeerSetupError::Internal { nonce, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
     impl From<ListPeerDescriptorsError> for ListPeerDescriptorsFailure {
         fn from(error: ListPeerDescriptorsError) -> Self {
             let proto_error = match error {
@@ -714,3 +1588,505 @@ pub mod peer_manager {
 pub mod peer_messaging_broker {
     tonic::include_proto!("opendut.carl.services.peer_messaging_broker");
 }
+
+pub mod reservation_manager {
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::reservation::ReservationId;
+
+    use crate::carl::reservation::{CreateReservationError, DeleteReservationError};
+
+    tonic::include_proto!("opendut.carl.services.reservation_manager");
+
+    impl From<CreateReservationError> for CreateReservationFailure {
+        fn from(error: CreateReservationError) -> Self {
+            let proto_error = match error {
+                CreateReservationError::IllegalReservation { reservation_id, message } => {
+                    create_reservation_failure::Error::IllegalReservation(CreateReservationFailureIllegalReservation {
+                        reservation_id: Some(reservation_id.into()),
+                        message,
+                    })
+                }
+                CreateReservationError::ReservationConflict { reservation_id, other_reservation_id, other_user } => {
+                    create_reservation_failure::Error::ReservationConflict(CreateReservationFailureReservationConflict {
+                        reservation_id: Some(reservation_id.into()),
+                        other_reservation_id: Some(other_reservation_id.into()),
+                        other_user,
+                    })
+                }
+                CreateReservationError::ClusterDeployed { reservation_id, cluster_id, deployed_by } => {
+                    create_reservation_failure::Error::ClusterDeployed(CreateReservationFailureClusterDeployed {
+                        reservation_id: Some(reservation_id.into()),
+                        cluster_id: Some(cluster_id.into()),
+                        deployed_by,
+                    })
+                }
+                CreateReservationError::Internal { reservation_id, cause } => {
+                    create_reservation_failure::Error::Internal(CreateReservationFailureInternal {
+                        reservation_id: Some(reservation_id.into()),
+                        cause,
+                    })
+                }
+            };
+            CreateReservationFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreateReservationFailure> for CreateReservationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateReservationFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateReservationFailure, CreateReservationError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_reservation_failure::Error::IllegalReservation(CreateReservationFailureIllegalReservation { reservation_id, message }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    CreateReservationError::IllegalReservation { reservation_id, message }
+                }
+                create_reservation_failure::Error::ReservationConflict(CreateReservationFailureReservationConflict { reservation_id, other_reservation_id, other_user }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    let other_reservation_id: ReservationId = other_reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_reservation_id"))?
+                        .try_into()?;
+                    CreateReservationError::ReservationConflict { reservation_id, other_reservation_id, other_user }
+                }
+                create_reservation_failure::Error::ClusterDeployed(CreateReservationFailureClusterDeployed { reservation_id, cluster_id, deployed_by }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    let cluster_id: ClusterId = cluster_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                        .try_into()?;
+                    CreateReservationError::ClusterDeployed { reservation_id, cluster_id, deployed_by }
+                }
+                create_reservation_failure::Error::Internal(CreateReservationFailureInternal { reservation_id, cause }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    CreateReservationError::Internal { reservation_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteReservationError> for DeleteReservationFailure {
+        fn from(error: DeleteReservationError) -> Self {
+            let proto_error = match error {
+                DeleteReservationError::ReservationNotFound { reservation_id } => {
+                    delete_reservation_failure::Error::ReservationNotFound(DeleteReservationFailureReservationNotFound {
+                        reservation_id: Some(reservation_id.into()),
+                    })
+                }
+                DeleteReservationError::ReservedByOtherUser { reservation_id, user } => {
+                    delete_reservation_failure::Error::ReservedByOtherUser(DeleteReservationFailureReservedByOtherUser {
+                        reservation_id: Some(reservation_id.into()),
+                        user,
+                    })
+                }
+                DeleteReservationError::Internal { reservation_id, cause } => {
+                    delete_reservation_failure::Error::Internal(DeleteReservationFailureInternal {
+                        reservation_id: Some(reservation_id.into()),
+                        cause,
+                    })
+                }
+            };
+            DeleteReservationFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteReservationFailure> for DeleteReservationError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteReservationFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteReservationFailure, DeleteReservationError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_reservation_failure::Error::ReservationNotFound(DeleteReservationFailureReservationNotFound { reservation_id }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    DeleteReservationError::ReservationNotFound { reservation_id }
+                }
+                delete_reservation_failure::Error::ReservedByOtherUser(DeleteReservationFailureReservedByOtherUser { reservation_id, user }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    DeleteReservationError::ReservedByOtherUser { reservation_id, user }
+                }
+                delete_reservation_failure::Error::Internal(DeleteReservationFailureInternal { reservation_id, cause }) => {
+                    let reservation_id: ReservationId = reservation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("reservation_id"))?
+                        .try_into()?;
+                    DeleteReservationError::Internal { reservation_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod service_account_manager {
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::service_account::{ApiTokenId, ServiceAccountId, ServiceAccountName};
+
+    use crate::carl::service_account::{CreateServiceAccountError, DeleteServiceAccountError, IssueApiTokenError, RevokeApiTokenError};
+
+    tonic::include_proto!("opendut.carl.services.service_account_manager");
+
+    impl From<CreateServiceAccountError> for CreateServiceAccountFailure {
+        fn from(error: CreateServiceAccountError) -> Self {
+            let proto_error = match error {
+                CreateServiceAccountError::ServiceAccountAlreadyExists { service_account_id, name, other_service_account_id } => {
+                    create_service_account_failure::Error::ServiceAccountAlreadyExists(CreateServiceAccountFailureServiceAccountAlreadyExists {
+                        service_account_id: Some(service_account_id.into()),
+                        name: Some(name.into()),
+                        other_service_account_id: Some(other_service_account_id.into()),
+                    })
+                }
+                CreateServiceAccountError::Internal { service_account_id, cause } => {
+                    create_service_account_failure::Error::Internal(CreateServiceAccountFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        cause,
+                    })
+                }
+            };
+            CreateServiceAccountFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreateServiceAccountFailure> for CreateServiceAccountError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateServiceAccountFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateServiceAccountFailure, CreateServiceAccountError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_service_account_failure::Error::ServiceAccountAlreadyExists(CreateServiceAccountFailureServiceAccountAlreadyExists { service_account_id, name, other_service_account_id }) => {
+                    let service_account_id: ServiceAccountId = service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    let name: ServiceAccountName = name
+                        .ok_or_else(|| ErrorBuilder::field_not_set("name"))?
+                        .try_into()?;
+                    let other_service_account_id: ServiceAccountId = other_service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_service_account_id"))?
+                        .try_into()?;
+                    CreateServiceAccountError::ServiceAccountAlreadyExists { service_account_id, name, other_service_account_id }
+                }
+                create_service_account_failure::Error::Internal(CreateServiceAccountFailureInternal { service_account_id, cause }) => {
+                    let service_account_id: ServiceAccountId = service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    CreateServiceAccountError::Internal { service_account_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteServiceAccountError> for DeleteServiceAccountFailure {
+        fn from(error: DeleteServiceAccountError) -> Self {
+            let proto_error = match error {
+                DeleteServiceAccountError::ServiceAccountNotFound { service_account_id } => {
+                    delete_service_account_failure::Error::ServiceAccountNotFound(DeleteServiceAccountFailureServiceAccountNotFound {
+                        service_account_id: Some(service_account_id.into()),
+                    })
+                }
+                DeleteServiceAccountError::Internal { service_account_id, cause } => {
+                    delete_service_account_failure::Error::Internal(DeleteServiceAccountFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        cause,
+                    })
+                }
+            };
+            DeleteServiceAccountFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteServiceAccountFailure> for DeleteServiceAccountError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteServiceAccountFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteServiceAccountFailure, DeleteServiceAccountError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_service_account_failure::Error::ServiceAccountNotFound(DeleteServiceAccountFailureServiceAccountNotFound { service_account_id }) => {
+                    let service_account_id: ServiceAccountId = service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    DeleteServiceAccountError::ServiceAccountNotFound { service_account_id }
+                }
+                delete_service_account_failure::Error::Internal(DeleteServiceAccountFailureInternal { service_account_id, cause }) => {
+                    let service_account_id: ServiceAccountId = service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    DeleteServiceAccountError::Internal { service_account_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<IssueApiTokenError> for IssueApiTokenFailure {
+        fn from(error: IssueApiTokenError) -> Self {
+            let proto_error = match error {
+                IssueApiTokenError::ServiceAccountNotFound { api_token_id, service_account_id } => {
+                    issue_api_token_failure::Error::ServiceAccountNotFound(IssueApiTokenFailureServiceAccountNotFound {
+                        api_token_id: Some(api_token_id.into()),
+                        service_account_id: Some(service_account_id.into()),
+                    })
+                }
+                IssueApiTokenError::IllegalApiToken { api_token_id, message } => {
+                    issue_api_token_failure::Error::IllegalApiToken(IssueApiTokenFailureIllegalApiToken {
+                        api_token_id: Some(api_token_id.into()),
+                        message,
+                    })
+                }
+                IssueApiTokenError::Internal { api_token_id, cause } => {
+                    issue_api_token_failure::Error::Internal(IssueApiTokenFailureInternal {
+                        api_token_id: Some(api_token_id.into()),
+                        cause,
+                    })
+                }
+            };
+            IssueApiTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<IssueApiTokenFailure> for IssueApiTokenError {
+        type Error = ConversionError;
+        fn try_from(failure: IssueApiTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<IssueApiTokenFailure, IssueApiTokenError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                issue_api_token_failure::Error::ServiceAccountNotFound(IssueApiTokenFailureServiceAccountNotFound { api_token_id, service_account_id }) => {
+                    let api_token_id: ApiTokenId = api_token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("api_token_id"))?
+                        .try_into()?;
+                    let service_account_id: ServiceAccountId = service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    IssueApiTokenError::ServiceAccountNotFound { api_token_id, service_account_id }
+                }
+                issue_api_token_failure::Error::IllegalApiToken(IssueApiTokenFailureIllegalApiToken { api_token_id, message }) => {
+                    let api_token_id: ApiTokenId = api_token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("api_token_id"))?
+                        .try_into()?;
+                    IssueApiTokenError::IllegalApiToken { api_token_id, message }
+                }
+                issue_api_token_failure::Error::Internal(IssueApiTokenFailureInternal { api_token_id, cause }) => {
+                    let api_token_id: ApiTokenId = api_token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("api_token_id"))?
+                        .try_into()?;
+                    IssueApiTokenError::Internal { api_token_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<RevokeApiTokenError> for RevokeApiTokenFailure {
+        fn from(error: RevokeApiTokenError) -> Self {
+            let proto_error = match error {
+                RevokeApiTokenError::ApiTokenNotFound { api_token_id } => {
+                    revoke_api_token_failure::Error::ApiTokenNotFound(RevokeApiTokenFailureApiTokenNotFound {
+                        api_token_id: Some(api_token_id.into()),
+                    })
+                }
+                RevokeApiTokenError::Internal { api_token_id, cause } => {
+                    revoke_api_token_failure::Error::Internal(RevokeApiTokenFailureInternal {
+                        api_token_id: Some(api_token_id.into()),
+                        cause,
+                    })
+                }
+            };
+            RevokeApiTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RevokeApiTokenFailure> for RevokeApiTokenError {
+        type Error = ConversionError;
+        fn try_from(failure: RevokeApiTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RevokeApiTokenFailure, RevokeApiTokenError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                revoke_api_token_failure::Error::ApiTokenNotFound(RevokeApiTokenFailureApiTokenNotFound { api_token_id }) => {
+                    let api_token_id: ApiTokenId = api_token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("api_token_id"))?
+                        .try_into()?;
+                    RevokeApiTokenError::ApiTokenNotFound { api_token_id }
+                }
+                revoke_api_token_failure::Error::Internal(RevokeApiTokenFailureInternal { api_token_id, cause }) => {
+                    let api_token_id: ApiTokenId = api_token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("api_token_id"))?
+                        .try_into()?;
+                    RevokeApiTokenError::Internal { api_token_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod webhook_manager {
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::webhook::WebhookId;
+
+    use crate::carl::webhook::{CreateWebhookError, DeleteWebhookError, ListWebhookDeliveriesError};
+
+    tonic::include_proto!("opendut.carl.services.webhook_manager");
+
+    impl From<CreateWebhookError> for CreateWebhookFailure {
+        fn from(error: CreateWebhookError) -> Self {
+            let proto_error = match error {
+                CreateWebhookError::WebhookAlreadyExists { webhook_id } => {
+                    create_webhook_failure::Error::WebhookAlreadyExists(CreateWebhookFailureWebhookAlreadyExists {
+                        webhook_id: Some(webhook_id.into()),
+                    })
+                }
+                CreateWebhookError::IllegalWebhook { webhook_id, message } => {
+                    create_webhook_failure::Error::IllegalWebhook(CreateWebhookFailureIllegalWebhook {
+                        webhook_id: Some(webhook_id.into()),
+                        message,
+                    })
+                }
+                CreateWebhookError::Internal { webhook_id, cause } => {
+                    create_webhook_failure::Error::Internal(CreateWebhookFailureInternal {
+                        webhook_id: Some(webhook_id.into()),
+                        cause,
+                    })
+                }
+            };
+            CreateWebhookFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreateWebhookFailure> for CreateWebhookError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateWebhookFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateWebhookFailure, CreateWebhookError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_webhook_failure::Error::WebhookAlreadyExists(CreateWebhookFailureWebhookAlreadyExists { webhook_id }) => {
+                    let webhook_id: WebhookId = webhook_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("webhook_id"))?
+                        .try_into()?;
+                    CreateWebhookError::WebhookAlreadyExists { webhook_id }
+                }
+                create_webhook_failure::Error::IllegalWebhook(CreateWebhookFailureIllegalWebhook { webhook_id, message }) => {
+                    let webhook_id: WebhookId = webhook_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("webhook_id"))?
+                        .try_into()?;
+                    CreateWebhookError::IllegalWebhook { webhook_id, message }
+                }
+                create_webhook_failure::Error::Internal(CreateWebhookFailureInternal { webhook_id, cause }) => {
+                    let webhook_id: WebhookId = webhook_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("webhook_id"))?
+                        .try_into()?;
+                    CreateWebhookError::Internal { webhook_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteWebhookError> for DeleteWebhookFailure {
+        fn from(error: DeleteWebhookError) -> Self {
+            let proto_error = match error {
+                DeleteWebhookError::WebhookNotFound { webhook_id } => {
+                    delete_webhook_failure::Error::WebhookNotFound(DeleteWebhookFailureWebhookNotFound {
+                        webhook_id: Some(webhook_id.into()),
+                    })
+                }
+                DeleteWebhookError::Internal { webhook_id, cause } => {
+                    delete_webhook_failure::Error::Internal(DeleteWebhookFailureInternal {
+                        webhook_id: Some(webhook_id.into()),
+                        cause,
+                    })
+                }
+            };
+            DeleteWebhookFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteWebhookFailure> for DeleteWebhookError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteWebhookFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteWebhookFailure, DeleteWebhookError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_webhook_failure::Error::WebhookNotFound(DeleteWebhookFailureWebhookNotFound { webhook_id }) => {
+                    let webhook_id: WebhookId = webhook_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("webhook_id"))?
+                        .try_into()?;
+                    DeleteWebhookError::WebhookNotFound { webhook_id }
+                }
+                delete_webhook_failure::Error::Internal(DeleteWebhookFailureInternal { webhook_id, cause }) => {
+                    let webhook_id: WebhookId = webhook_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("webhook_id"))?
+                        .try_into()?;
+                    DeleteWebhookError::Internal { webhook_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListWebhookDeliveriesError> for ListWebhookDeliveriesFailure {
+        fn from(error: ListWebhookDeliveriesError) -> Self {
+            let proto_error = match error {
+                ListWebhookDeliveriesError::WebhookNotFound { webhook_id } => {
+                    list_webhook_deliveries_failure::Error::WebhookNotFound(ListWebhookDeliveriesFailureWebhookNotFound {
+                        webhook_id: Some(webhook_id.into()),
+                    })
+                }
+            };
+            ListWebhookDeliveriesFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListWebhookDeliveriesFailure> for ListWebhookDeliveriesError {
+        type Error = ConversionError;
+        fn try_from(failure: ListWebhookDeliveriesFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListWebhookDeliveriesFailure, ListWebhookDeliveriesError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_webhook_deliveries_failure::Error::WebhookNotFound(ListWebhookDeliveriesFailureWebhookNotFound { webhook_id }) => {
+                    let webhook_id: WebhookId = webhook_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("webhook_id"))?
+                        .try_into()?;
+                    ListWebhookDeliveriesError::WebhookNotFound { webhook_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use std::ops::Not;
+
+use tracing::{debug, error, info, warn};
+
+pub use opendut_carl_api::carl::backup::{
+    ARCHIVE_FORMAT_VERSION,
+    ResourcesArchive,
+    RestoreBackupError,
+};
+use opendut_carl_api::carl::cluster::{CreateClusterConfigurationError, StoreClusterDeploymentError};
+use opendut_carl_api::carl::peer::StorePeerDescriptorError;
+use opendut_carl_api::carl::service_account::{CreateServiceAccountError, IssueApiTokenError};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::state::PeerState;
+use opendut_types::reservation::Reservation;
+use opendut_types::service_account::{ApiToken, ServiceAccount};
+use opendut_types::topology::DeviceDescriptor;
+use opendut_types::webhook::Webhook;
+use opendut_types::ShortName;
+
+use crate::actions;
+use crate::actions::{CreateClusterConfigurationParams, StorePeerDescriptorOptions, StorePeerDescriptorParams};
+use crate::cluster::manager::ClusterManagerRef;
+use crate::cluster::validation;
+use crate::peer::state::{assigned_cluster, require_unblocked};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+use crate::vpn::Vpn;
+
+pub struct CreateBackupParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_backup(params: CreateBackupParams) -> ResourcesArchive {
+
+    let archive = params.resources_manager.resources(|resources| {
+        ResourcesArchive {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            peer_descriptors: resources.iter::<PeerDescriptor>().cloned().collect(),
+            cluster_configurations: resources.iter::<ClusterConfiguration>().cloned().collect(),
+            cluster_deployments: resources.iter::<ClusterDeployment>().cloned().collect(),
+            reservations: resources.iter::<Reservation>().cloned().collect(),
+            service_accounts: resources.iter::<ServiceAccount>().cloned().collect(),
+            api_tokens: resources.iter::<ApiToken>().cloned().collect(),
+            webhooks: resources.iter::<Webhook>().cloned().collect(),
+        }
+    }).await;
+
+    info!("Created backup with {} peer(s), {} cluster configuration(s), {} cluster deployment(s), {} reservation(s), {} service account(s), {} API token(s) and {} webhook(s).",
+        archive.peer_descriptors.len(),
+        archive.cluster_configurations.len(),
+        archive.cluster_deployments.len(),
+        archive.reservations.len(),
+        archive.service_accounts.len(),
+        archive.api_tokens.len(),
+        archive.webhooks.len(),
+    );
+
+    archive
+}
+
+pub struct RestoreBackupParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub cluster_manager: ClusterManagerRef,
+    pub vpn: Vpn,
+    pub archive: ResourcesArchive,
+    pub options: StorePeerDescriptorOptions,
+    pub user: String,
+}
+
+/// Stores all resources of the archive, replacing resources with the same id.
+/// Peers are stored first, so that the VPN peers and devices exist, when the clusters are restored.
+/// Reservations, service accounts, API tokens and webhooks are stored last, so that restored reservations do not prevent
+/// redeploying the restored clusters.
+///
+/// The whole archive is validated before anything is stored, so that an archive, which cannot be restored, leaves CARL unchanged.
+/// Only failures, which cannot be detected beforehand, e.g. of the VPN or the storage, may interrupt a restore.
+/// Clusters, which cannot be redeployed right away, are restored undeployed.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn restore_backup(params: RestoreBackupParams) -> Result<(), RestoreBackupError> {
+
+    async fn inner(params: RestoreBackupParams) -> Result<(), RestoreBackupError> {
+
+        let archive = params.archive;
+
+        if archive.format_version != ARCHIVE_FORMAT_VERSION {
+            return Err(RestoreBackupError::UnsupportedFormatVersion {
+                actual_version: archive.format_version,
+                supported_version: ARCHIVE_FORMAT_VERSION,
+            });
+        }
+
+        params.resources_manager.resources(|resources| {
+            validate_archive(resources, &archive)
+        }).await?;
+
+        let peer_count = archive.peer_descriptors.len();
+        let cluster_configuration_count = archive.cluster_configurations.len();
+        let cluster_deployment_count = archive.cluster_deployments.len();
+        let other_count = archive.reservations.len() + archive.service_accounts.len() + archive.api_tokens.len() + archive.webhooks.len();
+
+        for peer_descriptor in archive.peer_descriptors {
+            let peer_id = peer_descriptor.id;
+            let peer_name = Clone::clone(&peer_descriptor.name);
+            debug!("Restoring peer descriptor of '{peer_name}' <{peer_id}>.");
+
+            actions::store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Clone::clone(&params.resources_manager),
+                vpn: Clone::clone(&params.vpn),
+                peer_descriptor,
+                expected_version: None,
+                options: Clone::clone(&params.options),
+            }).await
+            .map_err(|cause| RestoreBackupError::PeerDescriptor { peer_id, peer_name, cause: cause.to_string() })?;
+        }
+
+        for cluster_configuration in archive.cluster_configurations {
+            let cluster_id = cluster_configuration.id;
+            let cluster_name = Clone::clone(&cluster_configuration.name);
+            debug!("Restoring cluster configuration '{cluster_name}' <{cluster_id}>.");
+
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Clone::clone(&params.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await
+            .map_err(|cause| RestoreBackupError::ClusterConfiguration { cluster_id, cluster_name, cause: cause.to_string() })?;
+        }
+
+        for cluster_deployment in archive.cluster_deployments {
+            let cluster_id = cluster_deployment.id;
+            debug!("Restoring cluster deployment <{cluster_id}>.");
+
+            let result = params.cluster_manager.lock().await
+                .store_cluster_deployment(cluster_deployment, &params.user).await;
+
+            match result {
+                Ok(_) => {}
+                Err(StoreClusterDeploymentError::IllegalClusterState { actual_state, .. }) => {
+                    info!("Skipping cluster deployment <{cluster_id}>, since the cluster is already in state '{}'.", actual_state.short_name());
+                }
+                Err(cause @ (StoreClusterDeploymentError::Reserved { .. } | StoreClusterDeploymentError::Internal { .. })) => {
+                    warn!("Restored cluster deployment <{cluster_id}> could not be deployed yet:\n  {cause}");
+                }
+                Err(cause) => {
+                    return Err(RestoreBackupError::ClusterDeployment { cluster_id, cause: cause.to_string() });
+                }
+            }
+        }
+
+        params.resources_manager.resources_mut(|resources| {
+            for reservation in archive.reservations {
+                resources.insert(reservation.id, reservation);
+            }
+            for service_account in archive.service_accounts {
+                resources.insert(service_account.id, service_account);
+            }
+            for api_token in archive.api_tokens {
+                resources.insert(api_token.id, api_token);
+            }
+            for webhook in archive.webhooks {
+                resources.insert(webhook.id, webhook);
+            }
+        }).await
+        .map_err(|cause| RestoreBackupError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully restored backup with {peer_count} peer(s), {cluster_configuration_count} cluster configuration(s), {cluster_deployment_count} cluster deployment(s) and {other_count} other resource(s).");
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// Applies the checks of storing the archive's resources to a copy of the relevant resources, on which the archive is restored.
+fn validate_archive(resources: &Resources, archive: &ResourcesArchive) -> Result<(), RestoreBackupError> {
+
+    let mut restored = Resources::default();
+    for peer_descriptor in resources.iter::<PeerDescriptor>() {
+        let peer_id = peer_descriptor.id;
+        restored.insert(peer_id, Clone::clone(peer_descriptor));
+        if let Some(peer_configuration) = resources.get::<PeerConfiguration>(peer_id) {
+            restored.insert(peer_id, peer_configuration);
+        }
+        if let Some(peer_state) = resources.get::<PeerState>(peer_id) {
+            restored.insert(peer_id, peer_state);
+        }
+    }
+    for device in resources.iter::<DeviceDescriptor>() {
+        restored.insert(device.id, Clone::clone(device));
+    }
+    for cluster_configuration in resources.iter::<ClusterConfiguration>() {
+        let cluster_id = cluster_configuration.id;
+        restored.insert(cluster_id, Clone::clone(cluster_configuration));
+        if let Some(cluster_deployment) = resources.get::<ClusterDeployment>(cluster_id) {
+            restored.insert(cluster_id, cluster_deployment);
+        }
+        if let Some(cluster_state) = resources.get::<ClusterState>(cluster_id) {
+            restored.insert(cluster_id, cluster_state);
+        }
+    }
+
+    for peer_descriptor in &archive.peer_descriptors {
+        let peer_id = peer_descriptor.id;
+        let peer_name = Clone::clone(&peer_descriptor.name);
+
+        let error = if let Err((actual_state, required_states)) = require_unblocked(&restored, peer_id) {
+            Some(StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name: Clone::clone(&peer_name), actual_state, required_states })
+        } else {
+            assigned_cluster(&restored, peer_id)
+                .map(|cluster_id| StorePeerDescriptorError::ClusterMember { peer_id, peer_name: Clone::clone(&peer_name), cluster_id })
+        };
+        if let Some(cause) = error {
+            return Err(RestoreBackupError::PeerDescriptor { peer_id, peer_name, cause: cause.to_string() });
+        }
+
+        if let Some(old_peer_descriptor) = restored.get::<PeerDescriptor>(peer_id) {
+            for device in old_peer_descriptor.topology.devices {
+                restored.remove::<DeviceDescriptor>(device.id);
+            }
+        }
+        for device in &peer_descriptor.topology.devices {
+            restored.insert(device.id, Clone::clone(device));
+        }
+        restored.insert(peer_id, Clone::clone(peer_descriptor));
+    }
+
+    for cluster_configuration in &archive.cluster_configurations {
+        let cluster_id = cluster_configuration.id;
+        let cluster_name = Clone::clone(&cluster_configuration.name);
+
+        let errors = validation::validate_cluster_configuration(&restored, cluster_configuration);
+        if errors.is_empty().not() {
+            let cause = CreateClusterConfigurationError::IllegalClusterConfiguration { cluster_id, cluster_name: Clone::clone(&cluster_name), errors };
+            return Err(RestoreBackupError::ClusterConfiguration { cluster_id, cluster_name, cause: cause.to_string() });
+        }
+        restored.insert(cluster_id, Clone::clone(cluster_configuration));
+    }
+
+    for cluster_deployment in &archive.cluster_deployments {
+        let cluster_id = cluster_deployment.id;
+        if restored.get::<ClusterConfiguration>(cluster_id).is_none() {
+            let cause = StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id };
+            return Err(RestoreBackupError::ClusterDeployment { cluster_id, cause: cause.to_string() });
+        }
+    }
+
+    for service_account in &archive.service_accounts {
+        let service_account_id = service_account.id;
+        let other_service_account = archive.service_accounts.iter()
+            .chain(resources.iter::<ServiceAccount>()
+                .filter(|existing| archive.service_accounts.iter().any(|restored| restored.id == existing.id).not()))
+            .find(|other| other.id != service_account_id && other.name == service_account.name);
+        if let Some(other) = other_service_account {
+            let cause = CreateServiceAccountError::ServiceAccountAlreadyExists {
+                service_account_id,
+                name: Clone::clone(&service_account.name),
+                other_service_account_id: other.id,
+            };
+            return Err(RestoreBackupError::ServiceAccount {
+                service_account_id,
+                service_account_name: Clone::clone(&service_account.name),
+                cause: cause.to_string(),
+            });
+        }
+    }
+
+    for api_token in &archive.api_tokens {
+        let service_account_id = api_token.service_account;
+        let service_account_exists = archive.service_accounts.iter().any(|service_account| service_account.id == service_account_id)
+            || resources.get::<ServiceAccount>(service_account_id).is_some();
+        if service_account_exists.not() {
+            let cause = IssueApiTokenError::ServiceAccountNotFound { api_token_id: api_token.id, service_account_id };
+            return Err(RestoreBackupError::ApiToken { api_token_id: api_token.id, cause: cause.to_string() });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::peer::setup::{IssuedPeerSetup, IssuedPeerSetupState, PeerSetupNonce};
+    use opendut_types::project::ProjectName;
+    use opendut_types::reservation::ReservationId;
+    use opendut_types::service_account::{ApiTokenId, ApiTokenRole, ServiceAccountId, ServiceAccountName};
+    use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+    use opendut_types::webhook::{WebhookEventKind, WebhookId};
+
+    use crate::cluster::manager::{ClusterManager, ClusterManagerOptions};
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+    use crate::settings;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_restore_created_backup_into_another_instance() -> anyhow::Result<()> {
+
+        let options = StorePeerDescriptorOptions {
+            bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+        };
+
+        let peer_id = PeerId::random();
+        let device_id = DeviceId::random();
+        let peer_descriptor = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("BackupPeer").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology {
+                devices: vec![
+                    DeviceDescriptor {
+                        id: device_id,
+                        name: DeviceName::try_from("BackupDevice").unwrap(),
+                        description: DeviceDescription::try_from("Device in backup").ok(),
+                        interface: NetworkInterfaceDescriptor {
+                            name: NetworkInterfaceName::try_from("eth0").unwrap(),
+                            configuration: NetworkInterfaceConfiguration::Ethernet,
+                        },
+                        tags: vec![],
+                    },
+                ],
+            },
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        };
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("BackupCluster").unwrap(),
+            leader: peer_id,
+            devices: HashSet::from([device_id]),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+
+        let source = ResourcesManager::new();
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&source),
+            vpn: Vpn::Disabled,
+            peer_descriptor: Clone::clone(&peer_descriptor),
+            expected_version: None,
+            options: Clone::clone(&options),
+        }).await?;
+        source.insert(cluster_id, Clone::clone(&cluster_configuration)).await?;
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let reservation = Reservation {
+            id: ReservationId::random(),
+            user: String::from("alice"),
+            peers: HashSet::from([peer_id]),
+            devices: HashSet::new(),
+            start,
+            end: start + Duration::from_secs(3600),
+        };
+        let service_account = ServiceAccount {
+            id: ServiceAccountId::random(),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            created_by: String::from("alice"),
+        };
+        let api_token = ApiToken {
+            id: ApiTokenId::random(),
+            service_account: service_account.id,
+            role: ApiTokenRole::Operator,
+            projects: HashSet::from([ProjectName::default()]),
+            expires_at: start + Duration::from_secs(3600),
+            secret_hash: String::from("0123456789abcdef"),
+        };
+        let webhook = Webhook {
+            id: WebhookId::random(),
+            url: url::Url::parse("https://example.com/hook")?,
+            events: HashSet::from([WebhookEventKind::PeerDown]),
+            secret: String::from("secret"),
+            created_by: String::from("alice"),
+        };
+        let peer_setup = IssuedPeerSetup {
+            nonce: PeerSetupNonce::random(),
+            peer_id,
+            issued_at: start,
+            expires_at: start + Duration::from_secs(3600),
+            state: IssuedPeerSetupState::Outstanding,
+            client_id: None,
+        };
+        source.insert(reservation.id, Clone::clone(&reservation)).await?;
+        source.insert(service_account.id, Clone::clone(&service_account)).await?;
+        source.insert(api_token.id, Clone::clone(&api_token)).await?;
+        source.insert(webhook.id, Clone::clone(&webhook)).await?;
+        source.insert(peer_setup.nonce, peer_setup).await?;
+
+        let archive = create_backup(CreateBackupParams { resources_manager: Arc::clone(&source) }).await;
+        let archive = ResourcesArchive::decode(&archive.encode())?;
+
+        let target = ResourcesManager::new();
+        let settings = settings::load_defaults()?;
+        let cluster_manager = ClusterManager::new(
+            Arc::clone(&target),
+            PeerMessagingBroker::new(Arc::clone(&target), PeerMessagingBrokerOptions::load(&settings.config)?),
+            Vpn::Disabled,
+            ClusterManagerOptions::load(&settings.config)?,
+        );
+
+        restore_backup(RestoreBackupParams {
+            resources_manager: Arc::clone(&target),
+            cluster_manager,
+            vpn: Vpn::Disabled,
+            archive,
+            options,
+            user: String::from("tester"),
+        }).await?;
+
+        assert_that!(target.get::<PeerDescriptor>(peer_id).await, some(eq(peer_descriptor)));
+        assert_that!(target.get::<DeviceDescriptor>(device_id).await, some(anything()));
+        assert_that!(target.get::<ClusterConfiguration>(cluster_id).await, some(eq(cluster_configuration)));
+        assert_that!(target.get::<ClusterDeployment>(cluster_id).await, none());
+        assert_that!(target.get::<Reservation>(reservation.id).await, some(eq(reservation)));
+        assert_that!(target.get::<ServiceAccount>(service_account.id).await, some(eq(service_account)));
+        assert_that!(target.get::<ApiToken>(api_token.id).await, some(eq(api_token)));
+        assert_that!(target.get::<Webhook>(webhook.id).await, some(eq(webhook)));
+
+        // bound to the issuing instance, so peers have to be set up anew
+        let issued_peer_setups = target.resources(|resources| resources.iter::<IssuedPeerSetup>().count()).await;
+        assert_that!(issued_peer_setups, eq(0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_store_anything_if_the_archive_cannot_be_restored_completely() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let settings = settings::load_defaults()?;
+        let cluster_manager = ClusterManager::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions::load(&settings.config)?),
+            Vpn::Disabled,
+            ClusterManagerOptions::load(&settings.config)?,
+        );
+
+        let peer_id = PeerId::random();
+        let peer_descriptor = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("BackupPeer").unwrap(),
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology { devices: vec![] },
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        };
+        let cluster_id = ClusterId::random();
+        let missing_device_id = DeviceId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("BackupCluster").unwrap(),
+            leader: peer_id,
+            devices: HashSet::from([missing_device_id]),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+
+        let result = restore_backup(RestoreBackupParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_manager,
+            vpn: Vpn::Disabled,
+            archive: ResourcesArchive {
+                format_version: ARCHIVE_FORMAT_VERSION,
+                peer_descriptors: vec![peer_descriptor],
+                cluster_configurations: vec![cluster_configuration],
+                cluster_deployments: vec![],
+                reservations: vec![],
+                service_accounts: vec![],
+                api_tokens: vec![],
+                webhooks: vec![],
+            },
+            options: StorePeerDescriptorOptions {
+                bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+            },
+            user: String::from("tester"),
+        }).await;
+
+        assert_that!(result, err(matches_pattern!(RestoreBackupError::ClusterConfiguration {
+            cluster_id: eq(cluster_id),
+            cause: contains_substring(missing_device_id.to_string()),
+        })));
+        assert_that!(resources_manager.get::<PeerDescriptor>(peer_id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_service_accounts_with_the_name_of_another_service_account() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let settings = settings::load_defaults()?;
+        let cluster_manager = ClusterManager::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions::load(&settings.config)?),
+            Vpn::Disabled,
+            ClusterManagerOptions::load(&settings.config)?,
+        );
+
+        let existing = ServiceAccount {
+            id: ServiceAccountId::random(),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            created_by: String::from("alice"),
+        };
+        resources_manager.insert(existing.id, Clone::clone(&existing)).await?;
+
+        let restored = ServiceAccount { id: ServiceAccountId::random(), ..Clone::clone(&existing) };
+        let webhook = Webhook {
+            id: WebhookId::random(),
+            url: url::Url::parse("https://example.com/hook")?,
+            events: HashSet::new(),
+            secret: String::from("secret"),
+            created_by: String::from("alice"),
+        };
+
+        let result = restore_backup(RestoreBackupParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_manager,
+            vpn: Vpn::Disabled,
+            archive: ResourcesArchive {
+                format_version: ARCHIVE_FORMAT_VERSION,
+                peer_descriptors: vec![],
+                cluster_configurations: vec![],
+                cluster_deployments: vec![],
+                reservations: vec![],
+                service_accounts: vec![Clone::clone(&restored)],
+                api_tokens: vec![],
+                webhooks: vec![Clone::clone(&webhook)],
+            },
+            options: StorePeerDescriptorOptions {
+                bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+            },
+            user: String::from("tester"),
+        }).await;
+
+        assert_that!(result, err(matches_pattern!(RestoreBackupError::ServiceAccount {
+            service_account_id: eq(restored.id),
+        })));
+        assert_that!(resources_manager.get::<ServiceAccount>(restored.id).await, none());
+        assert_that!(resources_manager.get::<Webhook>(webhook.id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_archive_with_unsupported_format_version() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let settings = settings::load_defaults()?;
+        let cluster_manager = ClusterManager::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions::load(&settings.config)?),
+            Vpn::Disabled,
+            ClusterManagerOptions::load(&settings.config)?,
+        );
+
+        let result = restore_backup(RestoreBackupParams {
+            resources_manager,
+            cluster_manager,
+            vpn: Vpn::Disabled,
+            archive: ResourcesArchive {
+                format_version: ARCHIVE_FORMAT_VERSION + 1,
+                peer_descriptors: vec![],
+                cluster_configurations: vec![],
+                cluster_deployments: vec![],
+                reservations: vec![],
+                service_accounts: vec![],
+                api_tokens: vec![],
+                webhooks: vec![],
+            },
+            options: StorePeerDescriptorOptions {
+                bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+            },
+            user: String::from("tester"),
+        }).await;
+
+        assert_that!(result, err(matches_pattern!(RestoreBackupError::UnsupportedFormatVersion {
+            actual_version: eq(ARCHIVE_FORMAT_VERSION + 1),
+        })));
+
+        Ok(())
+    }
+}
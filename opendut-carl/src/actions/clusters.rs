@@ -1,15 +1,23 @@
+use std::ops::Not;
+
 use tracing::{debug, error, info};
 pub use opendut_carl_api::carl::cluster::{
     CreateClusterConfigurationError,
-    DeleteClusterConfigurationError
+    DeleteClusterConfigurationError,
+    IllegalClusterConfigurationError,
 };
 use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::cluster::state::ClusterState;
+use opendut_types::resources::Version;
 
+use crate::cluster::{state, validation};
 use crate::resources::manager::ResourcesManagerRef;
 
 pub struct CreateClusterConfigurationParams {
     pub resources_manager: ResourcesManagerRef,
     pub cluster_configuration: ClusterConfiguration,
+    /// If set, the cluster configuration is only stored, when it is currently in this version.
+    pub expected_version: Option<Version>,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -24,8 +32,31 @@ pub async fn create_cluster_configuration(params: CreateClusterConfigurationPara
         debug!("Creating cluster configuration '{cluster_name}' <{cluster_id}>.");
 
         resources_manager.resources_mut(|resources| {
+            if let Some(expected_version) = params.expected_version {
+                let actual_version = resources.version::<ClusterConfiguration>(cluster_id);
+                if actual_version != expected_version {
+                    return Err(CreateClusterConfigurationError::VersionConflict {
+                        cluster_id,
+                        cluster_name: Clone::clone(&cluster_name),
+                        expected_version,
+                        actual_version,
+                    });
+                }
+            }
+
+            let errors = validation::validate_cluster_configuration(resources, &params.cluster_configuration);
+            if errors.is_empty().not() {
+                return Err(CreateClusterConfigurationError::IllegalClusterConfiguration {
+                    cluster_id,
+                    cluster_name: Clone::clone(&cluster_name),
+                    errors,
+                });
+            }
+
             resources.insert(cluster_id, params.cluster_configuration);
-        }).await;
+            Ok(())
+        }).await
+        .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: Clone::clone(&cluster_name), cause: cause.to_string() })??;
 
         info!("Successfully created cluster configuration '{cluster_name}' <{cluster_id}>.");
 
@@ -51,10 +82,32 @@ pub async fn delete_cluster_configuration(params: DeleteClusterConfigurationPara
 
         debug!("Deleting cluster configuration <{cluster_id}>.");
 
+        let mut cluster_name = None;
+
         let cluster_configuration = resources_manager.resources_mut(|resources| {
-            resources.remove::<ClusterConfiguration>(cluster_id)
-                .ok_or_else(|| DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })
-        }).await?;
+            let cluster_configuration = resources.get::<ClusterConfiguration>(cluster_id)
+                .ok_or(DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })?;
+            cluster_name = Some(Clone::clone(&cluster_configuration.name));
+
+            let actual_state = state::cluster_state(resources, cluster_id);
+            if actual_state != ClusterState::Undeployed {
+                return Err(DeleteClusterConfigurationError::IllegalClusterState {
+                    cluster_id,
+                    cluster_name: cluster_configuration.name,
+                    actual_state,
+                    required_states: vec![ClusterState::Undeployed],
+                });
+            }
+
+            resources.remove::<ClusterConfiguration>(cluster_id);
+            resources.remove::<ClusterState>(cluster_id);
+            Ok(cluster_configuration)
+        }).await
+        .map_err(|cause| DeleteClusterConfigurationError::Internal {
+            cluster_id,
+            cluster_name: cluster_name.expect("cluster name should be known, since resources were only changed after the cluster configuration was found"),
+            cause: cause.to_string(),
+        })??;
 
         let cluster_name = Clone::clone(&cluster_configuration.name);
 
@@ -66,3 +119,178 @@ pub async fn delete_cluster_configuration(params: DeleteClusterConfigurationPara
     inner(params).await
         .inspect_err(|err| error!("{err}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterDeployment, ClusterName};
+    use opendut_types::cluster::state::DeployedClusterState;
+    use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::project::ProjectName;
+    use opendut_types::topology::{DeviceDescriptor, DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+
+    use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn create_cluster_configuration_should_reject_illegal_configurations() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let (peer_a, device_a) = store_peer(&resources_manager, "PeerA").await?;
+        let (peer_b, device_b) = store_peer(&resources_manager, "PeerB").await?;
+
+        let unknown_leader = PeerId::random();
+        let unknown_device = DeviceId::random();
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: ClusterId::random(),
+                name: ClusterName::try_from("IllegalCluster")?,
+                leader: unknown_leader,
+                devices: HashSet::from([device_a, unknown_device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            },
+            expected_version: None,
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateClusterConfigurationError::IllegalClusterConfiguration {
+            errors: unordered_elements_are![
+                eq(IllegalClusterConfigurationError::LeaderNotFound { leader_id: unknown_leader }),
+                eq(IllegalClusterConfigurationError::DeviceNotFound { device_id: unknown_device }),
+            ],
+        })));
+
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: ClusterId::random(),
+                name: ClusterName::try_from("OtherProjectCluster")?,
+                leader: peer_a,
+                devices: HashSet::from([device_b]),
+                device_selectors: vec![],
+                project: ProjectName::try_from("other-team")?,
+            },
+            expected_version: None,
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateClusterConfigurationError::IllegalClusterConfiguration {
+            errors: unordered_elements_are![
+                eq(IllegalClusterConfigurationError::LeaderInOtherProject { leader_id: peer_a, leader_project: ProjectName::default() }),
+                eq(IllegalClusterConfigurationError::DeviceInOtherProject { device_id: device_b, device_project: ProjectName::default() }),
+            ],
+        })));
+
+        let cluster_id = ClusterId::random();
+        let cluster_name = ClusterName::try_from("DeployedCluster")?;
+        create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: cluster_id,
+                name: Clone::clone(&cluster_name),
+                leader: peer_a,
+                devices: HashSet::from([device_a, device_b]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            },
+            expected_version: None,
+        }).await?;
+
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: ClusterId::random(),
+                name: Clone::clone(&cluster_name),
+                leader: unknown_leader,
+                devices: HashSet::from([device_b]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            },
+            expected_version: None,
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateClusterConfigurationError::IllegalClusterConfiguration {
+            errors: unordered_elements_are![
+                eq(IllegalClusterConfigurationError::NameInUse { other_cluster_id: cluster_id, other_cluster_name: Clone::clone(&cluster_name) }),
+                eq(IllegalClusterConfigurationError::LeaderNotFound { leader_id: unknown_leader }),
+            ],
+        })));
+
+        let other_project = ProjectName::try_from("other-project")?;
+        let (other_project_peer, _) = store_peer_in_project(&resources_manager, "OtherProjectPeer", Clone::clone(&other_project)).await?;
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: ClusterId::random(),
+                name: Clone::clone(&cluster_name),
+                leader: other_project_peer,
+                devices: HashSet::new(),
+                device_selectors: vec![],
+                project: other_project,
+            },
+            expected_version: None,
+        }).await;
+        assert_that!(result, ok(anything()));
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(cluster_id, ClusterDeployment { id: cluster_id, devices: HashSet::from([device_a, device_b]), deployed_by: String::from("tester") });
+            resources.insert(cluster_id, ClusterState::Deployed(DeployedClusterState::Healthy));
+        }).await?;
+
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: ClusterId::random(),
+                name: ClusterName::try_from("OverlappingCluster")?,
+                leader: peer_b,
+                devices: HashSet::from([device_b]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            },
+            expected_version: None,
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateClusterConfigurationError::IllegalClusterConfiguration {
+            errors: elements_are![
+                eq(IllegalClusterConfigurationError::DeviceInUse { device_id: device_b, other_cluster_id: cluster_id, other_cluster_name: cluster_name }),
+            ],
+        })));
+
+        Ok(())
+    }
+
+    async fn store_peer(resources_manager: &ResourcesManagerRef, peer_name: &str) -> anyhow::Result<(PeerId, DeviceId)> {
+        store_peer_in_project(resources_manager, peer_name, ProjectName::default()).await
+    }
+
+    async fn store_peer_in_project(resources_manager: &ResourcesManagerRef, peer_name: &str, project: ProjectName) -> anyhow::Result<(PeerId, DeviceId)> {
+        let peer_id = PeerId::random();
+        let device = DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from(format!("{peer_name}_Device_1"))?,
+            description: None,
+            interface: NetworkInterfaceDescriptor {
+                name: NetworkInterfaceName::try_from("eth0")?,
+                configuration: NetworkInterfaceConfiguration::Ethernet,
+            },
+            tags: vec![],
+        };
+        let device_id = device.id;
+        let peer_descriptor = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from(peer_name)?,
+            location: None,
+            network: Default::default(),
+            topology: Topology { devices: vec![Clone::clone(&device)] },
+            executors: ExecutorDescriptors { executors: vec![] },
+            project,
+        };
+        resources_manager.resources_mut(|resources| {
+            resources.insert(device_id, device);
+            resources.insert(peer_id, peer_descriptor);
+        }).await?;
+        Ok((peer_id, device_id))
+    }
+}
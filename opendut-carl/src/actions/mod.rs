@@ -4,6 +4,7 @@ pub use clusters::{
     create_cluster_configuration,
     CreateClusterConfigurationParams,
     CreateClusterConfigurationError,
+    IllegalClusterConfigurationError,
 };
 
 pub use clusters::{
@@ -25,6 +26,37 @@ pub use peers::{
     GeneratePeerSetupError,
 };
 
+pub use peer_setups::{
+    list_peer_setups,
+    ListPeerSetupsParams,
+    ListPeerSetupsError,
+};
+
+pub use peer_setups::{
+    revoke_peer_setup,
+    RevokePeerSetupParams,
+    RevokePeerSetupError,
+};
+
+pub use peer_setups::{
+    consume_peer_setup,
+    ConsumePeerSetupParams,
+    ConsumePeerSetupError,
+    rebind_consumed_peer_setups,
+};
+
+pub use peer_certificates::{
+    issue_peer_certificate,
+    IssuePeerCertificateParams,
+    IssuePeerCertificateError,
+};
+
+pub use peer_certificates::{
+    authenticate_peer_certificate,
+    AuthenticatePeerCertificateParams,
+    AuthenticatePeerCertificateError,
+};
+
 pub use peers::{
     generate_cleo_setup,
     GenerateCleoSetupParams,
@@ -37,6 +69,12 @@ pub use peers::{
     DeletePeerDescriptorError,
 };
 
+pub use peers::{
+    get_peer_status_reports,
+    GetPeerStatusReportsParams,
+    GetPeerStatusReportsError,
+};
+
 pub use peers::{
     list_peer_descriptors,
     ListPeerDescriptorsParams,
@@ -55,5 +93,101 @@ pub use peers::{
     AssignClusterError,
 };
 
+pub use peers::{
+    unassign_cluster,
+    UnassignClusterParams,
+    UnassignClusterError,
+};
+
+pub use peers::{
+    PendingPeerConfiguration,
+    SendPeerConfigurationError,
+};
+
+pub use backup::{
+    create_backup,
+    CreateBackupParams,
+};
+
+pub use backup::{
+    restore_backup,
+    RestoreBackupParams,
+    RestoreBackupError,
+};
+
+pub use reservations::{
+    create_reservation,
+    CreateReservationParams,
+    CreateReservationError,
+};
+
+pub use reservations::{
+    delete_reservation,
+    DeleteReservationParams,
+    DeleteReservationError,
+};
+
+pub use reservations::{
+    list_reservations,
+    ListReservationsParams,
+};
+
+pub use service_accounts::{
+    create_service_account,
+    CreateServiceAccountParams,
+    CreateServiceAccountError,
+};
+
+pub use service_accounts::{
+    delete_service_account,
+    DeleteServiceAccountParams,
+    DeleteServiceAccountError,
+};
+
+pub use service_accounts::{
+    list_service_accounts,
+    ListServiceAccountsParams,
+};
+
+pub use service_accounts::{
+    issue_api_token,
+    IssueApiTokenParams,
+    IssueApiTokenError,
+};
+
+pub use service_accounts::{
+    revoke_api_token,
+    RevokeApiTokenParams,
+    RevokeApiTokenError,
+};
+
+pub use service_accounts::{
+    list_api_tokens,
+    ListApiTokensParams,
+};
+
+pub use webhooks::{
+    create_webhook,
+    CreateWebhookParams,
+    CreateWebhookError,
+};
+
+pub use webhooks::{
+    delete_webhook,
+    DeleteWebhookParams,
+    DeleteWebhookError,
+};
+
+pub use webhooks::{
+    list_webhooks,
+    ListWebhooksParams,
+};
+
+mod backup;
 mod peers;
+mod peer_setups;
+mod peer_certificates;
 mod clusters;
+mod reservations;
+mod service_accounts;
+mod webhooks;
@@ -0,0 +1,188 @@
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, error, info, warn};
+
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::certificate::{IssuedPeerCertificate, PeerCertificate, PeerCertificateId};
+
+use crate::peer::certificate_authority;
+use crate::peer::certificate_authority::PeerCertificateAuthorityRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+
+pub struct IssuePeerCertificateParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub certificate_authority: PeerCertificateAuthorityRef,
+    pub peer_id: PeerId,
+    pub validity: Duration,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IssuePeerCertificateError {
+    #[error("A certificate for peer <{peer_id}> could not be issued, because a peer with that ID does not exist!")]
+    PeerNotFound { peer_id: PeerId },
+    #[error("An internal error occurred while issuing a certificate for peer <{peer_id}>:\n  {cause}")]
+    Internal { peer_id: PeerId, cause: String },
+}
+
+/// Issues a client certificate for the peer. Expired certificates of the peer are forgotten along the way.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn issue_peer_certificate(params: IssuePeerCertificateParams) -> Result<(PeerCertificate, IssuedPeerCertificate), IssuePeerCertificateError> {
+
+    async fn inner(params: IssuePeerCertificateParams) -> Result<(PeerCertificate, IssuedPeerCertificate), IssuePeerCertificateError> {
+
+        let peer_id = params.peer_id;
+
+        debug!("Issuing client certificate for peer <{peer_id}>.");
+
+        let now = SystemTime::now();
+        let (certificate, issued) = params.certificate_authority.issue(peer_id, now, params.validity)
+            .map_err(|cause| IssuePeerCertificateError::Internal { peer_id, cause: cause.to_string() })?;
+
+        params.resources_manager.resources_mut(|resources| {
+            if resources.get::<PeerDescriptor>(peer_id).is_none() {
+                return Err(IssuePeerCertificateError::PeerNotFound { peer_id });
+            }
+            let expired = resources.iter::<IssuedPeerCertificate>()
+                .filter(|other| other.peer_id == peer_id && other.expires_at <= now)
+                .map(|other| other.id)
+                .collect::<Vec<_>>();
+            for certificate_id in expired {
+                resources.remove::<IssuedPeerCertificate>(certificate_id);
+            }
+            resources.insert(issued.id, Clone::clone(&issued));
+            Ok(())
+        }).await
+        .map_err(|cause| IssuePeerCertificateError::Internal { peer_id, cause: cause.to_string() })??;
+
+        info!("Successfully issued client certificate <{}> for peer <{peer_id}>.", issued.id);
+
+        Ok((certificate, issued))
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct AuthenticatePeerCertificateParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    /// DER-encoded client certificate, which the peer presented during the TLS handshake.
+    pub certificate: Vec<u8>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuthenticatePeerCertificateError {
+    #[error("Peer <{peer_id}> presented a client certificate, which was not issued by CARL or was revoked!")]
+    UnknownCertificate { peer_id: PeerId },
+    #[error("Client certificate <{certificate_id}> was issued for peer <{expected_peer_id}>, but used by peer <{peer_id}>!")]
+    PeerMismatch { peer_id: PeerId, certificate_id: PeerCertificateId, expected_peer_id: PeerId },
+    #[error("Client certificate <{certificate_id}> of peer <{peer_id}> expired!")]
+    Expired { peer_id: PeerId, certificate_id: PeerCertificateId },
+}
+
+/// Checks the client certificate, which the peer authenticated with, against the certificates CARL issued and did not revoke yet.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn authenticate_peer_certificate(params: AuthenticatePeerCertificateParams) -> Result<IssuedPeerCertificate, AuthenticatePeerCertificateError> {
+
+    async fn inner(params: AuthenticatePeerCertificateParams) -> Result<IssuedPeerCertificate, AuthenticatePeerCertificateError> {
+
+        let peer_id = params.peer_id;
+        let fingerprint = certificate_authority::fingerprint(&params.certificate);
+
+        let issued = params.resources_manager.resources(|resources| {
+            resources.iter::<IssuedPeerCertificate>()
+                .find(|issued| issued.fingerprint == fingerprint)
+                .cloned()
+        }).await
+            .ok_or(AuthenticatePeerCertificateError::UnknownCertificate { peer_id })?;
+
+        if issued.peer_id != peer_id {
+            return Err(AuthenticatePeerCertificateError::PeerMismatch { peer_id, certificate_id: issued.id, expected_peer_id: issued.peer_id });
+        }
+        if issued.expires_at <= SystemTime::now() {
+            return Err(AuthenticatePeerCertificateError::Expired { peer_id, certificate_id: issued.id });
+        }
+
+        Ok(issued)
+    }
+
+    inner(params).await
+        .inspect_err(|err| warn!("{err}"))
+}
+
+/// Revokes all client certificates issued for the peer, so it cannot connect with them anymore.
+pub(super) fn revoke_peer_certificates(resources: &mut Resources, peer_id: PeerId) {
+    let certificate_ids = resources.iter::<IssuedPeerCertificate>()
+        .filter(|issued| issued.peer_id == peer_id)
+        .map(|issued| issued.id)
+        .collect::<Vec<_>>();
+    for certificate_id in certificate_ids {
+        resources.remove::<IssuedPeerCertificate>(certificate_id);
+        debug!("Revoked client certificate <{certificate_id}> of peer <{peer_id}>.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerName;
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::project::ProjectName;
+
+    use crate::peer::certificate_authority::PeerCertificateAuthority;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    async fn authenticate(resources_manager: &ResourcesManagerRef, peer_id: PeerId, certificate: &PeerCertificate) -> std::result::Result<IssuedPeerCertificate, AuthenticatePeerCertificateError> {
+        authenticate_peer_certificate(AuthenticatePeerCertificateParams {
+            resources_manager: Arc::clone(resources_manager),
+            peer_id,
+            certificate: certificate.certificate.0.contents().to_vec(),
+        }).await
+    }
+
+    #[tokio::test]
+    async fn should_authenticate_issued_certificates_until_they_are_revoked() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let certificate_authority = Arc::new(PeerCertificateAuthority::generate()?);
+        let peer_id = PeerId::random();
+
+        let issue = || issue_peer_certificate(IssuePeerCertificateParams {
+            resources_manager: Arc::clone(&resources_manager),
+            certificate_authority: Arc::clone(&certificate_authority),
+            peer_id,
+            validity: Duration::from_secs(3600),
+        });
+
+        let result = issue().await;
+        assert_that!(result, err(matches_pattern!(IssuePeerCertificateError::PeerNotFound { .. })));
+
+        resources_manager.insert(peer_id, PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("CertifiedPeer")?,
+            location: None,
+            network: Default::default(),
+            topology: Default::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        }).await?;
+        let (certificate, issued) = issue().await?;
+
+        let authenticated = authenticate(&resources_manager, peer_id, &certificate).await?;
+        assert_that!(authenticated, eq(Clone::clone(&issued)));
+
+        let result = authenticate(&resources_manager, PeerId::random(), &certificate).await;
+        assert_that!(result, err(matches_pattern!(AuthenticatePeerCertificateError::PeerMismatch { .. })));
+
+        resources_manager.resources_mut(|resources| revoke_peer_certificates(resources, peer_id)).await?;
+        let result = authenticate(&resources_manager, peer_id, &certificate).await;
+        assert_that!(result, err(matches_pattern!(AuthenticatePeerCertificateError::UnknownCertificate { .. })));
+
+        Ok(())
+    }
+}
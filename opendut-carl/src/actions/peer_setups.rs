@@ -0,0 +1,308 @@
+use std::time::SystemTime;
+
+use tracing::{debug, error, info, warn};
+
+pub use opendut_carl_api::carl::peer::{
+    ListPeerSetupsError,
+    RevokePeerSetupError,
+};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::setup::{IssuedPeerSetup, IssuedPeerSetupState, PeerSetupNonce};
+use opendut_types::util::net::ClientId;
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+
+pub struct ListPeerSetupsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_peer_setups(params: ListPeerSetupsParams) -> Result<Vec<IssuedPeerSetup>, ListPeerSetupsError> {
+    let peer_id = params.peer_id;
+
+    params.resources_manager.resources(|resources| {
+        if resources.get::<PeerDescriptor>(peer_id).is_none() {
+            return Err(ListPeerSetupsError::PeerNotFound { peer_id });
+        }
+        let mut setups = resources.iter::<IssuedPeerSetup>()
+            .filter(|setup| setup.peer_id == peer_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        setups.sort_by_key(|setup| setup.issued_at);
+        Ok(setups)
+    }).await
+}
+
+pub struct RevokePeerSetupParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub nonce: PeerSetupNonce,
+}
+
+/// Revokes the setup-string. If it was already consumed, the peer cannot connect with it anymore either.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn revoke_peer_setup(params: RevokePeerSetupParams) -> Result<IssuedPeerSetup, RevokePeerSetupError> {
+
+    async fn inner(params: RevokePeerSetupParams) -> Result<IssuedPeerSetup, RevokePeerSetupError> {
+
+        let nonce = params.nonce;
+
+        debug!("Revoking setup-string <{nonce}>.");
+
+        let setup = params.resources_manager.resources_mut(|resources| {
+            let mut setup = resources.get::<IssuedPeerSetup>(nonce)
+                .ok_or(RevokePeerSetupError::PeerSetupNotFound { nonce })?;
+            if matches!(setup.state, IssuedPeerSetupState::Revoked { .. }) {
+                return Ok(setup);
+            }
+            setup.state = IssuedPeerSetupState::Revoked { at: SystemTime::now() };
+            resources.insert(nonce, Clone::clone(&setup));
+            Ok(setup)
+        }).await
+        .map_err(|cause| RevokePeerSetupError::Internal { nonce, cause: cause.to_string() })??;
+
+        info!("Successfully revoked setup-string <{nonce}> of peer <{}>.", setup.peer_id);
+
+        Ok(setup)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ConsumePeerSetupParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    pub nonce: PeerSetupNonce,
+    /// OIDC client, which authenticated the connecting peer. `None`, if authentication is disabled.
+    pub client_id: Option<ClientId>,
+    /// Fingerprint of the client certificate, which the connecting peer authenticated with.
+    pub certificate_fingerprint: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConsumePeerSetupError {
+    #[error("Setup-string <{nonce}> used by peer <{peer_id}> was not issued by CARL!")]
+    PeerSetupNotFound { peer_id: PeerId, nonce: PeerSetupNonce },
+    #[error("Setup-string <{nonce}> was issued for peer <{expected_peer_id}>, but used by peer <{peer_id}>!")]
+    PeerMismatch { peer_id: PeerId, nonce: PeerSetupNonce, expected_peer_id: PeerId },
+    #[error("Setup-string <{nonce}> of peer <{peer_id}> expired before it was used!")]
+    Expired { peer_id: PeerId, nonce: PeerSetupNonce },
+    #[error("Setup-string <{nonce}> of peer <{peer_id}> was revoked!")]
+    Revoked { peer_id: PeerId, nonce: PeerSetupNonce },
+    #[error("Peer <{peer_id}> authenticated as OIDC client '{}', but setup-string <{nonce}> was issued for OIDC client '{}'!", display_client_id(.actual_client_id), .expected_client_id.0)]
+    ClientMismatch { peer_id: PeerId, nonce: PeerSetupNonce, expected_client_id: ClientId, actual_client_id: Option<ClientId> },
+    #[error("Setup-string <{nonce}> of peer <{peer_id}> was already consumed by a connection with another client certificate!")]
+    AlreadyConsumed { peer_id: PeerId, nonce: PeerSetupNonce },
+    #[error("Setup-string <{nonce}> of peer <{peer_id}> could not be checked, due to internal errors:\n  {cause}")]
+    Internal { peer_id: PeerId, nonce: PeerSetupNonce, cause: String },
+}
+
+fn display_client_id(client_id: &Option<ClientId>) -> &str {
+    client_id.as_ref()
+        .map(|client_id| client_id.0.as_str())
+        .unwrap_or("<none>")
+}
+
+/// Checks the setup-string, which EDGAR was set up with, whenever it connects.
+///
+/// An outstanding setup-string is marked as consumed on the first connect and bound to the client certificate the peer connected with.
+/// It remains valid for reconnects with that certificate (or its renewals), until it is revoked. If an OIDC client was registered along with the setup-string, the peer must be authenticated as that client.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn consume_peer_setup(params: ConsumePeerSetupParams) -> Result<(), ConsumePeerSetupError> {
+
+    async fn inner(params: ConsumePeerSetupParams) -> Result<(), ConsumePeerSetupError> {
+
+        let peer_id = params.peer_id;
+        let nonce = params.nonce;
+
+        params.resources_manager.resources_mut(|resources| {
+            let mut setup = resources.get::<IssuedPeerSetup>(nonce)
+                .ok_or(ConsumePeerSetupError::PeerSetupNotFound { peer_id, nonce })?;
+
+            if setup.peer_id != peer_id {
+                return Err(ConsumePeerSetupError::PeerMismatch { peer_id, nonce, expected_peer_id: setup.peer_id });
+            }
+
+            if let Some(expected_client_id) = &setup.client_id {
+                if params.client_id.as_ref() != Some(expected_client_id) {
+                    return Err(ConsumePeerSetupError::ClientMismatch {
+                        peer_id,
+                        nonce,
+                        expected_client_id: Clone::clone(expected_client_id),
+                        actual_client_id: params.client_id,
+                    });
+                }
+            }
+
+            match setup.state {
+                IssuedPeerSetupState::Outstanding => {
+                    let now = SystemTime::now();
+                    if setup.is_expired_at(now) {
+                        return Err(ConsumePeerSetupError::Expired { peer_id, nonce });
+                    }
+                    setup.state = IssuedPeerSetupState::Consumed { at: now, certificate_fingerprint: params.certificate_fingerprint };
+                    resources.insert(nonce, setup);
+                    info!("Peer <{peer_id}> consumed its setup-string <{nonce}>.");
+                    Ok(())
+                }
+                IssuedPeerSetupState::Consumed { certificate_fingerprint, .. } => {
+                    if certificate_fingerprint == params.certificate_fingerprint {
+                        Ok(())
+                    } else {
+                        Err(ConsumePeerSetupError::AlreadyConsumed { peer_id, nonce })
+                    }
+                }
+                IssuedPeerSetupState::Revoked { .. } => Err(ConsumePeerSetupError::Revoked { peer_id, nonce }),
+            }
+        }).await
+        .map_err(|cause| ConsumePeerSetupError::Internal { peer_id, nonce, cause: cause.to_string() })?
+    }
+
+    inner(params).await
+        .inspect_err(|err| warn!("{err}"))
+}
+
+/// Moves the binding of consumed setup-strings from a client certificate of the peer to its renewal.
+pub fn rebind_consumed_peer_setups(resources: &mut Resources, peer_id: PeerId, previous_fingerprint: &str, renewed_fingerprint: &str) {
+    let setups = resources.iter::<IssuedPeerSetup>()
+        .filter(|setup| setup.peer_id == peer_id)
+        .filter(|setup| matches!(&setup.state, IssuedPeerSetupState::Consumed { certificate_fingerprint, .. } if certificate_fingerprint == previous_fingerprint))
+        .cloned()
+        .collect::<Vec<_>>();
+    for mut setup in setups {
+        if let IssuedPeerSetupState::Consumed { certificate_fingerprint, .. } = &mut setup.state {
+            *certificate_fingerprint = renewed_fingerprint.to_owned();
+        }
+        resources.insert(setup.nonce, setup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn issued_peer_setup(peer_id: PeerId, expires_in: Duration, client_id: Option<ClientId>) -> IssuedPeerSetup {
+        let now = SystemTime::now();
+        IssuedPeerSetup {
+            nonce: PeerSetupNonce::random(),
+            peer_id,
+            issued_at: now,
+            expires_at: now + expires_in,
+            state: IssuedPeerSetupState::Outstanding,
+            client_id,
+        }
+    }
+
+    const CERTIFICATE_FINGERPRINT: &str = "a1b2c3";
+
+    async fn consume(resources_manager: &ResourcesManagerRef, peer_id: PeerId, nonce: PeerSetupNonce) -> std::result::Result<(), ConsumePeerSetupError> {
+        consume_with(resources_manager, peer_id, nonce, None, CERTIFICATE_FINGERPRINT).await
+    }
+
+    async fn consume_as(resources_manager: &ResourcesManagerRef, peer_id: PeerId, nonce: PeerSetupNonce, client_id: Option<&str>) -> std::result::Result<(), ConsumePeerSetupError> {
+        consume_with(resources_manager, peer_id, nonce, client_id, CERTIFICATE_FINGERPRINT).await
+    }
+
+    async fn consume_with(resources_manager: &ResourcesManagerRef, peer_id: PeerId, nonce: PeerSetupNonce, client_id: Option<&str>, certificate_fingerprint: &str) -> std::result::Result<(), ConsumePeerSetupError> {
+        consume_peer_setup(ConsumePeerSetupParams {
+            resources_manager: Arc::clone(resources_manager),
+            peer_id,
+            nonce,
+            client_id: client_id.map(|client_id| ClientId(String::from(client_id))),
+            certificate_fingerprint: String::from(certificate_fingerprint),
+        }).await
+    }
+
+    #[tokio::test]
+    async fn should_consume_a_setup_string_once_and_reject_it_after_revocation() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        let setup = issued_peer_setup(peer_id, Duration::from_secs(3600), None);
+        resources_manager.insert(setup.nonce, Clone::clone(&setup)).await?;
+
+        let result = consume(&resources_manager, PeerId::random(), setup.nonce).await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::PeerMismatch { .. })));
+
+        consume(&resources_manager, peer_id, setup.nonce).await?;
+        let consumed = resources_manager.get::<IssuedPeerSetup>(setup.nonce).await;
+        assert_that!(consumed, some(field!(IssuedPeerSetup.state, matches_pattern!(IssuedPeerSetupState::Consumed { .. }))));
+
+        consume(&resources_manager, peer_id, setup.nonce).await?;
+
+        revoke_peer_setup(RevokePeerSetupParams {
+            resources_manager: Arc::clone(&resources_manager),
+            nonce: setup.nonce,
+        }).await?;
+        let result = consume(&resources_manager, peer_id, setup.nonce).await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::Revoked { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_expired_and_unknown_setup_strings() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        let setup = issued_peer_setup(peer_id, Duration::ZERO, None);
+        resources_manager.insert(setup.nonce, Clone::clone(&setup)).await?;
+
+        let result = consume(&resources_manager, peer_id, setup.nonce).await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::Expired { .. })));
+
+        let result = consume(&resources_manager, peer_id, PeerSetupNonce::random()).await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::PeerSetupNotFound { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_accept_the_oidc_client_registered_for_the_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        let setup = issued_peer_setup(peer_id, Duration::from_secs(3600), Some(ClientId(String::from("opendut-peer-client"))));
+        resources_manager.insert(setup.nonce, Clone::clone(&setup)).await?;
+
+        let result = consume_as(&resources_manager, peer_id, setup.nonce, Some("opendut-other-client")).await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::ClientMismatch { .. })));
+
+        let result = consume_as(&resources_manager, peer_id, setup.nonce, None).await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::ClientMismatch { .. })));
+
+        let unconsumed = resources_manager.get::<IssuedPeerSetup>(setup.nonce).await;
+        assert_that!(unconsumed, some(field!(IssuedPeerSetup.state, eq(IssuedPeerSetupState::Outstanding))));
+
+        consume_as(&resources_manager, peer_id, setup.nonce, Some("opendut-peer-client")).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_reuse_of_a_consumed_setup_string_with_another_client_certificate() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        let setup = issued_peer_setup(peer_id, Duration::from_secs(3600), None);
+        resources_manager.insert(setup.nonce, Clone::clone(&setup)).await?;
+
+        consume_with(&resources_manager, peer_id, setup.nonce, None, "first").await?;
+
+        let result = consume_with(&resources_manager, peer_id, setup.nonce, None, "second").await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::AlreadyConsumed { .. })));
+
+        resources_manager.resources_mut(|resources| rebind_consumed_peer_setups(resources, peer_id, "first", "renewed")).await?;
+
+        let result = consume_with(&resources_manager, peer_id, setup.nonce, None, "first").await;
+        assert_that!(result, err(matches_pattern!(ConsumePeerSetupError::AlreadyConsumed { .. })));
+        consume_with(&resources_manager, peer_id, setup.nonce, None, "renewed").await?;
+
+        Ok(())
+    }
+}
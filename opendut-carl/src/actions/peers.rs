@@ -1,5 +1,6 @@
 use std::ops::Not;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use pem::Pem;
 use tracing::{debug, error, info, Span, warn};
@@ -9,6 +10,7 @@ use uuid::Uuid;
 
 pub use opendut_carl_api::carl::peer::{
     DeletePeerDescriptorError,
+    GetPeerStatusReportsError,
     IllegalDevicesError,
     ListDevicesError,
     ListPeerDescriptorsError,
@@ -17,24 +19,33 @@ pub use opendut_carl_api::carl::peer::{
 use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, downstream};
 use opendut_types::cluster::ClusterAssignment;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerSetup};
+use opendut_types::peer::setup::{IssuedPeerSetup, IssuedPeerSetupState, PeerSetupNonce};
 use opendut_types::{peer, proto};
 use opendut_types::cleo::{CleoId, CleoSetup};
-use opendut_types::peer::configuration::{PeerConfiguration, PeerNetworkConfiguration, PeerConfiguration2};
+use opendut_types::resources::Version;
+use opendut_types::peer::configuration::{ParameterStatusReport, PeerConfiguration, PeerConfigurationState, PeerNetworkConfiguration, PeerConfiguration2};
 use opendut_types::proto::peer::configuration::{peer_configuration_parameter, PeerConfigurationParameterTargetPresent, PeerConfigurationParameterExecutor};
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
 use opendut_types::util::net::{AuthConfig, Certificate, ClientCredentials, NetworkInterfaceName};
 use opendut_types::vpn::VpnPeerConfiguration;
 use opendut_util::ErrorOr;
+use crate::actions;
+use crate::actions::{IssuePeerCertificateParams, peer_certificates};
+use crate::peer::broker;
+use crate::peer::certificate_authority::PeerCertificateAuthorityRef;
+use crate::peer::state::{assigned_cluster, require_unblocked};
 use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerRef};
 use crate::resources::IntoId;
 
-use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::manager::{ResourcesManagerRef, Transaction};
 use crate::vpn::Vpn;
 
 pub struct StorePeerDescriptorParams {
     pub resources_manager: ResourcesManagerRef,
     pub vpn: Vpn,
     pub peer_descriptor: PeerDescriptor,
+    /// If set, the peer descriptor is only stored, when it is currently in this version.
+    pub expected_version: Option<Version>,
     pub options: StorePeerDescriptorOptions
 }
 
@@ -53,7 +64,40 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
         let peer_descriptor = params.peer_descriptor;
         let resources_manager = params.resources_manager;
 
-        let is_new_peer = resources_manager.resources_mut(|resources| {
+        let vpn_peer_created = if resources_manager.get::<PeerDescriptor>(peer_id).await.is_none() {
+            create_vpn_peer(&params.vpn, peer_id).await
+                .map_err(|cause| StorePeerDescriptorError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause })?
+        } else {
+            false
+        };
+
+        let mut transaction = resources_manager.begin().await;
+
+        let is_new_peer = transaction.resources_mut(|resources| {
+
+            if let Some(expected_version) = params.expected_version {
+                let actual_version = resources.version::<PeerDescriptor>(peer_id);
+                if actual_version != expected_version {
+                    return Err(StorePeerDescriptorError::VersionConflict {
+                        peer_id,
+                        peer_name: Clone::clone(&peer_name),
+                        expected_version,
+                        actual_version,
+                    });
+                }
+            }
+
+            if let Err((actual_state, required_states)) = require_unblocked(resources, peer_id) {
+                return Err(StorePeerDescriptorError::IllegalPeerState {
+                    peer_id,
+                    peer_name: Clone::clone(&peer_name),
+                    actual_state,
+                    required_states,
+                });
+            }
+            if let Some(cluster_id) = assigned_cluster(resources, peer_id) {
+                return Err(StorePeerDescriptorError::ClusterMember { peer_id, peer_name: Clone::clone(&peer_name), cluster_id });
+            }
 
             let old_peer_descriptor = resources.get::<PeerDescriptor>(peer_id);
             let is_new_peer = old_peer_descriptor.is_none();
@@ -114,24 +158,22 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
 
             resources.insert(peer_id, peer_descriptor);
 
-            is_new_peer
+            Ok(is_new_peer)
         }).await;
 
-        if is_new_peer {
-            if let Vpn::Enabled { vpn_client } = params.vpn {
-                debug!("Creating VPN peer <{peer_id}>.");
-                vpn_client.create_peer(peer_id)
-                    .await
-                    .map_err(|cause| StorePeerDescriptorError::Internal {
-                        peer_id,
-                        peer_name: Clone::clone(&peer_name),
-                        cause: cause.to_string()
-                    })?; // TODO: When a failure happens, we should rollback changes previously made to resources.
-                info!("Successfully created VPN peer <{peer_id}>.");
-            } else {
-                warn!("VPN disabled. Skipping VPN peer creation!");
+        let is_new_peer = match is_new_peer {
+            Ok(is_new_peer) => is_new_peer,
+            Err(error) => {
+                transaction.abort().await;
+                if vpn_peer_created {
+                    delete_vpn_peer_after_failed_store(&params.vpn, peer_id).await;
+                }
+                return Err(error);
             }
-        }
+        };
+
+        transaction.commit().await
+            .map_err(|cause| StorePeerDescriptorError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
 
         if is_new_peer {
             info!("Successfully stored peer descriptor of '{peer_name}' <{peer_id}>.");
@@ -147,6 +189,28 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
         .inspect_err(|err| error!("{err}"))
 }
 
+/// Creates the peer in the VPN service. Returns whether it was created, since this is skipped when the VPN is disabled.
+async fn create_vpn_peer(vpn: &Vpn, peer_id: PeerId) -> Result<bool, String> {
+    if let Vpn::Enabled { vpn_client } = vpn {
+        debug!("Creating VPN peer <{peer_id}>.");
+        vpn_client.create_peer(peer_id).await.map_err(|cause| cause.to_string())?;
+        info!("Successfully created VPN peer <{peer_id}>.");
+        Ok(true)
+    } else {
+        warn!("VPN disabled. Skipping VPN peer creation!");
+        Ok(false)
+    }
+}
+
+async fn delete_vpn_peer_after_failed_store(vpn: &Vpn, peer_id: PeerId) {
+    if let Vpn::Enabled { vpn_client } = vpn {
+        match vpn_client.delete_peer(peer_id).await {
+            Ok(()) => debug!("Deleted VPN peer <{peer_id}> after failing to store its peer descriptor."),
+            Err(cause) => error!("Failed to delete VPN peer <{peer_id}> after failing to store its peer descriptor:\n  {cause}"),
+        }
+    }
+}
+
 pub struct DeletePeerDescriptorParams {
     pub resources_manager: ResourcesManagerRef,
     pub vpn: Vpn,
@@ -164,11 +228,26 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
 
         debug!("Deleting peer descriptor of peer <{peer_id}>.");
 
-        let peer_descriptor = resources_manager.resources_mut(|resources| {
+        let mut transaction = resources_manager.begin().await;
+
+        let peer_descriptor = transaction.resources_mut(|resources| {
 
-            let peer_descriptor = resources.remove::<PeerDescriptor>(peer_id)
+            let peer_descriptor = resources.get::<PeerDescriptor>(peer_id)
                 .ok_or_else(|| DeletePeerDescriptorError::PeerNotFound { peer_id })?;
 
+            if let Err((actual_state, required_states)) = require_unblocked(resources, peer_id) {
+                return Err(DeletePeerDescriptorError::IllegalPeerState {
+                    peer_id,
+                    peer_name: peer_descriptor.name,
+                    actual_state,
+                    required_states,
+                });
+            }
+            if let Some(cluster_id) = assigned_cluster(resources, peer_id) {
+                return Err(DeletePeerDescriptorError::ClusterMember { peer_id, peer_name: peer_descriptor.name, cluster_id });
+            }
+            resources.remove::<PeerDescriptor>(peer_id);
+
             let peer_name = &peer_descriptor.name;
 
             peer_descriptor.topology.devices.iter().for_each(|device| {
@@ -178,16 +257,37 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
                 debug!("Deleted device '{device_name}' <{device_id}> of peer '{peer_name}' <{peer_id}>.");
             });
 
+            let setup_nonces = resources.iter::<IssuedPeerSetup>()
+                .filter(|setup| setup.peer_id == peer_id)
+                .map(|setup| setup.nonce)
+                .collect::<Vec<_>>();
+            for nonce in setup_nonces {
+                resources.remove::<IssuedPeerSetup>(nonce);
+            }
+
+            peer_certificates::revoke_peer_certificates(resources, peer_id);
+
             Ok(peer_descriptor)
-        }).await?;
+        }).await;
+
+        let peer_descriptor = match peer_descriptor {
+            Ok(peer_descriptor) => peer_descriptor,
+            Err(error) => {
+                transaction.abort().await;
+                return Err(error);
+            }
+        };
 
         let peer_name = &peer_descriptor.name;
 
+        transaction.commit().await
+            .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: Clone::clone(peer_name), cause: cause.to_string() })?;
+
+        // The peer is deleted first, so that it cannot connect anymore, even if cleaning up its OIDC client or VPN peer fails.
         if let Some(registration_client) = params.oidc_registration_client {
             let resource_id = peer_id.into();
             debug!("Deleting OIDC client for peer '{peer_name}' <{peer_id}>.");
-            let deleted_clients = registration_client.delete_client_by_resource_id(resource_id)
-                .await
+            let deleted_clients = registration_client.delete_client_by_resource_id(resource_id).await
                 .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: Clone::clone(peer_name), cause: cause.to_string() })?;
             let deleted_client_ids =  deleted_clients.value().into_iter().map(|client| client.client_id).collect::<Vec<String>>();
             debug!("Successfully deleted oidc clients for peer '{peer_name}' <{peer_id}>. OIDC client_ids='{}'.", deleted_client_ids.join(","));
@@ -195,13 +295,8 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
         
         if let Vpn::Enabled { vpn_client } = params.vpn {
             debug!("Deleting vpn peer <{peer_id}>.");
-            vpn_client.delete_peer(peer_id)
-                .await
-                .map_err(|cause| DeletePeerDescriptorError::Internal {
-                    peer_id,
-                    peer_name: Clone::clone(peer_name),
-                    cause: cause.to_string()
-                })?;
+            vpn_client.delete_peer(peer_id).await
+                .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: Clone::clone(peer_name), cause: cause.to_string() })?;
             info!("Successfully deleted VPN peer <{peer_id}>.");
         } else {
             warn!("VPN disabled. Skipping VPN peer deletion!");
@@ -216,14 +311,48 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
         .inspect_err(|err| error!("{err}"))
 }
 
+pub struct GetPeerStatusReportsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_peer_status_reports(params: GetPeerStatusReportsParams) -> Result<Vec<ParameterStatusReport>, GetPeerStatusReportsError> {
+
+    async fn inner(params: GetPeerStatusReportsParams) -> Result<Vec<ParameterStatusReport>, GetPeerStatusReportsError> {
+
+        let peer_id = params.peer_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying status reports of peer <{peer_id}>.");
+
+        let reports = resources_manager.resources(|resources| {
+            resources.get::<PeerDescriptor>(peer_id)
+                .ok_or(GetPeerStatusReportsError::PeerNotFound { peer_id })?;
+
+            let configuration = resources.get::<PeerConfiguration2>(peer_id).unwrap_or_default();
+            let state = resources.get::<PeerConfigurationState>(peer_id).unwrap_or_default();
+
+            Ok(configuration.status_reports(&state))
+        }).await?;
+
+        info!("Successfully queried status reports of peer <{peer_id}>.");
+
+        Ok(reports)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
 pub struct ListPeerDescriptorsParams {
     pub resources_manager: ResourcesManagerRef,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
-pub async fn list_peer_descriptors(params: ListPeerDescriptorsParams) -> Result<Vec<PeerDescriptor>, ListPeerDescriptorsError> {
+pub async fn list_peer_descriptors(params: ListPeerDescriptorsParams) -> Result<Vec<(PeerDescriptor, Version)>, ListPeerDescriptorsError> {
 
-    async fn inner(params: ListPeerDescriptorsParams) -> Result<Vec<PeerDescriptor>, ListPeerDescriptorsError> {
+    async fn inner(params: ListPeerDescriptorsParams) -> Result<Vec<(PeerDescriptor, Version)>, ListPeerDescriptorsError> {
 
         let resources_manager = params.resources_manager;
 
@@ -231,8 +360,8 @@ pub async fn list_peer_descriptors(params: ListPeerDescriptorsParams) -> Result<
 
         let peers = resources_manager.resources(|resources| {
             resources.iter::<PeerDescriptor>()
-                .cloned()
-                .collect::<Vec<PeerDescriptor>>()
+                .map(|peer| (Clone::clone(peer), resources.version::<PeerDescriptor>(peer.id)))
+                .collect::<Vec<_>>()
         }).await;
 
         info!("Successfully queried all peer descriptors.");
@@ -277,6 +406,10 @@ pub struct GeneratePeerSetupParams {
     pub ca: Pem,
     pub vpn: Vpn,
     pub oidc_registration_client: Option<RegistrationClientRef>,
+    /// How long the setup-string can be used to enrol the peer.
+    pub validity: Duration,
+    pub certificate_authority: PeerCertificateAuthorityRef,
+    pub certificate_validity: Duration,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -317,9 +450,9 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams, user_id: Strin
             VpnPeerConfiguration::Disabled
         };
 
-        let auth_config = match params.oidc_registration_client {
+        let (auth_config, client_id) = match params.oidc_registration_client {
             None => {
-                AuthConfig::Disabled
+                (AuthConfig::Disabled, None)
             }
             Some(registration_client) => {
                 let resource_id = peer_id.into();
@@ -329,16 +462,41 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams, user_id: Strin
                     .await
                     .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
                 debug!("Successfully generated peer setup for peer '{peer_name}' <{peer_id}>. OIDC client_id='{}'.", client_credentials.client_id.clone().value());
-                AuthConfig::from_credentials(issuer_url, client_credentials)
+                let client_id = Clone::clone(&client_credentials.client_id);
+                (AuthConfig::from_credentials(issuer_url, client_credentials), Some(client_id))
             }
         };
 
+        let (certificate, _) = actions::issue_peer_certificate(IssuePeerCertificateParams {
+            resources_manager: Arc::clone(&params.resources_manager),
+            certificate_authority: params.certificate_authority,
+            peer_id,
+            validity: params.certificate_validity,
+        }).await
+            .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+
+        let issued_at = SystemTime::now();
+        let issued_setup = IssuedPeerSetup {
+            nonce: PeerSetupNonce::random(),
+            peer_id,
+            issued_at,
+            expires_at: issued_at + params.validity,
+            state: IssuedPeerSetupState::Outstanding,
+            client_id,
+        };
+        params.resources_manager.insert(issued_setup.nonce, Clone::clone(&issued_setup)).await
+            .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+
         Ok(PeerSetup {
             id: peer_id,
             carl: params.carl_url,
             ca: Certificate(params.ca),
             auth_config,
             vpn: vpn_config,
+            nonce: issued_setup.nonce,
+            issued_at: issued_setup.issued_at,
+            expires_at: issued_setup.expires_at,
+            certificate,
         })
     }
 
@@ -398,9 +556,8 @@ pub async fn generate_cleo_setup(params: GenerateCleoSetupParams, user_id: Strin
 }
 
 
-pub struct AssignClusterParams {
-    pub resources_manager: ResourcesManagerRef,
-    pub peer_messaging_broker: PeerMessagingBrokerRef,
+pub struct AssignClusterParams<'a, 'tx> {
+    pub transaction: &'a mut Transaction<'tx>,
     pub peer_id: PeerId,
     pub cluster_assignment: ClusterAssignment,
 }
@@ -409,15 +566,15 @@ pub struct AssignClusterParams {
 pub enum AssignClusterError {
     #[error("Assigning cluster for peer <{0}> failed, because a peer with that ID does not exist!")]
     PeerNotFound(PeerId),
-    #[error("Sending PeerConfiguration with ClusterAssignment to peer <{peer_id}> failed: {cause}")]
-    SendingToPeerFailed { peer_id: PeerId, cause: String },
 }
 
-pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClusterError> {
+/// Stores the cluster assignment in the configuration of the peer.
+/// The returned configuration has to be sent to the peer, once the transaction is committed.
+pub async fn assign_cluster(params: AssignClusterParams<'_, '_>) -> Result<PendingPeerConfiguration, AssignClusterError> {
 
     let peer_id = params.peer_id;
 
-    let (peer_configuration, peer_configuration2) = params.resources_manager.resources_mut(|resources| {
+    params.transaction.resources_mut(|resources| {
         let peer_configuration = resources.get::<PeerConfiguration>(peer_id)
             .ok_or(AssignClusterError::PeerNotFound(peer_id))
             .map(|peer_configuration| {
@@ -432,22 +589,85 @@ pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClu
         let peer_configuration2 = resources.get::<PeerConfiguration2>(peer_id)
             .ok_or(AssignClusterError::PeerNotFound(peer_id))?;
 
-        Ok((peer_configuration, peer_configuration2))
-    }).await?;
-
-    params.peer_messaging_broker.send_to_peer(
-        peer_id,
-        downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
-            configuration: Some(peer_configuration.into()),
-            configuration2: Some(peer_configuration2.into()),
-        }),
-    ).await
-    .map_err(|cause| AssignClusterError::SendingToPeerFailed {
-        peer_id,
-        cause: cause.to_string()
-    })?;
-
-    Ok(())
+        Ok(PendingPeerConfiguration { peer_id, peer_configuration, peer_configuration2 })
+    }).await
+}
+
+
+pub struct UnassignClusterParams<'a, 'tx> {
+    pub transaction: &'a mut Transaction<'tx>,
+    pub peer_id: PeerId,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UnassignClusterError {
+    #[error("Unassigning cluster for peer <{0}> failed, because a peer with that ID does not exist!")]
+    PeerNotFound(PeerId),
+}
+
+/// Removes the cluster assignment from the configuration of the peer.
+/// The returned configuration has to be sent to the peer, once the transaction is committed.
+pub async fn unassign_cluster(params: UnassignClusterParams<'_, '_>) -> Result<PendingPeerConfiguration, UnassignClusterError> {
+
+    let peer_id = params.peer_id;
+
+    params.transaction.resources_mut(|resources| {
+        let peer_configuration = resources.get::<PeerConfiguration>(peer_id)
+            .ok_or(UnassignClusterError::PeerNotFound(peer_id))
+            .map(|peer_configuration| {
+                let peer_configuration = PeerConfiguration {
+                    cluster_assignment: None,
+                    ..peer_configuration
+                };
+                resources.insert(peer_id, Clone::clone(&peer_configuration));
+                peer_configuration
+            })?;
+
+        let peer_configuration2 = resources.get::<PeerConfiguration2>(peer_id)
+            .ok_or(UnassignClusterError::PeerNotFound(peer_id))?;
+
+        Ok(PendingPeerConfiguration { peer_id, peer_configuration, peer_configuration2 })
+    }).await
+}
+
+/// A configuration of a peer, which was stored in a transaction and still has to be sent to the peer.
+///
+/// It is sent only after the transaction is committed, so that waiting for the peer does not block other access to the resources.
+pub struct PendingPeerConfiguration {
+    pub peer_id: PeerId,
+    peer_configuration: PeerConfiguration,
+    peer_configuration2: PeerConfiguration2,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SendPeerConfigurationError {
+    #[error("Sending PeerConfiguration to peer <{0}> failed, because the peer is not connected.")]
+    PeerNotConnected(PeerId),
+    #[error("Sending PeerConfiguration to peer <{peer_id}> failed: {cause}")]
+    SendingToPeerFailed { peer_id: PeerId, cause: String },
+}
+
+impl PendingPeerConfiguration {
+    pub async fn send(self, peer_messaging_broker: &PeerMessagingBrokerRef) -> Result<(), SendPeerConfigurationError> {
+        let peer_id = self.peer_id;
+
+        let result = peer_messaging_broker.send_to_peer(
+            peer_id,
+            downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
+                configuration: Some(self.peer_configuration.into()),
+                configuration2: Some(self.peer_configuration2.into()),
+            }),
+        ).await;
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(broker::Error::PeerNotFound(_)) => Err(SendPeerConfigurationError::PeerNotConnected(peer_id)),
+            Err(cause) => Err(SendPeerConfigurationError::SendingToPeerFailed {
+                peer_id,
+                cause: cause.to_string()
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -459,6 +679,7 @@ mod test {
 
     use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
     use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::project::ProjectName;
     use opendut_types::topology::{DeviceDescription, DeviceName, Topology};
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
@@ -482,6 +703,7 @@ mod test {
                 resources_manager: Arc::clone(&resources_manager),
                 vpn: Clone::clone(&fixture.vpn),
                 peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+                expected_version: None,
                 options: store_peer_descriptor_options.clone(),
             }).await?;
 
@@ -515,6 +737,7 @@ mod test {
                 resources_manager: Arc::clone(&resources_manager),
                 vpn: Clone::clone(&fixture.vpn),
                 peer_descriptor: Clone::clone(&changed_descriptor),
+                expected_version: None,
                 options: store_peer_descriptor_options,
             }).await?;
 
@@ -523,6 +746,56 @@ mod test {
             assert_that!(resources_manager.get(additional_device_id).await.as_ref(), some(eq(&additional_device)));
             assert_that!(resources_manager.get(fixture.peer_a_device_2).await.as_ref(), none());
 
+            Ok(())
+        }
+        #[rstest]
+        #[tokio::test]
+        async fn should_reject_stale_peer_descriptor(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+            let peer_id = fixture.peer_a_id;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+                expected_version: Some(Version::NONE),
+                options: store_peer_descriptor_options.clone(),
+            }).await?;
+
+            let read_version = resources_manager.resources(|resources| resources.version::<PeerDescriptor>(peer_id)).await;
+
+            let changed_descriptor = PeerDescriptor {
+                name: PeerName::try_from("PeerA_Changed").unwrap(),
+                ..Clone::clone(&fixture.peer_a_descriptor)
+            };
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&changed_descriptor),
+                expected_version: Some(read_version),
+                options: store_peer_descriptor_options.clone(),
+            }).await?;
+
+            let stale_descriptor = PeerDescriptor {
+                name: PeerName::try_from("PeerA_Stale").unwrap(),
+                ..Clone::clone(&fixture.peer_a_descriptor)
+            };
+            let result = store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: stale_descriptor,
+                expected_version: Some(read_version),
+                options: store_peer_descriptor_options,
+            }).await;
+
+            let Err(StorePeerDescriptorError::VersionConflict { expected_version, actual_version, .. }) = result else {
+                panic!("Expected a version conflict, but got: {result:?}");
+            };
+            assert_that!(expected_version, eq(read_version));
+            assert_that!(actual_version, gt(read_version));
+            assert_that!(resources_manager.get::<PeerDescriptor>(peer_id).await, some(eq(changed_descriptor)));
+
             Ok(())
         }
     }
@@ -556,13 +829,13 @@ mod test {
             };
             resources_manager.resources_mut(|resources| {
                 resources.insert(peer_id, Clone::clone(&peer_configuration));
-            }).await;
+            }).await?;
             let peer_configuration2 = PeerConfiguration2 {
                 executors: vec![],
             };
             resources_manager.resources_mut(|resources| {
                 resources.insert(peer_id, Clone::clone(&peer_configuration2));
-            }).await;
+            }).await?;
 
             let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
             let received = receiver.recv().await.unwrap()
@@ -583,12 +856,14 @@ mod test {
             };
 
 
-            assign_cluster(AssignClusterParams {
-                resources_manager: Arc::clone(&resources_manager),
-                peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            let mut transaction = resources_manager.begin().await;
+            let pending_configuration = assign_cluster(AssignClusterParams {
+                transaction: &mut transaction,
                 peer_id,
                 cluster_assignment: Clone::clone(&cluster_assignment),
             }).await?;
+            transaction.commit().await?;
+            pending_configuration.send(&peer_messaging_broker).await?;
 
 
             let peer_configuration = PeerConfiguration {
@@ -703,7 +978,8 @@ mod test {
             },
             executors: ExecutorDescriptors {
                 executors: vec![],
-            }
+            },
+            project: ProjectName::default(),
         };
         Fixture {
             resources_manager: ResourcesManager::new(),
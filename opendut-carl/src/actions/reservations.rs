@@ -0,0 +1,281 @@
+use std::ops::Not;
+use std::time::SystemTime;
+
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::reservation::{
+    CreateReservationError,
+    DeleteReservationError,
+};
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::reservation::{Reservation, ReservationId};
+use opendut_types::topology::DeviceDescriptor;
+
+use crate::reservation;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct CreateReservationParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub reservation: Reservation,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_reservation(params: CreateReservationParams) -> Result<ReservationId, CreateReservationError> {
+
+    async fn inner(params: CreateReservationParams) -> Result<ReservationId, CreateReservationError> {
+
+        let reservation = params.reservation;
+        let reservation_id = reservation.id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Creating reservation <{reservation_id}> for user '{}'.", reservation.user);
+
+        let illegal = |message: &str| CreateReservationError::IllegalReservation { reservation_id, message: String::from(message) };
+
+        if reservation.end <= reservation.start {
+            return Err(illegal("The end of a reservation must be after its start."));
+        }
+        if reservation.end <= SystemTime::now() {
+            return Err(illegal("The reservation already ended."));
+        }
+        if reservation.peers.is_empty() && reservation.devices.is_empty() {
+            return Err(illegal("A reservation must claim at least one peer or device."));
+        }
+
+        resources_manager.resources_mut(|resources| {
+            if let Some(peer_id) = reservation.peers.iter().find(|peer_id| resources.get::<PeerDescriptor>(**peer_id).is_none()) {
+                return Err(illegal(&format!("Peer <{peer_id}> does not exist.")));
+            }
+            if let Some(device_id) = reservation.devices.iter().find(|device_id| resources.get::<DeviceDescriptor>(**device_id).is_none()) {
+                return Err(illegal(&format!("Device <{device_id}> does not exist.")));
+            }
+
+            let device_peers = reservation::device_peers(resources);
+            let conflicting_reservation = resources.iter::<Reservation>()
+                .filter(|other| other.id != reservation_id)
+                .find(|other| other.conflicts_with(&reservation, &device_peers));
+            if let Some(other) = conflicting_reservation {
+                return Err(CreateReservationError::ReservationConflict {
+                    reservation_id,
+                    other_reservation_id: other.id,
+                    other_user: Clone::clone(&other.user),
+                });
+            }
+
+            if let Some(deployment) = reservation::find_blocking_deployment(resources, &reservation) {
+                return Err(CreateReservationError::ClusterDeployed {
+                    reservation_id,
+                    cluster_id: deployment.id,
+                    deployed_by: deployment.deployed_by,
+                });
+            }
+
+            if let Some(existing) = resources.get::<Reservation>(reservation_id) {
+                if existing.user != reservation.user {
+                    return Err(CreateReservationError::ReservationConflict {
+                        reservation_id,
+                        other_reservation_id: existing.id,
+                        other_user: existing.user,
+                    });
+                }
+            }
+
+            resources.insert(reservation_id, Clone::clone(&reservation));
+            Ok(())
+        }).await
+        .map_err(|cause| CreateReservationError::Internal { reservation_id, cause: cause.to_string() })??;
+
+        info!("Successfully created reservation <{reservation_id}> for user '{}'.", reservation.user);
+
+        Ok(reservation_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteReservationParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub reservation_id: ReservationId,
+    /// Only the user holding the reservation may delete it.
+    pub user: String,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_reservation(params: DeleteReservationParams) -> Result<Reservation, DeleteReservationError> {
+
+    async fn inner(params: DeleteReservationParams) -> Result<Reservation, DeleteReservationError> {
+
+        let reservation_id = params.reservation_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Deleting reservation <{reservation_id}>.");
+
+        let reservation = resources_manager.resources_mut(|resources| {
+            let reservation = resources.get::<Reservation>(reservation_id)
+                .ok_or(DeleteReservationError::ReservationNotFound { reservation_id })?;
+
+            if reservation.user != params.user {
+                return Err(DeleteReservationError::ReservedByOtherUser { reservation_id, user: reservation.user });
+            }
+
+            resources.remove::<Reservation>(reservation_id)
+                .ok_or(DeleteReservationError::ReservationNotFound { reservation_id })
+        }).await
+        .map_err(|cause| DeleteReservationError::Internal { reservation_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted reservation <{reservation_id}>.");
+
+        Ok(reservation)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListReservationsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_reservations(params: ListReservationsParams) -> Vec<Reservation> {
+    params.resources_manager.resources(|resources| {
+        resources.iter::<Reservation>()
+            .filter(|reservation| reservation.is_expired_at(SystemTime::now()).not())
+            .cloned()
+            .collect()
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::project::ProjectName;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reject_conflicting_reservations_of_other_users() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        resources_manager.insert(peer_id, PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("ReservedPeer")?,
+            location: None,
+            network: Default::default(),
+            topology: Default::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        }).await?;
+
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        let reservation = |user: &str, start: SystemTime| Reservation {
+            id: ReservationId::random(),
+            user: String::from(user),
+            peers: HashSet::from([peer_id]),
+            devices: HashSet::new(),
+            start,
+            end: start + hour,
+        };
+
+        let alice_reservation = reservation("alice", now);
+        create_reservation(CreateReservationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            reservation: Clone::clone(&alice_reservation),
+        }).await?;
+
+        let result = create_reservation(CreateReservationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            reservation: reservation("bob", now + hour / 2),
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateReservationError::ReservationConflict {
+            other_reservation_id: eq(alice_reservation.id),
+            other_user: eq("alice"),
+        })));
+
+        let result = create_reservation(CreateReservationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            reservation: reservation("bob", now + hour),
+        }).await;
+        assert_that!(result, ok(anything()));
+
+        let result = delete_reservation(DeleteReservationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            reservation_id: alice_reservation.id,
+            user: String::from("bob"),
+        }).await;
+        assert_that!(result, err(matches_pattern!(DeleteReservationError::ReservedByOtherUser {
+            user: eq("alice"),
+        })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_reservations_of_resources_used_by_clusters_of_other_users() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        resources_manager.insert(peer_id, PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("DeployedPeer")?,
+            location: None,
+            network: Default::default(),
+            topology: Default::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        }).await?;
+
+        let cluster_id = ClusterId::random();
+        resources_manager.insert(cluster_id, ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("DeployedCluster")?,
+            leader: peer_id,
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        }).await?;
+        resources_manager.insert(cluster_id, ClusterDeployment {
+            id: cluster_id,
+            devices: HashSet::new(),
+            deployed_by: String::from("alice"),
+        }).await?;
+
+        let now = SystemTime::now();
+        let reservation = |user: &str| Reservation {
+            id: ReservationId::random(),
+            user: String::from(user),
+            peers: HashSet::from([peer_id]),
+            devices: HashSet::new(),
+            start: now,
+            end: now + Duration::from_secs(3600),
+        };
+
+        let result = create_reservation(CreateReservationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            reservation: reservation("bob"),
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateReservationError::ClusterDeployed {
+            cluster_id: eq(cluster_id),
+            deployed_by: eq("alice"),
+        })));
+
+        let result = create_reservation(CreateReservationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            reservation: reservation("alice"),
+        }).await;
+        assert_that!(result, ok(anything()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use std::time::SystemTime;
+
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::service_account::{
+    CreateServiceAccountError,
+    DeleteServiceAccountError,
+    IssueApiTokenError,
+    IssuedApiToken,
+    RevokeApiTokenError,
+};
+use opendut_types::service_account::{ApiToken, ApiTokenId, ApiTokenRole, ServiceAccount, ServiceAccountId};
+
+use crate::auth::api_token;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct CreateServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account: ServiceAccount,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_service_account(params: CreateServiceAccountParams) -> Result<ServiceAccountId, CreateServiceAccountError> {
+
+    async fn inner(params: CreateServiceAccountParams) -> Result<ServiceAccountId, CreateServiceAccountError> {
+
+        let service_account = params.service_account;
+        let service_account_id = service_account.id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Creating service account '{}' <{service_account_id}>.", service_account.name);
+
+        resources_manager.resources_mut(|resources| {
+            let other_service_account = resources.iter::<ServiceAccount>()
+                .find(|other| other.id != service_account_id && other.name == service_account.name);
+            if let Some(other) = other_service_account {
+                return Err(CreateServiceAccountError::ServiceAccountAlreadyExists {
+                    service_account_id,
+                    name: Clone::clone(&service_account.name),
+                    other_service_account_id: other.id,
+                });
+            }
+
+            resources.insert(service_account_id, Clone::clone(&service_account));
+            Ok(())
+        }).await
+        .map_err(|cause| CreateServiceAccountError::Internal { service_account_id, cause: cause.to_string() })??;
+
+        info!("Successfully created service account '{}' <{service_account_id}>.", service_account.name);
+
+        Ok(service_account_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: ServiceAccountId,
+}
+
+/// Deletes the service account and revokes all of its API tokens.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_service_account(params: DeleteServiceAccountParams) -> Result<ServiceAccount, DeleteServiceAccountError> {
+
+    async fn inner(params: DeleteServiceAccountParams) -> Result<ServiceAccount, DeleteServiceAccountError> {
+
+        let service_account_id = params.service_account_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Deleting service account <{service_account_id}>.");
+
+        let service_account = resources_manager.resources_mut(|resources| {
+            let service_account = resources.remove::<ServiceAccount>(service_account_id)
+                .ok_or(DeleteServiceAccountError::ServiceAccountNotFound { service_account_id })?;
+
+            let api_token_ids = resources.iter::<ApiToken>()
+                .filter(|api_token| api_token.service_account == service_account_id)
+                .map(|api_token| api_token.id)
+                .collect::<Vec<_>>();
+            for api_token_id in api_token_ids {
+                resources.remove::<ApiToken>(api_token_id);
+            }
+
+            Ok(service_account)
+        }).await
+        .map_err(|cause| DeleteServiceAccountError::Internal { service_account_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted service account '{}' <{service_account_id}>.", service_account.name);
+
+        Ok(service_account)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListServiceAccountsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_service_accounts(params: ListServiceAccountsParams) -> Vec<ServiceAccount> {
+    params.resources_manager.resources(|resources| {
+        resources.iter::<ServiceAccount>()
+            .cloned()
+            .collect()
+    }).await
+}
+
+pub struct IssueApiTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// The hash of the token's secret is replaced by the hash of a newly generated secret.
+    pub api_token: ApiToken,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn issue_api_token(params: IssueApiTokenParams) -> Result<IssuedApiToken, IssueApiTokenError> {
+
+    async fn inner(params: IssueApiTokenParams) -> Result<IssuedApiToken, IssueApiTokenError> {
+
+        let api_token_id = params.api_token.id;
+        let service_account_id = params.api_token.service_account;
+        let resources_manager = params.resources_manager;
+
+        debug!("Issuing API token <{api_token_id}> for service account <{service_account_id}>.");
+
+        let illegal = |message: &str| IssueApiTokenError::IllegalApiToken { api_token_id, message: String::from(message) };
+
+        if params.api_token.is_expired_at(SystemTime::now()) {
+            return Err(illegal("The API token already expired."));
+        }
+        if params.api_token.role != ApiTokenRole::Admin && params.api_token.projects.is_empty() {
+            return Err(illegal("An API token must be scoped to at least one project, unless it has the role 'admin'."));
+        }
+
+        let (secret, secret_hash) = api_token::generate_secret(api_token_id);
+        let api_token = ApiToken { secret_hash, ..params.api_token };
+
+        resources_manager.resources_mut(|resources| {
+            if resources.get::<ServiceAccount>(service_account_id).is_none() {
+                return Err(IssueApiTokenError::ServiceAccountNotFound { api_token_id, service_account_id });
+            }
+            if resources.get::<ApiToken>(api_token_id).is_some() {
+                return Err(illegal("An API token with that id already exists."));
+            }
+            resources.insert(api_token_id, Clone::clone(&api_token));
+            Ok(())
+        }).await
+        .map_err(|cause| IssueApiTokenError::Internal { api_token_id, cause: cause.to_string() })??;
+
+        info!("Successfully issued API token <{api_token_id}> for service account <{service_account_id}>.");
+
+        Ok(IssuedApiToken { api_token, secret })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct RevokeApiTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub api_token_id: ApiTokenId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn revoke_api_token(params: RevokeApiTokenParams) -> Result<ApiToken, RevokeApiTokenError> {
+
+    async fn inner(params: RevokeApiTokenParams) -> Result<ApiToken, RevokeApiTokenError> {
+
+        let api_token_id = params.api_token_id;
+
+        debug!("Revoking API token <{api_token_id}>.");
+
+        let api_token = params.resources_manager.remove::<ApiToken>(api_token_id).await
+            .map_err(|cause| RevokeApiTokenError::Internal { api_token_id, cause: cause.to_string() })?
+            .ok_or(RevokeApiTokenError::ApiTokenNotFound { api_token_id })?;
+
+        info!("Successfully revoked API token <{api_token_id}>.");
+
+        Ok(api_token)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListApiTokensParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// Only lists the tokens of the given service account, if set.
+    pub service_account_id: Option<ServiceAccountId>,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_api_tokens(params: ListApiTokensParams) -> Vec<ApiToken> {
+    params.resources_manager.resources(|resources| {
+        resources.iter::<ApiToken>()
+            .filter(|api_token| params.service_account_id.map_or(true, |service_account_id| api_token.service_account == service_account_id))
+            .cloned()
+            .collect()
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::project::ProjectName;
+    use opendut_types::service_account::ServiceAccountName;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_issue_api_tokens_and_revoke_them_with_their_service_account() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let service_account = ServiceAccount {
+            id: ServiceAccountId::random(),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            created_by: String::from("alice"),
+        };
+        create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account: Clone::clone(&service_account),
+        }).await?;
+
+        let result = create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account: ServiceAccount { id: ServiceAccountId::random(), ..Clone::clone(&service_account) },
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateServiceAccountError::ServiceAccountAlreadyExists {
+            other_service_account_id: eq(service_account.id),
+        })));
+
+        let api_token = ApiToken {
+            id: ApiTokenId::random(),
+            service_account: service_account.id,
+            role: ApiTokenRole::Operator,
+            projects: HashSet::from([ProjectName::default()]),
+            expires_at: SystemTime::now() + Duration::from_secs(3600),
+            secret_hash: String::new(),
+        };
+        let issued = issue_api_token(IssueApiTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            api_token: Clone::clone(&api_token),
+        }).await?;
+        let authenticated = resources_manager.resources(|resources| {
+            api_token::authenticate(resources, &issued.secret, SystemTime::now())
+        }).await;
+        assert_that!(authenticated, some((eq(Clone::clone(&service_account)), anything())));
+
+        let result = issue_api_token(IssueApiTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            api_token: ApiToken { id: ApiTokenId::random(), projects: HashSet::new(), ..Clone::clone(&api_token) },
+        }).await;
+        assert_that!(result, err(matches_pattern!(IssueApiTokenError::IllegalApiToken { .. })));
+
+        delete_service_account(DeleteServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+        }).await?;
+        let api_tokens = list_api_tokens(ListApiTokensParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: None,
+        }).await;
+        assert_that!(api_tokens, empty());
+
+        Ok(())
+    }
+}
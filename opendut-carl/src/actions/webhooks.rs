@@ -0,0 +1,155 @@
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::webhook::{
+    CreateWebhookError,
+    DeleteWebhookError,
+};
+use opendut_types::webhook::{Webhook, WebhookId};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::webhook;
+
+pub struct CreateWebhookParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// The secret of the webhook is replaced by a newly generated secret.
+    pub webhook: Webhook,
+}
+
+/// Creates the webhook. Returns it with its generated secret.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_webhook(params: CreateWebhookParams) -> Result<Webhook, CreateWebhookError> {
+
+    async fn inner(params: CreateWebhookParams) -> Result<Webhook, CreateWebhookError> {
+
+        let webhook_id = params.webhook.id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Creating webhook <{webhook_id}> to '{}'.", params.webhook.url);
+
+        if !matches!(params.webhook.url.scheme(), "http" | "https") {
+            return Err(CreateWebhookError::IllegalWebhook {
+                webhook_id,
+                message: format!("The URL must use the scheme 'http' or 'https', got '{}'.", params.webhook.url.scheme()),
+            });
+        }
+
+        let webhook = Webhook { secret: webhook::generate_secret(), ..params.webhook };
+
+        resources_manager.resources_mut(|resources| {
+            if resources.get::<Webhook>(webhook_id).is_some() {
+                return Err(CreateWebhookError::WebhookAlreadyExists { webhook_id });
+            }
+            resources.insert(webhook_id, Clone::clone(&webhook));
+            Ok(())
+        }).await
+        .map_err(|cause| CreateWebhookError::Internal { webhook_id, cause: cause.to_string() })??;
+
+        info!("Successfully created webhook <{webhook_id}> to '{}'.", webhook.url);
+
+        Ok(webhook)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteWebhookParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub webhook_id: WebhookId,
+}
+
+/// Deletes the webhook. Deliveries to it, which are being retried, are abandoned.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_webhook(params: DeleteWebhookParams) -> Result<Webhook, DeleteWebhookError> {
+
+    async fn inner(params: DeleteWebhookParams) -> Result<Webhook, DeleteWebhookError> {
+
+        let webhook_id = params.webhook_id;
+
+        debug!("Deleting webhook <{webhook_id}>.");
+
+        let webhook = params.resources_manager.remove::<Webhook>(webhook_id).await
+            .map_err(|cause| DeleteWebhookError::Internal { webhook_id, cause: cause.to_string() })?
+            .ok_or(DeleteWebhookError::WebhookNotFound { webhook_id })?;
+
+        info!("Successfully deleted webhook <{webhook_id}> to '{}'.", webhook.url);
+
+        Ok(webhook)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListWebhooksParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+/// Lists all webhooks without their secrets.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_webhooks(params: ListWebhooksParams) -> Vec<Webhook> {
+    params.resources_manager.resources(|resources| {
+        resources.iter::<Webhook>()
+            .map(|webhook| Webhook { secret: String::new(), ..Clone::clone(webhook) })
+            .collect()
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::webhook::WebhookEventKind;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_create_webhooks_with_secret_and_list_them_without() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let webhook = Webhook {
+            id: WebhookId::random(),
+            url: url::Url::parse("https://ci.example.com/opendut")?,
+            events: HashSet::from([WebhookEventKind::ClusterDeploymentFailed]),
+            secret: String::from("chosen-by-client"),
+            created_by: String::from("alice"),
+        };
+
+        let created = create_webhook(CreateWebhookParams {
+            resources_manager: Arc::clone(&resources_manager),
+            webhook: Clone::clone(&webhook),
+        }).await?;
+        assert_that!(created.secret, starts_with("whsec_"));
+
+        let result = create_webhook(CreateWebhookParams {
+            resources_manager: Arc::clone(&resources_manager),
+            webhook: Clone::clone(&webhook),
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateWebhookError::WebhookAlreadyExists { .. })));
+
+        let result = create_webhook(CreateWebhookParams {
+            resources_manager: Arc::clone(&resources_manager),
+            webhook: Webhook { id: WebhookId::random(), url: url::Url::parse("ftp://ci.example.com")?, ..Clone::clone(&webhook) },
+        }).await;
+        assert_that!(result, err(matches_pattern!(CreateWebhookError::IllegalWebhook { .. })));
+
+        let webhooks = list_webhooks(ListWebhooksParams { resources_manager: Arc::clone(&resources_manager) }).await;
+        assert_that!(webhooks, elements_are![matches_pattern!(Webhook {
+            id: eq(webhook.id),
+            secret: eq(""),
+        })]);
+
+        delete_webhook(DeleteWebhookParams {
+            resources_manager: Arc::clone(&resources_manager),
+            webhook_id: webhook.id,
+        }).await?;
+        let webhooks = list_webhooks(ListWebhooksParams { resources_manager }).await;
+        assert_that!(webhooks, empty());
+
+        Ok(())
+    }
+}
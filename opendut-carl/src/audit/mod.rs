@@ -0,0 +1,228 @@
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::ops::Not;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use config::Config;
+use prost::Message;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tracing::{debug, error};
+
+pub use opendut_carl_api::carl::audit::{AuditEntry, AuditQuery};
+use opendut_carl_api::proto::services::audit_log as proto;
+use opendut_util::project;
+
+use crate::auth::{CurrentServiceAccount, CurrentUser};
+use crate::resources::{IntoId, Resource};
+
+pub use summary::AuditSummary;
+
+mod summary;
+
+/// Name recorded as the user of an operation, when authentication is disabled.
+pub const UNAUTHENTICATED_USER: &str = "<unauthenticated>";
+
+/// Name recorded as the user of an operation, which CARL performed on its own, e.g. when a reservation expired.
+pub const SYSTEM_USER: &str = "<carl>";
+
+/// Prefix of the name recorded as the user of an operation, when a service account performed it.
+pub const SERVICE_ACCOUNT_USER_PREFIX: &str = "service-account:";
+
+pub type AuditLogRef = Arc<AuditLog>;
+
+/// Append-only log of all mutating operations performed on CARL's resources.
+pub struct AuditLog {
+    entries: RwLock<Vec<AuditEntry>>,
+    file: Option<PathBuf>,
+}
+
+impl AuditLog {
+
+    /// Creates an [`AuditLog`], which only keeps its entries in memory.
+    pub fn new() -> AuditLogRef {
+        Arc::new(Self {
+            entries: Default::default(),
+            file: None,
+        })
+    }
+
+    /// Creates an [`AuditLog`], which restores previously recorded entries from the given file and appends every new entry to it.
+    pub fn load(file: PathBuf) -> Result<AuditLogRef, AuditLogError> {
+        let entries = match std::fs::read(&file) {
+            Ok(content) => decode_entries(&content)
+                .map_err(|message| AuditLogError::InvalidFile { path: Clone::clone(&file), message })?,
+            Err(cause) if cause.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(cause) => return Err(AuditLogError::Io { path: file, cause }),
+        };
+        debug!("Restored {} audit entries from '{}'.", entries.len(), file.display());
+
+        Ok(Arc::new(Self {
+            entries: RwLock::new(entries),
+            file: Some(file),
+        }))
+    }
+
+    /// Records that `user` performed `operation` on the resource with the given id.
+    pub async fn record<R>(&self, user: String, operation: &str, id: impl IntoId<R>, before: Option<&R>, after: Option<&R>)
+    where R: Resource + AuditSummary {
+        let entry = AuditEntry {
+            timestamp: SystemTime::now(),
+            user,
+            operation: operation.to_owned(),
+            resource_id: id.into_id(),
+            before: before.map(AuditSummary::audit_summary),
+            after: after.map(AuditSummary::audit_summary),
+        };
+
+        let mut entries = self.entries.write().await;
+
+        if let Some(file) = &self.file {
+            if let Err(cause) = append_entry(file, Clone::clone(&entry)) {
+                error!("Failed to append entry to audit log '{}': {cause}\n  {entry:?}", file.display());
+            }
+        }
+        entries.push(entry);
+    }
+
+    /// Returns the entries matching the query, from oldest to latest.
+    pub async fn list(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        let entries = self.entries.read().await;
+
+        let mut result = entries.iter()
+            .rev()
+            .filter(|entry| query.user.as_ref().map_or(true, |user| &entry.user == user))
+            .filter(|entry| query.resource_id.map_or(true, |resource_id| entry.resource_id == resource_id))
+            .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+            .cloned()
+            .collect::<Vec<_>>();
+        result.reverse();
+        result
+    }
+}
+
+/// Returns the name of the user or service account, who sent the request.
+pub fn user_of<T>(request: &tonic::Request<T>) -> String {
+    let extensions = request.extensions();
+    if let Some(user) = extensions.get::<CurrentUser>() {
+        Clone::clone(&user.name)
+    } else if let Some(service_account) = extensions.get::<CurrentServiceAccount>() {
+        format!("{SERVICE_ACCOUNT_USER_PREFIX}{}", service_account.name)
+    } else {
+        String::from(UNAUTHENTICATED_USER)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuditLogError {
+    #[error("Failed to access audit log '{path}': {cause}")]
+    Io { path: PathBuf, cause: std::io::Error },
+    #[error("Invalid audit log '{path}': {message}")]
+    InvalidFile { path: PathBuf, message: String },
+}
+
+pub fn create(settings: &Config) -> anyhow::Result<AuditLogRef> {
+
+    let file = settings.get::<AuditFileConfig>("audit.file")?;
+
+    if file.enabled {
+        let path = project::make_path_absolute(file.path)?;
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        debug!("Appending audit entries to file: {}", path.display());
+        Ok(AuditLog::load(path)?)
+    } else {
+        Ok(AuditLog::new())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all="kebab-case")]
+struct AuditFileConfig {
+    enabled: bool,
+    path: PathBuf,
+}
+
+fn append_entry(path: &PathBuf, entry: AuditEntry) -> std::io::Result<()> {
+    let encoded = proto::AuditEntry::from(entry).encode_length_delimited_to_vec();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(&encoded)?;
+    file.flush()
+}
+
+fn decode_entries(mut content: &[u8]) -> Result<Vec<AuditEntry>, String> {
+    let mut entries = Vec::new();
+    while content.is_empty().not() {
+        let entry = proto::AuditEntry::decode_length_delimited(&mut content)
+            .map_err(|cause| cause.to_string())?;
+        entries.push(AuditEntry::try_from(entry).map_err(|cause| cause.to_string())?);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+    use opendut_types::peer::PeerId;
+    use opendut_types::project::ProjectName;
+    use opendut_types::resources::Id;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_recorded_entries_and_restore_them_from_file() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("audit.log");
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("AuditedCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+        let other_cluster_id = ClusterId::random();
+
+        let testee = AuditLog::load(Clone::clone(&file))?;
+        testee.record(String::from("alice"), "CreateClusterConfiguration", cluster_id, None, Some(&cluster_configuration)).await;
+        testee.record(String::from("bob"), "CreateClusterConfiguration", other_cluster_id, None, Some(&cluster_configuration)).await;
+        testee.record(String::from("bob"), "DeleteClusterConfiguration", cluster_id, Some(&cluster_configuration), None).await;
+
+        let testee = AuditLog::load(file)?;
+
+        let cluster_resource_id = Id::from(cluster_id.0);
+        assert_that!(testee.list(&AuditQuery { resource_id: Some(cluster_resource_id), ..Default::default() }).await, elements_are![
+            matches_pattern!(AuditEntry {
+                user: eq("alice"),
+                operation: eq("CreateClusterConfiguration"),
+                before: none(),
+                after: some(contains_substring("AuditedCluster")),
+            }),
+            matches_pattern!(AuditEntry {
+                user: eq("bob"),
+                operation: eq("DeleteClusterConfiguration"),
+                before: some(anything()),
+                after: none(),
+            }),
+        ]);
+        assert_that!(testee.list(&AuditQuery { user: Some(String::from("bob")), limit: Some(1), ..Default::default() }).await, elements_are![
+            matches_pattern!(AuditEntry {
+                operation: eq("DeleteClusterConfiguration"),
+            }),
+        ]);
+
+        Ok(())
+    }
+}
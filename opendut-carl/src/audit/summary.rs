@@ -0,0 +1,91 @@
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::setup::IssuedPeerSetup;
+use opendut_types::reservation::Reservation;
+use opendut_types::service_account::{ApiToken, ServiceAccount};
+use opendut_types::webhook::Webhook;
+
+/// Short, human-readable description of a resource, as recorded in the [`AuditLog`](super::AuditLog).
+pub trait AuditSummary {
+    fn audit_summary(&self) -> String;
+}
+
+impl AuditSummary for PeerDescriptor {
+    fn audit_summary(&self) -> String {
+        format!("Peer '{}' with {} network interface(s), {} device(s) and {} executor(s)",
+            self.name,
+            self.network.interfaces.len(),
+            self.topology.devices.len(),
+            self.executors.executors.len(),
+        )
+    }
+}
+
+impl AuditSummary for ClusterConfiguration {
+    fn audit_summary(&self) -> String {
+        format!("Cluster '{}' with leader <{}>, {} device(s) and {} device selector(s)",
+            self.name,
+            self.leader,
+            self.devices.len(),
+            self.device_selectors.len(),
+        )
+    }
+}
+
+impl AuditSummary for ClusterDeployment {
+    fn audit_summary(&self) -> String {
+        format!("Deployment of cluster <{}>", self.id)
+    }
+}
+
+impl AuditSummary for Reservation {
+    fn audit_summary(&self) -> String {
+        format!("Reservation of {} peer(s) and {} device(s) by '{}'",
+            self.peers.len(),
+            self.devices.len(),
+            self.user,
+        )
+    }
+}
+
+impl AuditSummary for IssuedPeerSetup {
+    fn audit_summary(&self) -> String {
+        format!("Setup-string for peer <{}> in state '{}'",
+            self.peer_id,
+            self.state,
+        )
+    }
+}
+
+impl AuditSummary for ServiceAccount {
+    fn audit_summary(&self) -> String {
+        format!("Service account '{}' created by '{}'",
+            self.name,
+            self.created_by,
+        )
+    }
+}
+
+impl AuditSummary for ApiToken {
+    fn audit_summary(&self) -> String {
+        format!("API token of service account <{}> with role '{}' for {} project(s)",
+            self.service_account,
+            self.role,
+            self.projects.len(),
+        )
+    }
+}
+
+impl AuditSummary for Webhook {
+    fn audit_summary(&self) -> String {
+        let events = if self.events.is_empty() {
+            String::from("all events")
+        } else {
+            format!("{} event(s)", self.events.len())
+        };
+        format!("Webhook to '{}' for {events} created by '{}'",
+            self.url,
+            self.created_by,
+        )
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccount};
+
+use crate::resources::Resources;
+
+/// Prefix of API tokens, to distinguish them from OIDC access tokens.
+pub const API_TOKEN_PREFIX: &str = "opendut_";
+
+/// Generates the secret of a new API token, which is handed out once, and the hash of it, which is stored by CARL.
+///
+/// The secret contains the id of the token, so the token can be looked up without comparing against every stored hash.
+pub fn generate_secret(api_token_id: ApiTokenId) -> (String, String) {
+    let random = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let secret = format!("{API_TOKEN_PREFIX}{}_{random}", api_token_id.0.simple());
+    let secret_hash = hash_secret(&secret);
+    (secret, secret_hash)
+}
+
+/// Whether the credentials are an API token, rather than an OIDC access token.
+pub fn is_api_token(credentials: &str) -> bool {
+    credentials.starts_with(API_TOKEN_PREFIX)
+}
+
+/// Returns the service account and the API token, if the secret belongs to a known, unexpired token.
+pub fn authenticate(resources: &Resources, secret: &str, now: SystemTime) -> Option<(ServiceAccount, ApiToken)> {
+    let (api_token_id, _) = secret.strip_prefix(API_TOKEN_PREFIX)?
+        .split_once('_')?;
+    let api_token_id = ApiTokenId::from(Uuid::try_parse(api_token_id).ok()?);
+
+    let api_token = resources.get::<ApiToken>(api_token_id)?;
+    if api_token.secret_hash != hash_secret(secret) || api_token.is_expired_at(now) {
+        return None;
+    }
+    let service_account = resources.get::<ServiceAccount>(api_token.service_account)?;
+    Some((service_account, api_token))
+}
+
+fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::service_account::{ApiTokenRole, ServiceAccountId, ServiceAccountName};
+
+    use super::*;
+
+    #[test]
+    fn should_only_authenticate_unexpired_tokens_with_matching_secret() {
+        let now = SystemTime::now();
+        let service_account = ServiceAccount {
+            id: ServiceAccountId::random(),
+            name: ServiceAccountName::try_from("ci-pipeline").unwrap(),
+            created_by: String::from("alice"),
+        };
+        let api_token_id = ApiTokenId::random();
+        let (secret, secret_hash) = generate_secret(api_token_id);
+        let api_token = ApiToken {
+            id: api_token_id,
+            service_account: service_account.id,
+            role: ApiTokenRole::Operator,
+            projects: HashSet::new(),
+            expires_at: now + Duration::from_secs(3600),
+            secret_hash,
+        };
+
+        let mut resources = Resources::default();
+        resources.insert(service_account.id, Clone::clone(&service_account));
+        resources.insert(api_token_id, Clone::clone(&api_token));
+
+        assert_that!(is_api_token(&secret), eq(true));
+        assert_that!(authenticate(&resources, &secret, now), some(eq((Clone::clone(&service_account), Clone::clone(&api_token)))));
+
+        let (other_secret, _) = generate_secret(api_token_id);
+        assert_that!(authenticate(&resources, &other_secret, now), none());
+        assert_that!(authenticate(&resources, &secret, now + Duration::from_secs(3600)), none());
+        assert_that!(authenticate(&resources, "opendut_invalid", now), none());
+    }
+}
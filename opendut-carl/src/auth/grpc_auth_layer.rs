@@ -1,17 +1,34 @@
+use std::ops::Not;
+use std::time::SystemTime;
+
 use tonic::Status;
 use url::Url;
 use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer::GrpcAuthLayerEnabled;
+use opendut_types::peer::setup::{IssuedPeerSetup, IssuedPeerSetupState};
+use opendut_types::util::net::ClientId;
+
+use crate::auth::{api_token, CurrentServiceAccount, CurrentUser};
 use crate::auth::json_web_key::JwkCacheValue;
+use crate::auth::mtls::ClientCertificate;
+use crate::auth::permission::{self, GrpcMethodPath, Role, RoleMapping};
+use crate::projects::ProjectScope;
+use crate::resources::manager::ResourcesManagerRef;
 use crate::util::in_memory_cache::CustomInMemoryCache;
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum GrpcAuthenticationLayer {
     AuthDisabled,
     GrpcAuthLayerEnabled {
         issuer_url: Url,
         issuer_remote_url: Url,
         cache: CustomInMemoryCache<String, JwkCacheValue>,
+        /// Restricts the methods a user may call, based on the user's roles. Disabled, if `None`.
+        role_mapping: Option<RoleMapping>,
+        /// Prefix of the OIDC groups, which make a user a member of a project.
+        project_group_prefix: String,
+        /// Used to look up the API tokens of service accounts.
+        resources_manager: ResourcesManagerRef,
     },
 }
 
@@ -22,7 +39,7 @@ impl GrpcAuthenticationLayer {
             GrpcAuthenticationLayer::AuthDisabled => {
                 Ok(request)
             }
-            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache } => {
+            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache, role_mapping, project_group_prefix, resources_manager } => {
                 let auth_header = match request.metadata().get("authorization") {
                     None => {
                         return Err(Status::unauthenticated("CARL says, you did not provide credentials!"))
@@ -32,7 +49,45 @@ impl GrpcAuthenticationLayer {
                     }
                 };
 
+                let credentials = auth_header.strip_prefix("Bearer ").unwrap_or_default();
+                if api_token::is_api_token(credentials) {
+                    let credentials = credentials.to_owned();
+                    let (service_account, api_token) = resources_manager.resources(|resources| {
+                        api_token::authenticate(resources, &credentials, SystemTime::now())
+                    }).await
+                        .ok_or_else(|| Status::unauthenticated("CARL says, invalid or expired API token!"))?;
+
+                    if permission::is_peer_method(method_path(&request)) {
+                        return Err(Status::permission_denied(format!("CARL says, service account '{}' is not a peer and may not call '{}'!", service_account.name, method_path(&request))));
+                    }
+
+                    // The permissions of an API token apply, even if role-based authorization of users is disabled.
+                    require_role(Some(Role::from(api_token.role)), &request, &format!("service account '{}'", service_account.name))?;
+
+                    request.extensions_mut().insert(ProjectScope::of_api_token(&api_token));
+                    request.extensions_mut().insert(CurrentServiceAccount {
+                        name: service_account.name,
+                    });
+                    return Ok(request);
+                }
+
                 if let Some(current_user) = crate::auth::authorization::authorize_current_user(auth_header, issuer_url, issuer_remote_url, cache).await {
+                    if permission::is_peer_method(method_path(&request)) {
+                        require_peer(&request, &current_user, &resources_manager).await?;
+                        request.extensions_mut().insert(current_user);
+                        return Ok(request);
+                    }
+
+                    let role = role_mapping.as_ref().and_then(|role_mapping| role_mapping.role_of(&current_user));
+                    if role_mapping.is_some() {
+                        require_role(role, &request, &format!("user '{}'", current_user.name))?;
+                    }
+                    let project_scope = if role == Some(Role::Admin) {
+                        ProjectScope::All
+                    } else {
+                        ProjectScope::of_user(&current_user, &project_group_prefix)
+                    };
+                    request.extensions_mut().insert(project_scope);
                     // insert the current user info into a request extension
                     request.extensions_mut().insert(current_user);
                     Ok(request)
@@ -43,3 +98,138 @@ impl GrpcAuthenticationLayer {
         }
     }
 }
+
+fn method_path<T>(request: &tonic::Request<T>) -> &str {
+    request.extensions().get::<GrpcMethodPath>()
+        .map(|GrpcMethodPath(path)| path.as_str())
+        .unwrap_or_default()
+}
+
+/// Peers are authorized by their identity instead of a role: Either by the client certificate CARL issued for them,
+/// which was verified during the TLS handshake, or by being authenticated as the OIDC client registered along with their setup-string.
+async fn require_peer<T>(request: &tonic::Request<T>, current_user: &CurrentUser, resources_manager: &ResourcesManagerRef) -> Result<(), Status> {
+    if request.extensions().get::<ClientCertificate>().is_some() {
+        return Ok(());
+    }
+
+    let is_peer_client = match current_user.claims.authorized_party() {
+        Some(client_id) => {
+            let client_id = ClientId(client_id.to_string());
+            resources_manager.resources(|resources| {
+                resources.iter::<IssuedPeerSetup>()
+                    .filter(|setup| matches!(setup.state, IssuedPeerSetupState::Revoked { .. }).not())
+                    .any(|setup| setup.client_id.as_ref() == Some(&client_id))
+            }).await
+        }
+        None => false,
+    };
+
+    if is_peer_client {
+        Ok(())
+    } else {
+        Err(Status::permission_denied(format!("CARL says, user '{}' is not a peer and may not call '{}'!", current_user.name, method_path(request))))
+    }
+}
+
+fn require_role<T>(role: Option<Role>, request: &tonic::Request<T>, caller: &str) -> Result<(), MissingRole> {
+    let path = method_path(request);
+    let required_role = permission::required_role(path);
+    let granted = role.is_some_and(|role| role >= required_role);
+    if granted {
+        Ok(())
+    } else {
+        Err(MissingRole { caller: caller.to_owned(), required_role, path: path.to_owned() })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("CARL says, {caller} requires the role '{required_role}' to call '{path}'!")]
+struct MissingRole {
+    caller: String,
+    required_role: Role,
+    path: String,
+}
+
+impl From<MissingRole> for Status {
+    fn from(error: MissingRole) -> Self {
+        Status::permission_denied(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use googletest::prelude::*;
+    use tonic::Code;
+
+    use opendut_types::peer::PeerId;
+    use opendut_types::peer::setup::PeerSetupNonce;
+
+    use crate::auth::json_web_key::OidcJsonWebKeySet;
+    use crate::auth::validation::tests::{ISSUER_URL, JWK_RAW_DATA, KEY_ID, TEST_TOKEN};
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    const OPEN: &str = "/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/Open";
+    const LIST_PEERS: &str = "/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/ListPeers";
+
+    fn testee(resources_manager: &ResourcesManagerRef) -> GrpcAuthenticationLayer {
+        let jwk = serde_json::from_str::<OidcJsonWebKeySet>(JWK_RAW_DATA).unwrap()
+            .keys.into_iter()
+            .map(|jwk| (Clone::clone(&jwk.key_identifier), jwk))
+            .collect::<BTreeMap<_, _>>()
+            .remove(KEY_ID).unwrap();
+        let mut cache = CustomInMemoryCache::new();
+        cache.insert(String::from(KEY_ID), JwkCacheValue { jwk, last_cached: Utc::now().timestamp() }).unwrap();
+
+        GrpcAuthLayerEnabled {
+            issuer_url: Url::parse(ISSUER_URL).unwrap(),
+            issuer_remote_url: Url::parse(ISSUER_URL).unwrap(),
+            cache,
+            role_mapping: Some(RoleMapping {
+                viewer: vec![String::from("testrole")],
+                operator: vec![],
+                admin: vec![],
+            }),
+            project_group_prefix: String::from("/"),
+            resources_manager: Arc::clone(resources_manager),
+        }
+    }
+
+    fn request(path: &str) -> tonic::Request<()> {
+        let mut request = tonic::Request::new(());
+        request.metadata_mut().insert("authorization", format!("Bearer {TEST_TOKEN}").parse().unwrap());
+        request.extensions_mut().insert(GrpcMethodPath(String::from(path)));
+        request
+    }
+
+    #[tokio::test]
+    async fn should_authorize_peer_messaging_broker_streams_by_peer_identity_instead_of_roles() {
+        let resources_manager = ResourcesManager::new();
+
+        let result = testee(&resources_manager).auth_interceptor(request(LIST_PEERS)).await;
+        assert_that!(result, ok(anything()));
+
+        let result = testee(&resources_manager).auth_interceptor(request(OPEN)).await;
+        assert_that!(result, err(property!(Status.code(), eq(Code::PermissionDenied))));
+
+        let now = SystemTime::now();
+        let setup = IssuedPeerSetup {
+            nonce: PeerSetupNonce::random(),
+            peer_id: PeerId::random(),
+            issued_at: now,
+            expires_at: now + Duration::from_secs(3600),
+            state: IssuedPeerSetupState::Outstanding,
+            client_id: Some(ClientId(String::from("opendut-lea-client"))),
+        };
+        resources_manager.insert(setup.nonce, setup).await.unwrap();
+
+        let result = testee(&resources_manager).auth_interceptor(request(OPEN)).await;
+        assert_that!(result, ok(anything()));
+    }
+}
@@ -2,11 +2,16 @@ mod validation;
 pub(crate) mod json_web_key;
 mod authorization;
 pub(crate) mod grpc_auth_layer;
+pub(crate) mod permission;
+pub(crate) mod api_token;
+pub(crate) mod mtls;
 
 use openidconnect::core::CoreGenderClaim;
 use openidconnect::{AdditionalClaims, IdTokenClaims};
 use serde::{Deserialize, Serialize};
 
+use opendut_types::service_account::ServiceAccountName;
+
 pub type Claims<AC> = IdTokenClaims<AC, CoreGenderClaim>;
 
 #[derive(Clone, Debug)]
@@ -15,6 +20,12 @@ pub struct CurrentUser {
     pub claims: Claims<MyAdditionalClaims>,
 }
 
+/// Service account, which authenticated with an API token instead of OIDC.
+#[derive(Clone, Debug)]
+pub struct CurrentServiceAccount {
+    pub name: ServiceAccountName,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MyAdditionalClaims {
     /// Roles the user belongs to (custom claim)
@@ -0,0 +1,164 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+
+use anyhow::{anyhow, Context as _};
+use axum_server::accept::Accept;
+use axum_server_dual_protocol::{DualProtocolAcceptor, DualProtocolService};
+use axum_server_dual_protocol::hyper::server::conn::AddrStream;
+use axum_server_dual_protocol::tokio_rustls::rustls;
+use axum_server_dual_protocol::tokio_util::either::Either;
+use futures::future::BoxFuture;
+use http::Request;
+use tower::Service;
+
+use opendut_util::project;
+
+use crate::peer::certificate_authority::PeerCertificateAuthority;
+
+/// DER-encoded client certificate, which was presented during the TLS handshake of the connection a request was received on.
+///
+/// Inserted as request extension by the [`ClientCertificateAcceptor`].
+#[derive(Clone, Debug)]
+pub struct ClientCertificate(pub Arc<Vec<u8>>);
+
+/// Files containing the TLS certificate and key of CARL's server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerTlsFiles {
+    pub certificate: PathBuf,
+    pub key: PathBuf,
+}
+impl ServerTlsFiles {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            certificate: project::make_path_absolute(config.get_string("network.tls.certificate")?)?,
+            key: project::make_path_absolute(config.get_string("network.tls.key")?)?,
+        })
+    }
+}
+
+/// Creates the TLS configuration of CARL's server.
+///
+/// Clients may connect without a client certificate, but if they present one, it has to be issued by the [`PeerCertificateAuthority`].
+/// Which requests require a client certificate is decided by the respective service.
+pub fn server_config(certificate: &Path, key: &Path, certificate_authority: &PeerCertificateAuthority) -> anyhow::Result<Arc<rustls::ServerConfig>> {
+
+    let certificate_chain = pem::parse_many(std::fs::read(certificate)?)
+        .context(format!("Failed to parse TLS certificate file at '{}'.", certificate.display()))?
+        .into_iter()
+        .filter(|pem| pem.tag() == "CERTIFICATE")
+        .map(|pem| rustls::Certificate(pem.into_contents()))
+        .collect::<Vec<_>>();
+
+    let key = pem::parse(std::fs::read(key)?)
+        .context(format!("Failed to parse TLS key file at '{}'.", key.display()))?;
+    let key = rustls::PrivateKey(key.into_contents());
+
+    let mut client_certificate_roots = rustls::RootCertStore::empty();
+    client_certificate_roots.add(&rustls::Certificate(certificate_authority.certificate().to_vec()))
+        .map_err(|cause| anyhow!("Failed to trust certificate authority for peers: {cause}"))?;
+
+    let mut config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(rustls::server::AllowAnyAnonymousOrAuthenticatedClient::new(client_certificate_roots).boxed())
+        .with_single_cert(certificate_chain, key)?;
+
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(Arc::new(config))
+}
+
+/// Accepts connections like the wrapped [`DualProtocolAcceptor`] and makes the client certificate of TLS connections available as [`ClientCertificate`].
+#[derive(Clone)]
+pub struct ClientCertificateAcceptor {
+    inner: DualProtocolAcceptor,
+}
+
+impl ClientCertificateAcceptor {
+    pub fn new(inner: DualProtocolAcceptor) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S: Send + 'static> Accept<AddrStream, S> for ClientCertificateAcceptor {
+    type Stream = <DualProtocolAcceptor as Accept<AddrStream, WithClientCertificate<S>>>::Stream;
+    type Service = DualProtocolService<WithClientCertificate<S>>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: AddrStream, service: S) -> Self::Future {
+        let certificate = Arc::new(OnceLock::new());
+        let service = WithClientCertificate { inner: service, certificate: Arc::clone(&certificate) };
+        let accept = self.inner.accept(stream, service);
+
+        Box::pin(async move {
+            let (stream, service) = accept.await?;
+            if let Either::Left(tls) = &stream {
+                let (_, connection) = tls.get_ref();
+                if let Some(client_certificate) = connection.peer_certificates().and_then(|certificates| certificates.first()) {
+                    let _ = certificate.set(ClientCertificate(Arc::new(Clone::clone(&client_certificate.0))));
+                }
+            }
+            Ok((stream, service))
+        })
+    }
+}
+
+/// Inserts the [`ClientCertificate`] of the connection into each request, once the TLS handshake completed.
+#[derive(Clone)]
+pub struct WithClientCertificate<S> {
+    inner: S,
+    certificate: Arc<OnceLock<ClientCertificate>>,
+}
+
+impl<S, B> Service<Request<B>> for WithClientCertificate<S>
+where S: Service<Request<B>> {
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        if let Some(certificate) = self.certificate.get() {
+            request.extensions_mut().insert(Clone::clone(certificate));
+        }
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerId;
+
+    use super::*;
+
+    #[test]
+    fn should_verify_client_certificates_issued_by_the_certificate_authority() -> anyhow::Result<()> {
+        let certificate_authority = PeerCertificateAuthority::generate()?;
+        let now = SystemTime::now();
+        let (certificate, _) = certificate_authority.issue(PeerId::random(), now, Duration::from_secs(3600))?;
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(&rustls::Certificate(certificate_authority.certificate().to_vec()))?;
+        let verifier = rustls::server::AllowAnyAuthenticatedClient::new(roots);
+
+        let certificate = rustls::Certificate(certificate.certificate.0.into_contents());
+        let result = rustls::server::ClientCertVerifier::verify_client_cert(&verifier, &certificate, &[], now);
+        assert_that!(result, ok(anything()));
+
+        let other_certificate_authority = PeerCertificateAuthority::generate()?;
+        let (other_certificate, _) = other_certificate_authority.issue(PeerId::random(), now, Duration::from_secs(3600))?;
+        let other_certificate = rustls::Certificate(other_certificate.certificate.0.into_contents());
+        let result = rustls::server::ClientCertVerifier::verify_client_cert(&verifier, &other_certificate, &[], now);
+        assert_that!(result, err(anything()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+use opendut_types::service_account::ApiTokenRole;
+
+use crate::auth::CurrentUser;
+
+/// Level of access to CARL's gRPC services. Each role includes the permissions of the roles below it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    /// May read all resources.
+    Viewer,
+    /// May additionally create, change and delete peers, clusters and reservations.
+    Operator,
+    /// May additionally generate setups, create and restore backups, manage webhooks and read the audit log.
+    Admin,
+}
+
+impl From<ApiTokenRole> for Role {
+    fn from(value: ApiTokenRole) -> Self {
+        match value {
+            ApiTokenRole::Viewer => Role::Viewer,
+            ApiTokenRole::Operator => Role::Operator,
+            ApiTokenRole::Admin => Role::Admin,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Role::Viewer => write!(f, "viewer"),
+            Role::Operator => write!(f, "operator"),
+            Role::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// Path of the gRPC method being called, e.g. `/opendut.carl.services.peer_manager.PeerManager/DeletePeerDescriptor`.
+///
+/// Inserted as request extension, since the path is not available anymore in a [`tonic::Request`].
+#[derive(Clone, Debug)]
+pub struct GrpcMethodPath(pub String);
+
+impl GrpcMethodPath {
+    pub fn insert_into<B>(mut request: http::Request<B>) -> http::Request<B> {
+        let path = GrpcMethodPath(request.uri().path().to_owned());
+        request.extensions_mut().insert(path);
+        request
+    }
+}
+
+/// Maps the OIDC roles and groups of a user to a [`Role`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RoleMapping {
+    #[serde(default)]
+    pub viewer: Vec<String>,
+    #[serde(default)]
+    pub operator: Vec<String>,
+    #[serde(default)]
+    pub admin: Vec<String>,
+}
+
+impl RoleMapping {
+    /// Loads the mapping, if authorization is enabled. Otherwise, every authenticated user may call every method.
+    pub fn load(config: &config::Config) -> Result<Option<Self>, opendut_util::settings::LoadError> {
+        if config.get_bool("network.oidc.authorization.enabled")? {
+            Ok(Some(config.get::<RoleMapping>("network.oidc.authorization.roles")?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the highest role granted to the user, if any.
+    pub fn role_of(&self, user: &CurrentUser) -> Option<Role> {
+        let additional_claims = user.claims.additional_claims();
+        let is_granted = |names: &[String]| {
+            additional_claims.roles.iter()
+                .chain(additional_claims.groups.iter())
+                .any(|claim| names.contains(claim))
+        };
+
+        [(Role::Admin, &self.admin), (Role::Operator, &self.operator), (Role::Viewer, &self.viewer)].into_iter()
+            .find(|(_, names)| is_granted(names))
+            .map(|(role, _)| role)
+    }
+}
+
+/// Returns whether the gRPC method with the given path may only be called by peers.
+///
+/// These methods are authorized by the identity of the peer instead of a [`Role`], since peers authenticate as their own OIDC client without any roles.
+pub fn is_peer_method(path: &str) -> bool {
+    matches!(service_and_method(path), ("PeerMessagingBroker", "Open"))
+}
+
+/// Returns the role required for calling the gRPC method with the given path.
+///
+/// Unknown methods require the [`Role::Admin`], so newly added methods are denied until they are listed here.
+pub fn required_role(path: &str) -> Role {
+    match service_and_method(path) {
+        ("MetadataProvider", _) => Role::Viewer,
+        ("PeerMessagingBroker", "ListPeers") => Role::Viewer,
+        ("AuditLog", _) | ("Backup", _) | ("ServiceAccountManager", _) | ("WebhookManager", _) => Role::Admin,
+        ("PeerManager", "GeneratePeerSetup" | "GenerateCleoSetup" | "RevokePeerSetup") => Role::Admin,
+        ("ClusterManager" | "PeerManager" | "ReservationManager", method)
+            if method.starts_with("Get") || method.starts_with("List") || method.starts_with("Watch") => Role::Viewer,
+        ("ClusterManager" | "PeerManager" | "ReservationManager", _) => Role::Operator,
+        _ => Role::Admin,
+    }
+}
+
+fn service_and_method(path: &str) -> (&str, &str) {
+    let (service, method) = path.trim_start_matches('/')
+        .split_once('/')
+        .unwrap_or_default();
+    let service = service.rsplit('.').next().unwrap_or_default();
+    (service, method)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use openidconnect::{Audience, IssuerUrl, StandardClaims, SubjectIdentifier};
+    use rstest::rstest;
+
+    use crate::auth::{Claims, MyAdditionalClaims};
+
+    use super::*;
+
+    #[rstest]
+    #[case("/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors", Role::Viewer)]
+    #[case("/opendut.carl.services.cluster_manager.ClusterManager/WatchClusterDeployments", Role::Viewer)]
+    #[case("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/ListPeers", Role::Viewer)]
+    #[case("/opendut.carl.services.cluster_manager.ClusterManager/DeleteClusterConfiguration", Role::Operator)]
+    #[case("/opendut.carl.services.reservation_manager.ReservationManager/CreateReservation", Role::Operator)]
+    #[case("/opendut.carl.services.peer_manager.PeerManager/GeneratePeerSetup", Role::Admin)]
+    #[case("/opendut.carl.services.peer_manager.PeerManager/RevokePeerSetup", Role::Admin)]
+    #[case("/opendut.carl.services.backup.Backup/RestoreBackup", Role::Admin)]
+    #[case("/opendut.carl.services.service_account_manager.ServiceAccountManager/ListApiTokens", Role::Admin)]
+    #[case("/opendut.carl.services.webhook_manager.WebhookManager/ListWebhookDeliveries", Role::Admin)]
+    #[case("/opendut.carl.services.unknown.Unknown/Call", Role::Admin)]
+    fn should_determine_required_role(#[case] path: &str, #[case] expected: Role) {
+        assert_that!(required_role(path), eq(expected));
+    }
+
+    #[rstest]
+    #[case("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/Open", true)]
+    #[case("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/ListPeers", false)]
+    #[case("/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors", false)]
+    fn should_determine_peer_methods(#[case] path: &str, #[case] expected: bool) {
+        assert_that!(is_peer_method(path), eq(expected));
+    }
+
+    #[rstest]
+    fn should_grant_highest_role_matching_roles_or_groups() {
+        let testee = RoleMapping {
+            viewer: vec![String::from("opendut-viewer")],
+            operator: vec![String::from("/operators")],
+            admin: vec![String::from("opendut-admin")],
+        };
+
+        assert_that!(testee.role_of(&user(vec!["opendut-viewer"], vec![])), some(eq(Role::Viewer)));
+        assert_that!(testee.role_of(&user(vec!["opendut-viewer"], vec!["/operators"])), some(eq(Role::Operator)));
+        assert_that!(testee.role_of(&user(vec!["opendut-admin", "opendut-viewer"], vec![])), some(eq(Role::Admin)));
+        assert_that!(testee.role_of(&user(vec!["offline_access"], vec![])), none());
+    }
+
+    fn user(roles: Vec<&str>, groups: Vec<&str>) -> CurrentUser {
+        let additional_claims = MyAdditionalClaims {
+            roles: roles.into_iter().map(String::from).collect(),
+            groups: groups.into_iter().map(String::from).collect(),
+        };
+        let claims = Claims::new(
+            IssuerUrl::new(String::from("https://keycloak/realms/opendut/")).unwrap(),
+            vec![Audience::new(String::from("account"))],
+            chrono::Utc::now(),
+            chrono::Utc::now(),
+            StandardClaims::new(SubjectIdentifier::new(String::from("user"))),
+            additional_claims,
+        );
+        CurrentUser {
+            name: String::from("user"),
+            claims,
+        }
+    }
+}
@@ -152,7 +152,7 @@ impl JwkRequester for Jwk {
 
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::BTreeMap;
     use std::ops::Sub;
     use chrono::{Duration, Utc};
@@ -164,10 +164,10 @@ mod tests {
     use crate::util::in_memory_cache::CustomInMemoryCache;
     use crate::auth::validation::{authorize_user, JwkRequester, validate_token, ValidationError};
 
-    const KEY_ID: &str = "9RcB1okOXQ6QibEeXzAxFVym9PmBynkFe8mbh6X-DB0";
-    const TEST_TOKEN: &str = "eyJhbGciOiJSUzI1NiIsInR5cCIgOiAiSldUIiwia2lkIiA6ICI5UmNCMW9rT1hRNlFpYkVlWHpBeEZWeW05UG1CeW5rRmU4bWJoNlgtREIwIn0.eyJleHAiOjE3MjE3MjUzMjgsImlhdCI6MTcyMTcyNTAyOCwiYXV0aF90aW1lIjoxNzIxNzI1MDI4LCJqdGkiOiJkNzI1ZjlkZi04OWQ5LTQwZGQtOGMzYi0yMzA2ZGUzNzkzODUiLCJpc3MiOiJodHRwczovL2tleWNsb2FrL3JlYWxtcy9vcGVuZHV0IiwiYXVkIjoiYWNjb3VudCIsInN1YiI6IjljNTBhOGU5LTRlZjYtNGE4Zi04ZDZlLWFkNjhiYjk4NGJhMSIsInR5cCI6IkJlYXJlciIsImF6cCI6Im9wZW5kdXQtbGVhLWNsaWVudCIsInNlc3Npb25fc3RhdGUiOiIzZGYxZGM5YS1jMjMzLTRiMWEtODdlYS1kMGYyOTVlMDBmNzUiLCJhY3IiOiIxIiwiYWxsb3dlZC1vcmlnaW5zIjpbIioiXSwicmVzb3VyY2VfYWNjZXNzIjp7ImFjY291bnQiOnsicm9sZXMiOlsibWFuYWdlLWFjY291bnQiLCJtYW5hZ2UtYWNjb3VudC1saW5rcyIsInZpZXctcHJvZmlsZSJdfX0sInNjb3BlIjoib3BlbmlkIGVtYWlsIHByb2ZpbGUgZ3JvdXBzIiwic2lkIjoiM2RmMWRjOWEtYzIzMy00YjFhLTg3ZWEtZDBmMjk1ZTAwZjc1IiwiZW1haWxfdmVyaWZpZWQiOmZhbHNlLCJyb2xlcyI6WyJvZmZsaW5lX2FjY2VzcyIsImRlZmF1bHQtcm9sZXMtb3BlbmR1dCIsInRlc3Ryb2xlIiwidW1hX2F1dGhvcml6YXRpb24iXSwibmFtZSI6IkZpcnN0bmFtZSBMYXN0bmFtZSIsImdyb3VwcyI6WyIvdGVzdGdyb3VwIl0sInByZWZlcnJlZF91c2VybmFtZSI6Im9wZW5kdXQiLCJnaXZlbl9uYW1lIjoiRmlyc3RuYW1lIiwiZmFtaWx5X25hbWUiOiJMYXN0bmFtZSIsImVtYWlsIjoib3BlbmR1dEBleGFtcGxlLmNvbSJ9.PLYTZ_v4GGM6YPZC_afI67eJ8U5sbV6aS2YbBDhmvNfhH-g-Sn_2NZImcPLxiz50_5pbRhhi8pnDnshbLHkxv2uEj1ltdPRmSCD4xqzlP7kDLn0kMVsBJHIeL5olj7zY8KjWJAieFH2oOZIiMiWRAsD9SAUSyr1tTNv38p6i0Pyy_Op-fDlF1zZel2adLke8j0Svb7H63OSsOTt8HES-sUIMd4VJDH3yb83OECFVBEieE3GRq_77BgtffzgXgJZAAA84ija7O-ao_raSoy1ycqykEqmdSu9X-dzw_YrjtroUBM7RS4hrI9iJ5pGwH_LESUd8L93xUX5yYEZeN-0r-g";
-    const ISSUER_URL: &str  = "https://keycloak/realms/opendut/";
-    const JWK_RAW_DATA: &str = r#"{"keys":[{"kid":"9RcB1okOXQ6QibEeXzAxFVym9PmBynkFe8mbh6X-DB0","kty":"RSA","alg":"RS256","use":"sig","n":"jJTeGo90wWqXEk4JHRlPVF5hOXViKk5qnIlwiUAyx3CfBBuwSVEKVCq73TtuG57EQFca-o01SYKGGg-yU2VyleEDKbSGBzdl2LelrUwHCdSphupnIGPJ12wU8EDBgfOh0llWpNYTrEtNjbHLaYbMZL9_a7sXOTJxC6-S9EcpyhvI0LZHjOJe_YAnkj1Wx5OKWRZhiV5_y00SQI8xHinnOKLWH86giOBBJuN5Z-Ii3xNPF8jtHLdEXNw6cbeueaeU56Rlmy9AkuGdnQzBnP4hMRVul7Poam7iDD30Rl_qfH4yO-jhDnw1Mz4JALBPToaZ3WC6oXkfoGQo0Q4wmN3oNQ","e":"AQAB","x5c":["MIICnTCCAYUCBgGQgfqpwDANBgkqhkiG9w0BAQsFADASMRAwDgYDVQQDDAdvcGVuZHV0MB4XDTI0MDcwNTA4MTgyNloXDTM0MDcwNTA4MjAwNlowEjEQMA4GA1UEAwwHb3BlbmR1dDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBAIyU3hqPdMFqlxJOCR0ZT1ReYTl1YipOapyJcIlAMsdwnwQbsElRClQqu907bhuexEBXGvqNNUmChhoPslNlcpXhAym0hgc3Zdi3pa1MBwnUqYbqZyBjyddsFPBAwYHzodJZVqTWE6xLTY2xy2mGzGS/f2u7FzkycQuvkvRHKcobyNC2R4ziXv2AJ5I9VseTilkWYYlef8tNEkCPMR4p5zii1h/OoIjgQSbjeWfiIt8TTxfI7Ry3RFzcOnG3rnmnlOekZZsvQJLhnZ0MwZz+ITEVbpez6Gpu4gw99EZf6nx+Mjvo4Q58NTM+CQCwT06Gmd1guqF5H6BkKNEOMJjd6DUCAwEAATANBgkqhkiG9w0BAQsFAAOCAQEAczQGabVZrMKsJNV+eoLcCUxzLv9tYRaFbLrT5+keotgl6YYfZ3W63wY9IaZp0wT5zKdG2meifJ48173VP/8/437A+t0zCkH2kfQY9sP3EXDKVbw8LuViaoVO2w3GoanRJP8BKSAMo3voRCnd6QAPCbaTIUM2M0bRl1RADRuAZXbWM8817Sk2w0qMkSyxDJY9JNRviUQBU0V4ziro9mB+pVIMJ/Z4anNGsTNL6D9HdI3/7iBuC7SLTVh8x/Yg0mYnud8WwRePOZuxDbA65V2lL3ixB4uhjq9yuo5F76c/TuyrFFUrXXmUMn5+0/OjRhHEKBZSUJHGvvQlgkjzkOcovg=="],"x5t":"pa3zfyZhNzSUhKHXzIn5QbOuFyA","x5t#S256":"v8an46MZ8wHfjnUW2fUGl5Xh602pXEC8Lb_p7EUSATg"},{"kid":"rSPOu3JnH_GrUFiekXboNx7s4xO816XM7Hb_F8bz8Y0","kty":"RSA","alg":"RSA-OAEP","use":"enc","n":"kKo_9nNiiLcImSd5xdNFEUEaQ6BFe9j__XOdEaFNMfa0zc-lu4J6wjyDEILR5HdgzQfaRlne66z4TwiJwyoyDRz7EqB75voagmsZn9UK8CGp4h27Tz7y1doPletRV3458PWPzy4epYAgsu-yEYVXTc8OT_XnXlnNAN4z1DpI-1Kk4uFS1zvRUiUvr8kzauJbPdA7LTKMU5vw5yfjATMZL3ZlhwNLnU82xqr4zqnMdrAeQewuGEXud8-IUHotTKCuM-KwkRjLRrIxYNMyM9h8UStOXpxlc8ARwyrjWGfFVbUPNlxossSzLP223OiCEBY_SEDF8d9gsl7NkSAJdOUE6w","e":"AQAB","x5c":["MIICnTCCAYUCBgGQgfqq2jANBgkqhkiG9w0BAQsFADASMRAwDgYDVQQDDAdvcGVuZHV0MB4XDTI0MDcwNTA4MTgyN1oXDTM0MDcwNTA4MjAwN1owEjEQMA4GA1UEAwwHb3BlbmR1dDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBAJCqP/ZzYoi3CJknecXTRRFBGkOgRXvY//1znRGhTTH2tM3PpbuCesI8gxCC0eR3YM0H2kZZ3uus+E8IicMqMg0c+xKge+b6GoJrGZ/VCvAhqeIdu08+8tXaD5XrUVd+OfD1j88uHqWAILLvshGFV03PDk/1515ZzQDeM9Q6SPtSpOLhUtc70VIlL6/JM2riWz3QOy0yjFOb8Ocn4wEzGS92ZYcDS51PNsaq+M6pzHawHkHsLhhF7nfPiFB6LUygrjPisJEYy0ayMWDTMjPYfFErTl6cZXPAEcMq41hnxVW1DzZcaLLEsyz9ttzoghAWP0hAxfHfYLJezZEgCXTlBOsCAwEAATANBgkqhkiG9w0BAQsFAAOCAQEAQsLO8nRuRGl5YqV0IJaX4GDunc7EGfD4Gofl5NNtG3SojISC0lmO4EyZdFsXJmmWgzFkg1aO91jdcZyIaf6qBbj+GPtoBltA0+nSAcCTDvOsmV1J1Gymxm/CJLTBGqIrLwEXDBFyFpF2W7OE7XdXby+d/mYVkpCc0fHC854w+tOLdvEr4AYD/3JNK5VWd1RLI1CeZ7nJeLbDUR5UkGGb2Na3SXaEsWWwor2L9OAY4bWq9+gIom7ihaDvXMMpMHbQ7gis8Ku5ltK80PISW/9b+G1IxKNYy+euCr9ZWiIeEcKBt0/dKSvCcfhG0mShmliETgGAfAdZu0eqqhuxATAi9A=="],"x5t":"gfshQCGXfVblp5YrHiYSlYUto90","x5t#S256":"h53Q-c8zYde1UjhjhLZB1I5Q7tjX-t9bz7lE_fV6Bbg"}]}"#;
+    pub(crate) const KEY_ID: &str = "9RcB1okOXQ6QibEeXzAxFVym9PmBynkFe8mbh6X-DB0";
+    pub(crate) const TEST_TOKEN: &str = "eyJhbGciOiJSUzI1NiIsInR5cCIgOiAiSldUIiwia2lkIiA6ICI5UmNCMW9rT1hRNlFpYkVlWHpBeEZWeW05UG1CeW5rRmU4bWJoNlgtREIwIn0.eyJleHAiOjE3MjE3MjUzMjgsImlhdCI6MTcyMTcyNTAyOCwiYXV0aF90aW1lIjoxNzIxNzI1MDI4LCJqdGkiOiJkNzI1ZjlkZi04OWQ5LTQwZGQtOGMzYi0yMzA2ZGUzNzkzODUiLCJpc3MiOiJodHRwczovL2tleWNsb2FrL3JlYWxtcy9vcGVuZHV0IiwiYXVkIjoiYWNjb3VudCIsInN1YiI6IjljNTBhOGU5LTRlZjYtNGE4Zi04ZDZlLWFkNjhiYjk4NGJhMSIsInR5cCI6IkJlYXJlciIsImF6cCI6Im9wZW5kdXQtbGVhLWNsaWVudCIsInNlc3Npb25fc3RhdGUiOiIzZGYxZGM5YS1jMjMzLTRiMWEtODdlYS1kMGYyOTVlMDBmNzUiLCJhY3IiOiIxIiwiYWxsb3dlZC1vcmlnaW5zIjpbIioiXSwicmVzb3VyY2VfYWNjZXNzIjp7ImFjY291bnQiOnsicm9sZXMiOlsibWFuYWdlLWFjY291bnQiLCJtYW5hZ2UtYWNjb3VudC1saW5rcyIsInZpZXctcHJvZmlsZSJdfX0sInNjb3BlIjoib3BlbmlkIGVtYWlsIHByb2ZpbGUgZ3JvdXBzIiwic2lkIjoiM2RmMWRjOWEtYzIzMy00YjFhLTg3ZWEtZDBmMjk1ZTAwZjc1IiwiZW1haWxfdmVyaWZpZWQiOmZhbHNlLCJyb2xlcyI6WyJvZmZsaW5lX2FjY2VzcyIsImRlZmF1bHQtcm9sZXMtb3BlbmR1dCIsInRlc3Ryb2xlIiwidW1hX2F1dGhvcml6YXRpb24iXSwibmFtZSI6IkZpcnN0bmFtZSBMYXN0bmFtZSIsImdyb3VwcyI6WyIvdGVzdGdyb3VwIl0sInByZWZlcnJlZF91c2VybmFtZSI6Im9wZW5kdXQiLCJnaXZlbl9uYW1lIjoiRmlyc3RuYW1lIiwiZmFtaWx5X25hbWUiOiJMYXN0bmFtZSIsImVtYWlsIjoib3BlbmR1dEBleGFtcGxlLmNvbSJ9.PLYTZ_v4GGM6YPZC_afI67eJ8U5sbV6aS2YbBDhmvNfhH-g-Sn_2NZImcPLxiz50_5pbRhhi8pnDnshbLHkxv2uEj1ltdPRmSCD4xqzlP7kDLn0kMVsBJHIeL5olj7zY8KjWJAieFH2oOZIiMiWRAsD9SAUSyr1tTNv38p6i0Pyy_Op-fDlF1zZel2adLke8j0Svb7H63OSsOTt8HES-sUIMd4VJDH3yb83OECFVBEieE3GRq_77BgtffzgXgJZAAA84ija7O-ao_raSoy1ycqykEqmdSu9X-dzw_YrjtroUBM7RS4hrI9iJ5pGwH_LESUd8L93xUX5yYEZeN-0r-g";
+    pub(crate) const ISSUER_URL: &str  = "https://keycloak/realms/opendut/";
+    pub(crate) const JWK_RAW_DATA: &str = r#"{"keys":[{"kid":"9RcB1okOXQ6QibEeXzAxFVym9PmBynkFe8mbh6X-DB0","kty":"RSA","alg":"RS256","use":"sig","n":"jJTeGo90wWqXEk4JHRlPVF5hOXViKk5qnIlwiUAyx3CfBBuwSVEKVCq73TtuG57EQFca-o01SYKGGg-yU2VyleEDKbSGBzdl2LelrUwHCdSphupnIGPJ12wU8EDBgfOh0llWpNYTrEtNjbHLaYbMZL9_a7sXOTJxC6-S9EcpyhvI0LZHjOJe_YAnkj1Wx5OKWRZhiV5_y00SQI8xHinnOKLWH86giOBBJuN5Z-Ii3xNPF8jtHLdEXNw6cbeueaeU56Rlmy9AkuGdnQzBnP4hMRVul7Poam7iDD30Rl_qfH4yO-jhDnw1Mz4JALBPToaZ3WC6oXkfoGQo0Q4wmN3oNQ","e":"AQAB","x5c":["MIICnTCCAYUCBgGQgfqpwDANBgkqhkiG9w0BAQsFADASMRAwDgYDVQQDDAdvcGVuZHV0MB4XDTI0MDcwNTA4MTgyNloXDTM0MDcwNTA4MjAwNlowEjEQMA4GA1UEAwwHb3BlbmR1dDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBAIyU3hqPdMFqlxJOCR0ZT1ReYTl1YipOapyJcIlAMsdwnwQbsElRClQqu907bhuexEBXGvqNNUmChhoPslNlcpXhAym0hgc3Zdi3pa1MBwnUqYbqZyBjyddsFPBAwYHzodJZVqTWE6xLTY2xy2mGzGS/f2u7FzkycQuvkvRHKcobyNC2R4ziXv2AJ5I9VseTilkWYYlef8tNEkCPMR4p5zii1h/OoIjgQSbjeWfiIt8TTxfI7Ry3RFzcOnG3rnmnlOekZZsvQJLhnZ0MwZz+ITEVbpez6Gpu4gw99EZf6nx+Mjvo4Q58NTM+CQCwT06Gmd1guqF5H6BkKNEOMJjd6DUCAwEAATANBgkqhkiG9w0BAQsFAAOCAQEAczQGabVZrMKsJNV+eoLcCUxzLv9tYRaFbLrT5+keotgl6YYfZ3W63wY9IaZp0wT5zKdG2meifJ48173VP/8/437A+t0zCkH2kfQY9sP3EXDKVbw8LuViaoVO2w3GoanRJP8BKSAMo3voRCnd6QAPCbaTIUM2M0bRl1RADRuAZXbWM8817Sk2w0qMkSyxDJY9JNRviUQBU0V4ziro9mB+pVIMJ/Z4anNGsTNL6D9HdI3/7iBuC7SLTVh8x/Yg0mYnud8WwRePOZuxDbA65V2lL3ixB4uhjq9yuo5F76c/TuyrFFUrXXmUMn5+0/OjRhHEKBZSUJHGvvQlgkjzkOcovg=="],"x5t":"pa3zfyZhNzSUhKHXzIn5QbOuFyA","x5t#S256":"v8an46MZ8wHfjnUW2fUGl5Xh602pXEC8Lb_p7EUSATg"},{"kid":"rSPOu3JnH_GrUFiekXboNx7s4xO816XM7Hb_F8bz8Y0","kty":"RSA","alg":"RSA-OAEP","use":"enc","n":"kKo_9nNiiLcImSd5xdNFEUEaQ6BFe9j__XOdEaFNMfa0zc-lu4J6wjyDEILR5HdgzQfaRlne66z4TwiJwyoyDRz7EqB75voagmsZn9UK8CGp4h27Tz7y1doPletRV3458PWPzy4epYAgsu-yEYVXTc8OT_XnXlnNAN4z1DpI-1Kk4uFS1zvRUiUvr8kzauJbPdA7LTKMU5vw5yfjATMZL3ZlhwNLnU82xqr4zqnMdrAeQewuGEXud8-IUHotTKCuM-KwkRjLRrIxYNMyM9h8UStOXpxlc8ARwyrjWGfFVbUPNlxossSzLP223OiCEBY_SEDF8d9gsl7NkSAJdOUE6w","e":"AQAB","x5c":["MIICnTCCAYUCBgGQgfqq2jANBgkqhkiG9w0BAQsFADASMRAwDgYDVQQDDAdvcGVuZHV0MB4XDTI0MDcwNTA4MTgyN1oXDTM0MDcwNTA4MjAwN1owEjEQMA4GA1UEAwwHb3BlbmR1dDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBAJCqP/ZzYoi3CJknecXTRRFBGkOgRXvY//1znRGhTTH2tM3PpbuCesI8gxCC0eR3YM0H2kZZ3uus+E8IicMqMg0c+xKge+b6GoJrGZ/VCvAhqeIdu08+8tXaD5XrUVd+OfD1j88uHqWAILLvshGFV03PDk/1515ZzQDeM9Q6SPtSpOLhUtc70VIlL6/JM2riWz3QOy0yjFOb8Ocn4wEzGS92ZYcDS51PNsaq+M6pzHawHkHsLhhF7nfPiFB6LUygrjPisJEYy0ayMWDTMjPYfFErTl6cZXPAEcMq41hnxVW1DzZcaLLEsyz9ttzoghAWP0hAxfHfYLJezZEgCXTlBOsCAwEAATANBgkqhkiG9w0BAQsFAAOCAQEAQsLO8nRuRGl5YqV0IJaX4GDunc7EGfD4Gofl5NNtG3SojISC0lmO4EyZdFsXJmmWgzFkg1aO91jdcZyIaf6qBbj+GPtoBltA0+nSAcCTDvOsmV1J1Gymxm/CJLTBGqIrLwEXDBFyFpF2W7OE7XdXby+d/mYVkpCc0fHC854w+tOLdvEr4AYD/3JNK5VWd1RLI1CeZ7nJeLbDUR5UkGGb2Na3SXaEsWWwor2L9OAY4bWq9+gIom7ihaDvXMMpMHbQ7gis8Ku5ltK80PISW/9b+G1IxKNYy+euCr9ZWiIeEcKBt0/dKSvCcfhG0mShmliETgGAfAdZu0eqqhuxATAi9A=="],"x5t":"gfshQCGXfVblp5YrHiYSlYUto90","x5t#S256":"h53Q-c8zYde1UjhjhLZB1I5Q7tjX-t9bz7lE_fV6Bbg"}]}"#;
 
     #[rstest]
     fn test_validate_token(fixture: Fixture) {
@@ -1,27 +1,50 @@
 use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::ops::{Not, Range};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::SystemTime;
+use tokio::sync::{broadcast, Mutex};
 
 use futures::future::join_all;
 use futures::FutureExt;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
 
-use opendut_carl_api::carl::cluster::{DeleteClusterDeploymentError, StoreClusterDeploymentError};
-use opendut_types::cluster::{ClusterAssignment, ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName, PeerClusterAssignment};
+use opendut_carl_api::carl::cluster::{DeleteClusterDeploymentError, StoreClusterDeploymentError, UpdateClusterConfigurationError};
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration};
+use opendut_types::cluster::{ClusterAssignment, ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName, ClusterPortAllocation, PeerClusterAssignment};
+use opendut_types::cluster::state::{ClusterState, DeployedClusterState};
 use opendut_types::peer::{PeerDescriptor, PeerId};
-use opendut_types::peer::state::PeerState;
+use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
+use opendut_types::reservation::{Reservation, ReservationId};
+use opendut_types::resources::Version;
 use opendut_types::topology::DeviceId;
 use opendut_types::util::net::NetworkInterfaceDescriptor;
-use opendut_types::util::Port;
+use opendut_types::ShortName;
 
 use crate::actions;
-use crate::actions::{AssignClusterParams, ListPeerDescriptorsParams};
+use crate::actions::{AssignClusterParams, ListPeerDescriptorsParams, PendingPeerConfiguration, SendPeerConfigurationError, UnassignClusterParams};
+use crate::audit;
+use crate::audit::AuditLog;
+use crate::cluster::{ports, state, validation};
+use crate::cluster::ports::CanServerPortsExhausted;
+use crate::{peer, reservation};
 use crate::peer::broker::PeerMessagingBrokerRef;
-use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::{IntoId, Resource, Resources};
+use crate::resources::manager::{PersistenceError, ResourcesManagerRef};
 use crate::vpn::Vpn;
 
 pub type ClusterManagerRef = Arc<Mutex<ClusterManager>>;
 
+const DEPLOYMENT_FAILURES_CAPACITY: usize = 64;
+
+/// Published by the [`ClusterManager`], when deploying a cluster failed and its deployment was removed again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterDeploymentFailure {
+    pub cluster_id: ClusterId,
+    pub cause: String,
+}
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum DeployClusterError {
     #[error("Cluster <{0}> not found!")]
@@ -38,6 +61,24 @@ pub enum DeployClusterError {
         cluster_id: ClusterId,
         cluster_name: ClusterName,
     },
+    #[error("Peer <{peer_id}> cannot be used in cluster <{cluster_id}> in state '{}'! A peer can only be a member of one cluster at a time.", actual_state.short_name())]
+    IllegalPeerState {
+        peer_id: PeerId,
+        cluster_id: ClusterId,
+        actual_state: PeerState,
+    },
+    #[error("Cluster '{cluster_name}' <{cluster_id}> cannot be deployed, because its peers or devices are reserved by user '{user}' in reservation <{reservation_id}>.")]
+    Reserved {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        reservation_id: ReservationId,
+        user: String,
+    },
+    #[error("Cluster <{cluster_id}> cannot be deployed, because not enough CAN server ports are free:\n  {cause}")]
+    CanServerPortsExhausted {
+        cluster_id: ClusterId,
+        cause: CanServerPortsExhausted,
+    },
     #[error("An error occurred while deploying cluster <{cluster_id}>:\n  {cause}")]
     Internal {
         cluster_id: ClusterId,
@@ -50,7 +91,7 @@ pub struct ClusterManager {
     peer_messaging_broker: PeerMessagingBrokerRef,
     vpn: Vpn,
     options: ClusterManagerOptions,
-    can_server_port_counter: u16,
+    deployment_failures: broadcast::Sender<ClusterDeploymentFailure>,
 }
 
 impl ClusterManager {
@@ -60,84 +101,87 @@ impl ClusterManager {
         vpn: Vpn,
         options: ClusterManagerOptions,
     ) -> ClusterManagerRef {
-        let can_server_port_counter = options.can_server_port_range_start;
         Arc::new(Mutex::new(Self {
             resources_manager,
             peer_messaging_broker,
             vpn,
             options,
-            can_server_port_counter
+            deployment_failures: broadcast::channel(DEPLOYMENT_FAILURES_CAPACITY).0,
         }))
     }
+
+    /// Subscribes to the failures of deployments, which are not visible in the resources, since a failed deployment is removed again.
+    pub fn subscribe_deployment_failures(&self) -> broadcast::Receiver<ClusterDeploymentFailure> {
+        self.deployment_failures.subscribe()
+    }
+
+    pub fn options(&self) -> &ClusterManagerOptions {
+        &self.options
+    }
+
+    /// Replaces the options. Clusters which are already deployed keep their CAN server ports.
+    pub fn set_options(&mut self, options: ClusterManagerOptions) {
+        self.options = options;
+    }
+    /// Deploys the cluster on behalf of the given user. Peers and devices reserved by another user are refused.
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn deploy(&mut self, cluster_id: ClusterId) -> Result<(), DeployClusterError> {
+    pub async fn deploy(&mut self, cluster_id: ClusterId, user: &str) -> Result<(), DeployClusterError> {
 
         let cluster_config = self.resources_manager.resources(|resources| {
             resources.get::<ClusterConfiguration>(cluster_id)
         }).await
         .ok_or(DeployClusterError::ClusterConfigurationNotFound(cluster_id))?;
 
-        let cluster_name = cluster_config.name;
+        let cluster_name = Clone::clone(&cluster_config.name);
 
         let all_peers = actions::list_peer_descriptors(ListPeerDescriptorsParams {
             resources_manager: Arc::clone(&self.resources_manager),
-        }).await.map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
+        }).await.map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?
+        .into_iter()
+        .map(|(peer, _)| peer)
+        .collect::<Vec<_>>();
 
+        let cluster_devices = resolve_cluster_devices(&cluster_config, &all_peers);
+        debug!("Resolved {} device(s) for cluster <{cluster_id}>.", cluster_devices.len());
 
-        let member_interface_mapping = determine_member_interface_mapping(cluster_config.devices, all_peers, cluster_config.leader)
+        let member_interface_mapping = determine_member_interface_mapping(Clone::clone(&cluster_devices), all_peers, cluster_config.leader)
             .map_err(|cause| match cause {
-                DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id } => DeployClusterError::PeerForDeviceNotFound { device_id, cluster_id, cluster_name },
+                DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id } => DeployClusterError::PeerForDeviceNotFound { device_id, cluster_id, cluster_name: Clone::clone(&cluster_name) },
             })?;
 
-        let member_ids = member_interface_mapping.keys().cloned().collect::<Vec<_>>();
+        let mut member_ids = member_interface_mapping.keys().cloned().collect::<Vec<_>>();
+        member_ids.sort_by_key(|peer_id| peer_id.uuid);
 
-        if let Vpn::Enabled { vpn_client } = &self.vpn {
-            vpn_client.create_cluster(cluster_id, &member_ids).await
-                .map_err(|cause| {
-                    let message = format!("Failure while creating cluster <{cluster_id}> in VPN service.");
-                    error!("{}\n  {cause}", message);
-                    DeployClusterError::Internal { cluster_id, cause: message }
-                })?;
-
-            let peers_string = member_ids.iter().map(|peer| peer.to_string()).collect::<Vec<_>>().join(",");
-            debug!("Created group for cluster <{cluster_id}> in VPN service, using peers: {peers_string}");
-        } else {
-            debug!("VPN disabled. Not creating VPN group.")
+        let blocking_reservation = self.resources_manager.resources(|resources| {
+            let member_ids = member_ids.iter().cloned().collect::<HashSet<_>>();
+            reservation::find_blocking_reservation(resources, &member_ids, &cluster_devices, user, SystemTime::now())
+        }).await;
+        if let Some(blocking_reservation) = blocking_reservation {
+            return Err(DeployClusterError::Reserved {
+                cluster_id,
+                cluster_name,
+                reservation_id: blocking_reservation.id,
+                user: blocking_reservation.user,
+            });
         }
 
-        let n_peers = u16::try_from(member_interface_mapping.len())
-            .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
-        if self.options.can_server_port_range_start + n_peers >= self.options.can_server_port_range_end {
-            return Err(DeployClusterError::Internal { 
-                cluster_id, 
-                cause: format!("Failure while creating cluster <{}>. Port range [{}, {}) specified by 'can_server_port_range_start' 
-                and 'can_server_port_range_start' is too narrow for the configured number of peers ({})", 
-                cluster_id, self.options.can_server_port_range_start, self.options.can_server_port_range_end, n_peers) 
-            })
-        } else if self.options.can_server_port_range_start + n_peers * 2 >= self.options.can_server_port_range_end {
-            warn!("Port range [{}, {}) specified by 'can_server_port_range_start' 
-                and 'can_server_port_range_start' is very narrow for the configured number of peers ({}). This may cause errors on EDGAR.", 
-                self.options.can_server_port_range_start, self.options.can_server_port_range_end, n_peers);
-        }
-
-        // Wrap-around the counter when we reached the end of the range of usable ports
-        if self.can_server_port_counter + n_peers >= self.options.can_server_port_range_end {
-            self.can_server_port_counter = self.options.can_server_port_range_start;
-        }
-        
-        let can_server_ports = (self.can_server_port_counter..self.can_server_port_counter + n_peers)
-            .map(Port)
-            .collect::<Vec<_>>();
-        self.can_server_port_counter += n_peers;
+        let can_server_port_range = self.options.can_server_port_range();
+        let can_server_ports = self.resources_manager.resources(|resources| {
+            ports::find_free_can_server_ports(resources, can_server_port_range, member_interface_mapping.len())
+        }).await
+        .map_err(|cause| DeployClusterError::CanServerPortsExhausted { cluster_id, cause })?;
 
         let member_assignments: Vec<Result<PeerClusterAssignment, DeployClusterError>> = {
             let assignment_futures = std::iter::zip(member_interface_mapping, can_server_ports)
                 .map(|((peer_id, device_interfaces), can_server_port)| {
                     self.resources_manager.get::<PeerState>(peer_id).map(move |peer_state: Option<PeerState>| {
                         let vpn_address = match peer_state {
-                            Some(PeerState::Up { remote_host, .. }) => {
+                            Some(PeerState::Up { inner: PeerUpState::Available, remote_host }) => {
                                 Ok(remote_host)
                             }
+                            Some(actual_state @ PeerState::Up { inner: PeerUpState::Blocked(_), .. }) => {
+                                Err(DeployClusterError::IllegalPeerState { peer_id, cluster_id, actual_state })
+                            }
                             Some(_) => {
                                 Err(DeployClusterError::Internal { cluster_id, cause: format!("Peer <{peer_id}> which is used in a cluster, should have a PeerState of 'Up'.") })
                             }
@@ -156,272 +200,1769 @@ impl ClusterManager {
         };
         let member_assignments: Vec<PeerClusterAssignment> = member_assignments.into_iter().collect::<Result<_, _>>()?;
 
-        for member_id in member_ids {
-            actions::assign_cluster(AssignClusterParams {
-                resources_manager: Arc::clone(&self.resources_manager),
-                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+        if let Vpn::Enabled { vpn_client } = &self.vpn {
+            if let Err(cause) = vpn_client.create_cluster(cluster_id, &member_ids).await.map_err(|cause| cause.to_string()) {
+                let message = format!("Failure while creating cluster <{cluster_id}> in VPN service.");
+                error!("{}\n  {cause}", message);
+                return Err(DeployClusterError::Internal { cluster_id, cause: message });
+            }
+
+            let peers_string = member_ids.iter().map(|peer| peer.to_string()).collect::<Vec<_>>().join(",");
+            debug!("Created group for cluster <{cluster_id}> in VPN service, using peers: {peers_string}");
+        } else {
+            debug!("VPN disabled. Not creating VPN group.")
+        }
+
+        let mut transaction = self.resources_manager.begin().await;
+
+        let snapshot = transaction.resources_mut(|resources| {
+            let snapshot = ClusterSnapshot::take(resources, cluster_id, &member_ids);
+            resources.insert(cluster_id, ClusterDeployment {
+                id: cluster_id,
+                devices: cluster_devices,
+                deployed_by: String::from(user),
+            });
+            resources.insert(cluster_id, ClusterPortAllocation {
+                id: cluster_id,
+                can_server_ports: member_assignments.iter().map(|assignment| assignment.can_server_port).collect(),
+            });
+            for member_id in &member_ids {
+                peer::state::set_up_state(resources, *member_id, PeerUpState::Blocked(PeerBlockedState::Deploying));
+            }
+            snapshot
+        }).await;
+
+        let mut pending_configurations = Vec::new();
+
+        for member_id in Clone::clone(&member_ids) {
+            let result = actions::assign_cluster(AssignClusterParams {
+                transaction: &mut transaction,
                 peer_id: member_id,
                 cluster_assignment: ClusterAssignment {
                     id: cluster_id,
                     leader: cluster_config.leader,
                     assignments: member_assignments.clone(),
                 },
-            }).await
-            .map_err(|cause| {
+            }).await;
+
+            match result {
+                Ok(pending_configuration) => pending_configurations.push(pending_configuration),
+                Err(cause) => {
+                    let message = format!("Failure while assigning cluster <{cluster_id}> to peer <{member_id}>.");
+                    error!("{}\n  {cause}", message);
+                    transaction.abort().await;
+                    self.delete_vpn_cluster_after_failed_deployment(cluster_id).await;
+                    return Err(DeployClusterError::Internal { cluster_id, cause: message });
+                }
+            }
+        }
+
+        if let Err(cause) = transaction.commit().await {
+            let message = format!("Failure while storing the assignments of cluster <{cluster_id}>.");
+            error!("{}\n  {cause}", message);
+            self.restore_snapshot(snapshot).await;
+            self.delete_vpn_cluster_after_failed_deployment(cluster_id).await;
+            return Err(DeployClusterError::Internal { cluster_id, cause: message });
+        }
+
+        let mut notified_member_ids = Vec::new();
+
+        for pending_configuration in pending_configurations {
+            let member_id = pending_configuration.peer_id;
+            notified_member_ids.push(member_id);
+
+            if let Err(cause) = pending_configuration.send(&self.peer_messaging_broker).await {
                 let message = format!("Failure while assigning cluster <{cluster_id}> to peer <{member_id}>.");
                 error!("{}\n  {cause}", message);
-                DeployClusterError::Internal { cluster_id, cause: message }
-            })?;
+                self.restore_snapshot(snapshot).await;
+                self.resend_stored_peer_configurations(&notified_member_ids).await;
+                self.delete_vpn_cluster_after_failed_deployment(cluster_id).await;
+                return Err(DeployClusterError::Internal { cluster_id, cause: message });
+            }
         }
 
+        self.resources_manager.resources_mut(|resources| {
+            for member_id in member_ids {
+                peer::state::set_up_state(resources, member_id, PeerUpState::Blocked(PeerBlockedState::Member));
+            }
+        }).await
+        .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
+
         Ok(())
     }
 
+    /// Restores the stored resources of a cluster and its members, after its deployment failed.
+    async fn restore_snapshot(&self, snapshot: ClusterSnapshot) {
+        let cluster_id = snapshot.cluster_id;
+        if let Err(cause) = self.resources_manager.resources_mut(|resources| snapshot.restore(resources)).await {
+            error!("Failed to persist the restored resources of cluster <{cluster_id}>:\n  {cause}");
+        }
+    }
+
+    async fn delete_vpn_cluster_after_failed_deployment(&self, cluster_id: ClusterId) {
+        if let Vpn::Enabled { vpn_client } = &self.vpn {
+            match vpn_client.delete_cluster(cluster_id).await {
+                Ok(()) => debug!("Deleted group for cluster <{cluster_id}> in VPN service after failed deployment."),
+                Err(cause) => error!("Failed to delete group for cluster <{cluster_id}> in VPN service after failed deployment:\n  {cause}"),
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn find_configuration(&self, id: ClusterId) -> Option<ClusterConfiguration> {
+    pub async fn find_configuration(&self, id: ClusterId) -> Option<(ClusterConfiguration, Version)> {
         self.resources_manager.resources(|resources| {
             resources.get::<ClusterConfiguration>(id)
+                .map(|configuration| (configuration, resources.version::<ClusterConfiguration>(id)))
         }).await
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn list_configuration(&self) -> Vec<ClusterConfiguration> {
+    pub async fn list_configuration(&self) -> Vec<(ClusterConfiguration, Version)> {
         self.resources_manager.resources(|resources| {
-            resources.iter::<ClusterConfiguration>().cloned().collect::<Vec<_>>()
+            resources.iter::<ClusterConfiguration>()
+                .map(|configuration| (Clone::clone(configuration), resources.version::<ClusterConfiguration>(configuration.id)))
+                .collect::<Vec<_>>()
         }).await
     }
 
+    /// Updates a cluster configuration. When the cluster is deployed, only the peers affected by the change
+    /// receive a new [`ClusterAssignment`], so that the other peers of the cluster are not disrupted.
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment) -> Result<ClusterId, StoreClusterDeploymentError> {
-        let cluster_id = deployment.id;
+    pub async fn update_cluster_configuration(&mut self, configuration: ClusterConfiguration, expected_version: Option<Version>) -> Result<ClusterId, UpdateClusterConfigurationError> {
+        let cluster_id = configuration.id;
+        let cluster_name = Clone::clone(&configuration.name);
+
+        let actual_state = self.resources_manager.resources(|resources| {
+            if resources.get::<ClusterConfiguration>(cluster_id).is_none() {
+                return Err(UpdateClusterConfigurationError::ClusterConfigurationNotFound { cluster_id });
+            }
+
+            if let Some(expected_version) = expected_version {
+                let actual_version = resources.version::<ClusterConfiguration>(cluster_id);
+                if actual_version != expected_version {
+                    return Err(UpdateClusterConfigurationError::VersionConflict {
+                        cluster_id,
+                        cluster_name: Clone::clone(&cluster_name),
+                        expected_version,
+                        actual_version,
+                    });
+                }
+            }
+
+            let errors = validation::validate_cluster_configuration(resources, &configuration);
+            if errors.is_empty().not() {
+                return Err(UpdateClusterConfigurationError::IllegalClusterConfiguration {
+                    cluster_id,
+                    cluster_name: Clone::clone(&cluster_name),
+                    errors,
+                });
+            }
+
+            let actual_state = state::cluster_state(resources, cluster_id);
+            if actual_state == ClusterState::Deploying {
+                return Err(UpdateClusterConfigurationError::IllegalClusterState {
+                    cluster_id,
+                    cluster_name: Clone::clone(&cluster_name),
+                    actual_state,
+                    required_states: vec![
+                        ClusterState::Undeployed,
+                        ClusterState::Deployed(DeployedClusterState::Healthy),
+                        ClusterState::Deployed(DeployedClusterState::Unhealthy),
+                    ],
+                });
+            }
+            Ok(actual_state)
+        }).await?;
+
+        if actual_state == ClusterState::Undeployed {
+            self.resources_manager.insert(cluster_id, configuration).await
+                .map_err(|cause| UpdateClusterConfigurationError::Internal { cluster_id, cluster_name, cause: cause.to_string() })?;
+        } else {
+            self.reconfigure(configuration).await
+                .map_err(|cause| UpdateClusterConfigurationError::Internal { cluster_id, cluster_name, cause: cause.to_string() })?;
+        }
+
+        Ok(cluster_id)
+    }
+
+    /// Applies a changed configuration to a deployed cluster.
+    ///
+    /// Removed peers are unassigned, added peers are assigned and the remaining peers keep their VPN address and CAN server port.
+    /// Only peers, whose view on the cluster changed, are sent their new [`ClusterAssignment`].
+    /// If a peer cannot be assigned, the VPN group and the peers, which were already sent a new configuration, are reverted.
+    async fn reconfigure(&mut self, configuration: ClusterConfiguration) -> Result<(), DeployClusterError> {
+        let cluster_id = configuration.id;
+        let cluster_name = Clone::clone(&configuration.name);
+
+        let all_peers = actions::list_peer_descriptors(ListPeerDescriptorsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await.map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?
+        .into_iter()
+        .map(|(peer, _)| peer)
+        .collect::<Vec<_>>();
+
+        let cluster_devices = resolve_cluster_devices(&configuration, &all_peers);
+
+        let member_interface_mapping = determine_member_interface_mapping(Clone::clone(&cluster_devices), all_peers, configuration.leader)
+            .map_err(|cause| match cause {
+                DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id } => DeployClusterError::PeerForDeviceNotFound { device_id, cluster_id, cluster_name: Clone::clone(&cluster_name) },
+            })?;
+
+        let (previous_assignment, peer_states, blocking_reservation) = self.resources_manager.resources(|resources| {
+            let previous_assignment = resources.iter::<PeerConfiguration>()
+                .filter_map(|peer_configuration| peer_configuration.cluster_assignment.as_ref())
+                .find(|assignment| assignment.id == cluster_id)
+                .cloned();
+            let peer_states = member_interface_mapping.keys()
+                .map(|peer_id| (*peer_id, resources.get::<PeerState>(*peer_id)))
+                .collect::<HashMap<_, _>>();
+            let deployed_by = resources.get::<ClusterDeployment>(cluster_id)
+                .map(|deployment| deployment.deployed_by)
+                .unwrap_or_default();
+            let member_ids = member_interface_mapping.keys().cloned().collect::<HashSet<_>>();
+            let blocking_reservation = reservation::find_blocking_reservation(resources, &member_ids, &cluster_devices, &deployed_by, SystemTime::now());
+            (previous_assignment, peer_states, blocking_reservation)
+        }).await;
+
+        if let Some(blocking_reservation) = blocking_reservation {
+            return Err(DeployClusterError::Reserved {
+                cluster_id,
+                cluster_name,
+                reservation_id: blocking_reservation.id,
+                user: blocking_reservation.user,
+            });
+        }
+
+        let previous_assignment = previous_assignment.unwrap_or(ClusterAssignment {
+            id: cluster_id,
+            leader: configuration.leader,
+            assignments: Vec::new(),
+        });
+
+        let previous_member_ids = previous_assignment.assignments.iter()
+            .map(|assignment| assignment.peer_id)
+            .collect::<HashSet<_>>();
+        let removed_member_ids = previous_member_ids.iter()
+            .filter(|peer_id| member_interface_mapping.contains_key(peer_id).not())
+            .copied()
+            .collect::<Vec<_>>();
+
+        let added_member_count = member_interface_mapping.keys()
+            .filter(|peer_id| previous_member_ids.contains(peer_id).not())
+            .count();
+        let can_server_port_range = self.options.can_server_port_range();
+        let mut can_server_ports = self.resources_manager.resources(|resources| {
+            ports::find_free_can_server_ports(resources, can_server_port_range, added_member_count)
+        }).await
+        .map_err(|cause| DeployClusterError::CanServerPortsExhausted { cluster_id, cause })?
+        .into_iter();
+
+        let mut member_assignments = Vec::new();
+        let mut added_member_ids = Vec::new();
+        for (peer_id, device_interfaces) in member_interface_mapping {
+            let assignment = match previous_assignment.assignments.iter().find(|assignment| assignment.peer_id == peer_id) {
+                Some(previous) => PeerClusterAssignment { device_interfaces, ..Clone::clone(previous) },
+                None => {
+                    let vpn_address = match peer_states.get(&peer_id).cloned().flatten() {
+                        Some(PeerState::Up { inner: PeerUpState::Available, remote_host }) => remote_host,
+                        Some(actual_state) => {
+                            return Err(DeployClusterError::IllegalPeerState { peer_id, cluster_id, actual_state });
+                        }
+                        None => {
+                            return Err(DeployClusterError::Internal { cluster_id, cause: format!("Peer <{peer_id}> which is used in a cluster, should have a PeerState associated.") });
+                        }
+                    };
+                    let can_server_port = can_server_ports.next()
+                        .ok_or_else(|| DeployClusterError::Internal { cluster_id, cause: format!("No CAN server port allocated for peer <{peer_id}>.") })?;
+                    added_member_ids.push(peer_id);
+                    PeerClusterAssignment { peer_id, vpn_address, can_server_port, device_interfaces }
+                }
+            };
+            member_assignments.push(assignment);
+        }
+        member_assignments.sort_by_key(|assignment| assignment.peer_id.uuid);
+
+        let cluster_assignment = ClusterAssignment {
+            id: cluster_id,
+            leader: configuration.leader,
+            assignments: member_assignments,
+        };
+        let member_ids = cluster_assignment.assignments.iter()
+            .map(|assignment| assignment.peer_id)
+            .collect::<Vec<_>>();
+
+        let members_changed = added_member_ids.is_empty().not() || removed_member_ids.is_empty().not();
+        if members_changed {
+            if let Vpn::Enabled { vpn_client } = &self.vpn {
+                if let Err(cause) = vpn_client.update_cluster(cluster_id, &member_ids).await {
+                    let message = format!("Failure while updating group for cluster <{cluster_id}> in VPN service.");
+                    error!("{}\n  {cause}", message);
+                    return Err(DeployClusterError::Internal { cluster_id, cause: message });
+                }
+            }
+        }
+
+        let mut transaction = self.resources_manager.begin().await;
+
+        let snapshot = transaction.resources_mut(|resources| {
+            let snapshot = ClusterSnapshot::take(resources, cluster_id, previous_member_ids.iter().chain(&member_ids));
+            let deployed_by = resources.get::<ClusterDeployment>(cluster_id)
+                .map(|deployment| deployment.deployed_by)
+                .unwrap_or_default();
+            resources.insert(cluster_id, configuration);
+            resources.insert(cluster_id, ClusterDeployment {
+                id: cluster_id,
+                devices: cluster_devices,
+                deployed_by,
+            });
+            resources.insert(cluster_id, ClusterPortAllocation {
+                id: cluster_id,
+                can_server_ports: cluster_assignment.assignments.iter().map(|assignment| assignment.can_server_port).collect(),
+            });
+            for member_id in &removed_member_ids {
+                peer::state::set_up_state(resources, *member_id, PeerUpState::Blocked(PeerBlockedState::Undeploying));
+            }
+            for member_id in &added_member_ids {
+                peer::state::set_up_state(resources, *member_id, PeerUpState::Blocked(PeerBlockedState::Deploying));
+            }
+            snapshot
+        }).await;
+
+        let mut pending_configurations = Vec::new();
+
+        for member_id in Clone::clone(&removed_member_ids) {
+            let result = actions::unassign_cluster(UnassignClusterParams {
+                transaction: &mut transaction,
+                peer_id: member_id,
+            }).await;
+
+            match result {
+                Ok(pending_configuration) => pending_configurations.push(pending_configuration),
+                Err(cause) => warn!("Failed to unassign cluster <{cluster_id}> from peer <{member_id}>:\n  {cause}"),
+            }
+        }
+
+        for member_id in member_ids {
+            let is_affected = added_member_ids.contains(&member_id)
+                || member_view(&previous_assignment, member_id) != member_view(&cluster_assignment, member_id);
+
+            if is_affected {
+                let result = actions::assign_cluster(AssignClusterParams {
+                    transaction: &mut transaction,
+                    peer_id: member_id,
+                    cluster_assignment: Clone::clone(&cluster_assignment),
+                }).await;
+
+                match result {
+                    Ok(pending_configuration) => pending_configurations.push(pending_configuration),
+                    Err(cause) => {
+                        let message = format!("Failure while assigning updated cluster <{cluster_id}> to peer <{member_id}>. Reverting the update.");
+                        error!("{}\n  {cause}", message);
+                        transaction.abort().await;
+                        self.revert_reconfiguration(cluster_id, &previous_member_ids, members_changed, &[]).await;
+                        return Err(DeployClusterError::Internal { cluster_id, cause: message });
+                    }
+                }
+            } else {
+                debug!("Peer <{member_id}> is not affected by the update of cluster <{cluster_id}>. Not sending it a new cluster assignment.");
+                let cluster_assignment = Clone::clone(&cluster_assignment);
+                transaction.resources_mut(|resources| {
+                    resources.update::<PeerConfiguration>(member_id)
+                        .modify(|peer_configuration| peer_configuration.cluster_assignment = Some(cluster_assignment));
+                }).await;
+            }
+        }
+
+        if let Err(cause) = transaction.commit().await {
+            let message = format!("Failure while storing the updated cluster <{cluster_id}>. Reverting the update.");
+            error!("{}\n  {cause}", message);
+            self.restore_snapshot(snapshot).await;
+            self.revert_reconfiguration(cluster_id, &previous_member_ids, members_changed, &[]).await;
+            return Err(DeployClusterError::Internal { cluster_id, cause: message });
+        }
+
+        let mut notified_member_ids = Vec::new();
+
+        for pending_configuration in pending_configurations {
+            let member_id = pending_configuration.peer_id;
+            notified_member_ids.push(member_id);
+
+            if removed_member_ids.contains(&member_id) {
+                if let Err(cause) = self.send_unassignment(pending_configuration).await {
+                    warn!("Failed to unassign cluster <{cluster_id}> from peer <{member_id}>:\n  {cause}");
+                }
+            }
+            else if let Err(cause) = pending_configuration.send(&self.peer_messaging_broker).await {
+                let message = format!("Failure while assigning updated cluster <{cluster_id}> to peer <{member_id}>. Reverting the update.");
+                error!("{}\n  {cause}", message);
+                self.restore_snapshot(snapshot).await;
+                self.revert_reconfiguration(cluster_id, &previous_member_ids, members_changed, &notified_member_ids).await;
+                return Err(DeployClusterError::Internal { cluster_id, cause: message });
+            }
+        }
+
         self.resources_manager.resources_mut(|resources| {
-            resources.insert(deployment.id, deployment);
+            for member_id in removed_member_ids {
+                peer::state::set_up_state(resources, member_id, PeerUpState::Available);
+            }
+            for member_id in added_member_ids {
+                peer::state::set_up_state(resources, member_id, PeerUpState::Blocked(PeerBlockedState::Member));
+            }
+        }).await
+        .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
+
+        Ok(())
+    }
+
+    /// Restores the VPN group and the configuration of the notified peers, after the stored resources were rolled back.
+    async fn revert_reconfiguration(&self, cluster_id: ClusterId, previous_member_ids: &HashSet<PeerId>, members_changed: bool, notified_member_ids: &[PeerId]) {
+        if members_changed {
+            if let Vpn::Enabled { vpn_client } = &self.vpn {
+                let previous_member_ids = previous_member_ids.iter().cloned().collect::<Vec<_>>();
+                if let Err(cause) = vpn_client.update_cluster(cluster_id, &previous_member_ids).await {
+                    error!("Failure while reverting group for cluster <{cluster_id}> in VPN service:\n  {cause}");
+                }
+            }
+        }
+
+        self.resend_stored_peer_configurations(notified_member_ids).await;
+    }
+
+    /// Sends the stored configuration to the given peers again, so that they drop a cluster assignment, which was rolled back.
+    async fn resend_stored_peer_configurations(&self, peer_ids: &[PeerId]) {
+        for peer_id in peer_ids {
+            let configurations = self.resources_manager.resources(|resources| {
+                resources.get::<PeerConfiguration>(*peer_id)
+                    .zip(resources.get::<PeerConfiguration2>(*peer_id))
+            }).await;
+
+            if let Some((configuration, configuration2)) = configurations {
+                let result = self.peer_messaging_broker.send_to_peer(
+                    *peer_id,
+                    downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
+                        configuration: Some(configuration.into()),
+                        configuration2: Some(configuration2.into()),
+                    }),
+                ).await;
+                if let Err(cause) = result {
+                    warn!("Failed to send reverted configuration to peer <{peer_id}>:\n  {cause}");
+                }
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment, user: &str) -> Result<ClusterId, StoreClusterDeploymentError> {
+        let cluster_id = deployment.id;
+
+        let mut transaction = self.resources_manager.begin().await;
+
+        let cluster_name = transaction.resources_mut(|resources| {
+            let configuration = resources.get::<ClusterConfiguration>(cluster_id)
+                .ok_or(StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id })?;
+
+            let actual_state = state::cluster_state(resources, cluster_id);
+            if actual_state != ClusterState::Undeployed {
+                return Err(StoreClusterDeploymentError::IllegalClusterState {
+                    cluster_id,
+                    cluster_name: configuration.name,
+                    actual_state,
+                    required_states: vec![ClusterState::Undeployed],
+                });
+            }
+
+            resources.insert(cluster_id, deployment);
+            resources.insert(cluster_id, ClusterState::Deploying);
+            Ok(configuration.name)
         }).await;
-        if let Err(error) = self.deploy(cluster_id).await {
-            error!("Failed to deploy cluster <{cluster_id}>, due to:\n  {error}");
+
+        let cluster_name = match cluster_name {
+            Ok(cluster_name) => cluster_name,
+            Err(error) => {
+                transaction.abort().await;
+                return Err(error);
+            }
+        };
+
+        if let Err(cause) = transaction.commit().await {
+            self.remove_failed_cluster_deployment(cluster_id).await;
+            return Err(StoreClusterDeploymentError::Internal { cluster_id, cluster_name, cause: cause.to_string() });
+        }
+
+        match self.deploy(cluster_id, user).await {
+            Ok(()) => {
+                self.resources_manager.insert(cluster_id, ClusterState::Deployed(DeployedClusterState::default())).await
+                    .map_err(|cause| StoreClusterDeploymentError::Internal { cluster_id, cluster_name, cause: cause.to_string() })?;
+                Ok(cluster_id)
+            }
+            Err(cause) => {
+                error!("Failed to deploy cluster <{cluster_id}>, due to:\n  {cause}");
+                self.remove_failed_cluster_deployment(cluster_id).await;
+                let _ = self.deployment_failures.send(ClusterDeploymentFailure { cluster_id, cause: cause.to_string() }); //fails only without subscribers
+                match cause {
+                    DeployClusterError::Reserved { reservation_id, user, .. } => {
+                        Err(StoreClusterDeploymentError::Reserved { cluster_id, cluster_name, reservation_id, user })
+                    }
+                    cause => {
+                        Err(StoreClusterDeploymentError::Internal { cluster_id, cluster_name, cause: cause.to_string() })
+                    }
+                }
+            }
+        }
+    }
+
+    async fn remove_failed_cluster_deployment(&self, cluster_id: ClusterId) {
+        let result = self.resources_manager.resources_mut(|resources| {
+            resources.remove::<ClusterDeployment>(cluster_id);
+            resources.remove::<ClusterState>(cluster_id);
+        }).await;
+        if let Err(cause) = result {
+            error!("Failed to persist the removal of the failed deployment of cluster <{cluster_id}>:\n  {cause}");
         }
-        Ok(cluster_id)
     }
 
     #[tracing::instrument(skip(self), level="trace")]
     pub async fn delete_cluster_deployment(&self, cluster_id: ClusterId) -> Result<ClusterDeployment, DeleteClusterDeploymentError> {
 
-        let (deployment, configuration) = self.resources_manager
-            .resources_mut(|resources| {
-                resources.remove::<ClusterDeployment>(cluster_id)
-                    .map(|deployment| (deployment, resources.get::<ClusterConfiguration>(cluster_id)))
-            })
-            .await
-            .ok_or(DeleteClusterDeploymentError::ClusterDeploymentNotFound { cluster_id })?;
+        let mut transaction = self.resources_manager.begin().await;
+
+        let result = transaction.resources_mut(|resources| {
+            let deployment = resources.get::<ClusterDeployment>(cluster_id)
+                .ok_or(DeleteClusterDeploymentError::ClusterDeploymentNotFound { cluster_id })?;
+            let configuration = resources.get::<ClusterConfiguration>(cluster_id);
+
+            if let Some(configuration) = &configuration {
+                let actual_state = state::cluster_state(resources, cluster_id);
+                if actual_state == ClusterState::Deploying {
+                    return Err(DeleteClusterDeploymentError::IllegalClusterState {
+                        cluster_id,
+                        cluster_name: Clone::clone(&configuration.name),
+                        actual_state,
+                        required_states: vec![
+                            ClusterState::Undeployed,
+                            ClusterState::Deployed(DeployedClusterState::Healthy),
+                            ClusterState::Deployed(DeployedClusterState::Unhealthy),
+                        ],
+                    });
+                }
+            }
+
+            resources.remove::<ClusterDeployment>(cluster_id);
+            resources.remove::<ClusterState>(cluster_id);
+            resources.remove::<ClusterPortAllocation>(cluster_id);
+            Ok((deployment, configuration))
+        }).await;
+
+        let (deployment, configuration) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                transaction.abort().await;
+                return Err(error);
+            }
+        };
+
+        // The deployment is removed from memory in any case, so the members are unassigned even if persisting the removal failed.
+        let persisted = transaction.commit().await;
+
+        let unassigned = self.unassign_cluster_members(cluster_id).await;
 
         if let Some(configuration) = configuration {
+            let internal = |cause: String| DeleteClusterDeploymentError::Internal { cluster_id, cluster_name: Clone::clone(&configuration.name), cause };
             if let Vpn::Enabled { vpn_client } = &self.vpn {
                 vpn_client.delete_cluster(cluster_id).await
-                    .map_err(|error| DeleteClusterDeploymentError::Internal { cluster_id, cluster_name: configuration.name, cause: error.to_string() })?;
+                    .map_err(|error| internal(error.to_string()))?;
+            }
+            persisted.map_err(|cause| internal(cause.to_string()))?;
+            unassigned.map_err(|cause| internal(cause.to_string()))?;
+        } else {
+            if let Err(cause) = persisted {
+                error!("Failed to persist the removal of the deployment of cluster <{cluster_id}>:\n  {cause}");
+            }
+            if let Err(cause) = unassigned {
+                error!("{cause}");
             }
         }
 
         Ok(deployment)
     }
 
+    /// Removes the cluster assignment from all peers assigned to the given cluster and sends them their new configuration,
+    /// so that they tear down their cluster setup.
+    ///
+    /// Peers, which could not be unassigned, remain blocked as undeploying, rather than being made available for other clusters.
+    async fn unassign_cluster_members(&self, cluster_id: ClusterId) -> Result<(), UnassignClusterMembersError> {
+        let member_ids = self.resources_manager.resources(|resources| state::members_of(resources, cluster_id)).await;
+
+        let mut failures = Vec::new();
+
+        let mut transaction = self.resources_manager.begin().await;
+
+        transaction.resources_mut(|resources| {
+            for member_id in &member_ids {
+                peer::state::set_up_state(resources, *member_id, PeerUpState::Blocked(PeerBlockedState::Undeploying));
+            }
+        }).await;
+
+        let mut pending_configurations = Vec::new();
+
+        for member_id in Clone::clone(&member_ids) {
+            let result = actions::unassign_cluster(UnassignClusterParams {
+                transaction: &mut transaction,
+                peer_id: member_id,
+            }).await;
+
+            match result {
+                Ok(pending_configuration) => pending_configurations.push(pending_configuration),
+                Err(cause) => failures.push(format!("Peer <{member_id}>: {cause}")),
+            }
+        }
+
+        if let Err(cause) = transaction.commit().await {
+            failures.push(cause.to_string());
+        }
+
+        let mut unassigned_member_ids = Vec::new();
+
+        for pending_configuration in pending_configurations {
+            let member_id = pending_configuration.peer_id;
+            match self.send_unassignment(pending_configuration).await {
+                Ok(()) => unassigned_member_ids.push(member_id),
+                Err(cause) => failures.push(format!("Peer <{member_id}>: {cause}")),
+            }
+        }
+
+        let result = self.resources_manager.resources_mut(|resources| {
+            for member_id in unassigned_member_ids {
+                peer::state::set_up_state(resources, member_id, PeerUpState::Available);
+            }
+        }).await;
+        if let Err(cause) = result {
+            failures.push(cause.to_string());
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(UnassignClusterMembersError { cluster_id, failures })
+        }
+    }
+
+    /// Sends a configuration without cluster assignment to the peer. A peer, which is not connected, receives it when it reconnects.
+    async fn send_unassignment(&self, pending_configuration: PendingPeerConfiguration) -> Result<(), SendPeerConfigurationError> {
+        match pending_configuration.send(&self.peer_messaging_broker).await {
+            Err(SendPeerConfigurationError::PeerNotConnected(peer_id)) => {
+                debug!("Peer <{peer_id}> is not connected. It will receive its configuration without cluster assignment, when it reconnects.");
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Undeploys all clusters, which use peers or devices of an expired reservation and were deployed by the user holding it.
+    /// Clusters of other users are left alone, as a reservation cannot claim resources of clusters deployed by another user.
+    /// Clusters, which are still claimed by another active reservation of the same user, are left alone as well.
+    /// The expired reservations are removed, once their clusters have been undeployed. Both is recorded in the audit log.
+    #[tracing::instrument(skip(self, audit_log), level="trace")]
+    pub async fn expire_reservations(&self, audit_log: &AuditLog) {
+        let now = SystemTime::now();
+
+        let expired_reservations = self.resources_manager.resources(|resources| {
+            resources.iter::<Reservation>()
+                .filter(|reservation| reservation.is_expired_at(now))
+                .cloned()
+                .collect::<Vec<_>>()
+        }).await;
+
+        for expired_reservation in expired_reservations {
+            let affected_clusters = self.resources_manager.resources(|resources| {
+                resources.iter::<ClusterDeployment>()
+                    .filter(|deployment| deployment.deployed_by == expired_reservation.user)
+                    .map(|deployment| deployment.id)
+                    .filter(|cluster_id| {
+                        let (peers, devices) = reservation::cluster_resources(resources, *cluster_id);
+                        let still_reserved = resources.iter::<Reservation>()
+                            .filter(|other| other.id != expired_reservation.id && other.user == expired_reservation.user)
+                            .any(|other| other.is_active_at(now) && other.claims_any(&peers, &devices));
+                        expired_reservation.claims_any(&peers, &devices) && still_reserved.not()
+                    })
+                    .collect::<Vec<_>>()
+            }).await;
+
+            let mut all_undeployed = true;
+            for cluster_id in affected_clusters {
+                info!("Reservation <{}> of user '{}' expired. Undeploying cluster <{cluster_id}>.", expired_reservation.id, expired_reservation.user);
+                match self.delete_cluster_deployment(cluster_id).await {
+                    Ok(deployment) => {
+                        audit_log.record(String::from(audit::SYSTEM_USER), "ExpireReservation", cluster_id, Some(&deployment), None).await;
+                    }
+                    Err(cause) => {
+                        error!("Failed to undeploy cluster <{cluster_id}> after reservation <{}> expired:\n  {cause}", expired_reservation.id);
+                        all_undeployed = false;
+                    }
+                }
+            }
+
+            if all_undeployed {
+                match self.resources_manager.remove::<Reservation>(expired_reservation.id).await {
+                    Ok(removed) => {
+                        audit_log.record(String::from(audit::SYSTEM_USER), "ExpireReservation", expired_reservation.id, removed.as_ref(), None).await;
+                    }
+                    Err(cause) => {
+                        error!("Failed to remove expired reservation <{}>:\n  {cause}", expired_reservation.id);
+                    }
+                }
+            }
+        }
+    }
+
     pub async fn find_deployment(&self, id: ClusterId) -> Option<ClusterDeployment> {
         self.resources_manager.resources(|resources| {
             resources.get::<ClusterDeployment>(id)
         }).await
     }
 
-    #[tracing::instrument(skip(self), level="trace")]
-    pub async fn list_deployment(&self) -> Vec<ClusterDeployment> {
-        self.resources_manager.resources(|resources| {
-            resources.iter::<ClusterDeployment>().cloned().collect::<Vec<_>>()
-        }).await
-    }
-}
+    #[tracing::instrument(skip(self), level="trace")]
+    pub async fn list_deployment(&self) -> Vec<ClusterDeployment> {
+        self.resources_manager.resources(|resources| {
+            resources.iter::<ClusterDeployment>().cloned().collect::<Vec<_>>()
+        }).await
+    }
+
+    /// Reverts deployments, which were still in state [`ClusterState::Deploying`] when CARL stopped, as if they failed.
+    /// Their members may already have been assigned the cluster, so the assignment is removed from their stored configuration.
+    /// Should be called on startup, before peers connect.
+    #[tracing::instrument(skip(self), level="trace")]
+    pub async fn recover_interrupted_deployments(&self) -> Result<(), PersistenceError> {
+        let interrupted_cluster_ids = self.resources_manager.resources_mut(|resources| {
+            let cluster_ids = resources.iter::<ClusterDeployment>()
+                .map(|deployment| deployment.id)
+                .chain(resources.iter::<ClusterConfiguration>().map(|configuration| configuration.id))
+                .filter(|cluster_id| resources.get::<ClusterState>(*cluster_id) == Some(ClusterState::Deploying))
+                .collect::<HashSet<_>>();
+
+            for cluster_id in &cluster_ids {
+                resources.remove::<ClusterDeployment>(*cluster_id);
+                resources.remove::<ClusterState>(*cluster_id);
+                resources.remove::<ClusterPortAllocation>(*cluster_id);
+            }
+            resources.modify_all::<PeerConfiguration, _>(|peer_configuration| {
+                if peer_configuration.cluster_assignment.as_ref().is_some_and(|assignment| cluster_ids.contains(&assignment.id)) {
+                    peer_configuration.cluster_assignment = None;
+                }
+            });
+            cluster_ids
+        }).await?;
+
+        for cluster_id in interrupted_cluster_ids {
+            warn!("Deployment of cluster <{cluster_id}> was interrupted by a restart. Reverted it.");
+            self.delete_vpn_cluster_after_failed_deployment(cluster_id).await;
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Failed to unassign cluster <{cluster_id}> from its members:\n  {}", failures.join("\n  "))]
+struct UnassignClusterMembersError {
+    cluster_id: ClusterId,
+    failures: Vec<String>,
+}
+
+/// The stored resources of a cluster and its members before a deployment changed them,
+/// so that they can be restored, when the changed configurations cannot be sent to all members.
+struct ClusterSnapshot {
+    cluster_id: ClusterId,
+    configuration: Option<ClusterConfiguration>,
+    deployment: Option<ClusterDeployment>,
+    port_allocation: Option<ClusterPortAllocation>,
+    peer_configurations: HashMap<PeerId, Option<PeerConfiguration>>,
+    peer_up_states: HashMap<PeerId, PeerUpState>,
+}
+
+impl ClusterSnapshot {
+    fn take<'a>(resources: &Resources, cluster_id: ClusterId, peer_ids: impl IntoIterator<Item=&'a PeerId>) -> Self {
+        let mut peer_configurations = HashMap::new();
+        let mut peer_up_states = HashMap::new();
+        for peer_id in peer_ids {
+            peer_configurations.insert(*peer_id, resources.get::<PeerConfiguration>(*peer_id));
+            if let Some(PeerState::Up { inner, .. }) = resources.get::<PeerState>(*peer_id) {
+                peer_up_states.insert(*peer_id, inner);
+            }
+        }
+        Self {
+            cluster_id,
+            configuration: resources.get(cluster_id),
+            deployment: resources.get(cluster_id),
+            port_allocation: resources.get(cluster_id),
+            peer_configurations,
+            peer_up_states,
+        }
+    }
+
+    /// Restores the resources, which changed since the snapshot was taken. Peers, which went down in the meantime, stay down.
+    fn restore(self, resources: &mut Resources) {
+        restore_resource(resources, self.cluster_id, self.configuration);
+        restore_resource(resources, self.cluster_id, self.deployment);
+        restore_resource(resources, self.cluster_id, self.port_allocation);
+        for (peer_id, peer_configuration) in self.peer_configurations {
+            restore_resource(resources, peer_id, peer_configuration);
+        }
+        for (peer_id, up_state) in self.peer_up_states {
+            peer::state::set_up_state(resources, peer_id, up_state);
+        }
+    }
+}
+
+fn restore_resource<R>(resources: &mut Resources, id: impl IntoId<R> + Copy, previous: Option<R>)
+where R: Resource + PartialEq {
+    if resources.get::<R>(id) != previous {
+        match previous {
+            Some(resource) => resources.insert(id, resource),
+            None => { resources.remove::<R>(id); }
+        }
+    }
+}
+
+/// Returns the configured devices of the cluster together with all devices of its project matched by any of its device selectors.
+fn resolve_cluster_devices(configuration: &ClusterConfiguration, all_peers: &[PeerDescriptor]) -> HashSet<DeviceId> {
+    let selected_devices = all_peers.iter()
+        .filter(|peer| peer.project == configuration.project)
+        .flat_map(|peer| peer.topology.devices.iter().map(move |device| (peer, device)))
+        .filter(|(peer, device)| configuration.device_selectors.iter().any(|selector| selector.matches(device, peer)))
+        .map(|(_, device)| device.id);
+
+    configuration.devices.iter().copied()
+        .chain(selected_devices)
+        .collect()
+}
+
+fn determine_member_interface_mapping(
+    cluster_devices: HashSet<DeviceId>,
+    all_peers: Vec<PeerDescriptor>,
+    leader: PeerId,
+) -> Result<HashMap<PeerId, Vec<NetworkInterfaceDescriptor>>, DetermineMemberInterfaceMappingError> {
+
+    let mut result: HashMap<PeerId, Vec<NetworkInterfaceDescriptor>> = HashMap::new();
+
+    result.insert(leader, Vec::new()); //will later be replaced, if leader has devices
+
+    for device_id in cluster_devices {
+        let member_interfaces = all_peers.iter().find_map(|peer| {
+            let interfaces: Vec<NetworkInterfaceDescriptor> = peer.topology.devices.iter()
+                .filter(|device| device.id == device_id)
+                .map(|device| device.interface.clone())
+                .collect();
+
+            if interfaces.is_empty() {
+                None
+            } else {
+                Some((peer.id, interfaces))
+            }
+        });
+
+        if let Some((peer, interfaces)) = member_interfaces {
+            result.entry(peer)
+                .or_default()
+                .extend(interfaces);
+        } else {
+            return Err(DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id });
+        }
+    }
+    Ok(result)
+}
+
+/// The part of a [`ClusterAssignment`], which a member's setup depends on:
+/// its own assignment, the assignments it connects to (all members for the leader, otherwise the leader)
+/// and the addresses of all members, which it measures network metrics to.
+fn member_view(cluster_assignment: &ClusterAssignment, member_id: PeerId) -> (PeerId, Vec<PeerClusterAssignment>, Vec<IpAddr>) {
+    let relevant_peer_ids = [member_id, cluster_assignment.leader];
+    let is_leader = cluster_assignment.leader == member_id;
+
+    let mut assignments = cluster_assignment.assignments.iter()
+        .filter(|assignment| is_leader || relevant_peer_ids.contains(&assignment.peer_id))
+        .cloned()
+        .collect::<Vec<_>>();
+    assignments.sort_by_key(|assignment| assignment.peer_id.uuid);
+
+    let mut vpn_addresses = cluster_assignment.assignments.iter()
+        .map(|assignment| assignment.vpn_address)
+        .collect::<Vec<_>>();
+    vpn_addresses.sort();
+
+    (cluster_assignment.leader, assignments, vpn_addresses)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterManagerOptions {
+    pub can_server_port_range_start: u16,
+    pub can_server_port_range_end: u16,
+}
+impl ClusterManagerOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let can_server_port_range_start = config.get::<u16>("peer.can.server_port_range_start")?;
+        let can_server_port_range_end = config.get::<u16>("peer.can.server_port_range_end")?;
+
+        Ok(ClusterManagerOptions {
+            can_server_port_range_start,
+            can_server_port_range_end,
+        })
+    }
+
+    pub fn can_server_port_range(&self) -> Range<u16> {
+        self.can_server_port_range_start..self.can_server_port_range_end
+    }
+}
+#[derive(Debug, thiserror::Error)]
+enum DetermineMemberInterfaceMappingError {
+    #[error("Peer for device <{device_id}> not found.")]
+    PeerForDeviceNotFound { device_id: DeviceId },
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use rstest::{fixture, rstest};
+    use tokio::sync::mpsc;
+
+    use opendut_carl_api::proto::services::peer_messaging_broker::Downstream;
+    use opendut_carl_api::proto::services::peer_messaging_broker::downstream;
+    use opendut_types::cluster::{ClusterName, DeviceSelector};
+    use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
+    use opendut_types::project::ProjectName;
+    use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceName};
+    use opendut_types::util::Port;
+
+    use crate::actions::{CreateClusterConfigurationParams, StorePeerDescriptorParams};
+    use crate::audit::{AuditEntry, AuditQuery};
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+    use crate::settings;
+
+    use super::*;
+
+    mod deploy_cluster {
+        use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, Pong};
+        use crate::actions::{DeleteClusterConfigurationError, DeleteClusterConfigurationParams, DeletePeerDescriptorError, DeletePeerDescriptorParams, StorePeerDescriptorError, StorePeerDescriptorOptions};
+        use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+
+        use super::*;
+
+        #[rstest]
+        #[tokio::test]
+        async fn deploy_cluster(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let leader_id = peer_a.id;
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("MyAwesomeCluster").unwrap(),
+                leader: leader_id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+
+
+            let mut peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let mut peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+
+
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            assert_that!(fixture.testee.lock().await.deploy(cluster_id, "tester").await, ok(eq(())));
+
+
+            let expectation = || {
+                matches_pattern!(ClusterAssignment {
+                    id: eq(cluster_id),
+                    leader: eq(leader_id),
+                    assignments: any![
+                        unordered_elements_are![
+                            eq(PeerClusterAssignment {
+                                peer_id: peer_a.id,
+                                vpn_address: peer_a.remote_host,
+                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start + 1),
+                                device_interfaces: peer_a.descriptor.topology.devices.clone().into_iter().map(|device| device.interface).collect(),
+                            }),
+                            eq(PeerClusterAssignment {
+                                peer_id: peer_b.id,
+                                vpn_address: peer_b.remote_host,
+                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start),
+                                device_interfaces: peer_b.descriptor.topology.devices.clone().into_iter().map(|device| device.interface).collect(),
+                            }),
+                        ],
+                        unordered_elements_are![
+                            eq(PeerClusterAssignment {
+                                peer_id: peer_a.id,
+                                vpn_address: peer_a.remote_host,
+                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start),
+                                device_interfaces: peer_a.descriptor.topology.devices.into_iter().map(|device| device.interface).collect(),
+                            }),
+                            eq(PeerClusterAssignment {
+                                peer_id: peer_b.id,
+                                vpn_address: peer_b.remote_host,
+                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start + 1),
+                                device_interfaces: peer_b.descriptor.topology.devices.into_iter().map(|device| device.interface).collect(),
+                            }),
+                        ],
+                    ]
+                })
+            };
+
+            let (result, _result2) = receive_peer_configuration_message(&mut peer_a_rx).await;
+            assert_that!(result.cluster_assignment.unwrap(), Clone::clone(&expectation)());
+
+            let (result, _result2) = receive_peer_configuration_message(&mut peer_b_rx).await;
+            assert_that!(result.cluster_assignment.unwrap(), expectation());
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn deploy_should_revert_notified_peers_when_assigning_a_later_peer_fails(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("RevertedCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            let (first_peer, second_peer) = if peer_a.id.uuid < peer_b.id.uuid { (&peer_a, &peer_b) } else { (&peer_b, &peer_a) };
+
+            let (_first_peer_tx, mut first_peer_rx) = fixture.peer_messaging_broker.open(first_peer.id, first_peer.remote_host).await?;
+            receive_peer_configuration_message(&mut first_peer_rx).await;
+            let (_second_peer_tx, second_peer_rx) = fixture.peer_messaging_broker.open(second_peer.id, second_peer.remote_host).await?;
+            drop(second_peer_rx);
+
+            let result = fixture.testee.lock().await.deploy(cluster_id, "tester").await;
+            assert_that!(result, err(matches_pattern!(DeployClusterError::Internal { cluster_id: eq(cluster_id) })));
+
+            let (configuration, _) = receive_peer_configuration_message(&mut first_peer_rx).await;
+            assert_that!(configuration.cluster_assignment, some(anything()));
+            let (configuration, _) = receive_peer_configuration_message(&mut first_peer_rx).await;
+            assert_that!(configuration.cluster_assignment, none());
+
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+            for peer in [first_peer, second_peer] {
+                let configuration = fixture.resources_manager.get::<PeerConfiguration>(peer.id).await;
+                assert_that!(configuration, some(field!(PeerConfiguration.cluster_assignment, none())));
+                let available_state = PeerState::Up { inner: PeerUpState::Available, remote_host: peer.remote_host };
+                assert_that!(fixture.resources_manager.get::<PeerState>(peer.id).await, some(eq(available_state)));
+            }
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn deploy_should_not_block_resources_while_waiting_for_a_peer(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("SlowCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            let (_peer_a_tx, mut peer_a_rx) = fixture.peer_messaging_broker.open(peer_a.id, peer_a.remote_host).await?;
+            let pong = || downstream::Message::Pong(Pong {});
+            while tokio::time::timeout(Duration::from_millis(10), fixture.peer_messaging_broker.send_to_peer(peer_a.id, pong())).await.is_ok() {}
+
+            let testee = Arc::clone(&fixture.testee);
+            let deployment = tokio::spawn(async move {
+                testee.lock().await.deploy(cluster_id, "tester").await
+            });
+
+            let mut deployment_stored = false;
+            for _ in 0..50 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                let read = tokio::time::timeout(Duration::from_millis(100), fixture.resources_manager.get::<ClusterDeployment>(cluster_id)).await;
+                assert_that!(read.is_ok(), eq(true));
+                if read.ok().flatten().is_some() {
+                    deployment_stored = true;
+                    break;
+                }
+            }
+            assert_that!(deployment_stored, eq(true));
+            assert_that!(deployment.is_finished(), eq(false));
+
+            while peer_a_rx.try_recv().is_ok() {}
+            assert_that!(deployment.await?, ok(eq(())));
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn store_cluster_deployment_should_track_cluster_state(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("StatefulCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            let cluster_state = || fixture.resources_manager.resources(|resources| state::cluster_state(resources, cluster_id));
+
+            let _peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let mut deployment_failures = fixture.testee.lock().await.subscribe_deployment_failures();
+
+            let result = fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await;
+            assert_that!(result, err(matches_pattern!(StoreClusterDeploymentError::Internal { cluster_id: eq(cluster_id) })));
+            assert_that!(cluster_state().await, eq(ClusterState::Undeployed));
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+            assert_that!(deployment_failures.try_recv(), ok(matches_pattern!(ClusterDeploymentFailure { cluster_id: eq(cluster_id) })));
+
+            let _peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+
+            let result = fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await;
+            assert_that!(result, ok(eq(cluster_id)));
+            assert_that!(cluster_state().await, eq(ClusterState::Deployed(DeployedClusterState::Healthy)));
+
+            let result = fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await;
+            assert_that!(result, err(matches_pattern!(StoreClusterDeploymentError::IllegalClusterState {
+                actual_state: eq(ClusterState::Deployed(DeployedClusterState::Healthy)),
+            })));
+
+            let result = actions::delete_cluster_configuration(DeleteClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_id,
+            }).await;
+            assert_that!(result, err(matches_pattern!(DeleteClusterConfigurationError::IllegalClusterState {
+                required_states: elements_are![eq(ClusterState::Undeployed)],
+            })));
+
+            fixture.resources_manager.insert(peer_b.id, PeerState::Down).await?;
+            assert_that!(cluster_state().await, eq(ClusterState::Deployed(DeployedClusterState::Unhealthy)));
+
+            fixture.testee.lock().await.delete_cluster_deployment(cluster_id).await?;
+            assert_that!(cluster_state().await, eq(ClusterState::Undeployed));
+
+            actions::delete_cluster_configuration(DeleteClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_id,
+            }).await?;
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn cluster_state_should_consider_members_selected_by_device_selectors(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let mut peer_b = peer_b;
+            peer_b.descriptor.topology.devices[0].tags = vec![DeviceTag::try_from("brake-ecu")?];
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("SelectedCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device]),
+                device_selectors: vec![DeviceSelector::try_from("tag=brake-ecu")?],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            let _peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let _peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
+
+            let cluster_state = || fixture.resources_manager.resources(|resources| state::cluster_state(resources, cluster_id));
+            assert_that!(cluster_state().await, eq(ClusterState::Deployed(DeployedClusterState::Healthy)));
+
+            fixture.resources_manager.insert(peer_b.id, PeerState::Down).await?;
+            assert_that!(cluster_state().await, eq(ClusterState::Deployed(DeployedClusterState::Unhealthy)));
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn recover_interrupted_deployments_should_revert_clusters_left_in_deploying_state(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("InterruptedCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            let _peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let _peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
+
+            fixture.resources_manager.insert(cluster_id, ClusterState::Deploying).await?; //as if CARL stopped while deploying
+
+            fixture.testee.lock().await.recover_interrupted_deployments().await?;
+
+            let cluster_state = fixture.resources_manager.resources(|resources| state::cluster_state(resources, cluster_id)).await;
+            assert_that!(cluster_state, eq(ClusterState::Undeployed));
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+            assert_that!(fixture.resources_manager.get::<ClusterPortAllocation>(cluster_id).await, none());
+            for peer_id in [peer_a.id, peer_b.id] {
+                let configuration = fixture.resources_manager.get::<PeerConfiguration>(peer_id).await;
+                assert_that!(configuration, some(field!(PeerConfiguration.cluster_assignment, none())));
+            }
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn delete_cluster_deployment_should_unassign_cluster_from_peers(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("UndeployedCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
+
+            let mut peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let mut peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
 
-fn determine_member_interface_mapping(
-    cluster_devices: HashSet<DeviceId>,
-    all_peers: Vec<PeerDescriptor>,
-    leader: PeerId,
-) -> Result<HashMap<PeerId, Vec<NetworkInterfaceDescriptor>>, DetermineMemberInterfaceMappingError> {
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
+            for peer_rx in [&mut peer_a_rx, &mut peer_b_rx] {
+                let (configuration, _) = receive_peer_configuration_message(peer_rx).await;
+                assert_that!(configuration.cluster_assignment, some(anything()));
+            }
+            assert_that!(
+                fixture.resources_manager.get::<ClusterPortAllocation>(cluster_id).await,
+                some(field!(ClusterPortAllocation.can_server_ports, len(eq(2))))
+            );
 
-    let mut result: HashMap<PeerId, Vec<NetworkInterfaceDescriptor>> = HashMap::new();
+            fixture.peer_messaging_broker.remove_peer(peer_b.id).await?;
 
-    result.insert(leader, Vec::new()); //will later be replaced, if leader has devices
+            fixture.testee.lock().await.delete_cluster_deployment(cluster_id).await?;
+            assert_that!(fixture.resources_manager.get::<ClusterPortAllocation>(cluster_id).await, none());
 
-    for device_id in cluster_devices {
-        let member_interfaces = all_peers.iter().find_map(|peer| {
-            let interfaces: Vec<NetworkInterfaceDescriptor> = peer.topology.devices.iter()
-                .filter(|device| device.id == device_id)
-                .map(|device| device.interface.clone())
-                .collect();
+            let (configuration, _) = receive_peer_configuration_message(&mut peer_a_rx).await;
+            assert_that!(configuration.cluster_assignment, none());
 
-            if interfaces.is_empty() {
-                None
-            } else {
-                Some((peer.id, interfaces))
+            for peer_id in [peer_a.id, peer_b.id] {
+                let configuration = fixture.resources_manager.get::<PeerConfiguration>(peer_id).await;
+                assert_that!(configuration, some(field!(PeerConfiguration.cluster_assignment, none())));
             }
-        });
 
-        if let Some((peer, interfaces)) = member_interfaces {
-            result.entry(peer)
-                .or_default()
-                .extend(interfaces);
-        } else {
-            return Err(DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id });
+            Ok(())
         }
-    }
-    Ok(result)
-}
-
-#[derive(Clone)]
-pub struct ClusterManagerOptions {
-    pub can_server_port_range_start: u16,
-    pub can_server_port_range_end: u16,
-}
-impl ClusterManagerOptions {
-    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
-        let can_server_port_range_start = config.get::<u16>("peer.can.server_port_range_start")?;
-        let can_server_port_range_end = config.get::<u16>("peer.can.server_port_range_end")?;
-
-        Ok(ClusterManagerOptions {
-            can_server_port_range_start,
-            can_server_port_range_end,
-        })
-    }
-}
-#[derive(Debug, thiserror::Error)]
-enum DetermineMemberInterfaceMappingError {
-    #[error("Peer for device <{device_id}> not found.")]
-    PeerForDeviceNotFound { device_id: DeviceId },
-}
-
 
-#[cfg(test)]
-mod test {
-    use std::collections::HashSet;
-    use std::net::IpAddr;
-    use std::str::FromStr;
-    use std::time::Duration;
+        #[rstest]
+        #[tokio::test]
+        async fn delete_cluster_deployment_should_keep_peers_blocked_which_could_not_be_unassigned(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
 
-    use googletest::prelude::*;
-    use rstest::{fixture, rstest};
-    use tokio::sync::mpsc;
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("UnreachableCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration,
+                expected_version: None,
+            }).await?;
 
-    use opendut_carl_api::proto::services::peer_messaging_broker::Downstream;
-    use opendut_carl_api::proto::services::peer_messaging_broker::downstream;
-    use opendut_types::cluster::ClusterName;
-    use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
-    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
-    use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
-    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceName};
+            let (_peer_a_tx, mut peer_a_rx) = fixture.peer_messaging_broker.open(peer_a.id, peer_a.remote_host).await?;
+            let (_peer_b_tx, peer_b_rx) = fixture.peer_messaging_broker.open(peer_b.id, peer_b.remote_host).await?;
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
 
-    use crate::actions::{CreateClusterConfigurationParams, StorePeerDescriptorParams};
-    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
-    use crate::resources::manager::ResourcesManager;
-    use crate::settings;
+            drop(peer_b_rx); //peer is still connected, but sending to it fails
 
-    use super::*;
+            let result = fixture.testee.lock().await.delete_cluster_deployment(cluster_id).await;
+            assert_that!(result, err(matches_pattern!(DeleteClusterDeploymentError::Internal { cluster_id: eq(cluster_id) })));
 
-    mod deploy_cluster {
-        use opendut_carl_api::proto::services::peer_messaging_broker::ApplyPeerConfiguration;
-        use crate::actions::StorePeerDescriptorOptions;
-        use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+            while peer_a_rx.try_recv().is_ok() {}
+            assert_that!(
+                fixture.resources_manager.get::<PeerState>(peer_a.id).await,
+                some(matches_pattern!(PeerState::Up { inner: eq(PeerUpState::Available) }))
+            );
+            assert_that!(
+                fixture.resources_manager.get::<PeerState>(peer_b.id).await,
+                some(matches_pattern!(PeerState::Up { inner: eq(PeerUpState::Blocked(PeerBlockedState::Undeploying)) }))
+            );
 
-        use super::*;
+            Ok(())
+        }
 
         #[rstest]
         #[tokio::test]
-        async fn deploy_cluster(
+        async fn deploy_should_block_member_peers(
             fixture: Fixture,
             peer_a: PeerFixture,
             peer_b: PeerFixture,
         ) -> anyhow::Result<()> {
 
-            let leader_id = peer_a.id;
             let cluster_id = ClusterId::random();
             let cluster_configuration = ClusterConfiguration {
                 id: cluster_id,
-                name: ClusterName::try_from("MyAwesomeCluster").unwrap(),
-                leader: leader_id,
+                name: ClusterName::try_from("BlockingCluster").unwrap(),
+                leader: peer_a.id,
                 devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
             };
-
-            let store_peer_descriptor_options = StorePeerDescriptorOptions {
-                bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+            let other_cluster_id = ClusterId::random();
+            let other_cluster_configuration = ClusterConfiguration {
+                id: other_cluster_id,
+                name: ClusterName::try_from("OtherCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
             };
-            actions::store_peer_descriptor(StorePeerDescriptorParams {
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
+            for cluster_configuration in [cluster_configuration, other_cluster_configuration] {
+                actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                    resources_manager: Arc::clone(&fixture.resources_manager),
+                    cluster_configuration,
+                    expected_version: None,
+                }).await?;
+            }
+
+            let _peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let _peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+
+            let peer_state = |peer_id: PeerId| fixture.resources_manager.get::<PeerState>(peer_id);
+            let member_state = |remote_host: IpAddr| PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Member), remote_host };
+
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
+            assert_that!(peer_state(peer_a.id).await, some(eq(member_state(peer_a.remote_host))));
+            assert_that!(peer_state(peer_b.id).await, some(eq(member_state(peer_b.remote_host))));
+
+            let result = fixture.testee.lock().await.deploy(other_cluster_id, "tester").await;
+            assert_that!(result, err(matches_pattern!(DeployClusterError::IllegalPeerState {
+                peer_id: eq(peer_a.id),
+                actual_state: eq(member_state(peer_a.remote_host)),
+            })));
+
+            let result = actions::store_peer_descriptor(StorePeerDescriptorParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
                 vpn: Vpn::Disabled,
-                peer_descriptor: Clone::clone(&peer_a.descriptor),
-                options: store_peer_descriptor_options.clone(),
-            }).await?;
+                peer_descriptor: Clone::clone(&peer_b.descriptor),
+                expected_version: None,
+                options: store_peer_descriptor_options(),
+            }).await;
+            assert_that!(result, err(matches_pattern!(StorePeerDescriptorError::IllegalPeerState {
+                actual_state: eq(member_state(peer_b.remote_host)),
+            })));
+
+            let result = actions::delete_peer_descriptor(DeletePeerDescriptorParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                vpn: Vpn::Disabled,
+                peer: peer_b.id,
+                oidc_registration_client: None,
+            }).await;
+            assert_that!(result, err(matches_pattern!(DeletePeerDescriptorError::IllegalPeerState {
+                actual_state: eq(member_state(peer_b.remote_host)),
+            })));
+
+            fixture.peer_messaging_broker.remove_peer(peer_b.id).await?;
+            assert_that!(peer_state(peer_b.id).await, some(eq(PeerState::Down)));
 
-            actions::store_peer_descriptor(StorePeerDescriptorParams {
+            let result = actions::store_peer_descriptor(StorePeerDescriptorParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&peer_b.descriptor),
-                options: store_peer_descriptor_options,
-            }).await?;
+                expected_version: None,
+                options: store_peer_descriptor_options(),
+            }).await;
+            assert_that!(result, err(matches_pattern!(StorePeerDescriptorError::ClusterMember {
+                cluster_id: eq(cluster_id),
+            })));
+
+            let result = actions::delete_peer_descriptor(DeletePeerDescriptorParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                vpn: Vpn::Disabled,
+                peer: peer_b.id,
+                oidc_registration_client: None,
+            }).await;
+            assert_that!(result, err(matches_pattern!(DeletePeerDescriptorError::ClusterMember {
+                cluster_id: eq(cluster_id),
+            })));
 
+            let _peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            assert_that!(peer_state(peer_b.id).await, some(eq(member_state(peer_b.remote_host))));
 
-            let mut peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
-            let mut peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            fixture.testee.lock().await.delete_cluster_deployment(cluster_id).await?;
+            let available_state = |remote_host: IpAddr| PeerState::Up { inner: PeerUpState::Available, remote_host };
+            assert_that!(peer_state(peer_a.id).await, some(eq(available_state(peer_a.remote_host))));
+            assert_that!(peer_state(peer_b.id).await, some(eq(available_state(peer_b.remote_host))));
+
+            Ok(())
+        }
 
+        #[rstest]
+        #[tokio::test]
+        async fn deploy_should_respect_reservations_and_undeploy_after_expiry(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+        ) -> anyhow::Result<()> {
 
+            let audit_log = AuditLog::new();
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("ReservedCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b]).await?;
             actions::create_cluster_configuration(CreateClusterConfigurationParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
                 cluster_configuration,
+                expected_version: None,
             }).await?;
 
-            assert_that!(fixture.testee.lock().await.deploy(cluster_id).await, ok(eq(())));
+            let mut peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let _peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+
+            let now = SystemTime::now();
+            let hour = Duration::from_secs(3600);
+            let reservation = Reservation {
+                id: ReservationId::random(),
+                user: String::from("alice"),
+                peers: HashSet::new(),
+                devices: HashSet::from([peer_b.device]),
+                start: now - hour,
+                end: now + hour,
+            };
+            fixture.resources_manager.insert(reservation.id, Clone::clone(&reservation)).await?;
+
+            let result = fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "bob").await;
+            assert_that!(result, err(matches_pattern!(StoreClusterDeploymentError::Reserved {
+                reservation_id: eq(reservation.id),
+                user: eq("alice"),
+            })));
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "alice").await?;
+            let (configuration, _) = receive_peer_configuration_message(&mut peer_a_rx).await;
+            assert_that!(configuration.cluster_assignment, some(anything()));
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, some(field!(ClusterDeployment.deployed_by, eq("alice"))));
+
+            fixture.testee.lock().await.expire_reservations(&audit_log).await;
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, some(anything()));
+
+            let expired_reservation_of_other_user = Reservation {
+                id: ReservationId::random(),
+                user: String::from("carol"),
+                end: now - hour / 2,
+                ..Clone::clone(&reservation)
+            };
+            fixture.resources_manager.insert(expired_reservation_of_other_user.id, Clone::clone(&expired_reservation_of_other_user)).await?;
 
+            fixture.testee.lock().await.expire_reservations(&audit_log).await;
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, some(anything()));
+            assert_that!(fixture.resources_manager.get::<Reservation>(expired_reservation_of_other_user.id).await, none());
 
-            let expectation = || {
-                matches_pattern!(ClusterAssignment {
-                    id: eq(cluster_id),
-                    leader: eq(leader_id),
-                    assignments: any![
-                        unordered_elements_are![
-                            eq(PeerClusterAssignment {
-                                peer_id: peer_a.id,
-                                vpn_address: peer_a.remote_host,
-                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start + 1),
-                                device_interfaces: peer_a.descriptor.topology.devices.clone().into_iter().map(|device| device.interface).collect(),
-                            }),
-                            eq(PeerClusterAssignment {
-                                peer_id: peer_b.id,
-                                vpn_address: peer_b.remote_host,
-                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start),
-                                device_interfaces: peer_b.descriptor.topology.devices.clone().into_iter().map(|device| device.interface).collect(),
-                            }),
-                        ],
-                        unordered_elements_are![
-                            eq(PeerClusterAssignment {
-                                peer_id: peer_a.id,
-                                vpn_address: peer_a.remote_host,
-                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start),
-                                device_interfaces: peer_a.descriptor.topology.devices.into_iter().map(|device| device.interface).collect(),
-                            }),
-                            eq(PeerClusterAssignment {
-                                peer_id: peer_b.id,
-                                vpn_address: peer_b.remote_host,
-                                can_server_port: Port(fixture.cluster_manager_options.can_server_port_range_start + 1),
-                                device_interfaces: peer_b.descriptor.topology.devices.into_iter().map(|device| device.interface).collect(),
-                            }),
-                        ],
-                    ]
-                })
+            let expired_reservation = Reservation { end: now - hour / 2, ..reservation };
+            let covering_reservation = Reservation { id: ReservationId::random(), ..Clone::clone(&expired_reservation) };
+            let covering_reservation = Reservation { end: now + hour, ..covering_reservation };
+            fixture.resources_manager.insert(expired_reservation.id, Clone::clone(&expired_reservation)).await?;
+            fixture.resources_manager.insert(covering_reservation.id, Clone::clone(&covering_reservation)).await?;
+
+            fixture.testee.lock().await.expire_reservations(&audit_log).await;
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, some(anything()));
+            assert_that!(fixture.resources_manager.get::<Reservation>(expired_reservation.id).await, none());
+
+            fixture.resources_manager.remove::<Reservation>(covering_reservation.id).await?;
+            fixture.resources_manager.insert(expired_reservation.id, Clone::clone(&expired_reservation)).await?;
+
+            fixture.testee.lock().await.expire_reservations(&audit_log).await;
+            assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+            assert_that!(fixture.resources_manager.get::<Reservation>(expired_reservation.id).await, none());
+
+            let undeployments = audit_log.list(&AuditQuery::default()).await.into_iter()
+                .filter(|entry| entry.operation == "ExpireReservation" && entry.resource_id == IntoId::<ClusterDeployment>::into_id(cluster_id))
+                .collect::<Vec<_>>();
+            assert_that!(undeployments, elements_are![
+                matches_pattern!(AuditEntry {
+                    user: eq(audit::SYSTEM_USER),
+                    before: some(anything()),
+                    after: none(),
+                }),
+            ]);
+
+            let (configuration, _) = receive_peer_configuration_message(&mut peer_a_rx).await;
+            assert_that!(configuration.cluster_assignment, none());
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn update_cluster_configuration_should_only_reassign_affected_peers(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            mut peer_b: PeerFixture,
+            peer_c: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let additional_device = DeviceDescriptor {
+                id: DeviceId::random(),
+                name: DeviceName::try_from("PeerB_Device_2").unwrap(),
+                description: DeviceDescription::try_from("Huii").ok(),
+                interface: NetworkInterfaceDescriptor {
+                    name: NetworkInterfaceName::try_from("eth1").unwrap(),
+                    configuration: NetworkInterfaceConfiguration::Ethernet,
+                },
+                tags: vec![],
             };
+            peer_b.descriptor.topology.devices.push(Clone::clone(&additional_device));
 
-            let (result, _result2) = receive_peer_configuration_message(&mut peer_a_rx).await;
-            assert_that!(result.cluster_assignment.unwrap(), Clone::clone(&expectation)());
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("ReconfiguredCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device, peer_c.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b, &peer_c]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration: Clone::clone(&cluster_configuration),
+                expected_version: None,
+            }).await?;
 
-            let (result, _result2) = receive_peer_configuration_message(&mut peer_b_rx).await;
-            assert_that!(result.cluster_assignment.unwrap(), expectation());
+            let mut peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let mut peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let mut peer_c_rx = peer_open(peer_c.id, peer_c.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
+            for peer_rx in [&mut peer_a_rx, &mut peer_b_rx, &mut peer_c_rx] {
+                receive_peer_configuration_message(peer_rx).await;
+            }
+
+            let mut updated_cluster_configuration = cluster_configuration;
+            updated_cluster_configuration.devices.insert(additional_device.id);
+
+            fixture.testee.lock().await.update_cluster_configuration(Clone::clone(&updated_cluster_configuration), None).await?;
+
+            let (peer_a_configuration, _) = receive_peer_configuration_message(&mut peer_a_rx).await;
+            let (peer_b_configuration, _) = receive_peer_configuration_message(&mut peer_b_rx).await;
+            assert_that!(peer_c_rx.try_recv(), err(anything()));
+
+            let peer_b_interfaces = peer_b_configuration.cluster_assignment.as_ref().unwrap().assignments.iter()
+                .find(|assignment| assignment.peer_id == peer_b.id).unwrap()
+                .device_interfaces.len();
+            assert_eq!(peer_b_interfaces, 2);
+            assert_eq!(peer_a_configuration.cluster_assignment, peer_b_configuration.cluster_assignment);
+
+            let stored_peer_c_configuration = fixture.resources_manager.get::<PeerConfiguration>(peer_c.id).await.unwrap();
+            assert_eq!(stored_peer_c_configuration.cluster_assignment, peer_b_configuration.cluster_assignment);
+
+            assert_that!(fixture.resources_manager.get::<ClusterConfiguration>(cluster_id).await, some(eq(updated_cluster_configuration)));
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn update_cluster_configuration_should_respect_reservations_and_revert_on_failure(
+            fixture: Fixture,
+            peer_a: PeerFixture,
+            peer_b: PeerFixture,
+            peer_c: PeerFixture,
+        ) -> anyhow::Result<()> {
+
+            let cluster_id = ClusterId::random();
+            let cluster_configuration = ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("RevertedCluster").unwrap(),
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                device_selectors: vec![],
+                project: ProjectName::default(),
+            };
+            store_peer_descriptors(&fixture.resources_manager, &[&peer_a, &peer_b, &peer_c]).await?;
+            actions::create_cluster_configuration(CreateClusterConfigurationParams {
+                resources_manager: Arc::clone(&fixture.resources_manager),
+                cluster_configuration: Clone::clone(&cluster_configuration),
+                expected_version: None,
+            }).await?;
+
+            let mut peer_a_rx = peer_open(peer_a.id, peer_a.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            let mut peer_b_rx = peer_open(peer_b.id, peer_b.remote_host, Arc::clone(&fixture.peer_messaging_broker)).await?;
+            // Peer C appears to be available, but is not connected, so sending its assignment fails.
+            fixture.resources_manager.insert(peer_c.id, PeerState::Up { inner: PeerUpState::Available, remote_host: peer_c.remote_host }).await?;
+
+            fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id, devices: HashSet::new(), deployed_by: String::new() }, "tester").await?;
+            let (deployed_configuration, _) = receive_peer_configuration_message(&mut peer_a_rx).await;
+            receive_peer_configuration_message(&mut peer_b_rx).await;
+
+            let mut updated_cluster_configuration = Clone::clone(&cluster_configuration);
+            updated_cluster_configuration.devices.insert(peer_c.device);
+
+            let now = SystemTime::now();
+            let reservation = Reservation {
+                id: ReservationId::random(),
+                user: String::from("carol"),
+                peers: HashSet::from([peer_c.id]),
+                devices: HashSet::new(),
+                start: now - Duration::from_secs(60),
+                end: now + Duration::from_secs(3600),
+            };
+            fixture.resources_manager.insert(reservation.id, Clone::clone(&reservation)).await?;
+
+            let result = fixture.testee.lock().await.update_cluster_configuration(Clone::clone(&updated_cluster_configuration), None).await;
+            assert_that!(result, err(matches_pattern!(UpdateClusterConfigurationError::Internal {
+                cause: contains_substring("reserved by user 'carol'"),
+            })));
+            assert_that!(peer_a_rx.try_recv(), err(anything()));
+
+            fixture.resources_manager.remove::<Reservation>(reservation.id).await?;
+
+            let result = fixture.testee.lock().await.update_cluster_configuration(updated_cluster_configuration, None).await;
+            assert_that!(result, err(matches_pattern!(UpdateClusterConfigurationError::Internal {
+                cause: contains_substring("Reverting the update"),
+            })));
+
+            assert_that!(fixture.resources_manager.get::<ClusterConfiguration>(cluster_id).await, some(eq(cluster_configuration)));
+            assert_that!(fixture.resources_manager.get::<PeerState>(peer_c.id).await, some(eq(PeerState::Up { inner: PeerUpState::Available, remote_host: peer_c.remote_host })));
+            let stored_peer_c_configuration = fixture.resources_manager.get::<PeerConfiguration>(peer_c.id).await.unwrap();
+            assert_that!(stored_peer_c_configuration.cluster_assignment, none());
+
+            for peer_rx in [&mut peer_a_rx, &mut peer_b_rx] {
+                let mut last_configuration = None;
+                while let Ok(Downstream { message: Some(downstream::Message::ApplyPeerConfiguration(message)), .. }) = peer_rx.try_recv() {
+                    last_configuration = Some(PeerConfiguration::try_from(message.configuration.unwrap())?);
+                }
+                if let Some(last_configuration) = last_configuration {
+                    assert_eq!(last_configuration.cluster_assignment, deployed_configuration.cluster_assignment);
+                }
+            }
+
+            Ok(())
+        }
 
+        async fn store_peer_descriptors(resources_manager: &ResourcesManagerRef, peers: &[&PeerFixture]) -> anyhow::Result<()> {
+            for peer in peers {
+                actions::store_peer_descriptor(StorePeerDescriptorParams {
+                    resources_manager: Arc::clone(resources_manager),
+                    vpn: Vpn::Disabled,
+                    peer_descriptor: Clone::clone(&peer.descriptor),
+                    expected_version: None,
+                    options: store_peer_descriptor_options(),
+                }).await?;
+            }
             Ok(())
         }
 
+        fn store_peer_descriptor_options() -> StorePeerDescriptorOptions {
+            StorePeerDescriptorOptions {
+                bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+            }
+        }
+
         async fn peer_open(peer_id: PeerId, peer_remote_host: IpAddr, peer_messaging_broker: PeerMessagingBrokerRef) -> anyhow::Result<mpsc::Receiver<Downstream>> {
             let (_peer_tx, mut peer_rx) = peer_messaging_broker.open(peer_id, peer_remote_host).await?;
             receive_peer_configuration_message(&mut peer_rx).await; //initial peer configuration after connect
@@ -452,7 +1993,7 @@ mod test {
         let unknown_cluster = ClusterId::random();
 
         assert_that!(
-            fixture.testee.lock().await.deploy(unknown_cluster).await,
+            fixture.testee.lock().await.deploy(unknown_cluster, "tester").await,
             err(eq(DeployClusterError::ClusterConfigurationNotFound(unknown_cluster)))
         );
 
@@ -497,6 +2038,7 @@ mod test {
                     devices,
                 },
                 executors: ExecutorDescriptors { executors: vec![] },
+                project: ProjectName::default(),
             }
         }
 
@@ -521,6 +2063,30 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn should_resolve_cluster_devices_by_device_selectors(peer_a: PeerFixture, peer_b: PeerFixture) -> anyhow::Result<()> {
+        let mut peer_a = peer_a;
+        peer_a.descriptor.topology.devices[0].tags = vec![DeviceTag::try_from("brake-ecu")?];
+        let mut peer_b = peer_b;
+        peer_b.descriptor.topology.devices[0].tags = vec![DeviceTag::try_from("brake-ecu")?];
+        peer_b.descriptor.location = Some(PeerLocation::try_from("Lab2")?);
+        let configured_device = DeviceId::random();
+
+        let configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("SelectorCluster")?,
+            leader: peer_a.id,
+            devices: HashSet::from([configured_device]),
+            device_selectors: vec![DeviceSelector::try_from("tag=brake-ecu AND location=Lab2")?],
+            project: ProjectName::default(),
+        };
+
+        let result = resolve_cluster_devices(&configuration, &[peer_a.descriptor, peer_b.descriptor]);
+
+        assert_that!(result, unordered_elements_are![eq(configured_device), eq(peer_b.device)]);
+        Ok(())
+    }
+
     struct Fixture {
         testee: ClusterManagerRef,
         resources_manager: ResourcesManagerRef,
@@ -561,6 +2127,10 @@ mod test {
     fn peer_b() -> PeerFixture {
         peer_fixture("PeerB")
     }
+    #[fixture]
+    fn peer_c() -> PeerFixture {
+        peer_fixture("PeerC")
+    }
 
     struct PeerFixture {
         id: PeerId,
@@ -618,6 +2188,7 @@ mod test {
                     }
                 ],
             },
+            project: ProjectName::default(),
         };
         PeerFixture {
             id,
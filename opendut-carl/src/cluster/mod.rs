@@ -1 +1,4 @@
 pub mod manager;
+pub mod ports;
+pub mod state;
+pub mod validation;
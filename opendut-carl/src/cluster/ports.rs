@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+use std::ops::{Not, Range};
+
+use opendut_types::cluster::ClusterPortAllocation;
+use opendut_types::util::Port;
+
+use crate::resources::Resources;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error("Only {available} of the {required} required CAN server port(s) are free in the range [{}, {}).", range.start, range.end)]
+pub struct CanServerPortsExhausted {
+    pub required: usize,
+    pub available: usize,
+    pub range: Range<u16>,
+}
+
+/// Returns the given number of ports from the range, which are not held by any deployed cluster.
+pub fn find_free_can_server_ports(resources: &Resources, range: Range<u16>, count: usize) -> Result<Vec<Port>, CanServerPortsExhausted> {
+    let allocated_ports = resources.iter::<ClusterPortAllocation>()
+        .flat_map(|allocation| allocation.can_server_ports.iter().copied())
+        .collect::<HashSet<_>>();
+
+    let free_ports = Clone::clone(&range)
+        .map(Port)
+        .filter(|port| allocated_ports.contains(port).not())
+        .take(count)
+        .collect::<Vec<_>>();
+
+    if free_ports.len() < count {
+        Err(CanServerPortsExhausted {
+            required: count,
+            available: free_ports.len(),
+            range,
+        })
+    } else {
+        Ok(free_ports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::ClusterId;
+
+    use super::*;
+
+    #[test]
+    fn should_skip_ports_held_by_other_clusters_and_fail_when_exhausted() {
+        let mut resources = Resources::default();
+        let cluster_id = ClusterId::random();
+        resources.insert(cluster_id, ClusterPortAllocation {
+            id: cluster_id,
+            can_server_ports: vec![Port(10000), Port(10002)],
+        });
+
+        assert_that!(
+            find_free_can_server_ports(&resources, 10000..10005, 2),
+            ok(eq(vec![Port(10001), Port(10003)]))
+        );
+        assert_that!(
+            find_free_can_server_ports(&resources, 10000..10005, 4),
+            err(eq(CanServerPortsExhausted { required: 4, available: 3, range: 10000..10005 }))
+        );
+
+        resources.remove::<ClusterPortAllocation>(cluster_id);
+
+        assert_that!(
+            find_free_can_server_ports(&resources, 10000..10005, 5),
+            ok(len(eq(5)))
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::cluster::state::{ClusterState, DeployedClusterState};
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::PeerId;
+use opendut_types::peer::state::PeerState;
+
+use crate::resources::Resources;
+
+/// Determines the current state of a cluster.
+///
+/// The lifecycle phase (undeployed, deploying, deployed) is stored as a resource by the [`ClusterManager`](super::manager::ClusterManager).
+/// The health of a deployed cluster is derived from the states of its member peers, so it always reflects their latest connection state.
+pub fn cluster_state(resources: &Resources, cluster_id: ClusterId) -> ClusterState {
+    match resources.get::<ClusterState>(cluster_id).unwrap_or_default() {
+        ClusterState::Deployed(_) => {
+            let all_members_up = members_of(resources, cluster_id).into_iter()
+                .all(|peer_id| matches!(resources.get::<PeerState>(peer_id), Some(PeerState::Up { .. })));
+
+            if all_members_up {
+                ClusterState::Deployed(DeployedClusterState::Healthy)
+            } else {
+                ClusterState::Deployed(DeployedClusterState::Unhealthy)
+            }
+        }
+        state => state,
+    }
+}
+
+/// Returns the peers, which the cluster is currently assigned to, as stored in their [`PeerConfiguration`].
+///
+/// This reflects the members the cluster was actually deployed with, including those selected via device selectors.
+pub fn members_of(resources: &Resources, cluster_id: ClusterId) -> HashSet<PeerId> {
+    resources.iter::<PeerConfiguration>()
+        .filter_map(|configuration| configuration.cluster_assignment.as_ref())
+        .filter(|assignment| assignment.id == cluster_id)
+        .flat_map(|assignment| assignment.assignments.iter().map(|member| member.peer_id))
+        .collect()
+}
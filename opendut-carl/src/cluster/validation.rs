@@ -0,0 +1,67 @@
+use opendut_carl_api::carl::cluster::IllegalClusterConfigurationError;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::topology::DeviceDescriptor;
+
+use crate::cluster::state;
+use crate::projects;
+use crate::resources::Resources;
+
+/// Returns another cluster configuration of the same project, which has the same name as the given one.
+fn find_cluster_with_same_name(resources: &Resources, configuration: &ClusterConfiguration) -> Option<ClusterConfiguration> {
+    resources.iter::<ClusterConfiguration>()
+        .filter(|other| other.project == configuration.project)
+        .find(|other| other.id != configuration.id && other.name == configuration.name)
+        .cloned()
+}
+
+/// Checks that the name of the cluster is unique within its project, that the leader and all devices of the cluster exist
+/// and belong to the cluster's project, and that no device is used by another deployed cluster.
+/// Device selectors are only resolved when deploying the cluster and only match devices of the cluster's project.
+pub fn validate_cluster_configuration(resources: &Resources, configuration: &ClusterConfiguration) -> Vec<IllegalClusterConfigurationError> {
+    let mut errors = Vec::new();
+
+    if let Some(other) = find_cluster_with_same_name(resources, configuration) {
+        errors.push(IllegalClusterConfigurationError::NameInUse { other_cluster_id: other.id, other_cluster_name: other.name });
+    }
+
+    match resources.get::<PeerDescriptor>(configuration.leader) {
+        None => errors.push(IllegalClusterConfigurationError::LeaderNotFound { leader_id: configuration.leader }),
+        Some(leader) if leader.project != configuration.project => {
+            errors.push(IllegalClusterConfigurationError::LeaderInOtherProject { leader_id: leader.id, leader_project: leader.project });
+        }
+        Some(_) => {}
+    }
+
+    for device_id in &configuration.devices {
+        if resources.get::<DeviceDescriptor>(*device_id).is_none() {
+            errors.push(IllegalClusterConfigurationError::DeviceNotFound { device_id: *device_id });
+        }
+        else if let Some(device_project) = projects::project_of_device(resources, *device_id) {
+            if device_project != configuration.project {
+                errors.push(IllegalClusterConfigurationError::DeviceInOtherProject { device_id: *device_id, device_project });
+            }
+        }
+    }
+
+    let deployed_clusters = resources.iter::<ClusterConfiguration>()
+        .filter(|other| other.id != configuration.id)
+        .filter(|other| state::cluster_state(resources, other.id) != ClusterState::Undeployed);
+
+    for other in deployed_clusters {
+        let other_devices = resources.get::<ClusterDeployment>(other.id)
+            .map(|deployment| deployment.devices)
+            .unwrap_or_else(|| Clone::clone(&other.devices));
+
+        for device_id in configuration.devices.intersection(&other_devices) {
+            errors.push(IllegalClusterConfigurationError::DeviceInUse {
+                device_id: *device_id,
+                other_cluster_id: other.id,
+                other_cluster_name: Clone::clone(&other.name),
+            });
+        }
+    }
+
+    errors
+}
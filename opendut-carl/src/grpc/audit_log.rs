@@ -0,0 +1,41 @@
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::audit_log::{ListAuditEntriesRequest, ListAuditEntriesResponse};
+use opendut_carl_api::proto::services::audit_log::audit_log_server::{AuditLog as AuditLogService, AuditLogServer};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::audit::{AuditLogRef, AuditQuery};
+
+pub struct AuditLogFacade {
+    audit_log: AuditLogRef,
+}
+
+impl AuditLogFacade {
+
+    pub fn new(audit_log: AuditLogRef) -> Self {
+        Self { audit_log }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<AuditLogServer<Self>> {
+        tonic_web::enable(AuditLogServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl AuditLogService for AuditLogFacade {
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_audit_entries(&self, request: Request<ListAuditEntriesRequest>) -> Result<Response<ListAuditEntriesResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let query = AuditQuery::from(request.into_inner());
+
+        let entries = self.audit_log.list(&query).await;
+
+        Ok(Response::new(ListAuditEntriesResponse {
+            entries: entries.into_iter().map(From::from).collect(),
+        }))
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::carl::backup::ResourcesArchive;
+use opendut_carl_api::proto::services::backup::*;
+use opendut_carl_api::proto::services::backup::backup_server::{Backup as BackupService, BackupServer};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{CreateBackupParams, RestoreBackupParams, StorePeerDescriptorOptions};
+use crate::audit;
+use crate::audit::AuditLogRef;
+use crate::cluster::manager::ClusterManagerRef;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::vpn::Vpn;
+
+pub struct BackupFacade {
+    resources_manager: ResourcesManagerRef,
+    cluster_manager: ClusterManagerRef,
+    audit_log: AuditLogRef,
+    vpn: Vpn,
+    options: StorePeerDescriptorOptions,
+}
+
+impl BackupFacade {
+
+    pub fn new(
+        resources_manager: ResourcesManagerRef,
+        cluster_manager: ClusterManagerRef,
+        audit_log: AuditLogRef,
+        vpn: Vpn,
+        options: StorePeerDescriptorOptions,
+    ) -> Self {
+        Self {
+            resources_manager,
+            cluster_manager,
+            audit_log,
+            vpn,
+            options,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<BackupServer<Self>> {
+        tonic_web::enable(BackupServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl BackupService for BackupFacade {
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn create_backup(&self, request: Request<CreateBackupRequest>) -> Result<Response<CreateBackupResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let archive = actions::create_backup(CreateBackupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        Ok(Response::new(CreateBackupResponse {
+            archive: Some(archive.into()),
+        }))
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn restore_backup(&self, request: Request<RestoreBackupRequest>) -> Result<Response<RestoreBackupResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let archive: ResourcesArchive = extract!(request.archive)?;
+
+        let previous = actions::create_backup(CreateBackupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        let result = actions::restore_backup(RestoreBackupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            cluster_manager: Arc::clone(&self.cluster_manager),
+            vpn: Clone::clone(&self.vpn),
+            archive: Clone::clone(&archive),
+            options: Clone::clone(&self.options),
+            user: Clone::clone(&user),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RestoreBackupResponse {
+                    reply: Some(restore_backup_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(()) => {
+                for peer_descriptor in &archive.peer_descriptors {
+                    let before = previous.peer_descriptors.iter().find(|previous| previous.id == peer_descriptor.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", peer_descriptor.id, before, Some(peer_descriptor)).await;
+                }
+                for cluster_configuration in &archive.cluster_configurations {
+                    let before = previous.cluster_configurations.iter().find(|previous| previous.id == cluster_configuration.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", cluster_configuration.id, before, Some(cluster_configuration)).await;
+                }
+                for cluster_deployment in &archive.cluster_deployments {
+                    let before = previous.cluster_deployments.iter().find(|previous| previous.id == cluster_deployment.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", cluster_deployment.id, before, Some(cluster_deployment)).await;
+                }
+                for reservation in &archive.reservations {
+                    let before = previous.reservations.iter().find(|previous| previous.id == reservation.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", reservation.id, before, Some(reservation)).await;
+                }
+                for service_account in &archive.service_accounts {
+                    let before = previous.service_accounts.iter().find(|previous| previous.id == service_account.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", service_account.id, before, Some(service_account)).await;
+                }
+                for api_token in &archive.api_tokens {
+                    let before = previous.api_tokens.iter().find(|previous| previous.id == api_token.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", api_token.id, before, Some(api_token)).await;
+                }
+                for webhook in &archive.webhooks {
+                    let before = previous.webhooks.iter().find(|previous| previous.id == webhook.id);
+                    self.audit_log.record(Clone::clone(&user), "RestoreBackup", webhook.id, before, Some(webhook)).await;
+                }
+                Ok(Response::new(RestoreBackupResponse {
+                    reply: Some(restore_backup_response::Reply::Success(RestoreBackupSuccess {}))
+                }))
+            }
+        }
+    }
+}
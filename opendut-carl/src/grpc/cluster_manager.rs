@@ -7,31 +7,50 @@ use tracing::trace;
 use opendut_carl_api::proto::services::cluster_manager::*;
 use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server::{ClusterManager as ClusterManagerService, ClusterManagerServer};
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::proto;
+use opendut_types::resources::Version;
 use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
 
 use crate::actions;
 use crate::actions::{CreateClusterConfigurationParams, DeleteClusterConfigurationParams};
+use crate::audit;
+use crate::audit::AuditLogRef;
 use crate::cluster::manager::ClusterManagerRef;
-use crate::grpc::extract;
+use crate::cluster::state;
+use crate::grpc;
+use crate::grpc::{extract, WatchStream};
+use crate::projects;
 use crate::resources::manager::ResourcesManagerRef;
 
+#[derive(Clone)]
 pub struct ClusterManagerFacade {
     cluster_manager: ClusterManagerRef,
     resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
 }
 
 impl ClusterManagerFacade {
 
-    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef) -> Self {
+    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef, audit_log: AuditLogRef) -> Self {
         Self {
             cluster_manager,
-            resources_manager
+            resources_manager,
+            audit_log,
         }
     }
 
     pub fn into_grpc_service(self) -> CorsGrpcWeb<ClusterManagerServer<Self>> {
         tonic_web::enable(ClusterManagerServer::new(self))
     }
+
+    /// Checks that the cluster belongs to a project in scope. Unknown clusters are left to the respective action to report.
+    async fn require_cluster_in_scope(&self, scope: &projects::ProjectScope, cluster_id: ClusterId) -> Result<(), Status> {
+        let project = self.resources_manager.resources(|resources| projects::project_of_cluster(resources, cluster_id)).await;
+        match project {
+            Some(project) => Ok(scope.require(&project)?),
+            None => Ok(()),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -41,12 +60,20 @@ impl ClusterManagerService for ClusterManagerFacade {
 
         trace!("Received request: {}", request.debug_output());
         
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let cluster_configuration: ClusterConfiguration = extract!(request.cluster_configuration)?;
+        let previous_cluster_configuration = self.resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await;
+        scope.require(&cluster_configuration.project)?;
+        if let Some(previous_cluster_configuration) = &previous_cluster_configuration {
+            scope.require(&previous_cluster_configuration.project)?;
+        }
 
         let result = actions::create_cluster_configuration(CreateClusterConfigurationParams {
             resources_manager: Arc::clone(&self.resources_manager),
-            cluster_configuration,
+            cluster_configuration: Clone::clone(&cluster_configuration),
+            expected_version: request.expected_version.map(Version::from),
         }).await;
 
         match result {
@@ -56,6 +83,7 @@ impl ClusterManagerService for ClusterManagerFacade {
                 }))
             }
             Ok(cluster_id) => {
+                self.audit_log.record(user, "CreateClusterConfiguration", cluster_id, previous_cluster_configuration.as_ref(), Some(&cluster_configuration)).await;
                 Ok(Response::new(CreateClusterConfigurationResponse {
                     reply: Some(create_cluster_configuration_response::Reply::Success(
                         CreateClusterConfigurationSuccess {
@@ -67,12 +95,53 @@ impl ClusterManagerService for ClusterManagerFacade {
         }
     }
     #[tracing::instrument(skip(self, request), level="trace")]
+    async fn update_cluster_configuration(&self, request: Request<UpdateClusterConfigurationRequest>) -> Result<Response<UpdateClusterConfigurationResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
+        let request = request.into_inner();
+        let cluster_configuration: ClusterConfiguration = extract!(request.cluster_configuration)?;
+        let previous_cluster_configuration = self.resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await;
+        scope.require(&cluster_configuration.project)?;
+        if let Some(previous_cluster_configuration) = &previous_cluster_configuration {
+            scope.require(&previous_cluster_configuration.project)?;
+        }
+
+        let result = self.cluster_manager.lock().await.update_cluster_configuration(
+            Clone::clone(&cluster_configuration),
+            request.expected_version.map(Version::from),
+        ).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(UpdateClusterConfigurationResponse {
+                    reply: Some(update_cluster_configuration_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(cluster_id) => {
+                self.audit_log.record(user, "UpdateClusterConfiguration", cluster_id, previous_cluster_configuration.as_ref(), Some(&cluster_configuration)).await;
+                Ok(Response::new(UpdateClusterConfigurationResponse {
+                    reply: Some(update_cluster_configuration_response::Reply::Success(
+                        UpdateClusterConfigurationSuccess {
+                            cluster_id: Some(cluster_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+    #[tracing::instrument(skip(self, request), level="trace")]
     async fn delete_cluster_configuration(&self, request: Request<DeleteClusterConfigurationRequest>) -> Result<Response<DeleteClusterConfigurationResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
 
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        self.require_cluster_in_scope(&scope, cluster_id).await?;
 
         let result =
             actions::delete_cluster_configuration(DeleteClusterConfigurationParams {
@@ -87,6 +156,7 @@ impl ClusterManagerService for ClusterManagerFacade {
                 }))
             }
             Ok(cluster_configuration) => {
+                self.audit_log.record(user, "DeleteClusterConfiguration", cluster_id, Some(&cluster_configuration), None).await;
                 Ok(Response::new(DeleteClusterConfigurationResponse {
                     reply: Some(delete_cluster_configuration_response::Reply::Success(
                         DeleteClusterConfigurationSuccess {
@@ -100,7 +170,8 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn get_cluster_configuration(&self, request: Request<GetClusterConfigurationRequest>) -> Result<Response<GetClusterConfigurationResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
-        
+
+        let scope = projects::scope_of(&request);
         match request.into_inner().id {
             None => {
                 Err(Status::invalid_argument("ClusterId is required."))
@@ -108,13 +179,15 @@ impl ClusterManagerService for ClusterManagerFacade {
             Some(id) => {
                 let id = ClusterId::try_from(id)
                     .map_err(|_| Status::invalid_argument("Invalid ClusterId."))?;
-                let configuration = self.cluster_manager.lock().await.find_configuration(id).await;
+                let configuration = self.cluster_manager.lock().await.find_configuration(id).await
+                    .filter(|(configuration, _)| scope.contains(&configuration.project));
                 match configuration {
-                    Some(configuration) => {
+                    Some((configuration, version)) => {
                         Ok(Response::new(GetClusterConfigurationResponse {
                             result: Some(get_cluster_configuration_response::Result::Success(
                                 GetClusterConfigurationSuccess {
-                                    configuration: Some(configuration.into())
+                                    configuration: Some(configuration.into()),
+                                    version: version.into(),
                                 }
                             ))
                         }))
@@ -133,25 +206,76 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn list_cluster_configurations(&self, request: Request<ListClusterConfigurationsRequest>) -> Result<Response<ListClusterConfigurationsResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
-        
-        let configurations = self.cluster_manager.lock().await.list_configuration().await;
+
+        let scope = projects::scope_of(&request);
+        let (configurations, versions, states) = self.resources_manager.resources(|resources| {
+            let mut configurations = Vec::new();
+            let mut versions = Vec::new();
+            let mut states = Vec::new();
+            for configuration in resources.iter::<ClusterConfiguration>().filter(|configuration| scope.contains(&configuration.project)) {
+                versions.push(u64::from(resources.version::<ClusterConfiguration>(configuration.id)));
+                states.push(proto::cluster::ClusterState::from(state::cluster_state(resources, configuration.id)));
+                configurations.push(proto::cluster::ClusterConfiguration::from(Clone::clone(configuration)));
+            }
+            (configurations, versions, states)
+        }).await;
         Ok(Response::new(ListClusterConfigurationsResponse {
             result: Some(list_cluster_configurations_response::Result::Success(
                 ListClusterConfigurationsSuccess {
-                    configurations: configurations.into_iter().map(|configuration| configuration.into()).collect::<Vec<_>>()
+                    configurations,
+                    versions,
+                    states,
                 }
             ))
         }))
     }
 
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn get_cluster_state(&self, request: Request<GetClusterStateRequest>) -> Result<Response<GetClusterStateResponse>, Status> {
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        let request = request.into_inner();
+        let cluster_id: ClusterId = extract!(request.id)?;
+
+        let state = self.resources_manager.resources(|resources| {
+            resources.get::<ClusterConfiguration>(cluster_id)
+                .filter(|configuration| scope.contains(&configuration.project))
+                .map(|_| state::cluster_state(resources, cluster_id))
+        }).await;
+
+        match state {
+            Some(state) => {
+                Ok(Response::new(GetClusterStateResponse {
+                    result: Some(get_cluster_state_response::Result::Success(
+                        GetClusterStateSuccess {
+                            state: Some(state.into()),
+                        }
+                    ))
+                }))
+            }
+            None => {
+                Ok(Response::new(GetClusterStateResponse {
+                    result: Some(get_cluster_state_response::Result::Failure(
+                        GetClusterStateFailure {}
+                    ))
+                }))
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn store_cluster_deployment(&self, request: Request<StoreClusterDeploymentRequest>) -> Result<Response<StoreClusterDeploymentResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
         
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let cluster_deployment: ClusterDeployment = extract!(request.cluster_deployment)?;
+        self.require_cluster_in_scope(&scope, cluster_deployment.id).await?;
+        let previous_cluster_deployment = self.resources_manager.get::<ClusterDeployment>(cluster_deployment.id).await;
 
-        let result = self.cluster_manager.lock().await.store_cluster_deployment(cluster_deployment).await;
+        let result = self.cluster_manager.lock().await.store_cluster_deployment(Clone::clone(&cluster_deployment), &user).await;
 
         match result {
             Err(error) => {
@@ -160,6 +284,7 @@ impl ClusterManagerService for ClusterManagerFacade {
                 }))
             }
             Ok(cluster_id) => {
+                self.audit_log.record(user, "StoreClusterDeployment", cluster_id, previous_cluster_deployment.as_ref(), Some(&cluster_deployment)).await;
                 Ok(Response::new(StoreClusterDeploymentResponse {
                     reply: Some(store_cluster_deployment_response::Reply::Success(
                         StoreClusterDeploymentSuccess {
@@ -174,8 +299,11 @@ impl ClusterManagerService for ClusterManagerFacade {
     async fn delete_cluster_deployment(&self, request: Request<DeleteClusterDeploymentRequest>) -> Result<Response<DeleteClusterDeploymentResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
 
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        self.require_cluster_in_scope(&scope, cluster_id).await?;
 
         let result = self.cluster_manager.lock().await.delete_cluster_deployment(cluster_id).await; // TODO: Replace with action
 
@@ -185,11 +313,12 @@ impl ClusterManagerService for ClusterManagerFacade {
                     reply: Some(delete_cluster_deployment_response::Reply::Failure(error.into()))
                 }))
             }
-            Ok(cluster_configuration) => {
+            Ok(cluster_deployment) => {
+                self.audit_log.record(user, "DeleteClusterDeployment", cluster_id, Some(&cluster_deployment), None).await;
                 Ok(Response::new(DeleteClusterDeploymentResponse {
                     reply: Some(delete_cluster_deployment_response::Reply::Success(
                         DeleteClusterDeploymentSuccess {
-                            cluster_deployment: Some(cluster_configuration.into())
+                            cluster_deployment: Some(cluster_deployment.into())
                         }
                     ))
                 }))
@@ -200,14 +329,45 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn list_cluster_deployments(&self, request: Request<ListClusterDeploymentsRequest>) -> Result<Response<ListClusterDeploymentsResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
-        
-        let deployments = self.cluster_manager.lock().await.list_deployment().await;
+
+        let scope = projects::scope_of(&request);
+        let (deployments, states) = self.resources_manager.resources(|resources| {
+            resources.iter::<ClusterDeployment>()
+                .filter(|deployment| projects::project_of_cluster(resources, deployment.id)
+                    .is_some_and(|project| scope.contains(&project)))
+                .map(|deployment| (
+                    proto::cluster::ClusterDeployment::from(Clone::clone(deployment)),
+                    proto::cluster::ClusterState::from(state::cluster_state(resources, deployment.id)),
+                ))
+                .unzip()
+        }).await;
         Ok(Response::new(ListClusterDeploymentsResponse {
             result: Some(list_cluster_deployments_response::Result::Success(
                 ListClusterDeploymentsSuccess {
-                    deployments: deployments.into_iter().map(|deployment| deployment.into()).collect::<Vec<_>>()
+                    deployments,
+                    states,
                 }
             ))
         }))
     }
+
+    type WatchClusterConfigurationsStream = WatchStream<WatchClusterConfigurationsResponse>;
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn watch_cluster_configurations(&self, request: Request<WatchClusterConfigurationsRequest>) -> Result<Response<Self::WatchClusterConfigurationsStream>, Status> {
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        Ok(Response::new(grpc::watch_in_scope::<ClusterConfiguration, ClusterId, _>(&self.resources_manager, scope, |resources, id| projects::project_of_cluster(resources, ClusterId::from(id.value())))))
+    }
+
+    type WatchClusterDeploymentsStream = WatchStream<WatchClusterDeploymentsResponse>;
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn watch_cluster_deployments(&self, request: Request<WatchClusterDeploymentsRequest>) -> Result<Response<Self::WatchClusterDeploymentsStream>, Status> {
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        Ok(Response::new(grpc::watch_in_scope::<ClusterDeployment, ClusterId, _>(&self.resources_manager, scope, |resources, id| projects::project_of_cluster(resources, ClusterId::from(id.value())))))
+    }
 }
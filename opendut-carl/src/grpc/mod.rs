@@ -1,14 +1,37 @@
 use std::fmt::Display;
+use std::pin::Pin;
+use std::sync::Arc;
 
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::WatchEvent;
+use opendut_types::project::ProjectName;
+use opendut_types::resources::Id;
+
+use crate::projects::ProjectScope;
+use crate::resources::{Resource, ResourceEvent, Resources};
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef, SubscriptionLagged};
+
+pub use audit_log::AuditLogFacade;
+pub use backup::BackupFacade;
 pub use cluster_manager::ClusterManagerFacade;
 pub use metadata_provider::MetadataProviderFacade;
 pub use peer_manager::{PeerManagerFacade, PeerManagerFacadeOptions};
 pub use peer_messaging_broker::PeerMessagingBrokerFacade;
+pub use reservation_manager::ReservationManagerFacade;
+pub use service_account_manager::ServiceAccountManagerFacade;
+pub use webhook_manager::WebhookManagerFacade;
 
+mod audit_log;
+mod backup;
 mod cluster_manager;
 mod peer_manager;
 mod peer_messaging_broker;
 mod metadata_provider;
+mod reservation_manager;
+mod service_account_manager;
+mod webhook_manager;
 
 pub trait ExtractOrInvalidArgument<A, B>
 where
@@ -41,3 +64,74 @@ macro_rules! extract {
 }
 
 pub(crate) use extract;
+
+pub(crate) type WatchStream<M> = Pin<Box<dyn Stream<Item = Result<M, tonic::Status>> + Send>>;
+
+/// Streams all changes of resources of type `R` as protobuf messages of type `M`.
+/// Ends the stream with an error, if the client cannot keep up with the changes.
+pub(crate) fn watch<R, I, M>(resources_manager: &ResourcesManager) -> WatchStream<M>
+where
+    R: Resource,
+    I: From<Uuid>,
+    M: From<WatchEvent<I, R>> + Send + 'static,
+{
+    into_watch_stream(resources_manager.subscribe::<R>())
+}
+
+/// Like [`watch`], but only streams changes of resources, which belong to a project in the given scope.
+/// Deletions are always streamed, since the project of a deleted resource is not known anymore.
+pub(crate) fn watch_in_scope<R, I, M>(
+    resources_manager: &ResourcesManagerRef,
+    scope: ProjectScope,
+    project_of: fn(&Resources, Id) -> Option<ProjectName>,
+) -> WatchStream<M>
+where
+    R: Resource,
+    I: From<Uuid>,
+    M: From<WatchEvent<I, R>> + Send + 'static,
+{
+    if scope == ProjectScope::All {
+        return watch::<R, I, M>(resources_manager);
+    }
+
+    let subscribing_resources_manager = Arc::clone(resources_manager);
+    let events = futures::StreamExt::then(resources_manager.subscribe::<R>(), move |event| {
+        let resources_manager = Arc::clone(&subscribing_resources_manager);
+        let scope = Clone::clone(&scope);
+        async move {
+            let is_visible = match &event {
+                Ok(ResourceEvent::Created { id, .. } | ResourceEvent::Updated { id, .. }) => {
+                    let id = *id;
+                    resources_manager.resources(|resources| project_of(resources, id)).await
+                        .is_some_and(|project| scope.contains(&project))
+                }
+                Ok(ResourceEvent::Deleted { .. }) | Err(_) => true,
+            };
+            is_visible.then_some(event)
+        }
+    })
+    .filter_map(|event| event);
+
+    into_watch_stream(events)
+}
+
+fn into_watch_stream<R, I, M>(events: impl Stream<Item=Result<ResourceEvent<R>, SubscriptionLagged>> + Send + 'static) -> WatchStream<M>
+where
+    R: Resource,
+    I: From<Uuid>,
+    M: From<WatchEvent<I, R>> + Send + 'static,
+{
+    let events = events
+        .map(|event| event
+            .map(|event| {
+                let event = match event {
+                    ResourceEvent::Created { id, resource } => WatchEvent::Created { id: I::from(id.value()), resource },
+                    ResourceEvent::Updated { id, resource } => WatchEvent::Updated { id: I::from(id.value()), resource },
+                    ResourceEvent::Deleted { id } => WatchEvent::Deleted { id: I::from(id.value()) },
+                };
+                M::from(event)
+            })
+            .map_err(|cause| tonic::Status::data_loss(cause.to_string()))
+        );
+    Box::pin(events)
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 use pem::Pem;
 
 use tonic::{Request, Response, Status};
@@ -12,40 +13,57 @@ use opendut_carl_api::proto::services::peer_manager;
 use opendut_carl_api::proto::services::peer_manager::*;
 use opendut_carl_api::proto::services::peer_manager::peer_manager_server::{PeerManager as PeerManagerService, PeerManagerServer};
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::setup::{IssuedPeerSetup, PeerSetupNonce};
+use opendut_types::peer::state::PeerState;
+use opendut_types::proto;
+use opendut_types::resources::Version;
 use opendut_types::cleo::{CleoId};
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
 
 use crate::actions;
-use crate::actions::{DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, ListDevicesParams, ListPeerDescriptorsParams, StorePeerDescriptorOptions, StorePeerDescriptorParams};
-use crate::grpc::extract;
+use crate::actions::{DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, GetPeerStatusReportsParams, ListDevicesParams, ListPeerDescriptorsParams, ListPeerSetupsParams, RevokePeerSetupParams, StorePeerDescriptorOptions, StorePeerDescriptorParams};
+use crate::audit;
+use crate::audit::AuditLogRef;
+use crate::grpc;
+use crate::grpc::{extract, WatchStream};
+use crate::peer::certificate_authority::PeerCertificateAuthorityRef;
+use crate::projects;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 
+#[derive(Clone)]
 pub struct PeerManagerFacade {
     resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
     vpn: Vpn,
     carl_url: Url,
     ca: Pem,
+    certificate_authority: PeerCertificateAuthorityRef,
     oidc_registration_client: Option<RegistrationClientRef>,
     options: PeerManagerFacadeOptions,
 }
 
 impl PeerManagerFacade {
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         resources_manager: ResourcesManagerRef,
+        audit_log: AuditLogRef,
         vpn: Vpn,
         carl_url: Url,
         ca: Pem,
+        certificate_authority: PeerCertificateAuthorityRef,
         oidc_registration_client: Option<RegistrationClientRef>,
         options: PeerManagerFacadeOptions
     ) -> Self {
         PeerManagerFacade {
             resources_manager,
+            audit_log,
             vpn,
             carl_url,
             ca,
+            certificate_authority,
             oidc_registration_client,
             options
         }
@@ -54,6 +72,15 @@ impl PeerManagerFacade {
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerManagerServer<Self>> {
         tonic_web::enable(PeerManagerServer::new(self))
     }
+
+    /// Checks that the peer belongs to a project in scope. Unknown peers are left to the respective action to report.
+    async fn require_peer_in_scope(&self, scope: &projects::ProjectScope, peer_id: PeerId) -> Result<(), Status> {
+        let project = self.resources_manager.resources(|resources| projects::project_of_peer(resources, peer_id)).await;
+        match project {
+            Some(project) => Ok(scope.require(&project)?),
+            None => Ok(()),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -64,13 +91,21 @@ impl PeerManagerService for PeerManagerFacade {
 
         trace!("Received request: {}", request.debug_output());
 
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let peer_descriptor: PeerDescriptor = extract!(request.peer)?;
+        let previous_peer_descriptor = self.resources_manager.get::<PeerDescriptor>(peer_descriptor.id).await;
+        scope.require(&peer_descriptor.project)?;
+        if let Some(previous_peer_descriptor) = &previous_peer_descriptor {
+            scope.require(&previous_peer_descriptor.project)?;
+        }
 
         let result = actions::store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&self.resources_manager),
             vpn: Clone::clone(&self.vpn),
             peer_descriptor: Clone::clone(&peer_descriptor),
+            expected_version: request.expected_version.map(Version::from),
             options: StorePeerDescriptorOptions {
                 bridge_name_default: Clone::clone(&self.options.bridge_name_default),
             }
@@ -83,6 +118,7 @@ impl PeerManagerService for PeerManagerFacade {
                 }))
             }
             Ok(peer_id) => {
+                self.audit_log.record(user, "StorePeerDescriptor", peer_id, previous_peer_descriptor.as_ref(), Some(&peer_descriptor)).await;
                 Ok(Response::new(StorePeerDescriptorResponse {
                     reply: Some(store_peer_descriptor_response::Reply::Success(
                         StorePeerDescriptorSuccess {
@@ -99,8 +135,11 @@ impl PeerManagerService for PeerManagerFacade {
 
         trace!("Received request: {}", request.debug_output());
 
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
+        self.require_peer_in_scope(&scope, peer_id).await?;
 
         let result =
             actions::delete_peer_descriptor(DeletePeerDescriptorParams {
@@ -117,6 +156,7 @@ impl PeerManagerService for PeerManagerFacade {
                 }))
             }
             Ok(peer) => {
+                self.audit_log.record(user, "DeletePeerDescriptor", peer_id, Some(&peer), None).await;
                 Ok(Response::new(DeletePeerDescriptorResponse {
                     reply: Some(peer_manager::delete_peer_descriptor_response::Reply::Success(
                         DeletePeerDescriptorSuccess {
@@ -133,6 +173,7 @@ impl PeerManagerService for PeerManagerFacade {
 
         trace!("Received request: {}", request.debug_output());
 
+        let scope = projects::scope_of(&request);
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
 
@@ -141,10 +182,9 @@ impl PeerManagerService for PeerManagerFacade {
                 resources_manager: Arc::clone(&self.resources_manager),
             }).await
             .map_err(|error| GetPeerDescriptorError::Internal { peer_id, cause: error.to_string() })
-            .and_then(|peers| peers.iter()
-                .find(|peer| peer.id == peer_id)
+            .and_then(|peers| peers.into_iter()
+                .find(|(peer, _)| peer.id == peer_id && scope.contains(&peer.project))
                 .ok_or_else(|| GetPeerDescriptorError::PeerNotFound { peer_id })
-                .cloned()
             );
 
         match result {
@@ -153,11 +193,46 @@ impl PeerManagerService for PeerManagerFacade {
                     reply: Some(get_peer_descriptor_response::Reply::Failure(error.into()))
                 }))
             }
-            Ok(descriptor) => {
+            Ok((descriptor, version)) => {
                 Ok(Response::new(GetPeerDescriptorResponse {
                     reply: Some(get_peer_descriptor_response::Reply::Success(
                         GetPeerDescriptorSuccess {
-                            descriptor: Some(descriptor.into())
+                            descriptor: Some(descriptor.into()),
+                            version: version.into(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn get_peer_status_reports(&self, request: Request<GetPeerStatusReportsRequest>) -> Result<Response<GetPeerStatusReportsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        self.require_peer_in_scope(&scope, peer_id).await?;
+
+        let result =
+            actions::get_peer_status_reports(GetPeerStatusReportsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_id,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetPeerStatusReportsResponse {
+                    reply: Some(get_peer_status_reports_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(reports) => {
+                Ok(Response::new(GetPeerStatusReportsResponse {
+                    reply: Some(get_peer_status_reports_response::Reply::Success(
+                        GetPeerStatusReportsSuccess {
+                            reports: reports.into_iter().map(Into::into).collect(),
                         }
                     ))
                 }))
@@ -170,13 +245,15 @@ impl PeerManagerService for PeerManagerFacade {
 
         trace!("Received request: {}", request.debug_output());
 
+        let scope = projects::scope_of(&request);
         let result =
             actions::list_peer_descriptors(ListPeerDescriptorsParams {
                 resources_manager: Arc::clone(&self.resources_manager),
             }).await
             .map(|peers| peers.into_iter()
-                .map(From::from)
-                .collect::<Vec<_>>()
+                .filter(|(peer, _)| scope.contains(&peer.project))
+                .map(|(peer, version)| (proto::peer::PeerDescriptor::from(peer), u64::from(version)))
+                .unzip::<_, _, Vec<_>, Vec<_>>()
             );
 
         match result {
@@ -185,11 +262,12 @@ impl PeerManagerService for PeerManagerFacade {
                     reply: Some(list_peer_descriptors_response::Reply::Failure(error.into()))
                 }))
             }
-            Ok(peers) => {
+            Ok((peers, versions)) => {
                 Ok(Response::new(ListPeerDescriptorsResponse {
                     reply: Some(list_peer_descriptors_response::Reply::Success(
                         ListPeerDescriptorsSuccess {
-                            peers
+                            peers,
+                            versions,
                         }
                     ))
                 }))
@@ -202,10 +280,18 @@ impl PeerManagerService for PeerManagerFacade {
 
         trace!("Received request: {}", request.debug_output());
 
+        let scope = projects::scope_of(&request);
         let devices = actions::list_devices(ListDevicesParams {
             resources_manager: Arc::clone(&self.resources_manager),
         }).await.expect("Devices should be listable");
 
+        let devices = self.resources_manager.resources(|resources| {
+            devices.into_iter()
+                .filter(|device| projects::project_of_device(resources, device.id)
+                    .is_some_and(|project| scope.contains(&project)))
+                .collect::<Vec<_>>()
+        }).await;
+
         let devices = devices.into_iter()
             .map(From::from)
             .collect();
@@ -217,11 +303,14 @@ impl PeerManagerService for PeerManagerFacade {
     async fn generate_peer_setup(&self, request: Request<GeneratePeerSetupRequest>) -> Result<Response<GeneratePeerSetupResponse>, Status> { // TODO: Refactor error types.
         trace!("Received request: {}", request.debug_output());
 
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
         let message = request.into_inner();
         let response = match message.peer {
             Some(peer_id) => {
                 let peer_id = PeerId::try_from(peer_id)
                     .map_err(|cause| Status::invalid_argument(format!("PeerId could not be converted: {}", cause)))?;
+                self.require_peer_in_scope(&scope, peer_id).await?;
                 let setup = actions::generate_peer_setup(GeneratePeerSetupParams {
                     resources_manager: Arc::clone(&self.resources_manager),
                     peer: peer_id,
@@ -229,8 +318,14 @@ impl PeerManagerService for PeerManagerFacade {
                     ca: Clone::clone(&self.ca),
                     vpn: Clone::clone(&self.vpn),
                     oidc_registration_client: self.oidc_registration_client.clone(),
+                    validity: self.options.setup_validity,
+                    certificate_authority: Arc::clone(&self.certificate_authority),
+                    certificate_validity: self.options.certificate_validity,
                 }, message.user_id).await.map_err(|cause| Status::internal(format!("Peer setup could not be created: {}", cause)))?;
 
+                let issued_setup = self.resources_manager.get::<IssuedPeerSetup>(setup.nonce).await;
+                self.audit_log.record(user, "GeneratePeerSetup", setup.nonce, None, issued_setup.as_ref()).await;
+
                 peer_manager::generate_peer_setup_response::Reply::Success(peer_manager::GeneratePeerSetupSuccess { peer: Some(peer_id.into()), setup: Some(setup.into()) })
             }
             None => {
@@ -241,6 +336,78 @@ impl PeerManagerService for PeerManagerFacade {
         Ok(Response::new(GeneratePeerSetupResponse { reply: Some(response) }))
     }
 
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_peer_setups(&self, request: Request<ListPeerSetupsRequest>) -> Result<Response<ListPeerSetupsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        self.require_peer_in_scope(&scope, peer_id).await?;
+
+        let result = actions::list_peer_setups(ListPeerSetupsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListPeerSetupsResponse {
+                    reply: Some(list_peer_setups_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(setups) => {
+                Ok(Response::new(ListPeerSetupsResponse {
+                    reply: Some(list_peer_setups_response::Reply::Success(
+                        ListPeerSetupsSuccess {
+                            setups: setups.into_iter().map(From::from).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn revoke_peer_setup(&self, request: Request<RevokePeerSetupRequest>) -> Result<Response<RevokePeerSetupResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
+        let request = request.into_inner();
+        let nonce: PeerSetupNonce = extract!(request.nonce)?;
+
+        let previous_setup = self.resources_manager.get::<IssuedPeerSetup>(nonce).await;
+        if let Some(previous_setup) = &previous_setup {
+            self.require_peer_in_scope(&scope, previous_setup.peer_id).await?;
+        }
+
+        let result = actions::revoke_peer_setup(RevokePeerSetupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            nonce,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RevokePeerSetupResponse {
+                    reply: Some(revoke_peer_setup_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(setup) => {
+                self.audit_log.record(user, "RevokePeerSetup", nonce, previous_setup.as_ref(), Some(&setup)).await;
+                Ok(Response::new(RevokePeerSetupResponse {
+                    reply: Some(revoke_peer_setup_response::Reply::Success(
+                        RevokePeerSetupSuccess {
+                            setup: Some(setup.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
     async fn generate_cleo_setup(&self, request: Request<GenerateCleoSetupRequest>) -> Result<Response<GenerateCleoSetupResponse>, Status> {
        trace!("Received request: {}", request.debug_output());
         
@@ -259,11 +426,35 @@ impl PeerManagerService for PeerManagerFacade {
 
         Ok(Response::new(GenerateCleoSetupResponse { reply: Some(response) }))
     }
+
+    type WatchPeerDescriptorsStream = WatchStream<WatchPeerDescriptorsResponse>;
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn watch_peer_descriptors(&self, request: Request<WatchPeerDescriptorsRequest>) -> Result<Response<Self::WatchPeerDescriptorsStream>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        Ok(Response::new(grpc::watch_in_scope::<PeerDescriptor, PeerId, _>(&self.resources_manager, scope, |resources, id| projects::project_of_peer(resources, PeerId::from(id.value())))))
+    }
+
+    type WatchPeerStatesStream = WatchStream<WatchPeerStatesResponse>;
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn watch_peer_states(&self, request: Request<WatchPeerStatesRequest>) -> Result<Response<Self::WatchPeerStatesStream>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        Ok(Response::new(grpc::watch_in_scope::<PeerState, PeerId, _>(&self.resources_manager, scope, |resources, id| projects::project_of_peer(resources, PeerId::from(id.value())))))
+    }
 }
 
 #[derive(Clone)]
 pub struct PeerManagerFacadeOptions {
     pub bridge_name_default: NetworkInterfaceName,
+    pub setup_validity: Duration,
+    pub certificate_validity: Duration,
 }
 impl PeerManagerFacadeOptions {
     pub fn load(config: &config::Config) -> Result<Self, PeerManagerFacadeOptionsLoadError> {
@@ -273,8 +464,18 @@ impl PeerManagerFacadeOptions {
         let bridge_name_default = NetworkInterfaceName::try_from(bridge_name_default)
             .map_err(|cause| PeerManagerFacadeOptionsLoadError { message: cause.to_string() })?;
 
+        let setup_validity = config.get::<u64>("peer.setup.validity.ms")
+            .map(Duration::from_millis)
+            .map_err(|cause| PeerManagerFacadeOptionsLoadError { message: cause.to_string() })?;
+
+        let certificate_validity = config.get::<u64>("peer.certificate.validity.ms")
+            .map(Duration::from_millis)
+            .map_err(|cause| PeerManagerFacadeOptionsLoadError { message: cause.to_string() })?;
+
         Ok(PeerManagerFacadeOptions {
             bridge_name_default,
+            setup_validity,
+            certificate_validity,
         })
     }
 }
@@ -296,11 +497,16 @@ mod tests {
 
     use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
     use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
+    use opendut_types::project::ProjectName;
     use opendut_types::proto;
+    use opendut_types::resources::Id;
     use opendut_types::topology::Topology;
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
     use opendut_auth_tests::registration_client;
 
+    use crate::audit::{AuditEntry, AuditLog, AuditQuery};
+    use crate::peer::certificate_authority::PeerCertificateAuthority;
+    use crate::resources::IntoId;
     use crate::resources::manager::ResourcesManager;
     use crate::vpn::Vpn;
 
@@ -317,17 +523,20 @@ mod tests {
 
     #[rstest]
     #[tokio::test]
-    async fn test_successful_create_delete(#[future] registration_client: RegistrationClientRef) -> Result<()> {
+    async fn test_successful_create_delete() -> Result<()> {
 
         let settings = crate::settings::load_defaults()?;
 
         let resources_manager = ResourcesManager::new();
+        let audit_log = AuditLog::new();
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            Arc::clone(&audit_log),
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
-            Some(registration_client.await),
+            Arc::new(PeerCertificateAuthority::generate()?),
+            None, //deleting the OIDC client requires a running Keycloak, otherwise the deletion is rolled back
             PeerManagerFacadeOptions::load(&settings.config)?
         );
 
@@ -364,11 +573,13 @@ mod tests {
                     }
                 ],
             },
+            project: ProjectName::default(),
         };
 
         let create_peer_reply = testee.store_peer_descriptor(Request::new(
             StorePeerDescriptorRequest {
                 peer: Some(Clone::clone(&peer_descriptor).into()),
+                expected_version: None,
             }
         )).await?;
 
@@ -396,6 +607,16 @@ mod tests {
             )))
         )?;
 
+        let _ = testee.generate_peer_setup(Request::new(
+            peer_manager::GeneratePeerSetupRequest {
+                peer: Some(peer_id.into()),
+                user_id: String::from("admin"),
+            }
+        )).await?;
+        let nonce = resources_manager.resources(|resources| {
+            resources.iter::<IssuedPeerSetup>().map(|setup| setup.nonce).next()
+        }).await.unwrap();
+
         let _ = testee.delete_peer_descriptor(Request::new(
             peer_manager::DeletePeerDescriptorRequest {
                 peer_id: Some(peer_id.into()),
@@ -414,6 +635,27 @@ mod tests {
             )))
         )?;
 
+        verify_that!(audit_log.list(&AuditQuery::default()).await, elements_are![
+            matches_pattern!(AuditEntry {
+                user: eq(audit::UNAUTHENTICATED_USER),
+                operation: eq("StorePeerDescriptor"),
+                resource_id: eq(Id::from(peer_id)),
+                before: none(),
+                after: some(contains_substring("TestPeer")),
+            }),
+            matches_pattern!(AuditEntry {
+                operation: eq("GeneratePeerSetup"),
+                resource_id: eq(IntoId::<IssuedPeerSetup>::into_id(nonce)),
+                before: none(),
+                after: some(contains_substring("outstanding")),
+            }),
+            matches_pattern!(AuditEntry {
+                operation: eq("DeletePeerDescriptor"),
+                before: some(contains_substring("TestPeer")),
+                after: none(),
+            }),
+        ])?;
+
         Ok(())
     }
 
@@ -426,16 +668,19 @@ mod tests {
         let resources_manager = ResourcesManager::new();
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            AuditLog::new(),
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
+            Arc::new(PeerCertificateAuthority::generate()?),
             Some(registration_client.await),
             PeerManagerFacadeOptions::load(&settings.config)?
         );
 
         let create_peer_reply = testee.store_peer_descriptor(Request::new(
             StorePeerDescriptorRequest {
-                peer: None
+                peer: None,
+                expected_version: None,
             }
         )).await;
 
@@ -469,9 +714,11 @@ mod tests {
         let resources_manager = ResourcesManager::new();
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            AuditLog::new(),
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
+            Arc::new(PeerCertificateAuthority::generate()?),
             Some(registration_client.await),
             PeerManagerFacadeOptions::load(&settings.config)?
         );
@@ -502,4 +749,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn watch_peer_descriptors_should_stream_changes() -> Result<()> {
+        use opendut_carl_api::carl::WatchEvent;
+        use tokio_stream::StreamExt;
+
+        let settings = crate::settings::load_defaults()?;
+
+        let resources_manager = ResourcesManager::new();
+        let testee = PeerManagerFacade::new(
+            Arc::clone(&resources_manager),
+            AuditLog::new(),
+            Vpn::Disabled,
+            Url::parse("https://example.com:1234").unwrap(),
+            get_cert(),
+            Arc::new(PeerCertificateAuthority::generate()?),
+            None,
+            PeerManagerFacadeOptions::load(&settings.config)?
+        );
+
+        let mut events = testee.watch_peer_descriptors(Request::new(
+            WatchPeerDescriptorsRequest {}
+        )).await?.into_inner();
+
+        let peer_id = PeerId::random();
+        let peer_descriptor = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("WatchedPeer").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: Some(NetworkInterfaceName::try_from("br-opendut-1").unwrap()),
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        };
+
+        testee.store_peer_descriptor(Request::new(
+            StorePeerDescriptorRequest {
+                peer: Some(Clone::clone(&peer_descriptor).into()),
+                expected_version: None,
+            }
+        )).await?;
+        testee.delete_peer_descriptor(Request::new(
+            DeletePeerDescriptorRequest {
+                peer_id: Some(peer_id.into()),
+            }
+        )).await?;
+
+        let event = WatchEvent::try_from(events.next().await.unwrap()?)?;
+        verify_that!(event, eq(WatchEvent::Created { id: peer_id, resource: peer_descriptor }))?;
+
+        let event = WatchEvent::<PeerId, PeerDescriptor>::try_from(events.next().await.unwrap()?)?;
+        verify_that!(event, eq(WatchEvent::Deleted { id: peer_id }))?;
+
+        Ok(())
+    }
 }
@@ -2,6 +2,8 @@ use std::net::IpAddr;
 use std::ops::Not;
 use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
 
 use futures::StreamExt;
 use tokio_stream::Stream;
@@ -12,20 +14,39 @@ use tonic_web::CorsGrpcWeb;
 use tracing::{error, info, trace, warn};
 use uuid::Uuid;
 
-use opendut_carl_api::proto::services::peer_messaging_broker::{Downstream, ListPeersRequest, ListPeersResponse, Upstream};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerCertificate, Downstream, downstream, ListPeersRequest, ListPeersResponse, Upstream};
 use opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_broker_server::PeerMessagingBrokerServer;
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
 use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::PeerSetupNonce;
+use opendut_types::util::net::ClientId;
 use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{AuthenticatePeerCertificateParams, ConsumePeerSetupParams, IssuePeerCertificateParams};
+use crate::auth::CurrentUser;
+use crate::auth::mtls::ClientCertificate;
 use crate::peer::broker::{OpenError, PeerMessagingBrokerRef};
+use crate::peer::certificate_authority::{PeerCertificateAuthorityRef, PeerCertificateOptions};
+use crate::projects;
+use crate::projects::ProjectScope;
+use crate::resources::manager::ResourcesManagerRef;
 
 pub struct PeerMessagingBrokerFacade {
     peer_messaging_broker: PeerMessagingBrokerRef,
+    resources_manager: ResourcesManagerRef,
+    certificate_authority: PeerCertificateAuthorityRef,
+    certificate_options: PeerCertificateOptions,
 }
 
 impl PeerMessagingBrokerFacade {
-    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef) -> Self {
-        Self { peer_messaging_broker }
+    pub fn new(
+        peer_messaging_broker: PeerMessagingBrokerRef,
+        resources_manager: ResourcesManagerRef,
+        certificate_authority: PeerCertificateAuthorityRef,
+        certificate_options: PeerCertificateOptions,
+    ) -> Self {
+        Self { peer_messaging_broker, resources_manager, certificate_authority, certificate_options }
     }
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerMessagingBrokerServer<Self>> {
         tonic_web::enable(PeerMessagingBrokerServer::new(self))
@@ -39,11 +60,19 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
 
         trace!("Received request: {}", request.debug_output());
 
+        let scope = projects::scope_of(&request);
         let peers = self.peer_messaging_broker.list_peers().await;
 
-        let peers = peers.into_iter()
-            .map(From::from)
-            .collect::<Vec<_>>();
+        let peers = self.resources_manager.resources(|resources| {
+            peers.into_iter()
+                .filter(|peer_id| match scope {
+                    ProjectScope::All => true,
+                    ProjectScope::Projects(_) => projects::project_of_peer(resources, *peer_id)
+                        .is_some_and(|project| scope.contains(&project)),
+                })
+                .map(From::from)
+                .collect::<Vec<_>>()
+        }).await;
 
         let reply = ListPeersResponse {
             peers,
@@ -63,6 +92,34 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                 Status::invalid_argument(message)
             })?;
 
+        let client_certificate = request.extensions().get::<ClientCertificate>()
+            .ok_or_else(|| {
+                warn!("Peer <{peer_id}> did not present a client certificate.");
+                Status::unauthenticated("Client should authenticate with the client certificate from its setup-string via mutual TLS. Re-run the setup of the peer with a new setup-string")
+            })?;
+
+        let issued_certificate = actions::authenticate_peer_certificate(AuthenticatePeerCertificateParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_id,
+            certificate: client_certificate.0.to_vec(),
+        }).await
+            .map_err(|cause| Status::permission_denied(cause.to_string()))?;
+
+        let setup_nonce = extract_setup_nonce(request.metadata())
+            .map_err(|message| {
+                warn!("Error while parsing setup-string nonce from client request of peer <{peer_id}>: {message}");
+                Status::unauthenticated(message)
+            })?;
+
+        actions::consume_peer_setup(ConsumePeerSetupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_id,
+            nonce: setup_nonce,
+            client_id: extract_client_id(&request),
+            certificate_fingerprint: Clone::clone(&issued_certificate.fingerprint),
+        }).await
+            .map_err(|cause| Status::permission_denied(cause.to_string()))?;
+
         let remote_host = extract_remote_host(request.metadata())
             .map_err(|message| {
                 warn!("Error while parsing remote host address from client request: {message}");
@@ -73,8 +130,19 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
         let (tx_inbound, rx_outbound) = self.peer_messaging_broker.open(peer_id, remote_host).await
             .map_err(|cause| match cause {
                 OpenError::PeerAlreadyConnected { .. } => Status::aborted(cause.to_string()),
+                OpenError::Internal { .. } => Status::internal(cause.to_string()),
             })?;
 
+        let certificate_renewal = tokio::spawn(renew_peer_certificate_before_expiry(PeerCertificateRenewal {
+            peer_id,
+            expires_at: issued_certificate.expires_at,
+            fingerprint: Clone::clone(&issued_certificate.fingerprint),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            resources_manager: Arc::clone(&self.resources_manager),
+            certificate_authority: Arc::clone(&self.certificate_authority),
+            options: Clone::clone(&self.certificate_options),
+        }));
+
         let peer_messaging_broker = Clone::clone(&self.peer_messaging_broker);
 
         let mut inbound = request.into_inner();
@@ -97,6 +165,8 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                 }
             }
 
+            certificate_renewal.abort();
+
             if let Err(cause) = peer_messaging_broker.remove_peer(peer_id).await {
                 error!("Failed to removed peer <{peer_id}>:\n  {cause}");
             }
@@ -114,6 +184,50 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
     }
 }
 
+struct PeerCertificateRenewal {
+    peer_id: PeerId,
+    expires_at: SystemTime,
+    fingerprint: String,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    resources_manager: ResourcesManagerRef,
+    certificate_authority: PeerCertificateAuthorityRef,
+    options: PeerCertificateOptions,
+}
+
+/// Issues a new client certificate for the connected peer shortly before its current one expires and sends it to the peer.
+async fn renew_peer_certificate_before_expiry(mut renewal: PeerCertificateRenewal) {
+    let peer_id = renewal.peer_id;
+    loop {
+        let renew_at = renewal.expires_at.checked_sub(renewal.options.renewal_before_expiry)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        tokio::time::sleep(renew_at.duration_since(SystemTime::now()).unwrap_or_default()).await;
+
+        let result = actions::issue_peer_certificate(IssuePeerCertificateParams {
+            resources_manager: Arc::clone(&renewal.resources_manager),
+            certificate_authority: Arc::clone(&renewal.certificate_authority),
+            peer_id,
+            validity: renewal.options.validity,
+        }).await;
+        let Ok((certificate, issued)) = result else { return }; //error is logged by the action
+
+        let message = downstream::Message::ApplyPeerCertificate(ApplyPeerCertificate {
+            certificate: Some(certificate.into()),
+        });
+        if let Err(cause) = renewal.peer_messaging_broker.send_to_peer(peer_id, message).await {
+            warn!("Failed to send renewed client certificate <{}> to peer <{peer_id}>:\n  {cause}", issued.id);
+            return;
+        }
+        info!("Sent renewed client certificate <{}> to peer <{peer_id}>.", issued.id);
+
+        renewal.resources_manager.resources_mut(|resources| {
+            actions::rebind_consumed_peer_setups(resources, peer_id, &renewal.fingerprint, &issued.fingerprint)
+        }).await
+        .unwrap_or_else(|cause| error!("Failed to rebind the setup-strings of peer <{peer_id}> to its renewed client certificate <{}>:\n  {cause}", issued.id));
+
+        renewal.expires_at = issued.expires_at;
+        renewal.fingerprint = issued.fingerprint;
+    }
+}
 
 fn extract_peer_id(metadata: &MetadataMap) -> Result<PeerId, UserError> {
     let peer_id = PeerId::from(
@@ -128,6 +242,26 @@ fn extract_peer_id(metadata: &MetadataMap) -> Result<PeerId, UserError> {
     Ok(peer_id)
 }
 
+fn extract_setup_nonce(metadata: &MetadataMap) -> Result<PeerSetupNonce, UserError> {
+    let nonce = PeerSetupNonce::from(
+        Uuid::parse_str(
+            metadata
+                .get("setup-nonce")
+                .ok_or("Client should have sent the nonce of its setup-string. Re-run the setup of the peer with a new setup-string")?
+                .to_str()
+                .map_err(|_| "Setup-string nonce should be a valid string")?
+        ).map_err(|_| "Setup-string nonce should be a valid UUID")?
+    );
+    Ok(nonce)
+}
+
+/// The OIDC client, which the peer authenticated as (`azp` claim of its access token). `None`, if authentication is disabled.
+fn extract_client_id<T>(request: &Request<T>) -> Option<ClientId> {
+    request.extensions().get::<CurrentUser>()
+        .and_then(|user| user.claims.authorized_party())
+        .map(|client_id| ClientId(client_id.to_string()))
+}
+
 fn extract_remote_host(metadata: &MetadataMap) -> Result<IpAddr, UserError> {
     let remote_host = IpAddr::from_str(
         metadata
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::reservation_manager::{create_reservation_response, CreateReservationRequest, CreateReservationResponse, CreateReservationSuccess, delete_reservation_response, DeleteReservationRequest, DeleteReservationResponse, DeleteReservationSuccess, ListReservationsRequest, ListReservationsResponse};
+use opendut_carl_api::proto::services::reservation_manager::reservation_manager_server::{ReservationManager as ReservationManagerService, ReservationManagerServer};
+use opendut_types::project::ProjectName;
+use opendut_types::reservation::{Reservation, ReservationId};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{CreateReservationParams, DeleteReservationParams, ListReservationsParams};
+use crate::audit;
+use crate::audit::AuditLogRef;
+use crate::grpc::extract;
+use crate::projects;
+use crate::projects::ProjectScope;
+use crate::resources::Resources;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ReservationManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
+}
+
+impl ReservationManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, audit_log: AuditLogRef) -> Self {
+        Self { resources_manager, audit_log }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ReservationManagerServer<Self>> {
+        tonic_web::enable(ReservationManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl ReservationManagerService for ReservationManagerFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn create_reservation(&self, request: Request<CreateReservationRequest>) -> Result<Response<CreateReservationResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let scope = projects::scope_of(&request);
+        let request = request.into_inner();
+        let reservation: Reservation = extract!(request.reservation)?;
+        let reservation = Reservation { user: Clone::clone(&user), ..reservation };
+        self.resources_manager.resources(|resources| {
+            projects_of_reservation(resources, &reservation)
+                .try_for_each(|project| scope.require(&project))
+        }).await?;
+        let previous_reservation = self.resources_manager.get::<Reservation>(reservation.id).await;
+
+        let result = actions::create_reservation(CreateReservationParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            reservation: Clone::clone(&reservation),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateReservationResponse {
+                    reply: Some(create_reservation_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(reservation_id) => {
+                self.audit_log.record(user, "CreateReservation", reservation_id, previous_reservation.as_ref(), Some(&reservation)).await;
+                Ok(Response::new(CreateReservationResponse {
+                    reply: Some(create_reservation_response::Reply::Success(
+                        CreateReservationSuccess {
+                            reservation: Some(reservation.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_reservation(&self, request: Request<DeleteReservationRequest>) -> Result<Response<DeleteReservationResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let reservation_id: ReservationId = extract!(request.reservation_id)?;
+
+        let result = actions::delete_reservation(DeleteReservationParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            reservation_id,
+            user: Clone::clone(&user),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteReservationResponse {
+                    reply: Some(delete_reservation_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(reservation) => {
+                self.audit_log.record(user, "DeleteReservation", reservation_id, Some(&reservation), None).await;
+                Ok(Response::new(DeleteReservationResponse {
+                    reply: Some(delete_reservation_response::Reply::Success(
+                        DeleteReservationSuccess {
+                            reservation: Some(reservation.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_reservations(&self, request: Request<ListReservationsRequest>) -> Result<Response<ListReservationsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let scope = projects::scope_of(&request);
+        let reservations = actions::list_reservations(ListReservationsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+        let reservations = self.resources_manager.resources(|resources| {
+            reservations.into_iter()
+                .filter(|reservation| is_reservation_in_scope(resources, reservation, &scope))
+                .collect::<Vec<_>>()
+        }).await;
+
+        Ok(Response::new(ListReservationsResponse {
+            reservations: reservations.into_iter().map(From::from).collect(),
+        }))
+    }
+}
+
+/// Returns the projects of the reserved peers and devices.
+fn projects_of_reservation<'a>(resources: &'a Resources, reservation: &'a Reservation) -> impl Iterator<Item=ProjectName> + 'a {
+    let peer_projects = reservation.peers.iter()
+        .filter_map(|peer_id| projects::project_of_peer(resources, *peer_id));
+    let device_projects = reservation.devices.iter()
+        .filter_map(|device_id| projects::project_of_device(resources, *device_id));
+    peer_projects.chain(device_projects)
+}
+
+fn is_reservation_in_scope(resources: &Resources, reservation: &Reservation, scope: &ProjectScope) -> bool {
+    projects_of_reservation(resources, reservation)
+        .all(|project| scope.contains(&project))
+}
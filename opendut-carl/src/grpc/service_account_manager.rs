@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::service_account_manager::{create_service_account_response, CreateServiceAccountRequest, CreateServiceAccountResponse, CreateServiceAccountSuccess, delete_service_account_response, DeleteServiceAccountRequest, DeleteServiceAccountResponse, DeleteServiceAccountSuccess, issue_api_token_response, IssueApiTokenRequest, IssueApiTokenResponse, IssueApiTokenSuccess, ListApiTokensRequest, ListApiTokensResponse, ListServiceAccountsRequest, ListServiceAccountsResponse, revoke_api_token_response, RevokeApiTokenRequest, RevokeApiTokenResponse, RevokeApiTokenSuccess};
+use opendut_carl_api::proto::services::service_account_manager::service_account_manager_server::{ServiceAccountManager as ServiceAccountManagerService, ServiceAccountManagerServer};
+use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccount, ServiceAccountId};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{CreateServiceAccountParams, DeleteServiceAccountParams, IssueApiTokenParams, ListApiTokensParams, ListServiceAccountsParams, RevokeApiTokenParams};
+use crate::audit;
+use crate::audit::AuditLogRef;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ServiceAccountManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
+}
+
+impl ServiceAccountManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, audit_log: AuditLogRef) -> Self {
+        Self { resources_manager, audit_log }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ServiceAccountManagerServer<Self>> {
+        tonic_web::enable(ServiceAccountManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl ServiceAccountManagerService for ServiceAccountManagerFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn create_service_account(&self, request: Request<CreateServiceAccountRequest>) -> Result<Response<CreateServiceAccountResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let service_account: ServiceAccount = extract!(request.service_account)?;
+        let service_account = ServiceAccount { created_by: Clone::clone(&user), ..service_account };
+        let previous_service_account = self.resources_manager.get::<ServiceAccount>(service_account.id).await;
+
+        let result = actions::create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            service_account: Clone::clone(&service_account),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateServiceAccountResponse {
+                    reply: Some(create_service_account_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(service_account_id) => {
+                self.audit_log.record(user, "CreateServiceAccount", service_account_id, previous_service_account.as_ref(), Some(&service_account)).await;
+                Ok(Response::new(CreateServiceAccountResponse {
+                    reply: Some(create_service_account_response::Reply::Success(
+                        CreateServiceAccountSuccess {
+                            service_account: Some(service_account.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_service_account(&self, request: Request<DeleteServiceAccountRequest>) -> Result<Response<DeleteServiceAccountResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let service_account_id: ServiceAccountId = extract!(request.service_account_id)?;
+
+        let result = actions::delete_service_account(DeleteServiceAccountParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            service_account_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteServiceAccountResponse {
+                    reply: Some(delete_service_account_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(service_account) => {
+                self.audit_log.record(user, "DeleteServiceAccount", service_account_id, Some(&service_account), None).await;
+                Ok(Response::new(DeleteServiceAccountResponse {
+                    reply: Some(delete_service_account_response::Reply::Success(
+                        DeleteServiceAccountSuccess {
+                            service_account: Some(service_account.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_service_accounts(&self, request: Request<ListServiceAccountsRequest>) -> Result<Response<ListServiceAccountsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let service_accounts = actions::list_service_accounts(ListServiceAccountsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        Ok(Response::new(ListServiceAccountsResponse {
+            service_accounts: service_accounts.into_iter().map(From::from).collect(),
+        }))
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn issue_api_token(&self, request: Request<IssueApiTokenRequest>) -> Result<Response<IssueApiTokenResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let api_token: ApiToken = extract!(request.api_token)?;
+
+        let result = actions::issue_api_token(IssueApiTokenParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            api_token,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(IssueApiTokenResponse {
+                    reply: Some(issue_api_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(issued) => {
+                self.audit_log.record(user, "IssueApiToken", issued.api_token.id, None, Some(&issued.api_token)).await;
+                Ok(Response::new(IssueApiTokenResponse {
+                    reply: Some(issue_api_token_response::Reply::Success(
+                        IssueApiTokenSuccess {
+                            api_token: Some(issued.api_token.into()),
+                            secret: issued.secret,
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn revoke_api_token(&self, request: Request<RevokeApiTokenRequest>) -> Result<Response<RevokeApiTokenResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let api_token_id: ApiTokenId = extract!(request.api_token_id)?;
+
+        let result = actions::revoke_api_token(RevokeApiTokenParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            api_token_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RevokeApiTokenResponse {
+                    reply: Some(revoke_api_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(api_token) => {
+                self.audit_log.record(user, "RevokeApiToken", api_token_id, Some(&api_token), None).await;
+                Ok(Response::new(RevokeApiTokenResponse {
+                    reply: Some(revoke_api_token_response::Reply::Success(
+                        RevokeApiTokenSuccess {
+                            api_token: Some(api_token.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_api_tokens(&self, request: Request<ListApiTokensRequest>) -> Result<Response<ListApiTokensResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let request = request.into_inner();
+        let service_account_id = request.service_account_id
+            .map(ServiceAccountId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'service_account_id' is not valid: {cause}")))?;
+
+        let api_tokens = actions::list_api_tokens(ListApiTokensParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            service_account_id,
+        }).await;
+
+        Ok(Response::new(ListApiTokensResponse {
+            api_tokens: api_tokens.into_iter().map(From::from).collect(),
+        }))
+    }
+}
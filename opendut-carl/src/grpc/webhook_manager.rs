@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::carl::webhook::ListWebhookDeliveriesError;
+use opendut_carl_api::proto::services::webhook_manager::{create_webhook_response, CreateWebhookRequest, CreateWebhookResponse, CreateWebhookSuccess, delete_webhook_response, DeleteWebhookRequest, DeleteWebhookResponse, DeleteWebhookSuccess, list_webhook_deliveries_response, ListWebhookDeliveriesRequest, ListWebhookDeliveriesResponse, ListWebhookDeliveriesSuccess, ListWebhooksRequest, ListWebhooksResponse};
+use opendut_carl_api::proto::services::webhook_manager::webhook_manager_server::{WebhookManager as WebhookManagerService, WebhookManagerServer};
+use opendut_types::webhook::{Webhook, WebhookId};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{CreateWebhookParams, DeleteWebhookParams, ListWebhooksParams};
+use crate::audit;
+use crate::audit::AuditLogRef;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::webhook::WebhookDispatcherRef;
+
+pub struct WebhookManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
+    webhook_dispatcher: WebhookDispatcherRef,
+}
+
+impl WebhookManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, audit_log: AuditLogRef, webhook_dispatcher: WebhookDispatcherRef) -> Self {
+        Self { resources_manager, audit_log, webhook_dispatcher }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<WebhookManagerServer<Self>> {
+        tonic_web::enable(WebhookManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl WebhookManagerService for WebhookManagerFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn create_webhook(&self, request: Request<CreateWebhookRequest>) -> Result<Response<CreateWebhookResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let webhook: Webhook = extract!(request.webhook)?;
+        let webhook = Webhook { created_by: Clone::clone(&user), ..webhook };
+
+        let result = actions::create_webhook(CreateWebhookParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            webhook,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateWebhookResponse {
+                    reply: Some(create_webhook_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(webhook) => {
+                self.audit_log.record(user, "CreateWebhook", webhook.id, None, Some(&webhook)).await;
+                Ok(Response::new(CreateWebhookResponse {
+                    reply: Some(create_webhook_response::Reply::Success(
+                        CreateWebhookSuccess {
+                            webhook: Some(webhook.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_webhook(&self, request: Request<DeleteWebhookRequest>) -> Result<Response<DeleteWebhookResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let user = audit::user_of(&request);
+        let request = request.into_inner();
+        let webhook_id: WebhookId = extract!(request.webhook_id)?;
+
+        let result = actions::delete_webhook(DeleteWebhookParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            webhook_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteWebhookResponse {
+                    reply: Some(delete_webhook_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(webhook) => {
+                self.webhook_dispatcher.forget(webhook_id).await;
+                self.audit_log.record(user, "DeleteWebhook", webhook_id, Some(&webhook), None).await;
+                let webhook = Webhook { secret: String::new(), ..webhook };
+                Ok(Response::new(DeleteWebhookResponse {
+                    reply: Some(delete_webhook_response::Reply::Success(
+                        DeleteWebhookSuccess {
+                            webhook: Some(webhook.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_webhooks(&self, request: Request<ListWebhooksRequest>) -> Result<Response<ListWebhooksResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let webhooks = actions::list_webhooks(ListWebhooksParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        Ok(Response::new(ListWebhooksResponse {
+            webhooks: webhooks.into_iter().map(From::from).collect(),
+        }))
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_webhook_deliveries(&self, request: Request<ListWebhookDeliveriesRequest>) -> Result<Response<ListWebhookDeliveriesResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+
+        let request = request.into_inner();
+        let webhook_id: WebhookId = extract!(request.webhook_id)?;
+
+        if self.resources_manager.get::<Webhook>(webhook_id).await.is_none() {
+            let error = ListWebhookDeliveriesError::WebhookNotFound { webhook_id };
+            return Ok(Response::new(ListWebhookDeliveriesResponse {
+                reply: Some(list_webhook_deliveries_response::Reply::Failure(error.into()))
+            }));
+        }
+
+        let deliveries = self.webhook_dispatcher.list_deliveries(webhook_id, request.limit).await;
+
+        Ok(Response::new(ListWebhookDeliveriesResponse {
+            reply: Some(list_webhook_deliveries_response::Reply::Success(
+                ListWebhookDeliveriesSuccess {
+                    deliveries: deliveries.into_iter().map(From::from).collect(),
+                }
+            ))
+        }))
+    }
+}
@@ -1,2 +1,3 @@
 pub mod state;
-pub mod router;
\ No newline at end of file
+pub mod rest;
+pub mod router;
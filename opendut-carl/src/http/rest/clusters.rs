@@ -0,0 +1,257 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use axum::response::IntoResponse;
+use const_format::concatcp;
+
+use opendut_carl_api::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError, UpdateClusterConfigurationError};
+use opendut_carl_api::proto::services::cluster_manager::*;
+use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server::ClusterManager as ClusterManagerService;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+
+use crate::http::rest::{ApiError, etag, expected_version, JsonBody, Operation, PathParam, reply, require_matching_id, RestState};
+
+const SERVICE: &str = "/opendut.carl.services.cluster_manager.ClusterManager";
+
+pub const LIST_CLUSTER_CONFIGURATIONS: Operation = Operation {
+    method: "get",
+    path: "/cluster-configurations",
+    grpc_method: concatcp!(SERVICE, "/ListClusterConfigurations"),
+    summary: "List all cluster configurations.",
+    request: None,
+    response: Some("ClusterConfiguration[]"),
+};
+pub const CREATE_CLUSTER_CONFIGURATION: Operation = Operation {
+    method: "post",
+    path: "/cluster-configurations",
+    grpc_method: concatcp!(SERVICE, "/CreateClusterConfiguration"),
+    summary: "Create a cluster configuration.",
+    request: Some("ClusterConfiguration"),
+    response: Some("ClusterId"),
+};
+pub const GET_CLUSTER_CONFIGURATION: Operation = Operation {
+    method: "get",
+    path: "/cluster-configurations/:cluster_id",
+    grpc_method: concatcp!(SERVICE, "/GetClusterConfiguration"),
+    summary: "Get a cluster configuration. Its version is returned in the header 'ETag'.",
+    request: None,
+    response: Some("ClusterConfiguration"),
+};
+pub const UPDATE_CLUSTER_CONFIGURATION: Operation = Operation {
+    method: "put",
+    path: "/cluster-configurations/:cluster_id",
+    grpc_method: concatcp!(SERVICE, "/UpdateClusterConfiguration"),
+    summary: "Update a cluster configuration. With the header 'If-Match', it is only updated, if it is still in the given version.",
+    request: Some("ClusterConfiguration"),
+    response: Some("ClusterId"),
+};
+pub const DELETE_CLUSTER_CONFIGURATION: Operation = Operation {
+    method: "delete",
+    path: "/cluster-configurations/:cluster_id",
+    grpc_method: concatcp!(SERVICE, "/DeleteClusterConfiguration"),
+    summary: "Delete a cluster configuration.",
+    request: None,
+    response: Some("ClusterConfiguration"),
+};
+pub const LIST_CLUSTER_DEPLOYMENTS: Operation = Operation {
+    method: "get",
+    path: "/cluster-deployments",
+    grpc_method: concatcp!(SERVICE, "/ListClusterDeployments"),
+    summary: "List all cluster deployments.",
+    request: None,
+    response: Some("ClusterDeployment[]"),
+};
+pub const STORE_CLUSTER_DEPLOYMENT: Operation = Operation {
+    method: "put",
+    path: "/cluster-deployments/:cluster_id",
+    grpc_method: concatcp!(SERVICE, "/StoreClusterDeployment"),
+    summary: "Deploy a cluster.",
+    request: Some("ClusterDeployment"),
+    response: Some("ClusterId"),
+};
+pub const DELETE_CLUSTER_DEPLOYMENT: Operation = Operation {
+    method: "delete",
+    path: "/cluster-deployments/:cluster_id",
+    grpc_method: concatcp!(SERVICE, "/DeleteClusterDeployment"),
+    summary: "Undeploy a cluster.",
+    request: None,
+    response: Some("ClusterDeployment"),
+};
+
+pub async fn list_cluster_configurations(State(state): State<RestState>, headers: HeaderMap) -> Result<Json<Vec<ClusterConfiguration>>, ApiError> {
+    let request = state.request(&headers, &LIST_CLUSTER_CONFIGURATIONS, ListClusterConfigurationsRequest {}).await?;
+    let response = state.cluster_manager.list_cluster_configurations(request).await?.into_inner();
+
+    match reply(response.result)? {
+        list_cluster_configurations_response::Result::Failure(_) => Err(ApiError::missing_reply()),
+        list_cluster_configurations_response::Result::Success(success) => {
+            let configurations = success.configurations.into_iter()
+                .map(ClusterConfiguration::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(ApiError::invalid_reply)?;
+            Ok(Json(configurations))
+        }
+    }
+}
+
+pub async fn create_cluster_configuration(State(state): State<RestState>, headers: HeaderMap, JsonBody(configuration): JsonBody<ClusterConfiguration>) -> Result<impl IntoResponse, ApiError> {
+    let cluster_id = configuration.id;
+
+    let request = state.request(&headers, &CREATE_CLUSTER_CONFIGURATION, CreateClusterConfigurationRequest {
+        cluster_configuration: Some(configuration.into()),
+        expected_version: None,
+    }).await?;
+    let response = state.cluster_manager.create_cluster_configuration(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        create_cluster_configuration_response::Reply::Failure(failure) => Err(CreateClusterConfigurationError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        create_cluster_configuration_response::Reply::Success(_) => Ok((StatusCode::CREATED, Json(cluster_id))),
+    }
+}
+
+pub async fn get_cluster_configuration(State(state): State<RestState>, headers: HeaderMap, PathParam(cluster_id): PathParam<ClusterId>) -> Result<impl IntoResponse, ApiError> {
+    let request = state.request(&headers, &GET_CLUSTER_CONFIGURATION, GetClusterConfigurationRequest { id: Some(cluster_id.into()) }).await?;
+    let response = state.cluster_manager.get_cluster_configuration(request).await?.into_inner();
+
+    match reply(response.result)? {
+        get_cluster_configuration_response::Result::Failure(_) => {
+            Err(ApiError::new(StatusCode::NOT_FOUND, format!("Cluster configuration <{cluster_id}> could not be found.")))
+        }
+        get_cluster_configuration_response::Result::Success(success) => {
+            let configuration = ClusterConfiguration::try_from(success.configuration.ok_or_else(ApiError::missing_reply)?)
+                .map_err(ApiError::invalid_reply)?;
+            Ok((etag(success.version), Json(configuration)))
+        }
+    }
+}
+
+pub async fn update_cluster_configuration(State(state): State<RestState>, headers: HeaderMap, PathParam(cluster_id): PathParam<ClusterId>, JsonBody(configuration): JsonBody<ClusterConfiguration>) -> Result<Json<ClusterId>, ApiError> {
+    require_matching_id(cluster_id, configuration.id)?;
+    let expected_version = expected_version(&headers)?;
+
+    let request = state.request(&headers, &UPDATE_CLUSTER_CONFIGURATION, UpdateClusterConfigurationRequest {
+        cluster_configuration: Some(configuration.into()),
+        expected_version: expected_version.map(u64::from),
+    }).await?;
+    let response = state.cluster_manager.update_cluster_configuration(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        update_cluster_configuration_response::Reply::Failure(failure) => Err(UpdateClusterConfigurationError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        update_cluster_configuration_response::Reply::Success(_) => Ok(Json(cluster_id)),
+    }
+}
+
+pub async fn delete_cluster_configuration(State(state): State<RestState>, headers: HeaderMap, PathParam(cluster_id): PathParam<ClusterId>) -> Result<Json<ClusterConfiguration>, ApiError> {
+    let request = state.request(&headers, &DELETE_CLUSTER_CONFIGURATION, DeleteClusterConfigurationRequest { cluster_id: Some(cluster_id.into()) }).await?;
+    let response = state.cluster_manager.delete_cluster_configuration(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        delete_cluster_configuration_response::Reply::Failure(failure) => Err(DeleteClusterConfigurationError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        delete_cluster_configuration_response::Reply::Success(success) => {
+            let configuration = ClusterConfiguration::try_from(success.cluster_configuration.ok_or_else(ApiError::missing_reply)?)
+                .map_err(ApiError::invalid_reply)?;
+            Ok(Json(configuration))
+        }
+    }
+}
+
+pub async fn list_cluster_deployments(State(state): State<RestState>, headers: HeaderMap) -> Result<Json<Vec<ClusterDeployment>>, ApiError> {
+    let request = state.request(&headers, &LIST_CLUSTER_DEPLOYMENTS, ListClusterDeploymentsRequest {}).await?;
+    let response = state.cluster_manager.list_cluster_deployments(request).await?.into_inner();
+
+    match reply(response.result)? {
+        list_cluster_deployments_response::Result::Failure(_) => Err(ApiError::missing_reply()),
+        list_cluster_deployments_response::Result::Success(success) => {
+            let deployments = success.deployments.into_iter()
+                .map(ClusterDeployment::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(ApiError::invalid_reply)?;
+            Ok(Json(deployments))
+        }
+    }
+}
+
+pub async fn store_cluster_deployment(State(state): State<RestState>, headers: HeaderMap, PathParam(cluster_id): PathParam<ClusterId>, JsonBody(deployment): JsonBody<ClusterDeployment>) -> Result<Json<ClusterId>, ApiError> {
+    require_matching_id(cluster_id, deployment.id)?;
+
+    let request = state.request(&headers, &STORE_CLUSTER_DEPLOYMENT, StoreClusterDeploymentRequest { cluster_deployment: Some(deployment.into()) }).await?;
+    let response = state.cluster_manager.store_cluster_deployment(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        store_cluster_deployment_response::Reply::Failure(failure) => Err(StoreClusterDeploymentError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        store_cluster_deployment_response::Reply::Success(_) => Ok(Json(cluster_id)),
+    }
+}
+
+pub async fn delete_cluster_deployment(State(state): State<RestState>, headers: HeaderMap, PathParam(cluster_id): PathParam<ClusterId>) -> Result<Json<ClusterDeployment>, ApiError> {
+    let request = state.request(&headers, &DELETE_CLUSTER_DEPLOYMENT, DeleteClusterDeploymentRequest { cluster_id: Some(cluster_id.into()) }).await?;
+    let response = state.cluster_manager.delete_cluster_deployment(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        delete_cluster_deployment_response::Reply::Failure(failure) => Err(DeleteClusterDeploymentError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        delete_cluster_deployment_response::Reply::Success(success) => {
+            let deployment = ClusterDeployment::try_from(success.cluster_deployment.ok_or_else(ApiError::missing_reply)?)
+                .map_err(ApiError::invalid_reply)?;
+            Ok(Json(deployment))
+        }
+    }
+}
+
+impl From<CreateClusterConfigurationError> for ApiError {
+    fn from(error: CreateClusterConfigurationError) -> Self {
+        let status = match error {
+            CreateClusterConfigurationError::ClusterConfigurationAlreadyExists { .. } => StatusCode::CONFLICT,
+            CreateClusterConfigurationError::VersionConflict { .. } => StatusCode::CONFLICT,
+            CreateClusterConfigurationError::IllegalClusterConfiguration { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            CreateClusterConfigurationError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<UpdateClusterConfigurationError> for ApiError {
+    fn from(error: UpdateClusterConfigurationError) -> Self {
+        let status = match error {
+            UpdateClusterConfigurationError::ClusterConfigurationNotFound { .. } => StatusCode::NOT_FOUND,
+            UpdateClusterConfigurationError::IllegalClusterConfiguration { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            UpdateClusterConfigurationError::IllegalClusterState { .. } => StatusCode::CONFLICT,
+            UpdateClusterConfigurationError::VersionConflict { .. } => StatusCode::CONFLICT,
+            UpdateClusterConfigurationError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<DeleteClusterConfigurationError> for ApiError {
+    fn from(error: DeleteClusterConfigurationError) -> Self {
+        let status = match error {
+            DeleteClusterConfigurationError::ClusterConfigurationNotFound { .. } => StatusCode::NOT_FOUND,
+            DeleteClusterConfigurationError::IllegalClusterState { .. } => StatusCode::CONFLICT,
+            DeleteClusterConfigurationError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<StoreClusterDeploymentError> for ApiError {
+    fn from(error: StoreClusterDeploymentError) -> Self {
+        let status = match error {
+            StoreClusterDeploymentError::ClusterConfigurationNotFound { .. } => StatusCode::NOT_FOUND,
+            StoreClusterDeploymentError::IllegalClusterState { .. } => StatusCode::CONFLICT,
+            StoreClusterDeploymentError::Reserved { .. } => StatusCode::CONFLICT,
+            StoreClusterDeploymentError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<DeleteClusterDeploymentError> for ApiError {
+    fn from(error: DeleteClusterDeploymentError) -> Self {
+        let status = match error {
+            DeleteClusterDeploymentError::ClusterDeploymentNotFound { .. } => StatusCode::NOT_FOUND,
+            DeleteClusterDeploymentError::IllegalClusterState { .. } => StatusCode::CONFLICT,
+            DeleteClusterDeploymentError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
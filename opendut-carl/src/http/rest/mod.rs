@@ -0,0 +1,357 @@
+//! HTTP/JSON API of CARL under `/api/v1/`, for clients which cannot use gRPC.
+//!
+//! Each operation corresponds to a gRPC method. Requests are authenticated like calls of that method
+//! and are then passed on to the respective gRPC facade, so both APIs share the same actions, permissions and audit log.
+
+use axum::extract::{FromRequest, FromRequestParts, Path};
+use axum::extract::rejection::{JsonRejection, PathRejection};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::http::header;
+use axum::http::request::Parts;
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use axum::routing::get;
+use serde_json::json;
+
+use opendut_types::proto::ConversionError;
+use opendut_types::resources::Version;
+
+use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer;
+use crate::auth::permission::GrpcMethodPath;
+use crate::grpc::{ClusterManagerFacade, PeerManagerFacade};
+
+pub mod clusters;
+pub mod openapi;
+pub mod peers;
+
+#[derive(Clone)]
+pub struct RestState {
+    pub auth: GrpcAuthenticationLayer,
+    pub peer_manager: PeerManagerFacade,
+    pub cluster_manager: ClusterManagerFacade,
+}
+
+pub fn router<S>(state: RestState) -> Router<S> {
+    Router::new()
+        .route(peers::LIST_PEER_DESCRIPTORS.path, get(peers::list_peer_descriptors))
+        .route(peers::GET_PEER_DESCRIPTOR.path, get(peers::get_peer_descriptor).put(peers::store_peer_descriptor).delete(peers::delete_peer_descriptor))
+        .route(peers::LIST_DEVICES.path, get(peers::list_devices))
+        .route(clusters::LIST_CLUSTER_CONFIGURATIONS.path, get(clusters::list_cluster_configurations).post(clusters::create_cluster_configuration))
+        .route(clusters::GET_CLUSTER_CONFIGURATION.path, get(clusters::get_cluster_configuration).put(clusters::update_cluster_configuration).delete(clusters::delete_cluster_configuration))
+        .route(clusters::LIST_CLUSTER_DEPLOYMENTS.path, get(clusters::list_cluster_deployments))
+        .route(clusters::STORE_CLUSTER_DEPLOYMENT.path, axum::routing::put(clusters::store_cluster_deployment).delete(clusters::delete_cluster_deployment))
+        .route(openapi::PATH, get(openapi::document))
+        .with_state(state)
+}
+
+/// Operation of the REST API. Used for routing, authentication and the OpenAPI document.
+pub struct Operation {
+    pub method: &'static str,
+    /// Path relative to `/api/v1`, with parameters written as `:name`.
+    pub path: &'static str,
+    /// Path of the gRPC method, whose permissions apply to this operation.
+    pub grpc_method: &'static str,
+    pub summary: &'static str,
+    /// Name of the schema of the request body, if any.
+    pub request: Option<&'static str>,
+    /// Name of the schema of the response body, if any. Lists are marked with a trailing `[]`.
+    pub response: Option<&'static str>,
+}
+
+impl RestState {
+    /// Authenticates the request like a call of the operation's gRPC method and wraps the message into a gRPC request.
+    async fn request<M>(&self, headers: &HeaderMap, operation: &Operation, message: M) -> Result<tonic::Request<M>, ApiError> {
+        let mut request = tonic::Request::new(());
+        *request.metadata_mut() = tonic::metadata::MetadataMap::from_headers(Clone::clone(headers));
+        request.extensions_mut().insert(GrpcMethodPath(operation.grpc_method.to_owned()));
+
+        let request = Clone::clone(&self.auth).auth_interceptor(request).await?;
+        let (metadata, extensions, ()) = request.into_parts();
+        Ok(tonic::Request::from_parts(metadata, extensions, message))
+    }
+}
+
+/// Error response of the REST API, with a JSON body of the form `{ "error": "<message>" }`.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+    fn missing_reply() -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "CARL says, the request could not be processed.")
+    }
+    /// Reply of a gRPC facade, which cannot be converted. Conversions of the client's request are rejected by
+    /// [`JsonBody`], [`PathParam`] or the facades themselves, which answer them with [`tonic::Code::InvalidArgument`].
+    fn invalid_reply(error: ConversionError) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, format!("CARL replied with an invalid message: {error}"))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+impl From<tonic::Status> for ApiError {
+    fn from(status: tonic::Status) -> Self {
+        let code = match status.code() {
+            tonic::Code::InvalidArgument => StatusCode::BAD_REQUEST,
+            tonic::Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+            tonic::Code::PermissionDenied => StatusCode::FORBIDDEN,
+            tonic::Code::NotFound => StatusCode::NOT_FOUND,
+            tonic::Code::AlreadyExists | tonic::Code::Aborted => StatusCode::CONFLICT,
+            tonic::Code::FailedPrecondition => StatusCode::PRECONDITION_FAILED,
+            tonic::Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self::new(code, status.message())
+    }
+}
+
+/// JSON request body, which is rejected with an [`ApiError`] instead of axum's plain text rejection.
+pub struct JsonBody<T>(pub T);
+
+#[axum::async_trait]
+impl<S, B, T> FromRequest<S, B> for JsonBody<T>
+where
+    Json<T>: FromRequest<S, B, Rejection=JsonRejection>,
+    S: Send + Sync,
+    B: Send + 'static,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: axum::http::Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state).await
+            .map_err(|rejection| ApiError::new(rejection.status(), rejection.body_text()))?;
+        Ok(Self(value))
+    }
+}
+
+/// Path parameters, which are rejected with an [`ApiError`] instead of axum's plain text rejection.
+pub struct PathParam<T>(pub T);
+
+#[axum::async_trait]
+impl<S, T> FromRequestParts<S> for PathParam<T>
+where
+    Path<T>: FromRequestParts<S, Rejection=PathRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await
+            .map_err(|rejection| ApiError::new(rejection.status(), rejection.body_text()))?;
+        Ok(Self(value))
+    }
+}
+
+/// Returns the reply of a gRPC response, which is always set by the facades.
+fn reply<R>(reply: Option<R>) -> Result<R, ApiError> {
+    reply.ok_or_else(ApiError::missing_reply)
+}
+
+/// Checks that the id in the path matches the id of the resource in the body.
+fn require_matching_id<I: PartialEq + std::fmt::Display>(path: I, body: I) -> Result<(), ApiError> {
+    if path == body {
+        Ok(())
+    } else {
+        Err(ApiError::new(StatusCode::BAD_REQUEST, format!("Id <{body}> in body does not match id <{path}> in path.")))
+    }
+}
+
+/// Parses the `If-Match` header, with which a resource is only changed, if it is still in the version returned as `ETag`.
+fn expected_version(headers: &HeaderMap) -> Result<Option<Version>, ApiError> {
+    headers.get(header::IF_MATCH)
+        .map(|value| {
+            value.to_str().ok()
+                .map(|value| value.trim().trim_start_matches("W/").trim_matches('"'))
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Version::from)
+                .ok_or_else(|| ApiError::new(StatusCode::BAD_REQUEST, "Header 'If-Match' must contain a version, as returned in the header 'ETag'."))
+        })
+        .transpose()
+}
+
+fn etag(version: u64) -> [(header::HeaderName, HeaderValue); 1] {
+    let value = HeaderValue::from_str(&format!("\"{version}\"")).expect("Quoted number should be a valid header value.");
+    [(header::ETAG, value)]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::Request;
+    use googletest::prelude::*;
+    use pem::Pem;
+    use tower::ServiceExt;
+
+    use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::project::ProjectName;
+
+    use crate::audit::AuditLog;
+    use crate::cluster::manager::{ClusterManager, ClusterManagerOptions};
+    use crate::grpc::PeerManagerFacadeOptions;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::peer::certificate_authority::PeerCertificateAuthority;
+    use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+    use crate::settings;
+    use crate::vpn::Vpn;
+
+    use super::*;
+
+    fn testee(resources_manager: &ResourcesManagerRef) -> anyhow::Result<Router> {
+        let settings = settings::load_defaults()?;
+        let audit_log = AuditLog::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(resources_manager), PeerMessagingBrokerOptions::load(&settings.config)?);
+        let cluster_manager = ClusterManager::new(
+            Arc::clone(resources_manager),
+            peer_messaging_broker,
+            Vpn::Disabled,
+            ClusterManagerOptions::load(&settings.config)?,
+        );
+        Ok(router(RestState {
+            auth: GrpcAuthenticationLayer::AuthDisabled,
+            peer_manager: PeerManagerFacade::new(
+                Arc::clone(resources_manager),
+                Arc::clone(&audit_log),
+                Vpn::Disabled,
+                url::Url::parse("https://carl.opendut.local")?,
+                Pem::new("CERTIFICATE", vec![]),
+                Arc::new(PeerCertificateAuthority::generate()?),
+                None,
+                PeerManagerFacadeOptions::load(&settings.config)?,
+            ),
+            cluster_manager: ClusterManagerFacade::new(cluster_manager, Arc::clone(resources_manager), audit_log),
+        }))
+    }
+
+    async fn send(router: &Router, request: Request<Body>) -> anyhow::Result<(StatusCode, HeaderMap, serde_json::Value)> {
+        let response = Clone::clone(router).oneshot(request).await?;
+        let status = response.status();
+        let headers = Clone::clone(response.headers());
+        let body = axum_server_dual_protocol::hyper::body::to_bytes(response.into_body()).await?;
+        let body = if body.is_empty() { serde_json::Value::Null } else { serde_json::from_slice(&body)? };
+        Ok((status, headers, body))
+    }
+
+    fn peer_descriptor(id: PeerId) -> anyhow::Result<PeerDescriptor> {
+        Ok(PeerDescriptor {
+            id,
+            name: PeerName::try_from("RestPeer")?,
+            location: Some(PeerLocation::try_from("Lab1")?),
+            network: Default::default(),
+            topology: Default::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn should_store_get_and_delete_peer_descriptors() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let testee = testee(&resources_manager)?;
+        let peer = peer_descriptor(PeerId::random())?;
+        let uri = format!("/peers/{}", peer.id);
+
+        let (status, _, _) = send(&testee, Request::get(&uri).body(Body::empty())?).await?;
+        assert_that!(status, eq(StatusCode::NOT_FOUND));
+
+        let (status, _, body) = send(&testee, Request::put(&uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&peer)?))?
+        ).await?;
+        assert_that!(status, eq(StatusCode::OK));
+        assert_that!(body, eq(serde_json::to_value(peer.id)?));
+
+        let (status, headers, body) = send(&testee, Request::get(&uri).body(Body::empty())?).await?;
+        assert_that!(status, eq(StatusCode::OK));
+        assert_that!(body, eq(serde_json::to_value(&peer)?));
+        let version = headers.get(header::ETAG).cloned().unwrap();
+
+        let (status, _, body) = send(&testee, Request::get("/peers").body(Body::empty())?).await?;
+        assert_that!(status, eq(StatusCode::OK));
+        assert_that!(body, eq(serde_json::to_value(vec![&peer])?));
+
+        let (status, _, _) = send(&testee, Request::put(&uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::IF_MATCH, "\"1000\"")
+            .body(Body::from(serde_json::to_vec(&peer)?))?
+        ).await?;
+        assert_that!(status, eq(StatusCode::CONFLICT));
+
+        let (status, _, _) = send(&testee, Request::put(&uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::IF_MATCH, version)
+            .body(Body::from(serde_json::to_vec(&peer)?))?
+        ).await?;
+        assert_that!(status, eq(StatusCode::OK));
+
+        let (status, _, _) = send(&testee, Request::put(format!("/peers/{}", PeerId::random()))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&peer)?))?
+        ).await?;
+        assert_that!(status, eq(StatusCode::BAD_REQUEST));
+
+        let (status, _, _) = send(&testee, Request::delete(&uri).body(Body::empty())?).await?;
+        assert_that!(status, eq(StatusCode::OK));
+        assert_that!(resources_manager.get::<PeerDescriptor>(peer.id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_requests_as_client_errors() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let testee = testee(&resources_manager)?;
+
+        let (status, _, body) = send(&testee, Request::get("/peers/not-a-peer-id").body(Body::empty())?).await?;
+        assert_that!(status, eq(StatusCode::BAD_REQUEST));
+        assert_that!(body["error"].as_str(), some(anything()));
+
+        let peer = peer_descriptor(PeerId::random())?;
+        let mut body = serde_json::to_value(&peer)?;
+        body["name"] = serde_json::Value::from("");
+        let (status, _, body) = send(&testee, Request::put(format!("/peers/{}", peer.id))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&body)?))?
+        ).await?;
+        assert_that!(status, eq(StatusCode::BAD_REQUEST));
+        assert_that!(body["error"].as_str(), some(anything()));
+        assert_that!(resources_manager.get::<PeerDescriptor>(peer.id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_route_every_documented_operation() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let testee = testee(&resources_manager)?;
+
+        for operation in openapi::OPERATIONS {
+            let uri = operation.path
+                .replace(":peer_id", &PeerId::random().to_string())
+                .replace(":cluster_id", &uuid::Uuid::new_v4().to_string());
+            let request = Request::builder()
+                .method(operation.method.to_uppercase().as_str())
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))?;
+            let response = Clone::clone(&testee).oneshot(request).await?;
+
+            let is_json = response.headers().get(header::CONTENT_TYPE)
+                .is_some_and(|content_type| content_type == "application/json");
+            assert!(is_json, "Operation '{} {}' should be routed to a JSON handler, but got status {}.", operation.method, operation.path, response.status());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, HashSet};
+
+use axum::Json;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName, DeviceSelector};
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerNetworkDescriptor};
+use opendut_types::peer::executor::ExecutorDescriptors;
+use opendut_types::project::ProjectName;
+use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+
+use crate::auth::permission;
+use crate::http::rest::{clusters, Operation, peers};
+
+pub const PATH: &str = "/openapi.json";
+
+/// All operations of the REST API, in the order they appear in the OpenAPI document.
+pub const OPERATIONS: &[&Operation] = &[
+    &peers::LIST_PEER_DESCRIPTORS,
+    &peers::GET_PEER_DESCRIPTOR,
+    &peers::STORE_PEER_DESCRIPTOR,
+    &peers::DELETE_PEER_DESCRIPTOR,
+    &peers::LIST_DEVICES,
+    &clusters::LIST_CLUSTER_CONFIGURATIONS,
+    &clusters::CREATE_CLUSTER_CONFIGURATION,
+    &clusters::GET_CLUSTER_CONFIGURATION,
+    &clusters::UPDATE_CLUSTER_CONFIGURATION,
+    &clusters::DELETE_CLUSTER_CONFIGURATION,
+    &clusters::LIST_CLUSTER_DEPLOYMENTS,
+    &clusters::STORE_CLUSTER_DEPLOYMENT,
+    &clusters::DELETE_CLUSTER_DEPLOYMENT,
+];
+
+pub async fn document() -> Json<Value> {
+    Json(generate())
+}
+
+/// Generates the OpenAPI document from the [`OPERATIONS`]. The schemas are derived from the actual types and carry examples, which are serialized from them.
+pub fn generate() -> Value {
+    let mut paths = BTreeMap::<String, serde_json::Map<String, Value>>::new();
+
+    for operation in OPERATIONS {
+        let path = operation.path.split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(parameter) => format!("{{{parameter}}}"),
+                None => segment.to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let parameters = operation.path.split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .map(|parameter| json!({
+                "name": parameter,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string", "format": "uuid" },
+            }))
+            .collect::<Vec<_>>();
+
+        let success_status = if operation.method == "post" { "201" } else { "200" };
+        let mut responses = json!({
+            "default": {
+                "description": "Error",
+                "content": { "application/json": { "schema": schema_ref("Error") } },
+            },
+        });
+        responses[success_status] = match operation.response {
+            Some(schema) => json!({
+                "description": "Success",
+                "content": { "application/json": { "schema": schema_ref(schema) } },
+            }),
+            None => json!({ "description": "Success" }),
+        };
+
+        let mut description = json!({
+            "operationId": operation.grpc_method.rsplit('/').next().unwrap_or_default(),
+            "summary": operation.summary,
+            "description": format!("Requires the role '{}'.", permission::required_role(operation.grpc_method)),
+            "parameters": parameters,
+            "responses": responses,
+        });
+        if let Some(schema) = operation.request {
+            description["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema_ref(schema) } },
+            });
+        }
+
+        paths.entry(path).or_default()
+            .insert(operation.method.to_owned(), description);
+    }
+
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let examples = [
+        example::<PeerDescriptor>(&mut generator, &examples::peer_descriptor()),
+        example::<DeviceDescriptor>(&mut generator, &examples::device_descriptor()),
+        example::<ClusterConfiguration>(&mut generator, &examples::cluster_configuration()),
+        example::<ClusterDeployment>(&mut generator, &examples::cluster_deployment()),
+    ];
+
+    let mut schemas = generator.take_definitions().into_iter()
+        .map(|(name, schema)| (name, serde_json::to_value(schema).expect("Schemas should be serializable.")))
+        .collect::<serde_json::Map<_, _>>();
+    for (name, example) in examples {
+        if let Some(schema) = schemas.get_mut(&name) {
+            schema["example"] = example;
+        }
+    }
+    schemas.insert(String::from("Error"), json!({
+        "type": "object",
+        "properties": { "error": { "type": "string" } },
+        "required": ["error"],
+    }));
+    schemas.insert(String::from("PeerId"), uuid_schema());
+    schemas.insert(String::from("ClusterId"), uuid_schema());
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "openDuT CARL",
+            "version": crate::app_info::CRATE_VERSION,
+        },
+        "servers": [{ "url": "/api/v1" }],
+        "security": [{ "bearer": [] }],
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+            "schemas": schemas,
+        },
+    })
+}
+
+fn schema_ref(schema: &str) -> Value {
+    match schema.strip_suffix("[]") {
+        Some(item) => json!({ "type": "array", "items": { "$ref": format!("#/components/schemas/{item}") } }),
+        None => json!({ "$ref": format!("#/components/schemas/{schema}") }),
+    }
+}
+
+fn uuid_schema() -> Value {
+    json!({ "type": "string", "format": "uuid" })
+}
+
+/// Adds the schema of the type to the generator's definitions and returns the serialized example, which belongs to it.
+fn example<T: JsonSchema + Serialize>(generator: &mut SchemaGenerator, example: &T) -> (String, Value) {
+    generator.subschema_for::<T>();
+    (T::schema_name(), serde_json::to_value(example).expect("Examples should be serializable."))
+}
+
+mod examples {
+    use super::*;
+
+    const PEER_ID: &str = "a9b8f2e4-3c5d-4f1a-9e7b-2d6c8a0f1b3e";
+    const DEVICE_ID: &str = "5f0e7c1d-9a2b-4e3f-8d6c-1b4a7e9f2c0d";
+    const CLUSTER_ID: &str = "c3d2e1f0-7b6a-4958-8f7e-6d5c4b3a2910";
+
+    pub fn device_descriptor() -> DeviceDescriptor {
+        DeviceDescriptor {
+            id: DeviceId(parse_uuid(DEVICE_ID)),
+            name: DeviceName::try_from("brake-ecu").expect("Example should be valid."),
+            description: Some(DeviceDescription::try_from("ECU of the brake system").expect("Example should be valid.")),
+            interface: NetworkInterfaceDescriptor {
+                name: NetworkInterfaceName::try_from("eth0").expect("Example should be valid."),
+                configuration: NetworkInterfaceConfiguration::Ethernet,
+            },
+            tags: vec![DeviceTag::try_from("brake").expect("Example should be valid.")],
+        }
+    }
+
+    pub fn peer_descriptor() -> PeerDescriptor {
+        let device = device_descriptor();
+        PeerDescriptor {
+            id: PeerId::from(parse_uuid(PEER_ID)),
+            name: PeerName::try_from("test-bench-1").expect("Example should be valid."),
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![Clone::clone(&device.interface)],
+                bridge_name: None,
+            },
+            topology: Topology::new(vec![device]),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        }
+    }
+
+    pub fn cluster_configuration() -> ClusterConfiguration {
+        ClusterConfiguration {
+            id: cluster_id(),
+            name: ClusterName::try_from("brake-cluster").expect("Example should be valid."),
+            leader: PeerId::from(parse_uuid(PEER_ID)),
+            devices: HashSet::from([DeviceId(parse_uuid(DEVICE_ID))]),
+            device_selectors: vec![DeviceSelector::try_from("tag=brake").expect("Example should be valid.")],
+            project: ProjectName::default(),
+        }
+    }
+
+    pub fn cluster_deployment() -> ClusterDeployment {
+        ClusterDeployment {
+            id: cluster_id(),
+            devices: HashSet::new(),
+            deployed_by: String::new(),
+        }
+    }
+
+    fn cluster_id() -> ClusterId {
+        ClusterId::from(parse_uuid(CLUSTER_ID))
+    }
+
+    fn parse_uuid(value: &str) -> uuid::Uuid {
+        uuid::Uuid::parse_str(value).expect("Example should be a valid UUID.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_document_every_operation_with_deserializable_examples() -> anyhow::Result<()> {
+        let document = generate();
+
+        for operation in OPERATIONS {
+            let path = operation.path.replace(":peer_id", "{peer_id}").replace(":cluster_id", "{cluster_id}");
+            let described = &document["paths"][&path][operation.method];
+            assert_that!(described["operationId"].as_str(), some(eq(operation.grpc_method.rsplit('/').next().unwrap())));
+        }
+
+        let schemas = &document["components"]["schemas"];
+        serde_json::from_value::<PeerDescriptor>(Clone::clone(&schemas["PeerDescriptor"]["example"]))?;
+        serde_json::from_value::<DeviceDescriptor>(Clone::clone(&schemas["DeviceDescriptor"]["example"]))?;
+        serde_json::from_value::<ClusterConfiguration>(Clone::clone(&schemas["ClusterConfiguration"]["example"]))?;
+        serde_json::from_value::<ClusterDeployment>(Clone::clone(&schemas["ClusterDeployment"]["example"]))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_derive_schemas_with_properties_from_the_types() {
+        let document = generate();
+        let schemas = &document["components"]["schemas"];
+
+        assert_that!(schemas["PeerDescriptor"]["properties"]["name"].is_object(), eq(true));
+        assert_that!(schemas["PeerDescriptor"]["required"].as_array().cloned().unwrap_or_default(), contains(eq(json!("id"))));
+        assert_that!(schemas["ClusterConfiguration"]["required"].as_array().cloned().unwrap_or_default(), contains(eq(json!("leader"))));
+        assert_that!(schemas["DeviceDescriptor"]["properties"]["tags"]["type"].as_str(), some(eq("array")));
+
+        let document = document.to_string();
+        for reference in document.split("\"$ref\":\"#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap_or_default();
+            assert_that!(schemas[name].is_object(), eq(true), "Schema '{name}' should be defined.");
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use axum::response::IntoResponse;
+use const_format::concatcp;
+
+use opendut_carl_api::carl::peer::{DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, StorePeerDescriptorError};
+use opendut_carl_api::proto::services::peer_manager::*;
+use opendut_carl_api::proto::services::peer_manager::peer_manager_server::PeerManager as PeerManagerService;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::topology::DeviceDescriptor;
+
+use crate::http::rest::{ApiError, etag, expected_version, JsonBody, Operation, PathParam, reply, require_matching_id, RestState};
+
+const SERVICE: &str = "/opendut.carl.services.peer_manager.PeerManager";
+
+pub const LIST_PEER_DESCRIPTORS: Operation = Operation {
+    method: "get",
+    path: "/peers",
+    grpc_method: concatcp!(SERVICE, "/ListPeerDescriptors"),
+    summary: "List all peers.",
+    request: None,
+    response: Some("PeerDescriptor[]"),
+};
+pub const GET_PEER_DESCRIPTOR: Operation = Operation {
+    method: "get",
+    path: "/peers/:peer_id",
+    grpc_method: concatcp!(SERVICE, "/GetPeerDescriptor"),
+    summary: "Get a peer. Its version is returned in the header 'ETag'.",
+    request: None,
+    response: Some("PeerDescriptor"),
+};
+pub const STORE_PEER_DESCRIPTOR: Operation = Operation {
+    method: "put",
+    path: "/peers/:peer_id",
+    grpc_method: concatcp!(SERVICE, "/StorePeerDescriptor"),
+    summary: "Create or update a peer. With the header 'If-Match', it is only stored, if it is still in the given version.",
+    request: Some("PeerDescriptor"),
+    response: Some("PeerId"),
+};
+pub const DELETE_PEER_DESCRIPTOR: Operation = Operation {
+    method: "delete",
+    path: "/peers/:peer_id",
+    grpc_method: concatcp!(SERVICE, "/DeletePeerDescriptor"),
+    summary: "Delete a peer.",
+    request: None,
+    response: Some("PeerId"),
+};
+pub const LIST_DEVICES: Operation = Operation {
+    method: "get",
+    path: "/devices",
+    grpc_method: concatcp!(SERVICE, "/ListDevices"),
+    summary: "List the devices of all peers.",
+    request: None,
+    response: Some("DeviceDescriptor[]"),
+};
+
+pub async fn list_peer_descriptors(State(state): State<RestState>, headers: HeaderMap) -> Result<Json<Vec<PeerDescriptor>>, ApiError> {
+    let request = state.request(&headers, &LIST_PEER_DESCRIPTORS, ListPeerDescriptorsRequest {}).await?;
+    let response = state.peer_manager.list_peer_descriptors(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        list_peer_descriptors_response::Reply::Failure(failure) => Err(ListPeerDescriptorsError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        list_peer_descriptors_response::Reply::Success(success) => {
+            let peers = success.peers.into_iter()
+                .map(PeerDescriptor::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(ApiError::invalid_reply)?;
+            Ok(Json(peers))
+        }
+    }
+}
+
+pub async fn get_peer_descriptor(State(state): State<RestState>, headers: HeaderMap, PathParam(peer_id): PathParam<PeerId>) -> Result<impl IntoResponse, ApiError> {
+    let request = state.request(&headers, &GET_PEER_DESCRIPTOR, GetPeerDescriptorRequest { peer_id: Some(peer_id.into()) }).await?;
+    let response = state.peer_manager.get_peer_descriptor(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        get_peer_descriptor_response::Reply::Failure(failure) => Err(GetPeerDescriptorError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        get_peer_descriptor_response::Reply::Success(success) => {
+            let peer = PeerDescriptor::try_from(success.descriptor.ok_or_else(ApiError::missing_reply)?)
+                .map_err(ApiError::invalid_reply)?;
+            Ok((etag(success.version), Json(peer)))
+        }
+    }
+}
+
+pub async fn store_peer_descriptor(State(state): State<RestState>, headers: HeaderMap, PathParam(peer_id): PathParam<PeerId>, JsonBody(peer): JsonBody<PeerDescriptor>) -> Result<Json<PeerId>, ApiError> {
+    require_matching_id(peer_id, peer.id)?;
+    let expected_version = expected_version(&headers)?;
+
+    let request = state.request(&headers, &STORE_PEER_DESCRIPTOR, StorePeerDescriptorRequest {
+        peer: Some(peer.into()),
+        expected_version: expected_version.map(u64::from),
+    }).await?;
+    let response = state.peer_manager.store_peer_descriptor(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        store_peer_descriptor_response::Reply::Failure(failure) => Err(StorePeerDescriptorError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        store_peer_descriptor_response::Reply::Success(_) => Ok(Json(peer_id)),
+    }
+}
+
+pub async fn delete_peer_descriptor(State(state): State<RestState>, headers: HeaderMap, PathParam(peer_id): PathParam<PeerId>) -> Result<Json<PeerId>, ApiError> {
+    let request = state.request(&headers, &DELETE_PEER_DESCRIPTOR, DeletePeerDescriptorRequest { peer_id: Some(peer_id.into()) }).await?;
+    let response = state.peer_manager.delete_peer_descriptor(request).await?.into_inner();
+
+    match reply(response.reply)? {
+        delete_peer_descriptor_response::Reply::Failure(failure) => Err(DeletePeerDescriptorError::try_from(failure).map_err(ApiError::invalid_reply)?.into()),
+        delete_peer_descriptor_response::Reply::Success(_) => Ok(Json(peer_id)),
+    }
+}
+
+pub async fn list_devices(State(state): State<RestState>, headers: HeaderMap) -> Result<Json<Vec<DeviceDescriptor>>, ApiError> {
+    let request = state.request(&headers, &LIST_DEVICES, ListDevicesRequest {}).await?;
+    let response = state.peer_manager.list_devices(request).await?.into_inner();
+
+    let devices = response.devices.into_iter()
+        .map(DeviceDescriptor::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(ApiError::invalid_reply)?;
+    Ok(Json(devices))
+}
+
+impl From<StorePeerDescriptorError> for ApiError {
+    fn from(error: StorePeerDescriptorError) -> Self {
+        let status = match error {
+            StorePeerDescriptorError::IllegalPeerState { .. } => StatusCode::CONFLICT,
+            StorePeerDescriptorError::ClusterMember { .. } => StatusCode::CONFLICT,
+            StorePeerDescriptorError::IllegalDevices { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            StorePeerDescriptorError::VersionConflict { .. } => StatusCode::CONFLICT,
+            StorePeerDescriptorError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<DeletePeerDescriptorError> for ApiError {
+    fn from(error: DeletePeerDescriptorError) -> Self {
+        let status = match error {
+            DeletePeerDescriptorError::PeerNotFound { .. } => StatusCode::NOT_FOUND,
+            DeletePeerDescriptorError::IllegalPeerState { .. } => StatusCode::CONFLICT,
+            DeletePeerDescriptorError::ClusterMember { .. } => StatusCode::CONFLICT,
+            DeletePeerDescriptorError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<GetPeerDescriptorError> for ApiError {
+    fn from(error: GetPeerDescriptorError) -> Self {
+        let status = match error {
+            GetPeerDescriptorError::PeerNotFound { .. } => StatusCode::NOT_FOUND,
+            GetPeerDescriptorError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<ListPeerDescriptorsError> for ApiError {
+    fn from(error: ListPeerDescriptorsError) -> Self {
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+    }
+}
@@ -0,0 +1,66 @@
+use axum::response::{IntoResponse, Response};
+use http::{header, StatusCode};
+use tracing::error;
+
+use opendut_util::telemetry::metrics::{prometheus_registry, PrometheusRegistry};
+
+/// Serves the metrics in the Prometheus text-exposition format, if exposing them for Prometheus is enabled.
+pub async fn prometheus_metrics() -> impl IntoResponse {
+    let Some(registry) = prometheus_registry() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    render_metrics(registry).await
+}
+
+/// Gathering the metrics invokes the callbacks of observable instruments, which may block, so it runs on a blocking thread.
+async fn render_metrics(registry: PrometheusRegistry) -> Response {
+    match tokio::task::spawn_blocking(move || registry.render()).await {
+        Ok(Ok(metrics)) => {
+            ([(header::CONTENT_TYPE, PrometheusRegistry::CONTENT_TYPE)], metrics).into_response()
+        }
+        Ok(Err(cause)) => {
+            error!("Failed to render metrics for Prometheus: {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(cause) => {
+            error!("Task rendering metrics for Prometheus failed: {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::Router;
+    use axum::routing::get;
+    use googletest::prelude::*;
+    use http::Request;
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_serve_registered_gauges_in_the_prometheus_format() -> anyhow::Result<()> {
+        let registry = PrometheusRegistry::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(registry.exporter(true)?)
+            .build();
+        let _gauge = meter_provider.meter("test")
+            .u64_observable_gauge("carl_test_gauge")
+            .with_callback(|observer| observer.observe(42, &[]))
+            .init();
+
+        let router = Router::new()
+            .route("/metrics", get(move || render_metrics(Clone::clone(&registry))));
+        let response = router.oneshot(Request::get("/metrics").body(Body::empty())?).await?;
+
+        assert_that!(response.status(), eq(StatusCode::OK));
+        let body = axum_server_dual_protocol::hyper::body::to_bytes(response.into_body()).await?;
+        assert_that!(String::from_utf8(body.to_vec())?, contains_substring("carl_test_gauge{otel_scope_name=\"test\"} 42"));
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use crate::http::state::LeaConfig;
 
 pub mod cleo;
 pub mod edgar;
+pub mod metrics;
 
 pub async fn lea_config(State(config): State<LeaConfig>) -> Json<LeaConfig> {
     Json(Clone::clone(&config))
@@ -14,7 +14,7 @@ use futures::TryFutureExt;
 use pem::Pem;
 use tonic::transport::Server;
 use tonic_async_interceptor::async_interceptor;
-use tower::{BoxError, make::Shared, ServiceExt, steer::Steer};
+use tower::{BoxError, make::Shared, ServiceExt, steer::Steer, util::MapRequestLayer};
 use tower_http::services::{ServeDir, ServeFile};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
@@ -24,26 +24,36 @@ use opendut_auth::registration::resources::ResourceHomeUrl;
 
 use opendut_util::{telemetry, project};
 use opendut_util::telemetry::logging::LoggingConfig;
-use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
-use opendut_util::settings::LoadedConfig;
+use opendut_util::telemetry::opentelemetry_types::{Opentelemetry, Prometheus};
 use crate::auth::grpc_auth_layer::{GrpcAuthenticationLayer};
+use crate::auth::mtls;
+use crate::auth::mtls::{ClientCertificateAcceptor, ServerTlsFiles};
+use crate::auth::permission::{GrpcMethodPath, RoleMapping};
 use crate::auth::json_web_key::JwkCacheValue;
 use util::in_memory_cache::CustomInMemoryCache;
+use crate::actions::StorePeerDescriptorOptions;
+use crate::audit::AuditLogRef;
 use crate::cluster::manager::{ClusterManager, ClusterManagerOptions, ClusterManagerRef};
 
-use crate::grpc::{ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerManagerFacadeOptions, PeerMessagingBrokerFacade};
+use crate::grpc::{AuditLogFacade, BackupFacade, ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerManagerFacadeOptions, PeerMessagingBrokerFacade, ReservationManagerFacade, ServiceAccountManagerFacade, WebhookManagerFacade};
+use crate::http::rest::{self, RestState};
 use crate::http::router;
 use crate::http::state::{CarlInstallDirectory, HttpState, LeaConfig, LeaIdentityProviderConfig};
 use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions, PeerMessagingBrokerRef};
+use crate::peer::certificate_authority::{PeerCertificateAuthority, PeerCertificateAuthorityFiles, PeerCertificateAuthorityRef, PeerCertificateOptions};
 use crate::provisioning::cleo_script::CleoScript;
+use crate::reservation::ReservationExpiryOptions;
 use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use crate::settings::reload::{SettingsReloader, SettingsReloadOptions};
 use crate::vpn::Vpn;
+use crate::webhook::{WebhookDispatcher, WebhookDispatcherRef, WebhookOptions};
 
 pub mod grpc;
 pub mod util;
 opendut_util::app_info!();
 
 mod actions;
+mod audit;
 mod cluster;
 mod metrics;
 mod peer;
@@ -52,32 +62,36 @@ pub mod settings;
 mod vpn;
 mod http;
 mod provisioning;
+mod projects;
+mod reservation;
 mod auth;
+mod webhook;
 
 #[tracing::instrument]
 pub async fn create_with_telemetry(settings_override: config::Config) -> Result<()> {
-    let settings = settings::load_with_overrides(settings_override)?;
+    let settings = settings::load_with_overrides(Clone::clone(&settings_override))?;
 
     let service_instance_id = format!("carl-{}", Uuid::new_v4());
 
     let logging_config = LoggingConfig::load(&settings.config)?;
     let opentelemetry = Opentelemetry::load(&settings.config, service_instance_id).await?;
+    let prometheus = Prometheus::load(&settings.config)?;
 
-    let mut shutdown = telemetry::initialize_with_config(logging_config, opentelemetry.clone()).await?;
+    let mut shutdown = telemetry::initialize_with_config(logging_config, opentelemetry, prometheus).await?;
     
-    if let (Opentelemetry::Enabled { cpu_collection_interval_ms, .. },
-        Some(meter_providers, ..)) = (opentelemetry, &shutdown.meter_providers) {
-        telemetry::metrics::initialize_metrics_collection(cpu_collection_interval_ms, meter_providers);
+    if let Some(meter_providers) = &shutdown.meter_providers {
+        telemetry::metrics::initialize_metrics_collection(meter_providers);
     }
 
-    create(settings).await?;
+    create(settings_override).await?;
 
     shutdown.shutdown();
 
     Ok(())
 }
 
-pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
+pub async fn create(settings_override: config::Config) -> Result<()> { //TODO
+    let settings = settings::load_with_overrides(Clone::clone(&settings_override))?;
     info!("Started with configuration: {settings:?}");
 
     let address: SocketAddr = {
@@ -86,17 +100,6 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         SocketAddr::from_str(&format!("{host}:{port}"))?
     };
 
-    let tls_config = {
-        let cert_path = project::make_path_absolute(settings.config.get_string("network.tls.certificate")?)?;
-        debug!("Using TLS certificate: {}", cert_path.display());
-        assert!(cert_path.exists(), "TLS certificate file at '{}' not found.", cert_path.display());
-
-        let key_path = project::make_path_absolute(settings.config.get_string("network.tls.key")?)?;
-        debug!("Using TLS key: {}", key_path.display());
-        assert!(key_path.exists(), "TLS key file at '{}' not found.", key_path.display());
-
-        RustlsConfig::from_pem_file(cert_path, key_path).await?
-    };
     let carl_url = ResourceHomeUrl::try_from(&settings.config)?;
 
     let ca_certificate = Pem::from_config_path("network.tls.ca", &settings.config).await?;
@@ -105,9 +108,30 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
     let vpn = vpn::create(&settings.config)
         .context("Error while parsing VPN configuration.")?;
 
-    let resources_manager = ResourcesManager::new();
+    let persistence = resources::persistence::create(&settings.config)
+        .context("Error while parsing persistence configuration.")?;
+    let resources_manager = ResourcesManager::load(persistence)
+        .context("Error while loading persisted resources.")?;
     metrics::initialize_metrics_collection(Arc::clone(&resources_manager));
 
+    let peer_certificate_authority = PeerCertificateAuthorityFiles::load(&settings.config)
+        .and_then(|files| Ok(PeerCertificateAuthority::load_or_generate(&files)?))
+        .context("Error while loading certificate authority for peers.")?;
+
+    let tls_files = ServerTlsFiles::load(&settings.config)?;
+    let tls_config = {
+        debug!("Using TLS certificate: {}", tls_files.certificate.display());
+        assert!(tls_files.certificate.exists(), "TLS certificate file at '{}' not found.", tls_files.certificate.display());
+
+        debug!("Using TLS key: {}", tls_files.key.display());
+        assert!(tls_files.key.exists(), "TLS key file at '{}' not found.", tls_files.key.display());
+
+        RustlsConfig::from_config(mtls::server_config(&tls_files.certificate, &tls_files.key, &peer_certificate_authority)?)
+    };
+
+    let audit_log = audit::create(&settings.config)
+        .context("Error while parsing audit configuration.")?;
+
     let peer_messaging_broker = PeerMessagingBroker::new(
         Arc::clone(&resources_manager),
         PeerMessagingBrokerOptions::load(&settings.config)?,
@@ -118,6 +142,26 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         Clone::clone(&vpn),
         ClusterManagerOptions::load(&settings.config)?,
     );
+    cluster_manager.lock().await.recover_interrupted_deployments().await
+        .context("Error while recovering interrupted cluster deployments.")?;
+    SettingsReloader::new(
+        Box::new(move || settings::load_with_overrides(Clone::clone(&settings_override))),
+        Clone::clone(&settings),
+        Clone::clone(&tls_config),
+        Arc::clone(&peer_certificate_authority),
+        Arc::clone(&cluster_manager),
+        Arc::clone(&peer_messaging_broker),
+    )?.spawn(SettingsReloadOptions::load(&settings.config)?)?;
+    reservation::spawn_expiry_task(
+        Arc::clone(&cluster_manager),
+        Arc::clone(&audit_log),
+        ReservationExpiryOptions::load(&settings.config)?,
+    );
+    let webhook_dispatcher = WebhookDispatcher::new(
+        Arc::clone(&resources_manager),
+        WebhookOptions::load(&settings.config)?,
+    )?;
+    webhook_dispatcher.spawn(cluster_manager.lock().await.subscribe_deployment_failures());
 
     let jwk_cache: CustomInMemoryCache<String, JwkCacheValue> = CustomInMemoryCache::new();
 
@@ -130,6 +174,9 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
                 issuer_url: oidc_client_ref.inner.config.issuer_url.clone(),
                 issuer_remote_url: oidc_client_ref.config.issuer_remote_url.clone(),
                 cache: jwk_cache,
+                role_mapping: RoleMapping::load(&settings.config)?,
+                project_group_prefix: settings.config.get_string("network.oidc.projects.group.prefix")?,
+                resources_manager: Arc::clone(&resources_manager),
             }
         }
     };
@@ -141,8 +188,11 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         address: SocketAddr,
         tls_config: RustlsConfig,
         resources_manager: ResourcesManagerRef,
+        audit_log: AuditLogRef,
         cluster_manager: ClusterManagerRef,
         peer_messaging_broker: PeerMessagingBrokerRef,
+        peer_certificate_authority: PeerCertificateAuthorityRef,
+        webhook_dispatcher: WebhookDispatcherRef,
         vpn: Vpn,
         carl_url: ResourceHomeUrl,
         settings: config::Config,
@@ -152,29 +202,61 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
     ) -> BoxFuture<'static, Result<()>> {
         let oidc_enabled = settings.get_bool("network.oidc.enabled").unwrap_or(false);
 
-        let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager));
+        let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager), Arc::clone(&audit_log));
         let metadata_provider_facade = MetadataProviderFacade::new();
 
         let peer_manager_facade_options = PeerManagerFacadeOptions::load(&settings).expect("Error while loading PeerManagerFacadeOptions.");
+        let backup_facade = BackupFacade::new(
+            Arc::clone(&resources_manager),
+            Arc::clone(&cluster_manager),
+            Arc::clone(&audit_log),
+            Clone::clone(&vpn),
+            StorePeerDescriptorOptions {
+                bridge_name_default: Clone::clone(&peer_manager_facade_options.bridge_name_default),
+            },
+        );
         let peer_manager_facade = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            Arc::clone(&audit_log),
             vpn,
             Clone::clone(&carl_url.value()),
             ca.clone(),
+            Arc::clone(&peer_certificate_authority),
             oidc_registration_client,
             peer_manager_facade_options
         );
-        let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker));
+        let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(
+            Arc::clone(&peer_messaging_broker),
+            Arc::clone(&resources_manager),
+            peer_certificate_authority,
+            PeerCertificateOptions::load(&settings).expect("Error while loading PeerCertificateOptions."),
+        );
+        let reservation_manager_facade = ReservationManagerFacade::new(Arc::clone(&resources_manager), Arc::clone(&audit_log));
+        let service_account_manager_facade = ServiceAccountManagerFacade::new(Arc::clone(&resources_manager), Arc::clone(&audit_log));
+        let webhook_manager_facade = WebhookManagerFacade::new(Arc::clone(&resources_manager), Arc::clone(&audit_log), webhook_dispatcher);
+        let audit_log_facade = AuditLogFacade::new(audit_log);
+
+        let rest_state = RestState {
+            auth: Clone::clone(&grpc_auth_layer),
+            peer_manager: Clone::clone(&peer_manager_facade),
+            cluster_manager: Clone::clone(&cluster_manager_facade),
+        };
 
         let grpc = Server::builder()
+            .layer(MapRequestLayer::new(GrpcMethodPath::insert_into))
             .layer(async_interceptor(move |request| {
                 Clone::clone(&grpc_auth_layer).auth_interceptor(request)
             }))
             .accept_http1(true) //gRPC-web uses HTTP1
+            .add_service(audit_log_facade.into_grpc_service())
+            .add_service(backup_facade.into_grpc_service())
             .add_service(cluster_manager_facade.into_grpc_service())
             .add_service(metadata_provider_facade.into_grpc_service())
             .add_service(peer_manager_facade.into_grpc_service())
             .add_service(peer_messaging_broker_facade.into_grpc_service())
+            .add_service(reservation_manager_facade.into_grpc_service())
+            .add_service(service_account_manager_facade.into_grpc_service())
+            .add_service(webhook_manager_facade.into_grpc_service())
             .into_service()
             .map_response(|response| response.map(axum::body::boxed))
             .boxed_clone();
@@ -238,6 +320,8 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
                     .route("/api/cleo/:architecture/download", get(router::cleo::download_cleo))
                     .route("/api/edgar/:architecture/download", get(router::edgar::download_edgar))
                     .route("/api/lea/config", get(router::lea_config))
+                    .route("/metrics", get(router::metrics::prometheus_metrics))
+                    .nest("/api/v1", rest::router(rest_state))
                     .nest_service(
                         "/",
                         ServeDir::new(&lea_dir)
@@ -265,6 +349,7 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         Box::pin(
             axum_server_dual_protocol::bind_dual_protocol(address, tls_config)
                 .set_upgrade(true) //http -> https
+                .map(ClientCertificateAcceptor::new)
                 .serve(Shared::new(http_grpc))
                 .map_err(|cause| anyhow!(cause))
         )
@@ -275,8 +360,11 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         address,
         tls_config,
         resources_manager,
+        audit_log,
         cluster_manager,
         peer_messaging_broker,
+        peer_certificate_authority,
+        webhook_dispatcher,
         vpn,
         carl_url,
         settings.config,
@@ -11,12 +11,13 @@ use tracing::{debug, error, info, Span, warn};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, downstream, Downstream, TracingContext};
-use opendut_carl_api::proto::services::peer_messaging_broker::Pong;
+use opendut_carl_api::proto::services::peer_messaging_broker::{Pong, ReportPeerConfigurationState};
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
 use opendut_types::peer::PeerId;
-use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
-use opendut_types::peer::state::{PeerState, PeerUpState};
+use opendut_types::peer::configuration::{ClusterTeardownState, PeerConfiguration, PeerConfiguration2, PeerConfigurationState};
+use opendut_types::peer::state::PeerState;
 
+use crate::peer::state;
 use crate::resources::manager::ResourcesManagerRef;
 
 pub type PeerMessagingBrokerRef = Arc<PeerMessagingBroker>;
@@ -25,7 +26,7 @@ pub type PeerMessagingBrokerRef = Arc<PeerMessagingBroker>;
 pub struct PeerMessagingBroker {
     resources_manager: ResourcesManagerRef,
     peers: Arc<RwLock<HashMap<PeerId, PeerMessagingRef>>>,
-    options: PeerMessagingBrokerOptions,
+    options: Arc<RwLock<PeerMessagingBrokerOptions>>,
 }
 struct PeerMessagingRef {
     downstream: mpsc::Sender<Downstream>,
@@ -36,10 +37,19 @@ impl PeerMessagingBroker {
         Arc::new(Self {
             resources_manager,
             peers: Default::default(),
-            options,
+            options: Arc::new(RwLock::new(options)),
         })
     }
 
+    pub async fn options(&self) -> PeerMessagingBrokerOptions {
+        Clone::clone(&*self.options.read().await)
+    }
+
+    /// Replaces the options. They also apply to the streams of peers, which are already connected.
+    pub async fn set_options(&self, options: PeerMessagingBrokerOptions) {
+        *self.options.write().await = options;
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     pub async fn send_to_peer(&self, peer_id: PeerId, message: downstream::Message) -> Result<(), Error> {
         let downstream = {
@@ -88,11 +98,9 @@ impl PeerMessagingBroker {
 
         self.peers.write().await.insert(peer_id, peer_messaging_ref);
 
-        fn new_peer_up_state(remote_host: IpAddr) -> PeerState {
-            PeerState::Up { inner: PeerUpState::Available, remote_host }
-        }
-
         self.resources_manager.resources_mut(|resources| {
+            let new_peer_up_state = PeerState::Up { inner: state::initial_up_state(resources, peer_id), remote_host };
+
             match resources.get::<PeerState>(peer_id) {
                 None => {
                     info!("Peer <{}> opened stream which has not been seen before.", peer_id);
@@ -117,12 +125,13 @@ impl PeerMessagingBroker {
                             *peer_remote_host = remote_host
                         }
                         PeerState::Down => {
-                            *peer_state = new_peer_up_state(remote_host)
+                            *peer_state = Clone::clone(&new_peer_up_state)
                         }
                     })
-                    .or_insert(new_peer_up_state(remote_host))
+                    .or_insert(new_peer_up_state)
             })
-        }).await?;
+        }).await
+        .map_err(|cause| OpenError::Internal { peer_id, cause: cause.to_string() })??;
 
         if let Some(configuration) = self.resources_manager.get::<PeerConfiguration>(peer_id).await {
             if let Some(configuration2) = self.resources_manager.get::<PeerConfiguration2>(peer_id).await {
@@ -145,18 +154,18 @@ impl PeerMessagingBroker {
             error!("Failed to send ApplyPeerConfiguration message, because no PeerConfiguration found for peer <{peer_id}>.")
         }
 
-        let timeout_duration = self.options.peer_disconnect_timeout;
-
         {
             let peers = Arc::clone(&self.peers);
             let resources_manager = Arc::clone(&self.resources_manager);
+            let options = Arc::clone(&self.options);
 
             tokio::spawn(async move {
                 loop {
+                    let timeout_duration = options.read().await.peer_disconnect_timeout;
                     let received = tokio::time::timeout(timeout_duration, rx_inbound.recv()).await;
 
                     match received {
-                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound).await,
+                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound, &resources_manager).await,
                         Ok(None) => {
                             info!("Peer <{peer_id}> disconnected!");
                             break;
@@ -197,6 +206,7 @@ async fn handle_stream_message(
     message: upstream::Message,
     peer_id: PeerId,
     tx_outbound: &mpsc::Sender<Downstream>,
+    resources_manager: &ResourcesManagerRef,
 ) {
     match message {
         upstream::Message::Ping(_) => {
@@ -206,6 +216,24 @@ async fn handle_stream_message(
                 tx_outbound.send(Downstream{message:Some(message), context}).await
                     .inspect_err(|cause| warn!("Failed to send ping to peer <{peer_id}>: {cause}"));
         },
+        upstream::Message::ReportPeerConfigurationState(ReportPeerConfigurationState { state }) => {
+            let state = state
+                .ok_or_else(|| String::from("Field 'state' not set."))
+                .and_then(|state| PeerConfigurationState::try_from(state).map_err(|cause| cause.to_string()));
+
+            match state {
+                Ok(state) => {
+                    debug!("Peer <{peer_id}> reported the state of its configuration: {state:?}");
+                    if let Some(ClusterTeardownState::Failed { cluster_id, cause }) = &state.cluster_teardown {
+                        warn!("Peer <{peer_id}> failed to tear down its setup for cluster <{cluster_id}>:\n  {cause}");
+                    }
+                    if let Err(cause) = resources_manager.insert(peer_id, state).await {
+                        error!("Failed to store the state of the configuration of peer <{peer_id}>:\n  {cause}");
+                    }
+                }
+                Err(cause) => warn!("Received illegal configuration state from peer <{peer_id}>:\n  {cause}"),
+            }
+        }
     }
 }
 
@@ -217,8 +245,10 @@ async fn down_peer_impl(resources_manager: ResourcesManagerRef, peer_id: PeerId)
             .modify(|peer_state| {
                 *peer_state = PeerState::Down;
             })
-            .or_insert(PeerState::Down)
-    }).await;
+            .or_insert(PeerState::Down);
+        resources.remove::<PeerConfigurationState>(peer_id);
+    }).await
+    .unwrap_or_else(|cause| error!("Failed to store the state of peer <{peer_id}> as Down:\n  {cause}"));
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -239,9 +269,11 @@ pub enum OpenError {
         Rejecting connection."
     )]
     PeerAlreadyConnected { peer_id: PeerId },
+    #[error("Peer <{peer_id}> opened stream, but its state could not be stored:\n  {cause}")]
+    Internal { peer_id: PeerId, cause: String },
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PeerMessagingBrokerOptions {
     pub peer_disconnect_timeout: Duration,
 }
@@ -267,6 +299,8 @@ mod tests {
     use tokio::sync::mpsc::Receiver;
 
     use opendut_carl_api::proto::services::peer_messaging_broker::Ping;
+    use opendut_types::peer::configuration::{ParameterState, ParameterStateKind, ParameterValue};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
 
     use crate::resources::manager::ResourcesManager;
 
@@ -358,6 +392,47 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_store_reported_peer_configuration_state() -> Result<()> {
+        let resources_manager = ResourcesManager::new();
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
+
+        let peer_id = PeerId::random();
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+
+        let (sender, _receiver) = testee.open(peer_id, remote_host).await?;
+
+        let executor = ExecutorDescriptor {
+            kind: ExecutorKind::Executable,
+            results_url: None,
+        };
+        let state = PeerConfigurationState {
+            executors: vec![
+                ParameterState {
+                    id: executor.parameter_identifier(),
+                    state: ParameterStateKind::Present,
+                    value: executor,
+                },
+            ],
+            cluster_teardown: None,
+        };
+        sender.send(upstream::Message::ReportPeerConfigurationState(ReportPeerConfigurationState {
+            state: Some(Clone::clone(&state).into()),
+        })).await?;
+
+        tokio::time::sleep(options.peer_disconnect_timeout / 2).await;
+        assert_that!(resources_manager.get::<PeerConfigurationState>(peer_id).await, some(eq(state)));
+
+        testee.remove_peer(peer_id).await?;
+        assert_that!(resources_manager.get::<PeerConfigurationState>(peer_id).await, none());
+
+        Ok(())
+    }
+
     async fn do_ping(sender: &mpsc::Sender<upstream::Message>, receiver: &mut Receiver<Downstream>) {
         sender.send(upstream::Message::Ping(Ping {})).await
             .unwrap();
@@ -0,0 +1,335 @@
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use config::Config;
+use pem::Pem;
+use ring::rand::SystemRandom;
+use ring::signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, KeyPair};
+use sha2::{Digest, Sha256};
+use simple_asn1::{ASN1Block, ASN1Class, BigInt, BigUint, oid, OID};
+use time::{OffsetDateTime, PrimitiveDateTime};
+use tracing::info;
+use uuid::Uuid;
+
+use opendut_types::peer::certificate::{IssuedPeerCertificate, PeerCertificate, PeerCertificateId, PeerCertificateKey};
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::Certificate;
+use opendut_util::project;
+
+pub type PeerCertificateAuthorityRef = Arc<PeerCertificateAuthority>;
+
+const CERTIFICATE_AUTHORITY_NAME: &str = "openDuT CARL Peer CA";
+const CERTIFICATE_AUTHORITY_VALIDITY: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+/// Internal certificate authority of CARL, which issues the client certificates, with which peers authenticate themselves via mutual TLS.
+///
+/// It is generated when CARL starts for the first time and kept in the configured [`PeerCertificateAuthorityFiles`].
+#[derive(Clone)]
+pub struct PeerCertificateAuthority {
+    /// DER-encoded, self-signed certificate.
+    certificate: Vec<u8>,
+    /// PKCS#8-encoded ECDSA P-256 key.
+    key: Vec<u8>,
+}
+
+impl PeerCertificateAuthority {
+
+    pub fn generate() -> Result<Self, CertificateAuthorityError> {
+        let rng = SystemRandom::new();
+        let key = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)
+            .map_err(|_| CertificateAuthorityError::new("Failed to generate key."))?;
+        let key = key.as_ref().to_vec();
+        let key_pair = key_pair(&key)?;
+
+        let not_before = SystemTime::now();
+        let certificate = sign_certificate(TbsCertificate {
+            serial: Uuid::new_v4().as_u128(),
+            subject: CERTIFICATE_AUTHORITY_NAME,
+            public_key: key_pair.public_key().as_ref(),
+            not_before,
+            not_after: not_before + CERTIFICATE_AUTHORITY_VALIDITY,
+            extensions: vec![
+                extension(oid!(2, 5, 29, 19), true, sequence(vec![ASN1Block::Boolean(0, true)])), // basicConstraints: CA
+                extension(oid!(2, 5, 29, 15), true, ASN1Block::BitString(0, 7, vec![0x06])), // keyUsage: keyCertSign, cRLSign
+            ],
+        }, &key_pair)?;
+
+        Ok(Self { certificate, key })
+    }
+
+    pub fn from_der(certificate: Vec<u8>, key: Vec<u8>) -> Result<Self, CertificateAuthorityError> {
+        key_pair(&key)?;
+        Ok(Self { certificate, key })
+    }
+
+    /// Loads the certificate authority from the given files or generates a new one into them, if neither of them exists yet.
+    pub fn load_or_generate(files: &PeerCertificateAuthorityFiles) -> Result<PeerCertificateAuthorityRef, CertificateAuthorityError> {
+        let certificate_authority = match (files.certificate.exists(), files.key.exists()) {
+            (true, true) => {
+                let certificate = read_pem(&files.certificate, "CERTIFICATE")?;
+                let key = read_pem(&files.key, "PRIVATE KEY")?;
+                PeerCertificateAuthority::from_der(certificate, key)?
+            }
+            (false, false) => {
+                let certificate_authority = PeerCertificateAuthority::generate()?;
+                write_pem(&files.certificate, &Pem::new("CERTIFICATE", certificate_authority.certificate()), 0o644)?;
+                write_pem(&files.key, &Pem::new("PRIVATE KEY", certificate_authority.key()), 0o600)?;
+                info!("Generated new certificate authority for peers at '{}'.", files.certificate.display());
+                certificate_authority
+            }
+            _ => return Err(CertificateAuthorityError::new(format!(
+                "Either both or neither of the certificate file at '{}' and the key file at '{}' must exist.",
+                files.certificate.display(), files.key.display(),
+            ))),
+        };
+        Ok(Arc::new(certificate_authority))
+    }
+
+    pub fn certificate(&self) -> &[u8] {
+        &self.certificate
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Issues a client certificate for the peer, with the peer's id as common name.
+    pub fn issue(&self, peer_id: PeerId, issued_at: SystemTime, validity: Duration) -> Result<(PeerCertificate, IssuedPeerCertificate), CertificateAuthorityError> {
+        let rng = SystemRandom::new();
+        let key = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)
+            .map_err(|_| CertificateAuthorityError::new("Failed to generate key."))?;
+        let public_key = key_pair(key.as_ref())?.public_key().as_ref().to_vec();
+
+        let id = PeerCertificateId::random();
+        let expires_at = issued_at + validity;
+        let subject = peer_id.to_string();
+
+        let certificate = sign_certificate(TbsCertificate {
+            serial: id.0.as_u128(),
+            subject: &subject,
+            public_key: &public_key,
+            not_before: issued_at,
+            not_after: expires_at,
+            extensions: vec![
+                extension(oid!(2, 5, 29, 19), true, sequence(vec![])), // basicConstraints: no CA
+                extension(oid!(2, 5, 29, 15), true, ASN1Block::BitString(0, 1, vec![0x80])), // keyUsage: digitalSignature
+                extension(oid!(2, 5, 29, 37), false, sequence(vec![ASN1Block::ObjectIdentifier(0, oid!(1, 3, 6, 1, 5, 5, 7, 3, 2))])), // extKeyUsage: clientAuth
+            ],
+        }, &key_pair(&self.key)?)?;
+
+        let issued = IssuedPeerCertificate {
+            id,
+            peer_id,
+            fingerprint: fingerprint(&certificate),
+            issued_at,
+            expires_at,
+        };
+        let certificate = PeerCertificate {
+            certificate: Certificate(Pem::new("CERTIFICATE", certificate)),
+            key: PeerCertificateKey(Pem::new("PRIVATE KEY", key.as_ref())),
+        };
+        Ok((certificate, issued))
+    }
+}
+
+/// Files containing the certificate and private key of the [`PeerCertificateAuthority`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerCertificateAuthorityFiles {
+    pub certificate: PathBuf,
+    pub key: PathBuf,
+}
+impl PeerCertificateAuthorityFiles {
+    pub fn load(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            certificate: project::make_path_absolute(config.get_string("peer.certificate.authority.certificate")?)?,
+            key: project::make_path_absolute(config.get_string("peer.certificate.authority.key")?)?,
+        })
+    }
+}
+
+/// Hex-encoded SHA-256 hash of a DER-encoded certificate, under which CARL keeps track of the certificates it issued.
+pub fn fingerprint(certificate: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(certificate))
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Error in certificate authority for peers: {message}")]
+pub struct CertificateAuthorityError {
+    message: String,
+}
+impl CertificateAuthorityError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PeerCertificateOptions {
+    pub validity: Duration,
+    /// How long before its expiry a certificate is renewed for a connected peer.
+    pub renewal_before_expiry: Duration,
+}
+impl PeerCertificateOptions {
+    pub fn load(config: &Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let validity = config.get::<u64>("peer.certificate.validity.ms")?;
+        let renewal_before_expiry = config.get::<u64>("peer.certificate.renewal.before.expiry.ms")?;
+        if renewal_before_expiry >= validity {
+            return Err(config::ConfigError::Message(String::from(
+                "Field 'peer.certificate.renewal.before.expiry.ms' must be smaller than 'peer.certificate.validity.ms'."
+            )).into());
+        }
+        Ok(Self {
+            validity: Duration::from_millis(validity),
+            renewal_before_expiry: Duration::from_millis(renewal_before_expiry),
+        })
+    }
+}
+
+struct TbsCertificate<'a> {
+    serial: u128,
+    subject: &'a str,
+    public_key: &'a [u8],
+    not_before: SystemTime,
+    not_after: SystemTime,
+    extensions: Vec<ASN1Block>,
+}
+
+fn sign_certificate(tbs: TbsCertificate, issuer_key: &EcdsaKeyPair) -> Result<Vec<u8>, CertificateAuthorityError> {
+    let signature_algorithm = sequence(vec![ASN1Block::ObjectIdentifier(0, oid!(1, 2, 840, 10045, 4, 3, 2))]); // ecdsa-with-SHA256
+
+    let tbs_certificate = sequence(vec![
+        ASN1Block::Explicit(ASN1Class::ContextSpecific, 0, BigUint::from(0u8), Box::new(ASN1Block::Integer(0, BigInt::from(2)))), // v3
+        ASN1Block::Integer(0, BigInt::from(tbs.serial)),
+        Clone::clone(&signature_algorithm),
+        name(CERTIFICATE_AUTHORITY_NAME),
+        sequence(vec![
+            ASN1Block::UTCTime(0, utc_time(tbs.not_before)),
+            ASN1Block::UTCTime(0, utc_time(tbs.not_after)),
+        ]),
+        name(tbs.subject),
+        sequence(vec![
+            sequence(vec![
+                ASN1Block::ObjectIdentifier(0, oid!(1, 2, 840, 10045, 2, 1)), // ecPublicKey
+                ASN1Block::ObjectIdentifier(0, oid!(1, 2, 840, 10045, 3, 1, 7)), // prime256v1
+            ]),
+            bit_string(tbs.public_key),
+        ]),
+        ASN1Block::Explicit(ASN1Class::ContextSpecific, 0, BigUint::from(3u8), Box::new(sequence(tbs.extensions))),
+    ]);
+
+    let signature = issuer_key.sign(&SystemRandom::new(), &to_der(&tbs_certificate)?)
+        .map_err(|_| CertificateAuthorityError::new("Failed to sign certificate."))?;
+
+    to_der(&sequence(vec![
+        tbs_certificate,
+        signature_algorithm,
+        bit_string(signature.as_ref()),
+    ]))
+}
+
+fn read_pem(path: &Path, tag: &str) -> Result<Vec<u8>, CertificateAuthorityError> {
+    let invalid = |message: String| CertificateAuthorityError::new(format!("Failed to read file at '{}': {message}", path.display()));
+
+    let pem = fs::read(path).map_err(|cause| invalid(cause.to_string()))?;
+    let pem = pem::parse(pem).map_err(|cause| invalid(cause.to_string()))?;
+    if pem.tag() != tag {
+        return Err(invalid(format!("Expected PEM with tag '{tag}', but found '{}'.", pem.tag())));
+    }
+    Ok(pem.into_contents())
+}
+
+/// Creates the file with the given permissions, so the private key is never readable by others, not even briefly.
+fn write_pem(path: &Path, pem: &Pem, mode: u32) -> Result<(), CertificateAuthorityError> {
+    let failed = |cause: std::io::Error| CertificateAuthorityError::new(format!("Failed to write file at '{}': {cause}", path.display()));
+
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(failed)?;
+    }
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(mode)
+        .open(path)
+        .and_then(|mut file| file.write_all(pem::encode(pem).as_bytes()))
+        .map_err(failed)
+}
+
+fn key_pair(pkcs8: &[u8]) -> Result<EcdsaKeyPair, CertificateAuthorityError> {
+    EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8, &SystemRandom::new())
+        .map_err(|cause| CertificateAuthorityError::new(format!("Invalid key: {cause}")))
+}
+
+fn name(common_name: &str) -> ASN1Block {
+    sequence(vec![
+        ASN1Block::Set(0, vec![
+            sequence(vec![
+                ASN1Block::ObjectIdentifier(0, oid!(2, 5, 4, 3)), // commonName
+                ASN1Block::UTF8String(0, String::from(common_name)),
+            ]),
+        ]),
+    ])
+}
+
+fn extension(id: OID, critical: bool, value: ASN1Block) -> ASN1Block {
+    let value = simple_asn1::to_der(&value).expect("Extension values should be encodable.");
+    let mut extension = vec![ASN1Block::ObjectIdentifier(0, id)];
+    if critical { //DER requires omitting the default value FALSE
+        extension.push(ASN1Block::Boolean(0, true));
+    }
+    extension.push(ASN1Block::OctetString(0, value));
+    sequence(extension)
+}
+
+fn sequence(blocks: Vec<ASN1Block>) -> ASN1Block {
+    ASN1Block::Sequence(0, blocks)
+}
+
+fn bit_string(bytes: &[u8]) -> ASN1Block {
+    ASN1Block::BitString(0, bytes.len() * 8, bytes.to_vec())
+}
+
+fn utc_time(time: SystemTime) -> PrimitiveDateTime {
+    let time = OffsetDateTime::from(time);
+    PrimitiveDateTime::new(time.date(), time.time())
+}
+
+fn to_der(block: &ASN1Block) -> Result<Vec<u8>, CertificateAuthorityError> {
+    simple_asn1::to_der(block)
+        .map_err(|cause| CertificateAuthorityError::new(cause.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_generate_the_certificate_authority_into_files_and_load_it_from_them() -> anyhow::Result<()> {
+        let temp = tempfile::tempdir()?;
+        let files = PeerCertificateAuthorityFiles {
+            certificate: temp.path().join("peer-ca/ca.pem"),
+            key: temp.path().join("peer-ca/ca.key"),
+        };
+
+        let generated = PeerCertificateAuthority::load_or_generate(&files)?;
+        let key_mode = fs::metadata(&files.key)?.permissions().mode();
+        assert_that!(key_mode & 0o777, eq(0o600));
+
+        let loaded = PeerCertificateAuthority::load_or_generate(&files)?;
+        assert_that!(loaded.certificate(), eq(generated.certificate()));
+        assert_that!(loaded.key(), eq(generated.key()));
+
+        fs::remove_file(&files.certificate)?;
+        let result = PeerCertificateAuthority::load_or_generate(&files);
+        assert_that!(result.is_err(), eq(true));
+
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
 pub mod broker;
+pub mod certificate_authority;
+pub mod state;
@@ -0,0 +1,50 @@
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
+
+use crate::resources::Resources;
+
+/// Determines the state of a peer, which just connected.
+///
+/// A peer, which is assigned to a cluster, stays a member of that cluster across reconnects.
+pub fn initial_up_state(resources: &Resources, peer_id: PeerId) -> PeerUpState {
+    let is_member = resources.get::<PeerConfiguration>(peer_id)
+        .map(|configuration| configuration.cluster_assignment.is_some())
+        .unwrap_or(false);
+
+    if is_member {
+        PeerUpState::Blocked(PeerBlockedState::Member)
+    } else {
+        PeerUpState::Available
+    }
+}
+
+/// Changes the state of a peer, which is up. The state of a peer, which is down, is left unchanged.
+pub fn set_up_state(resources: &mut Resources, peer_id: PeerId, up_state: PeerUpState) {
+    if let Some(PeerState::Up { remote_host, .. }) = resources.get::<PeerState>(peer_id) {
+        resources.insert(peer_id, PeerState::Up { inner: up_state, remote_host });
+    }
+}
+
+/// Checks that the peer is not blocked by a cluster, so that it may be changed.
+///
+/// Returns the actual state and the states the peer is required to be in, otherwise.
+pub fn require_unblocked(resources: &Resources, peer_id: PeerId) -> Result<(), (PeerState, Vec<PeerState>)> {
+    match resources.get::<PeerState>(peer_id) {
+        Some(actual_state @ PeerState::Up { inner: PeerUpState::Blocked(_), remote_host }) => {
+            Err((actual_state, vec![
+                PeerState::Down,
+                PeerState::Up { inner: PeerUpState::Available, remote_host },
+            ]))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the cluster, which the peer is assigned to, independent of whether the peer is currently connected.
+pub fn assigned_cluster(resources: &Resources, peer_id: PeerId) -> Option<ClusterId> {
+    resources.get::<PeerConfiguration>(peer_id)
+        .and_then(|configuration| configuration.cluster_assignment)
+        .map(|assignment| assignment.id)
+}
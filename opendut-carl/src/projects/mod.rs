@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::ProjectName;
+use opendut_types::service_account::{ApiToken, ApiTokenRole};
+use opendut_types::topology::DeviceId;
+
+use crate::auth::CurrentUser;
+use crate::resources::Resources;
+
+/// The projects, whose resources a caller may see and change.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProjectScope {
+    /// Authentication is disabled or the caller is an administrator.
+    All,
+    Projects(HashSet<ProjectName>),
+}
+
+impl ProjectScope {
+
+    /// Determines the projects of the user from the user's groups, which start with the given prefix, e.g. `/projects/brakes`.
+    /// Every user is a member of the default project.
+    pub fn of_user(user: &CurrentUser, group_prefix: &str) -> Self {
+        let projects = user.claims.additional_claims().groups.iter()
+            .filter_map(|group| group.strip_prefix(group_prefix))
+            .filter_map(|project| ProjectName::try_from(project).ok())
+            .chain([ProjectName::default()])
+            .collect();
+        ProjectScope::Projects(projects)
+    }
+
+    /// Determines the projects accessible with the API token. Administrators may access all projects.
+    pub fn of_api_token(api_token: &ApiToken) -> Self {
+        match api_token.role {
+            ApiTokenRole::Admin => ProjectScope::All,
+            ApiTokenRole::Viewer | ApiTokenRole::Operator => ProjectScope::Projects(Clone::clone(&api_token.projects)),
+        }
+    }
+
+    pub fn contains(&self, project: &ProjectName) -> bool {
+        match self {
+            ProjectScope::All => true,
+            ProjectScope::Projects(projects) => projects.contains(project),
+        }
+    }
+
+    /// Checks that the caller may change resources of the given project.
+    pub fn require(&self, project: &ProjectName) -> Result<(), NotAProjectMember> {
+        if self.contains(project) {
+            Ok(())
+        } else {
+            Err(NotAProjectMember { project: Clone::clone(project) })
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("CARL says, you are not a member of the project '{project}'!")]
+pub struct NotAProjectMember {
+    pub project: ProjectName,
+}
+
+impl From<NotAProjectMember> for tonic::Status {
+    fn from(error: NotAProjectMember) -> Self {
+        tonic::Status::permission_denied(error.to_string())
+    }
+}
+
+/// Returns the projects the caller of the request may access.
+pub fn scope_of<T>(request: &tonic::Request<T>) -> ProjectScope {
+    request.extensions().get::<ProjectScope>()
+        .cloned()
+        .unwrap_or(ProjectScope::All)
+}
+
+pub fn project_of_peer(resources: &Resources, peer_id: PeerId) -> Option<ProjectName> {
+    resources.get::<PeerDescriptor>(peer_id)
+        .map(|peer| peer.project)
+}
+
+/// Devices belong to the project of the peer they are attached to.
+pub fn project_of_device(resources: &Resources, device_id: DeviceId) -> Option<ProjectName> {
+    resources.iter::<PeerDescriptor>()
+        .find(|peer| peer.topology.devices.iter().any(|device| device.id == device_id))
+        .map(|peer| Clone::clone(&peer.project))
+}
+
+/// Deployments and states of a cluster belong to the project of its configuration.
+pub fn project_of_cluster(resources: &Resources, cluster_id: ClusterId) -> Option<ProjectName> {
+    resources.get::<ClusterConfiguration>(cluster_id)
+        .map(|configuration| configuration.project)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use openidconnect::{Audience, IssuerUrl, StandardClaims, SubjectIdentifier};
+
+    use crate::auth::{Claims, MyAdditionalClaims};
+
+    use super::*;
+
+    #[test]
+    fn should_determine_projects_of_user_from_groups() -> anyhow::Result<()> {
+        let user = CurrentUser {
+            name: String::from("user"),
+            claims: Claims::new(
+                IssuerUrl::new(String::from("https://keycloak/realms/opendut/"))?,
+                vec![Audience::new(String::from("account"))],
+                chrono::Utc::now(),
+                chrono::Utc::now(),
+                StandardClaims::new(SubjectIdentifier::new(String::from("user"))),
+                MyAdditionalClaims {
+                    roles: vec![],
+                    groups: vec![String::from("/projects/brakes"), String::from("/testgroup")],
+                },
+            ),
+        };
+
+        let testee = ProjectScope::of_user(&user, "/projects/");
+
+        assert!(testee.contains(&ProjectName::try_from("brakes")?));
+        assert!(testee.contains(&ProjectName::default()));
+        assert!(!testee.contains(&ProjectName::try_from("steering")?));
+        assert_that!(testee.require(&ProjectName::try_from("steering")?), err(anything()));
+        Ok(())
+    }
+}
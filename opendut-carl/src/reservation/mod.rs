@@ -0,0 +1,89 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+
+use tracing::debug;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::reservation::Reservation;
+use opendut_types::topology::DeviceId;
+
+use crate::audit::AuditLogRef;
+use crate::cluster::manager::ClusterManagerRef;
+use crate::resources::Resources;
+
+/// Returns a reservation of another user, which claims any of the given peers or devices at the given time.
+pub fn find_blocking_reservation(resources: &Resources, peers: &HashSet<PeerId>, devices: &HashSet<DeviceId>, user: &str, time: SystemTime) -> Option<Reservation> {
+    resources.iter::<Reservation>()
+        .filter(|reservation| reservation.user != user)
+        .filter(|reservation| reservation.is_active_at(time))
+        .find(|reservation| reservation.claims_any(peers, devices))
+        .cloned()
+}
+
+/// Returns a deployment of another user, which uses any of the peers or devices claimed by the given reservation.
+pub fn find_blocking_deployment(resources: &Resources, reservation: &Reservation) -> Option<ClusterDeployment> {
+    resources.iter::<ClusterDeployment>()
+        .filter(|deployment| deployment.deployed_by != reservation.user)
+        .find(|deployment| {
+            let (peers, devices) = cluster_resources(resources, deployment.id);
+            reservation.claims_any(&peers, &devices)
+        })
+        .cloned()
+}
+
+/// Returns the peer of each device, as reserving a peer reserves all of its devices.
+pub fn device_peers(resources: &Resources) -> HashMap<DeviceId, PeerId> {
+    resources.iter::<PeerDescriptor>()
+        .flat_map(|peer| peer.topology.devices.iter().map(|device| (device.id, peer.id)))
+        .collect()
+}
+
+/// Returns the peers and devices used by the given cluster.
+pub fn cluster_resources(resources: &Resources, cluster_id: ClusterId) -> (HashSet<PeerId>, HashSet<DeviceId>) {
+    let mut peers = resources.iter::<PeerConfiguration>()
+        .filter_map(|configuration| configuration.cluster_assignment.as_ref())
+        .filter(|assignment| assignment.id == cluster_id)
+        .flat_map(|assignment| assignment.assignments.iter().map(|member| member.peer_id))
+        .collect::<HashSet<_>>();
+
+    let mut devices = resources.get::<ClusterDeployment>(cluster_id)
+        .map(|deployment| deployment.devices)
+        .unwrap_or_default();
+
+    if let Some(configuration) = resources.get::<ClusterConfiguration>(cluster_id) {
+        peers.insert(configuration.leader);
+        devices.extend(configuration.devices);
+    }
+
+    (peers, devices)
+}
+
+/// Periodically undeploys the clusters of expired reservations.
+pub fn spawn_expiry_task(cluster_manager: ClusterManagerRef, audit_log: AuditLogRef, options: ReservationExpiryOptions) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(options.check_interval);
+        loop {
+            interval.tick().await;
+            debug!("Checking for expired reservations.");
+            cluster_manager.lock().await.expire_reservations(&audit_log).await;
+        }
+    });
+}
+
+#[derive(Clone)]
+pub struct ReservationExpiryOptions {
+    pub check_interval: Duration,
+}
+impl ReservationExpiryOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let check_interval = Duration::from_millis(
+            config.get::<u64>("reservation.expiry.check.interval.ms")?
+        );
+
+        Ok(ReservationExpiryOptions {
+            check_interval,
+        })
+    }
+}
@@ -1,12 +1,25 @@
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterPortAllocation};
+use opendut_types::cluster::state::ClusterState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
-use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+use opendut_types::peer::certificate::{IssuedPeerCertificate, PeerCertificateId};
+use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2, PeerConfigurationState};
+use opendut_types::peer::setup::{IssuedPeerSetup, PeerSetupNonce};
 use opendut_types::peer::state::PeerState;
+use opendut_types::reservation::{Reservation, ReservationId};
 use opendut_types::resources::Id;
+use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccount, ServiceAccountId};
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
+use opendut_types::webhook::{Webhook, WebhookId};
 
 use crate::resources::IntoId;
 
+impl<R> IntoId<R> for Id
+where R: std::any::Any + Send + Sync {
+    fn into_id(self) -> Id {
+        self
+    }
+}
+
 impl IntoId<ClusterConfiguration> for ClusterId {
     fn into_id(self) -> Id {
         Id::from(self.0)
@@ -17,6 +30,16 @@ impl IntoId<ClusterDeployment> for ClusterId {
         Id::from(self.0)
     }
 }
+impl IntoId<ClusterState> for ClusterId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<ClusterPortAllocation> for ClusterId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
 
 impl IntoId<DeviceDescriptor> for DeviceId {
     fn into_id(self) -> Id {
@@ -45,3 +68,44 @@ impl IntoId<PeerConfiguration2> for PeerId {
         Id::from(self.uuid)
     }
 }
+impl IntoId<PeerConfigurationState> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
+
+impl IntoId<IssuedPeerSetup> for PeerSetupNonce {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<IssuedPeerCertificate> for PeerCertificateId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<Reservation> for ReservationId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<ServiceAccount> for ServiceAccountId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<ApiToken> for ApiTokenId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<Webhook> for WebhookId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
@@ -1,14 +1,24 @@
-use std::any::Any;
 use std::sync::Arc;
 
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, oneshot, RwLock, RwLockWriteGuard};
+use tokio_stream::{Stream, StreamExt};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tracing::{debug, error, warn};
 
-use crate::resources::{IntoId, Resources};
+use opendut_types::resources::{Id, Version};
+
+use crate::resources::{IntoId, Resource, ResourceChange, ResourceEvent, ResourceOperation, Resources, Staged};
+use crate::resources::persistence::{LoadResourcesError, Persistence, ResourcesStorage, StorageError};
 
 pub type ResourcesManagerRef = Arc<ResourcesManager>;
 
+const EVENTS_CAPACITY: usize = 1024;
+
 pub struct ResourcesManager {
     state: RwLock<State>,
+    writer: Option<StorageWriter>,
+    events: broadcast::Sender<ResourceChange>,
 }
 
 struct State {
@@ -21,24 +31,65 @@ impl ResourcesManager {
         Arc::new(Self {
             state: RwLock::new(State {
                 resources: Default::default()
-            })
+            }),
+            writer: None,
+            events: broadcast::channel(EVENTS_CAPACITY).0,
         })
     }
 
-    pub async fn insert<R>(&self, id: impl IntoId<R>, resource: R)
-    where R: Any  + Send + Sync {
-        let mut state = self.state.write().await;
-        state.resources.insert(id, resource);
+    /// Creates a [`ResourcesManager`], which restores previously persisted resources and persists every change.
+    pub fn load(persistence: Persistence) -> Result<ResourcesManagerRef, LoadResourcesError> {
+        let mut resources = Resources::default();
+
+        let writer = match persistence {
+            Persistence::Enabled { storage } => {
+                let stored_resources = storage.load()?;
+                let count = stored_resources.len();
+
+                for stored in stored_resources {
+                    let kind = Clone::clone(&stored.kind);
+                    let id = stored.id;
+                    crate::resources::persistence::restore(&mut resources, stored)
+                        .map_err(|cause| LoadResourcesError::Decode { kind, id, cause })?;
+                }
+                resources.take_changes(); //restored resources are already persisted
+
+                debug!("Restored {count} resources from storage.");
+                Some(StorageWriter::spawn(storage).map_err(LoadResourcesError::Writer)?)
+            }
+            Persistence::Disabled => None,
+        };
+
+        Ok(Arc::new(Self {
+            state: RwLock::new(State { resources }),
+            writer,
+            events: broadcast::channel(EVENTS_CAPACITY).0,
+        }))
+    }
+
+    pub async fn insert<R>(&self, id: impl IntoId<R>, resource: R) -> Result<(), PersistenceError>
+    where R: Resource {
+        let pending = {
+            let mut state = self.state.write().await;
+            state.resources.insert(id, resource);
+            self.publish_changes(&mut state.resources)
+        };
+        pending.wait().await
     }
 
-    pub async fn remove<R>(&self, id: impl IntoId<R>) -> Option<R>
-    where R: Any  + Send + Sync {
-        let mut state = self.state.write().await;
-        state.resources.remove(id)
+    pub async fn remove<R>(&self, id: impl IntoId<R>) -> Result<Option<R>, PersistenceError>
+    where R: Resource {
+        let (result, pending) = {
+            let mut state = self.state.write().await;
+            let result = state.resources.remove(id);
+            (result, self.publish_changes(&mut state.resources))
+        };
+        pending.wait().await?;
+        Ok(result)
     }
 
     pub async fn get<R>(&self, id: impl IntoId<R>) -> Option<R>
-    where R: Any  + Send + Sync + Clone {
+    where R: Resource + Clone {
         let state = self.state.read().await;
         state.resources.get(id)
     }
@@ -49,17 +100,229 @@ impl ResourcesManager {
         f(&state.resources)
     }
 
-    pub async fn resources_mut<F, T>(&self, f: F) -> T
+    /// Applies `f` to the resources and waits until its changes are persisted.
+    ///
+    /// If persisting fails, the changes remain applied in memory, but the error is returned instead of the result of `f`.
+    pub async fn resources_mut<F, T>(&self, f: F) -> Result<T, PersistenceError>
     where F: FnOnce(&mut Resources) -> T {
-        let mut state = self.state.write().await;
-        f(&mut state.resources)
+        let (result, pending) = {
+            let mut state = self.state.write().await;
+            let result = f(&mut state.resources);
+            (result, self.publish_changes(&mut state.resources))
+        };
+        pending.wait().await?;
+        Ok(result)
+    }
+
+    /// Begins a [`Transaction`], waiting for any other access to the resources to finish first.
+    pub async fn begin(&self) -> Transaction<'_> {
+        let state = self.state.write().await;
+        Transaction {
+            manager: self,
+            state: Some(state),
+            staged: Some(Staged::default()),
+        }
+    }
+
+    /// Subscribes to all changes of resources of type `R`, which happen after this call.
+    ///
+    /// If a subscriber cannot keep up with the changes, it receives a [`SubscriptionLagged`] error,
+    /// after which it should re-read the resources it is interested in.
+    pub fn subscribe<R>(&self) -> impl Stream<Item=Result<ResourceEvent<R>, SubscriptionLagged>> + Send + 'static
+    where R: Resource {
+        BroadcastStream::new(self.events.subscribe())
+            .filter_map(|change| match change {
+                Ok(change) => ResourceEvent::from_change(change).map(Ok),
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => Some(Err(SubscriptionLagged { skipped })),
+            })
+    }
+
+    /// Hands the changes made to the resources to the [`StorageWriter`] and notifies subscribers about them.
+    ///
+    /// Must be called while holding the write lock, so that the writes are queued in the order of the changes.
+    /// The returned [`PendingWrites`] should be awaited only after releasing the lock.
+    fn publish_changes(&self, resources: &mut Resources) -> PendingWrites {
+        let changes = resources.take_changes();
+
+        let pending = match &self.writer {
+            Some(writer) => {
+                let writes = changes.iter()
+                    .filter_map(|change| match &change.operation {
+                        ResourceOperation::Created { encoded: Some(encoded), version, .. }
+                        | ResourceOperation::Updated { encoded: Some(encoded), version, .. } => Some(StorageWrite::Store { kind: change.kind, id: change.id, version: *version, encoded: Clone::clone(encoded) }),
+                        ResourceOperation::Created { encoded: None, .. }
+                        | ResourceOperation::Updated { encoded: None, .. } => None, //volatile resource
+                        ResourceOperation::Removed => Some(StorageWrite::Remove { kind: change.kind, id: change.id }),
+                    })
+                    .collect::<Vec<_>>();
+                writer.enqueue(writes)
+            }
+            None => PendingWrites::None,
+        };
+
+        for change in changes {
+            let _ = self.events.send(change); //fails only when there are no subscribers
+        }
+        pending
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PersistenceError {
+    #[error("Failed to persist changes of resources: {0}")]
+    Storage(#[from] StorageError),
+    #[error("Failed to persist changes of resources, because the storage writer stopped.")]
+    WriterStopped,
+}
+
+enum StorageWrite {
+    Store { kind: &'static str, id: Id, version: Version, encoded: Vec<u8> },
+    Remove { kind: &'static str, id: Id },
+}
+
+struct WriteBatch {
+    writes: Vec<StorageWrite>,
+    result: oneshot::Sender<Result<(), StorageError>>,
+}
+
+/// Writes changes to the [`ResourcesStorage`] on a dedicated thread, since the storage may block.
+///
+/// Batches are written in the order in which they were enqueued.
+/// The thread stops once the [`ResourcesManager`] is dropped.
+struct StorageWriter {
+    batches: std::sync::mpsc::Sender<WriteBatch>,
+}
+
+impl StorageWriter {
+    fn spawn(storage: Arc<dyn ResourcesStorage + Send + Sync>) -> std::io::Result<Self> {
+        let (batches, receiver) = std::sync::mpsc::channel::<WriteBatch>();
+
+        std::thread::Builder::new()
+            .name(String::from("resources-storage-writer"))
+            .spawn(move || {
+                for batch in receiver {
+                    let result = batch.writes.into_iter()
+                        .try_for_each(|write| match write {
+                            StorageWrite::Store { kind, id, version, encoded } => storage.store(kind, id, version, &encoded),
+                            StorageWrite::Remove { kind, id } => storage.remove(kind, id),
+                        });
+                    if let Err(cause) = &result {
+                        error!("Failed to persist change of resources:\n  {cause}");
+                    }
+                    let _ = batch.result.send(result); //fails only when the caller is not interested in the result anymore
+                }
+            })?;
+
+        Ok(Self { batches })
+    }
+
+    fn enqueue(&self, writes: Vec<StorageWrite>) -> PendingWrites {
+        if writes.is_empty() {
+            return PendingWrites::None;
+        }
+        let (result, receiver) = oneshot::channel();
+        match self.batches.send(WriteBatch { writes, result }) {
+            Ok(()) => PendingWrites::Queued(receiver),
+            Err(_) => PendingWrites::WriterStopped,
+        }
+    }
+}
+
+#[must_use = "Pending writes should be awaited to learn whether persisting the changes succeeded."]
+enum PendingWrites {
+    None,
+    Queued(oneshot::Receiver<Result<(), StorageError>>),
+    WriterStopped,
+}
+
+impl PendingWrites {
+    async fn wait(self) -> Result<(), PersistenceError> {
+        match self {
+            PendingWrites::None => Ok(()),
+            PendingWrites::Queued(receiver) => match receiver.await {
+                Ok(result) => result.map_err(PersistenceError::from),
+                Err(_) => Err(PersistenceError::WriterStopped),
+            },
+            PendingWrites::WriterStopped => Err(PersistenceError::WriterStopped),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("Subscriber could not keep up with changes of resources and skipped {skipped} events.")]
+pub struct SubscriptionLagged {
+    pub skipped: u64,
+}
+
+/// Groups changes to the resources, so that they can be reverted as a whole.
+///
+/// A transaction holds exclusive access to the resources until it is committed or aborted,
+/// so other readers and writers never observe its staged changes. It should therefore not wait for external services.
+/// A transaction, which is neither committed nor aborted, is aborted when dropped.
+#[must_use = "A transaction should either be committed or aborted."]
+pub struct Transaction<'a> {
+    manager: &'a ResourcesManager,
+    state: Option<RwLockWriteGuard<'a, State>>,
+    staged: Option<Staged>,
+}
+
+impl Transaction<'_> {
+
+    pub async fn get<R>(&self, id: impl IntoId<R>) -> Option<R>
+    where R: Resource + Clone {
+        self.state().resources.get(id)
+    }
+
+    pub async fn resources_mut<F, T>(&mut self, f: F) -> T
+    where F: FnOnce(&mut Resources) -> T {
+        let staged = self.staged.get_or_insert_with(Default::default);
+        let state = self.state.as_mut().expect("state should be present until the transaction is finished");
+        state.resources.stage(staged, f)
+    }
+
+    /// Commits the staged changes and waits, after releasing the resources, until they are persisted.
+    pub async fn commit(mut self) -> Result<(), PersistenceError> {
+        let pending = {
+            let mut state = self.state.take().expect("state should be present until the transaction is finished");
+            if let Some(staged) = self.staged.take() {
+                state.resources.commit(staged);
+            }
+            self.manager.publish_changes(&mut state.resources)
+        };
+        pending.wait().await
+    }
+
+    pub async fn abort(mut self) {
+        self.rollback();
+    }
+
+    fn state(&self) -> &State {
+        self.state.as_ref().expect("state should be present until the transaction is finished")
+    }
+
+    fn rollback(&mut self) {
+        if let Some(mut state) = self.state.take() {
+            if let Some(staged) = self.staged.take() {
+                state.resources.rollback(staged);
+            }
+            let _ = self.manager.publish_changes(&mut state.resources); //rolling back restores the last published state, so nothing is written
+        }
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if self.staged.is_some() {
+            self.rollback();
+            warn!("Transaction was dropped without being committed or aborted. Rolled back its changes.");
+        }
     }
 }
 
 #[cfg(test)]
 impl ResourcesManager {
     async fn contains<R>(&self, id: impl IntoId<R>) -> bool
-    where R: Any + Send + Sync + Clone {
+    where R: Resource + Clone {
         let state = self.state.read().await;
         state.resources.contains(id)
     }
@@ -81,9 +344,14 @@ mod test {
     use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
     use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
+    use opendut_types::project::ProjectName;
     use opendut_types::topology::Topology;
+    use opendut_types::peer::state::PeerState;
+    use opendut_types::resources::Version;
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
+    use crate::resources::persistence::file::FileStorage;
+
     use super::*;
 
     #[tokio::test]
@@ -123,7 +391,8 @@ mod test {
                         results_url: None,
                     }
                 ],
-            }
+            },
+            project: ProjectName::default(),
         };
 
         let cluster_resource_id = ClusterId::random();
@@ -132,15 +401,17 @@ mod test {
             name: ClusterName::try_from("ClusterX032").unwrap(),
             leader: peer.id,
             devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
         };
 
         assert!(testee.is_empty().await);
 
-        testee.insert(peer_resource_id, Clone::clone(&peer)).await;
+        testee.insert(peer_resource_id, Clone::clone(&peer)).await?;
 
         assert!(testee.is_empty().await.not());
 
-        testee.insert(cluster_resource_id, Clone::clone(&cluster_configuration)).await;
+        testee.insert(cluster_resource_id, Clone::clone(&cluster_configuration)).await?;
 
         assert_that!(testee.get::<PeerDescriptor>(peer_resource_id).await, some(eq(Clone::clone(&peer))));
         assert_that!(testee.get::<ClusterConfiguration>(cluster_resource_id).await, some(eq(Clone::clone(&cluster_configuration))));
@@ -149,12 +420,12 @@ mod test {
 
         assert_that!(testee.get::<PeerDescriptor>(PeerId::random()).await, none());
 
-        assert_that!(testee.remove::<PeerDescriptor>(peer_resource_id).await, some(eq(Clone::clone(&peer))));
+        assert_that!(testee.remove::<PeerDescriptor>(peer_resource_id).await?, some(eq(Clone::clone(&peer))));
 
         let id = testee.resources_mut(|resources| {
             resources.insert(peer_resource_id, Clone::clone(&peer));
             peer_resource_id
-        }).await;
+        }).await?;
 
         assert_that!(testee.get::<PeerDescriptor>(id).await, some(eq(Clone::clone(&peer))));
 
@@ -166,14 +437,431 @@ mod test {
         }).await;
 
         testee.resources_mut(|resources| {
-            resources.iter_mut::<PeerDescriptor>()
-                .for_each(|peer| {
-                    peer.name = PeerName::try_from("ChangedPeer").unwrap()
-                });
-        }).await;
+            resources.modify_all::<PeerDescriptor, _>(|peer| {
+                peer.name = PeerName::try_from("ChangedPeer").unwrap()
+            });
+        }).await?;
 
         assert_that!(testee.get::<PeerDescriptor>(peer_resource_id).await, some(not(eq(Clone::clone(&peer)))));
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_restore_persisted_resources() -> Result<()> {
+
+        let directory = tempfile::tempdir()?;
+        let persistence = || Persistence::Enabled {
+            storage: Arc::new(FileStorage::create(directory.path().to_owned()).unwrap())
+        };
+
+        let peer_id = PeerId::random();
+        let peer = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PersistedPeer").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        };
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("PersistedCluster").unwrap(),
+            leader: peer_id,
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+
+        {
+            let testee = ResourcesManager::load(persistence())?;
+            testee.insert(peer_id, Clone::clone(&peer)).await?;
+            testee.insert(cluster_id, Clone::clone(&cluster_configuration)).await?;
+            testee.insert(peer_id, PeerState::Down).await?;
+        }
+
+        let testee = ResourcesManager::load(persistence())?;
+        assert_that!(testee.get::<PeerDescriptor>(peer_id).await, some(eq(Clone::clone(&peer))));
+        assert_that!(testee.get::<ClusterConfiguration>(cluster_id).await, some(eq(Clone::clone(&cluster_configuration))));
+        assert_that!(testee.get::<PeerState>(peer_id).await, none());
+
+        testee.resources_mut(|resources| {
+            resources.remove::<ClusterConfiguration>(cluster_id);
+            resources.modify_all::<PeerDescriptor, _>(|peer| {
+                peer.name = PeerName::try_from("ChangedPeer").unwrap()
+            });
+        }).await?;
+
+        let testee = ResourcesManager::load(persistence())?;
+        assert_that!(testee.get::<ClusterConfiguration>(cluster_id).await, none());
+        assert_that!(testee.get::<PeerDescriptor>(peer_id).await.map(|peer| peer.name), some(eq(PeerName::try_from("ChangedPeer").unwrap())));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_roll_back_aborted_transaction() -> Result<()> {
+
+        let directory = tempfile::tempdir()?;
+        let persistence = || Persistence::Enabled {
+            storage: Arc::new(FileStorage::create(directory.path().to_owned()).unwrap())
+        };
+
+        let existing_cluster_id = ClusterId::random();
+        let existing_cluster = ClusterConfiguration {
+            id: existing_cluster_id,
+            name: ClusterName::try_from("ExistingCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+        let removed_cluster_id = ClusterId::random();
+        let removed_cluster = ClusterConfiguration {
+            id: removed_cluster_id,
+            name: ClusterName::try_from("RemovedCluster").unwrap(),
+            ..Clone::clone(&existing_cluster)
+        };
+        let new_cluster_id = ClusterId::random();
+
+        let testee = ResourcesManager::load(persistence())?;
+        testee.insert(existing_cluster_id, Clone::clone(&existing_cluster)).await?;
+        testee.insert(removed_cluster_id, Clone::clone(&removed_cluster)).await?;
+
+        let mut transaction = testee.begin().await;
+        transaction.resources_mut(|resources| {
+            resources.update::<ClusterConfiguration>(existing_cluster_id)
+                .modify(|cluster| cluster.name = ClusterName::try_from("ChangedCluster").unwrap());
+            resources.remove::<ClusterConfiguration>(removed_cluster_id);
+            resources.insert(new_cluster_id, ClusterConfiguration {
+                id: new_cluster_id,
+                name: ClusterName::try_from("NewCluster").unwrap(),
+                ..Clone::clone(&existing_cluster)
+            });
+        }).await;
+
+        assert_that!(transaction.get::<ClusterConfiguration>(existing_cluster_id).await.map(|cluster| cluster.name), some(eq(ClusterName::try_from("ChangedCluster").unwrap())));
+        assert_that!(transaction.get::<ClusterConfiguration>(removed_cluster_id).await, none());
+        assert_that!(transaction.get::<ClusterConfiguration>(new_cluster_id).await, some(anything()));
+
+        transaction.abort().await;
+
+        assert_that!(testee.get::<ClusterConfiguration>(existing_cluster_id).await, some(eq(Clone::clone(&existing_cluster))));
+        assert_that!(testee.get::<ClusterConfiguration>(removed_cluster_id).await, some(eq(Clone::clone(&removed_cluster))));
+        assert_that!(testee.get::<ClusterConfiguration>(new_cluster_id).await, none());
+
+        let testee = ResourcesManager::load(persistence())?;
+        assert_that!(testee.get::<ClusterConfiguration>(existing_cluster_id).await, some(eq(existing_cluster)));
+        assert_that!(testee.get::<ClusterConfiguration>(removed_cluster_id).await, some(eq(removed_cluster)));
+        assert_that!(testee.get::<ClusterConfiguration>(new_cluster_id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_hide_staged_changes_and_roll_back_dropped_transaction() -> Result<()> {
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("DroppedCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+
+        let testee = ResourcesManager::new();
+
+        {
+            let mut transaction = testee.begin().await;
+            transaction.resources_mut(|resources| {
+                resources.insert(cluster_id, Clone::clone(&cluster_configuration));
+            }).await;
+
+            let read_during_transaction = tokio::time::timeout(std::time::Duration::from_millis(10), testee.get::<ClusterConfiguration>(cluster_id)).await;
+            assert_that!(read_during_transaction.is_err(), eq(true));
+            let write_during_transaction = tokio::time::timeout(std::time::Duration::from_millis(10), testee.resources_mut(|_| ())).await;
+            assert_that!(write_during_transaction.is_err(), eq(true));
+        }
+
+        assert_that!(testee.get::<ClusterConfiguration>(cluster_id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_persist_changes_of_transaction_only_when_committed() -> Result<()> {
+
+        let directory = tempfile::tempdir()?;
+        let persistence = || Persistence::Enabled {
+            storage: Arc::new(FileStorage::create(directory.path().to_owned()).unwrap())
+        };
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("CommittedCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+
+        let testee = ResourcesManager::load(persistence())?;
+
+        let mut transaction = testee.begin().await;
+        transaction.resources_mut(|resources| {
+            resources.insert(cluster_id, Clone::clone(&cluster_configuration));
+        }).await;
+
+        assert_that!(ResourcesManager::load(persistence())?.get::<ClusterConfiguration>(cluster_id).await, none());
+
+        transaction.commit().await?;
+
+        assert_that!(testee.get::<ClusterConfiguration>(cluster_id).await, some(eq(Clone::clone(&cluster_configuration))));
+        assert_that!(ResourcesManager::load(persistence())?.get::<ClusterConfiguration>(cluster_id).await, some(eq(cluster_configuration)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_increase_versions_of_changed_resources() -> Result<()> {
+
+        let directory = tempfile::tempdir()?;
+        let persistence = || Persistence::Enabled {
+            storage: Arc::new(FileStorage::create(directory.path().to_owned()).unwrap())
+        };
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("VersionedCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+        let version = |testee: &ResourcesManagerRef| {
+            let testee = Arc::clone(testee);
+            async move { testee.resources(|resources| resources.version::<ClusterConfiguration>(cluster_id)).await }
+        };
+
+        let testee = ResourcesManager::load(persistence())?;
+        assert_that!(version(&testee).await, eq(Version::NONE));
+
+        testee.insert(cluster_id, Clone::clone(&cluster_configuration)).await?;
+        let created_version = version(&testee).await;
+        assert_that!(created_version, gt(Version::NONE));
+
+        testee.insert(cluster_id, Clone::clone(&cluster_configuration)).await?;
+        let updated_version = version(&testee).await;
+        assert_that!(updated_version, gt(created_version));
+
+        let mut transaction = testee.begin().await;
+        transaction.resources_mut(|resources| resources.insert(cluster_id, Clone::clone(&cluster_configuration))).await;
+        transaction.abort().await;
+        assert_that!(version(&testee).await, eq(updated_version));
+
+        let testee = ResourcesManager::load(persistence())?;
+        assert_that!(version(&testee).await, eq(updated_version));
+
+        testee.insert(cluster_id, Clone::clone(&cluster_configuration)).await?;
+        assert_that!(version(&testee).await, gt(updated_version));
+
+        testee.remove::<ClusterConfiguration>(cluster_id).await?;
+        assert_that!(version(&testee).await, eq(Version::NONE));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_versions_of_resources_which_were_not_changed() -> Result<()> {
+
+        let testee = ResourcesManager::new();
+
+        let cluster_id = ClusterId::random();
+        let other_cluster_id = ClusterId::random();
+        let cluster_configuration = |id: ClusterId, name: &str| ClusterConfiguration {
+            id,
+            name: ClusterName::try_from(name).unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+        testee.insert(cluster_id, cluster_configuration(cluster_id, "UnchangedCluster")).await?;
+        testee.insert(other_cluster_id, cluster_configuration(other_cluster_id, "OtherCluster")).await?;
+
+        let versions = || testee.resources(|resources| {
+            (resources.version::<ClusterConfiguration>(cluster_id), resources.version::<ClusterConfiguration>(other_cluster_id))
+        });
+        let (version, other_version) = versions().await;
+        let mut events = Box::pin(testee.subscribe::<ClusterConfiguration>());
+
+        testee.resources_mut(|resources| {
+            resources.update::<ClusterConfiguration>(cluster_id)
+                .modify(|cluster| cluster.name = ClusterName::try_from("UnchangedCluster").unwrap());
+            resources.modify_all::<ClusterConfiguration, _>(|cluster| {
+                if cluster.id == other_cluster_id {
+                    cluster.name = ClusterName::try_from("ChangedCluster").unwrap();
+                }
+            });
+        }).await?;
+
+        let (unchanged_version, changed_version) = versions().await;
+        assert_that!(unchanged_version, eq(version));
+        assert_that!(changed_version, gt(other_version));
+
+        let id = IntoId::<ClusterConfiguration>::into_id(other_cluster_id);
+        assert_that!(events.next().await, some(ok(matches_pattern!(ResourceEvent::Updated { id: eq(id) }))));
+        testee.remove::<ClusterConfiguration>(cluster_id).await?;
+        assert_that!(events.next().await, some(ok(matches_pattern!(ResourceEvent::Deleted { id: eq(IntoId::<ClusterConfiguration>::into_id(cluster_id)) }))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_publish_events_of_changed_resources() -> Result<()> {
+
+        let testee = ResourcesManager::new();
+        let mut events = Box::pin(testee.subscribe::<ClusterConfiguration>());
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("WatchedCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+        let changed_cluster_configuration = ClusterConfiguration {
+            name: ClusterName::try_from("ChangedCluster").unwrap(),
+            ..Clone::clone(&cluster_configuration)
+        };
+
+        testee.insert(cluster_id, Clone::clone(&cluster_configuration)).await?;
+        testee.insert(PeerId::random(), PeerState::Down).await?;
+        testee.resources_mut(|resources| {
+            resources.update::<ClusterConfiguration>(cluster_id)
+                .modify(|cluster| cluster.name = ClusterName::try_from("ChangedCluster").unwrap());
+        }).await?;
+        let mut transaction = testee.begin().await;
+        transaction.resources_mut(|resources| resources.remove::<ClusterConfiguration>(cluster_id)).await;
+        transaction.abort().await;
+        testee.remove::<ClusterConfiguration>(cluster_id).await?;
+
+        let id = IntoId::<ClusterConfiguration>::into_id(cluster_id);
+        assert_that!(events.next().await, some(ok(eq(ResourceEvent::Created { id, resource: cluster_configuration }))));
+        assert_that!(events.next().await, some(ok(eq(ResourceEvent::Updated { id, resource: changed_cluster_configuration }))));
+        assert_that!(events.next().await, some(ok(eq(ResourceEvent::Deleted { id }))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_write_changes_to_storage_in_the_order_of_their_versions() -> Result<()> {
+
+        let storage = Arc::new(RecordingStorage::default());
+        let testee = ResourcesManager::load(Persistence::Enabled { storage: Clone::clone(&storage) as Arc<dyn ResourcesStorage + Send + Sync> })?;
+
+        let cluster_id = ClusterId::random();
+        let writers = (0..20)
+            .map(|index| {
+                let testee = Arc::clone(&testee);
+                tokio::spawn(async move {
+                    testee.insert(cluster_id, ClusterConfiguration {
+                        id: cluster_id,
+                        name: ClusterName::try_from(format!("Cluster{index:02}")).unwrap(),
+                        leader: PeerId::random(),
+                        devices: HashSet::new(),
+                        device_selectors: vec![],
+                        project: ProjectName::default(),
+                    }).await
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.await.unwrap()?;
+        }
+
+        let written_versions = storage.written_versions();
+        assert_that!(written_versions.len(), eq(20));
+        assert_that!(written_versions.windows(2).all(|versions| versions[0] < versions[1]), eq(true));
+        let version = testee.resources(|resources| resources.version::<ClusterConfiguration>(cluster_id)).await;
+        assert_that!(written_versions.last(), some(eq(&version)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_report_failures_of_the_storage_to_the_caller() -> Result<()> {
+
+        let storage = Arc::new(RecordingStorage { failing: true, ..Default::default() });
+        let testee = ResourcesManager::load(Persistence::Enabled { storage })?;
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("UnpersistedCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            device_selectors: vec![],
+            project: ProjectName::default(),
+        };
+
+        let result = testee.insert(cluster_id, Clone::clone(&cluster_configuration)).await;
+        assert_that!(result, err(matches_pattern!(PersistenceError::Storage(anything()))));
+
+        let result = testee.resources_mut(|resources| resources.remove::<ClusterConfiguration>(cluster_id)).await;
+        assert_that!(result, err(matches_pattern!(PersistenceError::Storage(anything()))));
+
+        let mut transaction = testee.begin().await;
+        transaction.resources_mut(|resources| resources.insert(cluster_id, cluster_configuration)).await;
+        assert_that!(transaction.commit().await, err(matches_pattern!(PersistenceError::Storage(anything()))));
+
+        let result = testee.insert(PeerId::random(), PeerState::Down).await;
+        assert_that!(result, ok(eq(())));
+
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct RecordingStorage {
+        failing: bool,
+        written: std::sync::Mutex<Vec<Version>>,
+    }
+
+    impl RecordingStorage {
+        fn written_versions(&self) -> Vec<Version> {
+            Clone::clone(&self.written.lock().unwrap())
+        }
+    }
+
+    impl ResourcesStorage for RecordingStorage {
+        fn load(&self) -> std::result::Result<Vec<crate::resources::persistence::StoredResource>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn store(&self, kind: &str, _: Id, version: Version, _: &[u8]) -> std::result::Result<(), StorageError> {
+            if self.failing {
+                return Err(StorageError::Io { path: std::path::PathBuf::from(kind), cause: std::io::Error::other("storage is failing") });
+            }
+            self.written.lock().unwrap().push(version);
+            Ok(())
+        }
+
+        fn remove(&self, kind: &str, _: Id) -> std::result::Result<(), StorageError> {
+            if self.failing {
+                return Err(StorageError::Io { path: std::path::PathBuf::from(kind), cause: std::io::Error::other("storage is failing") });
+            }
+            Ok(())
+        }
+    }
 }
@@ -1,49 +1,74 @@
 use std::any::{Any, TypeId};
-use std::collections::hash_map::{Values, ValuesMut};
+use std::collections::hash_map::Values;
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::ops::Not;
+use std::sync::Arc;
 
-use opendut_types::resources::Id;
+use opendut_types::resources::{Id, Version};
 
 pub mod manager;
 pub mod ids;
+pub mod persistence;
 
 pub trait IntoId<R: Any + Send + Sync> {
     fn into_id(self) -> Id;
 }
 
+/// A type which can be held by [`Resources`].
+///
+/// Resources are compared when they are modified, so that only actual changes increase their version.
+pub trait Resource: Any + Send + Sync + Clone + PartialEq + Sized {
+    /// Unique name of this kind of resource, used as key when persisting it.
+    const KIND: &'static str;
+
+    /// Serializes the resource for persistence.
+    /// Returns `None` for volatile resources, which should not survive a restart of CARL.
+    fn encode(&self) -> Option<Vec<u8>>;
+
+    fn decode(bytes: &[u8]) -> Result<Self, persistence::DecodeError>;
+}
+
 #[derive(Default)]
 pub struct Resources {
-    storage: HashMap<TypeId, HashMap<Id, Box<dyn Any + Send + Sync>>>
+    storage: HashMap<TypeId, HashMap<Id, Box<dyn Any + Send + Sync>>>,
+    changes: Changes,
+    journal: Option<Journal>,
+    versions: HashMap<(TypeId, Id), Version>,
+    /// Incremented with every change, to assign each change a new [`Version`].
+    revision: u64,
 }
 
 impl Resources {
 
     pub fn insert<R>(&mut self, id: impl IntoId<R>, resource: R)
-    where R: Any + Send + Sync {
+    where R: Resource {
+        let id = id.into_id();
+        self.record_change::<R>(id);
         let column = self.storage
             .entry(TypeId::of::<R>())
             .or_default();
-        column.insert(id.into_id(), Box::new(resource));
+        column.insert(id, Box::new(resource));
     }
 
-    pub fn update<R>(&mut self, id: impl IntoId<R>) -> Update<R>
-    where R: Any + Send + Sync {
-        let column = self.storage
-            .entry(TypeId::of::<R>())
-            .or_default();
+    pub fn update<R>(&mut self, id: impl IntoId<R>) -> Update<'_, R>
+    where R: Resource {
         Update {
             id: id.into_id(),
-            column,
+            resources: self,
             marker: Default::default(),
         }
     }
 
     pub fn remove<R>(&mut self, id: impl IntoId<R>) -> Option<R>
-    where R: Any + Send + Sync {
+    where R: Resource {
         let type_id = TypeId::of::<R>();
+        let id = id.into_id();
+        if self.column_of::<R>()?.contains_key(&id) {
+            self.record_change::<R>(id);
+        }
         let column = self.column_mut_of::<R>()?;
-        let result = column.remove(&id.into_id())
+        let result = column.remove(&id)
             .and_then(|old_value| old_value
                 .downcast()
                 .map(|value| *value)
@@ -56,7 +81,7 @@ impl Resources {
     }
 
     pub fn get<R>(&self, id: impl IntoId<R>) -> Option<R>
-    where R: Any + Send + Sync + Clone {
+    where R: Resource + Clone {
         let column = self.column_of::<R>()?;
         column.get(&id.into_id())
             .and_then(|resource| resource
@@ -65,24 +90,170 @@ impl Resources {
             )
     }
 
+    /// Returns the current version of the resource or [`Version::NONE`], if it does not exist.
+    pub fn version<R>(&self, id: impl IntoId<R>) -> Version
+    where R: Resource {
+        let id = id.into_id();
+        let exists = self.column_of::<R>()
+            .map(|column| column.contains_key(&id))
+            .unwrap_or(false);
+        if exists {
+            self.versions.get(&(TypeId::of::<R>(), id)).copied().unwrap_or(Version::NONE)
+        } else {
+            Version::NONE
+        }
+    }
+
     pub fn iter<R>(&self) -> Iter<R>
-    where R: Any + Send + Sync {
+    where R: Resource {
         Iter::new(self.column_of::<R>().map(HashMap::values))
     }
 
-    pub fn iter_mut<R>(&mut self) -> IterMut<R>
-    where R: Any + Send + Sync {
-        IterMut::new(self.column_mut_of::<R>().map(HashMap::values_mut))
+    /// Applies `f` to every resource of type `R`. Only resources, which `f` actually changed, are recorded as changed.
+    pub fn modify_all<R, F>(&mut self, mut f: F)
+    where R: Resource, F: FnMut(&mut R) {
+        let ids = self.column_of::<R>()
+            .map(|column| column.keys().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for id in ids {
+            self.modify::<R, _>(id, &mut f);
+        }
+    }
+
+    /// Drains all changes made since the last call, resolved to the current state of the affected resources.
+    pub(crate) fn take_changes(&mut self) -> Vec<ResourceChange> {
+        self.changes.drain()
+            .filter_map(|((type_id, id), change)| {
+                let codec = change.codec;
+                let current = self.storage.get(&type_id)
+                    .and_then(|column| column.get(&id));
+                let version = self.versions.get(&(type_id, id)).copied().unwrap_or(Version::NONE);
+                let operation = match (current, change.existed) {
+                    (Some(resource), existed) => {
+                        let resource = (codec.clone)(resource.as_ref());
+                        let encoded = (codec.encode)(resource.as_ref());
+                        if existed {
+                            ResourceOperation::Updated { resource, encoded, version }
+                        } else {
+                            ResourceOperation::Created { resource, encoded, version }
+                        }
+                    }
+                    (None, existed) => {
+                        self.versions.remove(&(type_id, id));
+                        if existed {
+                            ResourceOperation::Removed
+                        } else {
+                            return None; //created and removed again
+                        }
+                    }
+                };
+                Some(ResourceChange { type_id, kind: codec.kind, id, operation })
+            })
+            .collect()
+    }
+
+    /// Runs `f` with all changes being recorded into the given [`Staged`] changes instead,
+    /// so that they can later be committed or rolled back as a whole.
+    pub(crate) fn stage<F, T>(&mut self, staged: &mut Staged, f: F) -> T
+    where F: FnOnce(&mut Resources) -> T {
+        std::mem::swap(&mut self.changes, &mut staged.changes);
+        self.journal = Some(std::mem::take(&mut staged.journal));
+
+        let result = f(self);
+
+        staged.journal = self.journal.take().unwrap_or_default();
+        std::mem::swap(&mut self.changes, &mut staged.changes);
+        result
+    }
+
+    /// Keeps the staged changes, so that they get included in the next call of [`Resources::take_changes`].
+    pub(crate) fn commit(&mut self, staged: Staged) {
+        for (key, change) in staged.changes {
+            self.changes.entry(key).or_insert(change);
+        }
+    }
+
+    /// Inserts a previously persisted resource with its version, without recording a change.
+    pub(crate) fn restore<R>(&mut self, id: Id, resource: R, version: Version)
+    where R: Resource {
+        self.storage.entry(TypeId::of::<R>()).or_default()
+            .insert(id, Box::new(resource));
+        self.versions.insert((TypeId::of::<R>(), id), version);
+        self.revision = self.revision.max(version.0);
+    }
+
+    /// Restores the state of all resources touched by the staged changes.
+    pub(crate) fn rollback(&mut self, staged: Staged) {
+        for ((type_id, id), original) in staged.journal {
+            match original.version {
+                Some(version) => self.versions.insert((type_id, id), version),
+                None => self.versions.remove(&(type_id, id)),
+            };
+            match original.value {
+                Some(value) => {
+                    self.storage.entry(type_id).or_default().insert(id, value);
+                }
+                None => {
+                    if let Some(column) = self.storage.get_mut(&type_id) {
+                        column.remove(&id);
+                        if column.is_empty() {
+                            self.storage.remove(&type_id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies `f` to a copy of the resource and only stores and records it, if it changed.
+    fn modify<R, F>(&mut self, id: Id, f: F)
+    where R: Resource, F: FnOnce(&mut R) {
+        let Some(original) = self.column_of::<R>()
+            .and_then(|column| column.get(&id))
+            .and_then(|resource| resource.downcast_ref::<R>())
+        else { return };
+
+        let mut modified = Clone::clone(original);
+        f(&mut modified);
+
+        if &modified != original {
+            self.record_change::<R>(id);
+            self.storage.entry(TypeId::of::<R>()).or_default()
+                .insert(id, Box::new(modified));
+        }
     }
 
+    fn record_change<R>(&mut self, id: Id)
+    where R: Resource {
+        let key = (TypeId::of::<R>(), id);
+        let current = self.storage.get(&key.0)
+            .and_then(|column| column.get(&id));
+
+        if let Some(journal) = &mut self.journal {
+            if journal.contains_key(&key).not() {
+                let value = current
+                    .and_then(|resource| resource.downcast_ref::<R>())
+                    .map(|resource| Box::new(Clone::clone(resource)) as Box<dyn Any + Send + Sync>);
+                let version = self.versions.get(&key).copied();
+                journal.insert(key, Original { value, version });
+            }
+        }
+        self.revision += 1;
+        self.versions.insert(key, Version(self.revision));
+
+        self.changes.entry(key).or_insert(Change {
+            codec: ResourceCodec::of::<R>(),
+            existed: current.is_some(),
+        });
+    }
 
     fn column_of<R>(&self) -> Option<&HashMap<Id, Box<dyn Any + Send + Sync>>>
-    where R: Any + Send + Sync {
+    where R: Resource {
         self.storage.get(&TypeId::of::<R>())
     }
 
     fn column_mut_of<R>(&mut self) -> Option<&mut HashMap<Id, Box<dyn Any + Send + Sync>>>
-        where R: Any + Send + Sync {
+        where R: Resource {
         self.storage.get_mut(&TypeId::of::<R>())
     }
 }
@@ -90,7 +261,7 @@ impl Resources {
 #[cfg(test)]
 impl Resources {
     pub(super) fn contains<R>(&self, id: impl IntoId<R>) -> bool
-    where R: Any + Send + Sync {
+    where R: Resource {
         if let Some(column) = self.column_of::<R>() {
             column.contains_key(&id.into_id())
         }
@@ -104,39 +275,135 @@ impl Resources {
     }
 }
 
+#[derive(Clone, Debug)]
+pub(crate) struct ResourceChange {
+    pub type_id: TypeId,
+    pub kind: &'static str,
+    pub id: Id,
+    pub operation: ResourceOperation,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum ResourceOperation {
+    Created { resource: Arc<dyn Any + Send + Sync>, encoded: Option<Vec<u8>>, version: Version },
+    Updated { resource: Arc<dyn Any + Send + Sync>, encoded: Option<Vec<u8>>, version: Version },
+    Removed,
+}
+
+/// A change of a resource of type `R`, as published by the [`ResourcesManager`](manager::ResourcesManager).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResourceEvent<R> {
+    Created { id: Id, resource: R },
+    Updated { id: Id, resource: R },
+    Deleted { id: Id },
+}
+
+impl<R: Resource> ResourceEvent<R> {
+    /// Returns `None`, if the change does not concern resources of type `R`.
+    pub(crate) fn from_change(change: ResourceChange) -> Option<Self> {
+        if change.type_id != TypeId::of::<R>() {
+            return None;
+        }
+        let id = change.id;
+        let event = match change.operation {
+            ResourceOperation::Created { resource, .. } => ResourceEvent::Created { id, resource: downcast(resource)? },
+            ResourceOperation::Updated { resource, .. } => ResourceEvent::Updated { id, resource: downcast(resource)? },
+            ResourceOperation::Removed => ResourceEvent::Deleted { id },
+        };
+        Some(event)
+    }
+}
+
+fn downcast<R: Resource>(resource: Arc<dyn Any + Send + Sync>) -> Option<R> {
+    resource.downcast::<R>().ok()
+        .map(|resource| Arc::unwrap_or_clone(resource))
+}
+
+/// Changes made to [`Resources`] as part of a transaction, which have not yet been committed.
+#[derive(Default)]
+pub(crate) struct Staged {
+    changes: Changes,
+    journal: Journal,
+}
+
+type Changes = HashMap<(TypeId, Id), Change>;
+
+#[derive(Clone, Copy)]
+struct Change {
+    codec: ResourceCodec,
+    /// Whether the resource existed before it was first changed.
+    existed: bool,
+}
+
+type Journal = HashMap<(TypeId, Id), Original>;
+
+/// The value of a resource before it was first changed in a transaction.
+struct Original {
+    value: Option<Box<dyn Any + Send + Sync>>,
+    version: Option<Version>,
+}
+
+#[derive(Clone, Copy)]
+struct ResourceCodec {
+    kind: &'static str,
+    encode: fn(&(dyn Any + Send + Sync)) -> Option<Vec<u8>>,
+    clone: fn(&(dyn Any + Send + Sync)) -> Arc<dyn Any + Send + Sync>,
+}
+
+impl ResourceCodec {
+    fn of<R: Resource>() -> Self {
+        fn encode<R: Resource>(resource: &(dyn Any + Send + Sync)) -> Option<Vec<u8>> {
+            resource.downcast_ref::<R>()
+                .and_then(R::encode)
+        }
+        fn clone<R: Resource>(resource: &(dyn Any + Send + Sync)) -> Arc<dyn Any + Send + Sync> {
+            match resource.downcast_ref::<R>() {
+                Some(resource) => Arc::new(Clone::clone(resource)),
+                None => unreachable!("Resources are stored in the column of their own type."),
+            }
+        }
+        Self {
+            kind: R::KIND,
+            encode: encode::<R>,
+            clone: clone::<R>,
+        }
+    }
+}
+
 pub struct Update<'a, R>
-where R: Any + Send + Sync {
+where R: Resource {
     id: Id,
-    column: &'a mut HashMap<Id, Box<dyn Any + Send + Sync>>,
+    resources: &'a mut Resources,
     marker: PhantomData<R>,
 }
 
 impl <R> Update<'_, R>
-where R: Any + Send + Sync {
+where R: Resource {
 
     pub fn modify<F>(self, f: F) -> Self
     where F: FnOnce(&mut R) {
-        if let Some(boxed) = self.column.get_mut(&self.id) {
-            if let Some(resource) = boxed.downcast_mut() {
-                f(resource)
-            }
-        }
+        self.resources.modify::<R, F>(self.id, f);
         self
     }
 
     pub fn or_insert(self, resource: R) {
-        self.column.entry(self.id).or_insert_with(|| Box::new(resource));
+        let exists = self.resources.column_of::<R>()
+            .map(|column| column.contains_key(&self.id))
+            .unwrap_or(false);
+        if exists.not() {
+            self.resources.insert(self.id, resource);
+        }
     }
 }
 
 pub struct Iter<'a, R>
-where R: Any + Send + Sync {
+where R: Resource {
     column: Option<Values<'a, Id, Box<dyn Any + Send + Sync>>>,
     marker: PhantomData<R>
 }
 
 impl <'a, R> Iter<'a, R>
-where R: Any + Send + Sync {
+where R: Resource {
     fn new(column: Option<Values<'a, Id, Box<dyn Any + Send + Sync>>>) -> Iter<'a, R> {
         Self {
             column,
@@ -146,7 +413,7 @@ where R: Any + Send + Sync {
 }
 
 impl <'a, R> Iterator for Iter<'a, R>
-where R: Any + Send + Sync {
+where R: Resource {
 
     type Item = &'a R;
 
@@ -157,31 +424,3 @@ where R: Any + Send + Sync {
     }
 }
 
-
-pub struct IterMut<'a, R>
-where R: Any + Send + Sync {
-    column: Option<ValuesMut<'a, Id, Box<dyn Any + Send + Sync>>>,
-    marker: PhantomData<R>
-}
-
-impl <'a, R> IterMut<'a, R>
-where R: Any + Send + Sync {
-    fn new(column: Option<ValuesMut<'a, Id, Box<dyn Any + Send + Sync>>>) -> IterMut<'a, R> {
-        Self {
-            column,
-            marker: PhantomData
-        }
-    }
-}
-
-impl <'a, R> Iterator for IterMut<'a, R>
-where R: Any + Send + Sync {
-
-    type Item = &'a mut R;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let column = self.column.as_mut()?;
-        column.next()
-            .and_then(|value| value.downcast_mut())
-    }
-}
@@ -0,0 +1,106 @@
+use prost::Message;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterPortAllocation};
+use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::certificate::IssuedPeerCertificate;
+use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2, PeerConfigurationState};
+use opendut_types::peer::setup::IssuedPeerSetup;
+use opendut_types::peer::state::PeerState;
+use opendut_types::proto;
+use opendut_types::reservation::Reservation;
+use opendut_types::resources::{Id, Version};
+use opendut_types::service_account::{ApiToken, ServiceAccount};
+use opendut_types::topology::DeviceDescriptor;
+use opendut_types::webhook::Webhook;
+
+use crate::resources::{Resource, Resources};
+use crate::resources::persistence::{DecodeError, StoredResource};
+
+macro_rules! persistent_resource {
+    ($resource:ty, $proto:ty, $kind:literal) => {
+        impl Resource for $resource {
+            const KIND: &'static str = $kind;
+
+            fn encode(&self) -> Option<Vec<u8>> {
+                Some(<$proto>::from(Clone::clone(self)).encode_to_vec())
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+                let proto = <$proto>::decode(bytes)?;
+                Ok(<$resource>::try_from(proto)?)
+            }
+        }
+    };
+}
+
+persistent_resource!(ClusterConfiguration, proto::cluster::ClusterConfiguration, "cluster-configuration");
+persistent_resource!(ClusterDeployment, proto::cluster::ClusterDeployment, "cluster-deployment");
+persistent_resource!(ClusterPortAllocation, proto::cluster::ClusterPortAllocation, "cluster-port-allocation");
+persistent_resource!(ClusterState, proto::cluster::ClusterState, "cluster-state");
+persistent_resource!(DeviceDescriptor, proto::topology::DeviceDescriptor, "device-descriptor");
+persistent_resource!(PeerConfiguration, proto::peer::configuration::PeerConfiguration, "peer-configuration");
+persistent_resource!(PeerConfiguration2, proto::peer::configuration::PeerConfiguration2, "peer-configuration2");
+persistent_resource!(PeerDescriptor, proto::peer::PeerDescriptor, "peer-descriptor");
+persistent_resource!(IssuedPeerSetup, proto::peer::IssuedPeerSetup, "issued-peer-setup");
+persistent_resource!(IssuedPeerCertificate, proto::peer::IssuedPeerCertificate, "issued-peer-certificate");
+persistent_resource!(Reservation, proto::reservation::Reservation, "reservation");
+persistent_resource!(ServiceAccount, proto::service_account::ServiceAccount, "service-account");
+persistent_resource!(ApiToken, proto::service_account::ApiToken, "api-token");
+persistent_resource!(Webhook, proto::webhook::Webhook, "webhook");
+
+/// The state of a peer is only known while CARL is running, since it is derived from the peer's connection.
+impl Resource for PeerState {
+    const KIND: &'static str = "peer-state";
+
+    fn encode(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn decode(_: &[u8]) -> Result<Self, DecodeError> {
+        Err(DecodeError::Volatile { kind: Self::KIND })
+    }
+}
+
+/// The state of a peer's configuration is reported by the peer again, whenever it connects and applies its configuration.
+impl Resource for PeerConfigurationState {
+    const KIND: &'static str = "peer-configuration-state";
+
+    fn encode(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn decode(_: &[u8]) -> Result<Self, DecodeError> {
+        Err(DecodeError::Volatile { kind: Self::KIND })
+    }
+}
+
+/// Inserts a resource loaded from a [`ResourcesStorage`](super::ResourcesStorage) into the given [`Resources`].
+pub(crate) fn restore(resources: &mut Resources, stored: StoredResource) -> Result<(), DecodeError> {
+
+    fn restore_as<R: Resource>(resources: &mut Resources, id: Id, version: Version, encoded: &[u8]) -> Result<(), DecodeError> {
+        let resource = R::decode(encoded)?;
+        resources.restore::<R>(id, resource, version);
+        Ok(())
+    }
+
+    let StoredResource { kind, id, version, encoded } = stored;
+
+    match kind.as_str() {
+        kind if kind == ClusterConfiguration::KIND => restore_as::<ClusterConfiguration>(resources, id, version, &encoded),
+        kind if kind == ClusterDeployment::KIND => restore_as::<ClusterDeployment>(resources, id, version, &encoded),
+        kind if kind == ClusterPortAllocation::KIND => restore_as::<ClusterPortAllocation>(resources, id, version, &encoded),
+        kind if kind == ClusterState::KIND => restore_as::<ClusterState>(resources, id, version, &encoded),
+        kind if kind == DeviceDescriptor::KIND => restore_as::<DeviceDescriptor>(resources, id, version, &encoded),
+        kind if kind == PeerConfiguration::KIND => restore_as::<PeerConfiguration>(resources, id, version, &encoded),
+        kind if kind == PeerConfiguration2::KIND => restore_as::<PeerConfiguration2>(resources, id, version, &encoded),
+        kind if kind == PeerDescriptor::KIND => restore_as::<PeerDescriptor>(resources, id, version, &encoded),
+        kind if kind == IssuedPeerSetup::KIND => restore_as::<IssuedPeerSetup>(resources, id, version, &encoded),
+        kind if kind == IssuedPeerCertificate::KIND => restore_as::<IssuedPeerCertificate>(resources, id, version, &encoded),
+        kind if kind == Reservation::KIND => restore_as::<Reservation>(resources, id, version, &encoded),
+        kind if kind == ServiceAccount::KIND => restore_as::<ServiceAccount>(resources, id, version, &encoded),
+        kind if kind == ApiToken::KIND => restore_as::<ApiToken>(resources, id, version, &encoded),
+        kind if kind == Webhook::KIND => restore_as::<Webhook>(resources, id, version, &encoded),
+        _ => Err(DecodeError::UnknownKind { kind }),
+    }
+}
@@ -0,0 +1,145 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use opendut_types::resources::{Id, Version};
+
+use crate::resources::persistence::{ResourcesStorage, StorageError, StoredResource};
+
+const FILE_EXTENSION: &str = "pb";
+const VERSION_LENGTH: usize = std::mem::size_of::<u64>();
+
+/// Stores each resource in its own file, at `<directory>/<kind>/<id>.pb`.
+/// The file starts with the big-endian encoded version of the resource, followed by the encoded resource.
+pub struct FileStorage {
+    directory: PathBuf,
+}
+
+impl FileStorage {
+    pub fn create(directory: PathBuf) -> Result<Self, StorageError> {
+        fs::create_dir_all(&directory)
+            .map_err(|cause| StorageError::Io { path: Clone::clone(&directory), cause })?;
+        Ok(Self { directory })
+    }
+
+    fn path_of(&self, kind: &str, id: Id) -> PathBuf {
+        self.directory.join(kind).join(format!("{}.{FILE_EXTENSION}", id.value()))
+    }
+}
+
+impl ResourcesStorage for FileStorage {
+
+    fn load(&self) -> Result<Vec<StoredResource>, StorageError> {
+        let mut result = Vec::new();
+
+        for kind_directory in read_dir(&self.directory)? {
+            if kind_directory.is_dir().not() {
+                continue;
+            }
+            let kind = file_name(&kind_directory)?;
+
+            for path in read_dir(&kind_directory)? {
+                if path.extension().map(|extension| extension == FILE_EXTENSION) != Some(true) {
+                    continue; //e.g. leftover temporary files
+                }
+                let id = path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| Uuid::parse_str(stem).ok())
+                    .map(Id::from)
+                    .ok_or_else(|| StorageError::InvalidEntry { path: Clone::clone(&path), message: String::from("File name is not a valid UUID.") })?;
+
+                let content = fs::read(&path)
+                    .map_err(|cause| StorageError::Io { path: Clone::clone(&path), cause })?;
+
+                if content.len() < VERSION_LENGTH {
+                    return Err(StorageError::InvalidEntry { path, message: String::from("File is too short to contain a version.") });
+                }
+                let (version, encoded) = content.split_at(VERSION_LENGTH);
+                let version = Version(u64::from_be_bytes(version.try_into().expect("slice should have the length of a u64")));
+
+                result.push(StoredResource { kind: Clone::clone(&kind), id, version, encoded: encoded.to_vec() });
+            }
+        }
+        Ok(result)
+    }
+
+    fn store(&self, kind: &str, id: Id, version: Version, encoded: &[u8]) -> Result<(), StorageError> {
+        let path = self.path_of(kind, id);
+        let kind_directory = self.directory.join(kind);
+        fs::create_dir_all(&kind_directory)
+            .map_err(|cause| StorageError::Io { path: kind_directory, cause })?;
+
+        let temporary_path = path.with_extension("tmp");
+        let content = [&version.0.to_be_bytes(), encoded].concat();
+        fs::write(&temporary_path, content)
+            .map_err(|cause| StorageError::Io { path: Clone::clone(&temporary_path), cause })?;
+        fs::rename(&temporary_path, &path)
+            .map_err(|cause| StorageError::Io { path, cause })?;
+        Ok(())
+    }
+
+    fn remove(&self, kind: &str, id: Id) -> Result<(), StorageError> {
+        let path = self.path_of(kind, id);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(cause) if cause.kind() == ErrorKind::NotFound => Ok(()),
+            Err(cause) => Err(StorageError::Io { path, cause }),
+        }
+    }
+}
+
+fn read_dir(directory: &Path) -> Result<Vec<PathBuf>, StorageError> {
+    let entries = fs::read_dir(directory)
+        .map_err(|cause| StorageError::Io { path: directory.to_owned(), cause })?;
+
+    entries
+        .map(|entry| entry
+            .map(|entry| entry.path())
+            .map_err(|cause| StorageError::Io { path: directory.to_owned(), cause })
+        )
+        .collect()
+}
+
+fn file_name(path: &Path) -> Result<String, StorageError> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| StorageError::InvalidEntry { path: path.to_owned(), message: String::from("Name is not valid UTF-8.") })
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_store_load_and_remove_resources() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let testee = FileStorage::create(directory.path().to_owned())?;
+
+        let id_a = Id::random();
+        let id_b = Id::random();
+
+        testee.store("peer-descriptor", id_a, Version(1), b"a")?;
+        testee.store("cluster-configuration", id_b, Version(2), b"b")?;
+        testee.store("peer-descriptor", id_a, Version(3), b"a2")?;
+
+        assert_that!(testee.load()?, unordered_elements_are![
+            eq(StoredResource { kind: String::from("peer-descriptor"), id: id_a, version: Version(3), encoded: b"a2".to_vec() }),
+            eq(StoredResource { kind: String::from("cluster-configuration"), id: id_b, version: Version(2), encoded: b"b".to_vec() }),
+        ]);
+
+        testee.remove("peer-descriptor", id_a)?;
+        testee.remove("peer-descriptor", Id::random())?;
+
+        assert_that!(testee.load()?, elements_are![
+            eq(StoredResource { kind: String::from("cluster-configuration"), id: id_b, version: Version(2), encoded: b"b".to_vec() }),
+        ]);
+
+        Ok(())
+    }
+}
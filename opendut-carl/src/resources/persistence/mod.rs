@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use config::Config;
+use serde::Deserialize;
+use tracing::debug;
+
+use opendut_types::proto::ConversionError;
+use opendut_types::resources::{Id, Version};
+use opendut_util::project;
+
+use crate::resources::persistence::file::FileStorage;
+
+pub mod file;
+mod codec;
+
+pub(crate) use codec::restore;
+
+#[derive(Clone)]
+pub enum Persistence {
+    Enabled { storage: Arc<dyn ResourcesStorage + Send + Sync> },
+    Disabled,
+}
+
+/// Backend for persisting [`Resources`](crate::resources::Resources) across restarts of CARL.
+pub trait ResourcesStorage {
+    fn load(&self) -> Result<Vec<StoredResource>, StorageError>;
+
+    fn store(&self, kind: &str, id: Id, version: Version, encoded: &[u8]) -> Result<(), StorageError>;
+
+    fn remove(&self, kind: &str, id: Id) -> Result<(), StorageError>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredResource {
+    pub kind: String,
+    pub id: Id,
+    pub version: Version,
+    pub encoded: Vec<u8>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StorageError {
+    #[error("Failed to access '{path}': {cause}")]
+    Io { path: PathBuf, cause: std::io::Error },
+    #[error("Invalid entry '{path}': {message}")]
+    InvalidEntry { path: PathBuf, message: String },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeError {
+    #[error("Failed to decode protobuf message: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+    #[error("{0}")]
+    Conversion(#[from] ConversionError),
+    #[error("Resources of kind '{kind}' are volatile and cannot be restored.")]
+    Volatile { kind: &'static str },
+    #[error("Unknown kind of resource '{kind}'.")]
+    UnknownKind { kind: String },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoadResourcesError {
+    #[error("Failed to load resources from storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("Failed to restore resource '{kind}' <{id}>: {cause}")]
+    Decode { kind: String, id: Id, cause: DecodeError },
+    #[error("Failed to start the thread for writing resources to storage: {0}")]
+    Writer(std::io::Error),
+}
+
+pub fn create(settings: &Config) -> anyhow::Result<Persistence> {
+
+    let persistence = settings.get::<PersistenceConfig>("persistence")?;
+
+    if persistence.enabled {
+        match persistence.kind {
+            PersistenceKind::File => {
+                let directory = settings.get::<PersistenceFileConfig>("persistence.file")?.directory;
+                let directory = project::make_path_absolute(directory)?;
+                debug!("Persisting resources in directory: {}", directory.display());
+                let storage = FileStorage::create(directory)?;
+                Ok(Persistence::Enabled { storage: Arc::new(storage) })
+            }
+        }
+    } else {
+        Ok(Persistence::Disabled)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all="kebab-case")]
+struct PersistenceConfig {
+    enabled: bool,
+    kind: PersistenceKind,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all="kebab-case")]
+enum PersistenceKind {
+    File,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all="kebab-case")]
+struct PersistenceFileConfig {
+    directory: PathBuf,
+}
@@ -1,3 +1,5 @@
+pub mod reload;
+
 use opendut_util::settings::{LoadedConfig, LoadError};
 
 pub fn load_with_overrides(overrides: config::Config) -> Result<LoadedConfig, LoadError> {
@@ -6,7 +8,7 @@ pub fn load_with_overrides(overrides: config::Config) -> Result<LoadedConfig, Lo
         .set_override("network.oidc.client.secret", "redacted")?
         .build()?;
 
-    opendut_util::settings::load_config("carl", include_str!("../carl.toml"), config::FileFormat::Toml, overrides, carl_config_hide_secrets_override)
+    opendut_util::settings::load_config("carl", include_str!("../../carl.toml"), config::FileFormat::Toml, overrides, carl_config_hide_secrets_override)
 }
 
 #[cfg(test)]
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use axum_server::tls_rustls::RustlsConfig;
+use tokio::signal::unix::{signal, SignalKind};
+use tracing::{debug, error, info, warn};
+
+use opendut_util::settings::{LoadedConfig, LoadError};
+
+use crate::auth::mtls;
+use crate::auth::mtls::ServerTlsFiles;
+use crate::cluster::manager::{ClusterManagerOptions, ClusterManagerRef};
+use crate::peer::broker::{PeerMessagingBrokerOptions, PeerMessagingBrokerRef};
+use crate::peer::certificate_authority::PeerCertificateAuthorityRef;
+
+/// Settings, which are only read when CARL starts. Changing them has no effect until CARL is restarted.
+const RESTART_REQUIRED: &[&str] = &[
+    "network.bind",
+    "network.remote",
+    "network.tls.ca",
+    "network.oidc",
+    "peer.ethernet",
+    "peer.setup",
+    "peer.certificate",
+    "reservation",
+    "serve",
+    "persistence",
+    "audit",
+    "webhook",
+    "vpn",
+    "logging",
+    "opentelemetry",
+    "settings",
+];
+
+pub type LoadSettings = Box<dyn Fn() -> Result<LoadedConfig, LoadError> + Send>;
+
+/// Applies changes of the settings and of the TLS certificate while CARL is running, where this is possible without dropping connections.
+///
+/// A reload is triggered by `SIGHUP` or when one of the configuration files or TLS files is modified.
+pub struct SettingsReloader {
+    load: LoadSettings,
+    /// Settings CARL was started with, to determine which changes require a restart.
+    initial: config::Config,
+    current: LoadedConfig,
+    modified: HashMap<PathBuf, Option<SystemTime>>,
+    tls_config: RustlsConfig,
+    tls_files: TlsFilesContent,
+    certificate_authority: PeerCertificateAuthorityRef,
+    cluster_manager: ClusterManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Reloaded {
+    /// Settings, whose changes were applied.
+    pub applied: Vec<&'static str>,
+    /// Settings, whose changes only take effect after a restart.
+    pub restart_required: Vec<&'static str>,
+}
+
+impl SettingsReloader {
+    pub fn new(
+        load: LoadSettings,
+        settings: LoadedConfig,
+        tls_config: RustlsConfig,
+        certificate_authority: PeerCertificateAuthorityRef,
+        cluster_manager: ClusterManagerRef,
+        peer_messaging_broker: PeerMessagingBrokerRef,
+    ) -> anyhow::Result<Self> {
+        let tls_files = TlsFilesContent::read(ServerTlsFiles::load(&settings.config)?)?;
+        let mut reloader = Self {
+            load,
+            initial: Clone::clone(&settings.config),
+            current: settings,
+            modified: HashMap::new(),
+            tls_config,
+            tls_files,
+            certificate_authority,
+            cluster_manager,
+            peer_messaging_broker,
+        };
+        reloader.modified = reloader.modification_times();
+        Ok(reloader)
+    }
+
+    /// Reloads on `SIGHUP` and checks for modified files in the given interval.
+    pub fn spawn(mut self, options: SettingsReloadOptions) -> anyhow::Result<()> {
+        let mut hangup = signal(SignalKind::hangup())?;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(options.check_interval);
+            loop {
+                tokio::select! {
+                    _ = hangup.recv() => {
+                        info!("Received SIGHUP. Reloading settings.");
+                    }
+                    _ = interval.tick() => {
+                        let modified = self.modification_times();
+                        if modified == self.modified {
+                            continue;
+                        }
+                        self.modified = modified;
+                        info!("Configuration or TLS files were modified. Reloading settings.");
+                    }
+                }
+                self.reload().await;
+            }
+        });
+        Ok(())
+    }
+
+    pub async fn reload(&mut self) -> Reloaded {
+        let settings = match (self.load)() {
+            Ok(settings) => settings,
+            Err(cause) => {
+                error!("Failed to reload settings. Keeping the current settings.\n  {cause}");
+                return Reloaded::default();
+            }
+        };
+
+        let mut reloaded = Reloaded::default();
+
+        match self.reload_tls(&settings.config) {
+            Ok(true) => reloaded.applied.push("network.tls"),
+            Ok(false) => {}
+            Err(cause) => error!("Failed to reload TLS certificate. Keeping the current certificate.\n  {cause:#}"),
+        }
+
+        match ClusterManagerOptions::load(&settings.config) {
+            Ok(options) => {
+                let mut cluster_manager = self.cluster_manager.lock().await;
+                if cluster_manager.options() != &options {
+                    cluster_manager.set_options(options);
+                    reloaded.applied.push("peer.can");
+                }
+            }
+            Err(cause) => error!("Failed to reload options of cluster manager.\n  {cause}"),
+        }
+
+        match PeerMessagingBrokerOptions::load(&settings.config) {
+            Ok(options) => {
+                if self.peer_messaging_broker.options().await != options {
+                    self.peer_messaging_broker.set_options(options).await;
+                    reloaded.applied.push("peer.disconnect.timeout");
+                }
+            }
+            Err(cause) => error!("Failed to reload options of peer messaging broker.\n  {cause}"),
+        }
+
+        reloaded.restart_required = restart_required_changes(&self.initial, &settings.config);
+
+        for key in &reloaded.applied {
+            info!("Applied changed settings '{key}'.");
+        }
+        for key in &reloaded.restart_required {
+            warn!("Settings '{key}' differ from the settings CARL was started with. Restart CARL to apply them.");
+        }
+        if reloaded == Reloaded::default() {
+            debug!("No changed settings found.");
+        }
+
+        self.current = settings;
+        reloaded
+    }
+
+    /// Replaces the TLS configuration of the server, if the certificate or key changed. Established connections are kept.
+    fn reload_tls(&mut self, config: &config::Config) -> anyhow::Result<bool> {
+        let tls_files = TlsFilesContent::read(ServerTlsFiles::load(config)?)?;
+        if tls_files == self.tls_files {
+            return Ok(false);
+        }
+        let server_config = mtls::server_config(&tls_files.files.certificate, &tls_files.files.key, &self.certificate_authority)?;
+        self.tls_config.reload_from_config(server_config);
+        self.tls_files = tls_files;
+        Ok(true)
+    }
+
+    fn modification_times(&self) -> HashMap<PathBuf, Option<SystemTime>> {
+        self.current.config_files_declared.iter()
+            .chain([&self.tls_files.files.certificate, &self.tls_files.files.key])
+            .map(|path| {
+                let modified = std::fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                (Clone::clone(path), modified)
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq)]
+struct TlsFilesContent {
+    files: ServerTlsFiles,
+    certificate: Vec<u8>,
+    key: Vec<u8>,
+}
+impl TlsFilesContent {
+    fn read(files: ServerTlsFiles) -> anyhow::Result<Self> {
+        let certificate = std::fs::read(&files.certificate)?;
+        let key = std::fs::read(&files.key)?;
+        Ok(Self { files, certificate, key })
+    }
+}
+
+/// Returns those of the [`RESTART_REQUIRED`] settings, which differ between both configurations.
+fn restart_required_changes(running: &config::Config, loaded: &config::Config) -> Vec<&'static str> {
+    let value = |config: &config::Config, key: &str| config.get::<serde_json::Value>(key).ok();
+
+    RESTART_REQUIRED.iter()
+        .filter(|key| value(running, key) != value(loaded, key))
+        .copied()
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct SettingsReloadOptions {
+    pub check_interval: Duration,
+}
+impl SettingsReloadOptions {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        let check_interval = Duration::from_millis(
+            config.get::<u64>("settings.reload.check.interval.ms")?
+        );
+
+        Ok(SettingsReloadOptions {
+            check_interval,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Not;
+    use std::sync::{Arc, Mutex};
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use opendut_util::project;
+
+    use crate::cluster::manager::ClusterManager;
+    use crate::peer::broker::PeerMessagingBroker;
+    use crate::peer::certificate_authority::PeerCertificateAuthority;
+    use crate::resources::manager::ResourcesManager;
+    use crate::settings;
+    use crate::vpn::Vpn;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_apply_changed_settings_without_restart() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let certificate = temp.child("carl.pem");
+        let key = temp.child("carl.key");
+        certificate.write_file(&project::make_path_absolute("resources/development/tls/insecure-development-carl.pem")?)?;
+        key.write_file(&project::make_path_absolute("resources/development/tls/insecure-development-carl.key")?)?;
+
+        let overrides = Arc::new(Mutex::new(
+            config::Config::builder()
+                .set_override("network.tls.certificate", certificate.to_string_lossy().to_string())?
+                .set_override("network.tls.key", key.to_string_lossy().to_string())?
+        ));
+        let load: LoadSettings = {
+            let overrides = Arc::clone(&overrides);
+            Box::new(move || {
+                let overrides = Clone::clone(&*overrides.lock().unwrap()).build()?;
+                settings::load_with_overrides(overrides)
+            })
+        };
+        let settings = load()?;
+
+        let certificate_authority = Arc::new(PeerCertificateAuthority::generate()?);
+        let tls_files = ServerTlsFiles::load(&settings.config)?;
+        let tls_config = RustlsConfig::from_config(mtls::server_config(&tls_files.certificate, &tls_files.key, &certificate_authority)?);
+
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions::load(&settings.config)?);
+        let cluster_manager = ClusterManager::new(
+            Arc::clone(&resources_manager),
+            Arc::clone(&peer_messaging_broker),
+            Vpn::Disabled,
+            ClusterManagerOptions::load(&settings.config)?,
+        );
+
+        let mut testee = SettingsReloader::new(
+            load,
+            settings,
+            Clone::clone(&tls_config),
+            certificate_authority,
+            Arc::clone(&cluster_manager),
+            Arc::clone(&peer_messaging_broker),
+        )?;
+
+        let reloaded = testee.reload().await;
+        assert_that!(reloaded, eq(Reloaded::default()));
+
+        let initial_server_config = tls_config.get_inner();
+        key.write_file(&project::make_path_absolute("resources/development/tls/carl.key")?)?;
+        certificate.write_file(&project::make_path_absolute("resources/development/tls/carl.pem")?)?;
+        {
+            let mut overrides = overrides.lock().unwrap();
+            *overrides = Clone::clone(&*overrides)
+                .set_override("peer.can.server_port_range_start", 30000)?
+                .set_override("peer.can.server_port_range_end", 30100)?
+                .set_override("peer.disconnect.timeout.ms", 1000)?
+                .set_override("network.bind.port", 8443)?;
+        }
+
+        let reloaded = testee.reload().await;
+        assert_that!(reloaded.applied, elements_are![eq("network.tls"), eq("peer.can"), eq("peer.disconnect.timeout")]);
+        assert_that!(reloaded.restart_required, elements_are![eq("network.bind")]);
+
+        assert!(Arc::ptr_eq(&initial_server_config, &tls_config.get_inner()).not());
+        assert_that!(cluster_manager.lock().await.options().can_server_port_range(), eq(30000..30100));
+        assert_that!(peer_messaging_broker.options().await.peer_disconnect_timeout, eq(Duration::from_millis(1000)));
+
+        Ok(())
+    }
+}
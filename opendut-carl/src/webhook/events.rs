@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::configuration::{ParameterId, ParameterStateKind, PeerConfigurationState};
+use opendut_types::peer::state::PeerState;
+use opendut_types::resources::Id;
+use opendut_types::webhook::WebhookEventKind;
+
+use crate::cluster::manager::ClusterDeploymentFailure;
+use crate::resources::{ResourceEvent, Resources};
+
+/// Event, as sent to the webhooks subscribed to its kind.
+#[derive(Clone, Debug)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    pub kind: WebhookEventKind,
+    pub timestamp: SystemTime,
+    pub resource_id: Uuid,
+    /// Kind-specific details, e.g. the stored resource.
+    pub data: Value,
+}
+
+impl WebhookEvent {
+    fn new(kind: WebhookEventKind, resource_id: Id, data: Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            timestamp: SystemTime::now(),
+            resource_id: resource_id.value(),
+            data,
+        }
+    }
+
+    /// The JSON body posted to the webhooks.
+    pub fn payload(&self) -> Vec<u8> {
+        let payload = json!({
+            "id": self.id,
+            "event": self.kind,
+            "timestamp": DateTime::<Utc>::from(self.timestamp).to_rfc3339_opts(SecondsFormat::Millis, true),
+            "resource_id": self.resource_id,
+            "data": self.data,
+        });
+        serde_json::to_vec(&payload).expect("Payload of webhook event should be serializable.")
+    }
+}
+
+/// Change of a resource, from which [`WebhookEvent`]s are derived.
+pub enum ObservedChange {
+    PeerDescriptor(ResourceEvent<PeerDescriptor>),
+    PeerState(ResourceEvent<PeerState>),
+    PeerConfigurationState(ResourceEvent<PeerConfigurationState>),
+    ClusterConfiguration(ResourceEvent<ClusterConfiguration>),
+    ClusterState(ResourceEvent<ClusterState>),
+    ClusterDeploymentFailed(ClusterDeploymentFailure),
+}
+
+/// Derives [`WebhookEvent`]s from changes of resources.
+///
+/// Remembers the previous states of peers, clusters and executors, since the events are transitions between them.
+#[derive(Default)]
+pub struct EventDetector {
+    peer_states: HashMap<Id, PeerState>,
+    cluster_states: HashMap<Id, ClusterState>,
+    executor_states: HashMap<Id, HashMap<ParameterId, ParameterStateKind>>,
+}
+
+impl EventDetector {
+
+    /// Creates an [`EventDetector`], which takes the current states as the previous states.
+    pub fn initialize(resources: &Resources) -> Self {
+        let mut detector = Self::default();
+
+        let peer_ids = resources.iter::<PeerDescriptor>()
+            .map(|peer| peer.id)
+            .collect::<Vec<PeerId>>();
+        for peer_id in peer_ids {
+            if let Some(peer_state) = resources.get::<PeerState>(peer_id) {
+                detector.peer_states.insert(Id::from(peer_id), peer_state);
+            }
+            if let Some(configuration_state) = resources.get::<PeerConfigurationState>(peer_id) {
+                detector.executor_states.insert(Id::from(peer_id), executor_states(&configuration_state));
+            }
+        }
+
+        let cluster_ids = resources.iter::<ClusterConfiguration>()
+            .map(|cluster| cluster.id)
+            .collect::<Vec<ClusterId>>();
+        for cluster_id in cluster_ids {
+            if let Some(cluster_state) = resources.get::<ClusterState>(cluster_id) {
+                detector.cluster_states.insert(Id::from(cluster_id.0), cluster_state);
+            }
+        }
+
+        detector
+    }
+
+    pub fn detect(&mut self, change: ObservedChange) -> Vec<WebhookEvent> {
+        match change {
+            ObservedChange::PeerDescriptor(event) => stored_or_deleted(event, WebhookEventKind::PeerStored, WebhookEventKind::PeerDeleted),
+            ObservedChange::ClusterConfiguration(event) => stored_or_deleted(event, WebhookEventKind::ClusterConfigurationStored, WebhookEventKind::ClusterConfigurationDeleted),
+            ObservedChange::PeerState(event) => self.detect_peer_state(event),
+            ObservedChange::ClusterState(event) => self.detect_cluster_state(event),
+            ObservedChange::PeerConfigurationState(event) => self.detect_executor_states(event),
+            ObservedChange::ClusterDeploymentFailed(failure) => {
+                let data = json!({ "error": failure.cause });
+                vec![WebhookEvent::new(WebhookEventKind::ClusterDeploymentFailed, Id::from(failure.cluster_id.0), data)]
+            }
+        }
+    }
+
+    fn detect_peer_state(&mut self, event: ResourceEvent<PeerState>) -> Vec<WebhookEvent> {
+        match event {
+            ResourceEvent::Created { id, resource } | ResourceEvent::Updated { id, resource } => {
+                let previous = self.peer_states.insert(id, Clone::clone(&resource));
+                let was_up = matches!(previous, Some(PeerState::Up { .. }));
+                match resource {
+                    PeerState::Up { .. } if !was_up => vec![WebhookEvent::new(WebhookEventKind::PeerUp, id, to_value(&resource))],
+                    PeerState::Down if was_up => vec![WebhookEvent::new(WebhookEventKind::PeerDown, id, to_value(&resource))],
+                    _ => vec![],
+                }
+            }
+            ResourceEvent::Deleted { id } => {
+                self.peer_states.remove(&id);
+                vec![]
+            }
+        }
+    }
+
+    /// A failed deployment also removes the state of the cluster while it is still deploying, but is reported separately as [`ObservedChange::ClusterDeploymentFailed`].
+    fn detect_cluster_state(&mut self, event: ResourceEvent<ClusterState>) -> Vec<WebhookEvent> {
+        let (id, current) = match event {
+            ResourceEvent::Created { id, resource } | ResourceEvent::Updated { id, resource } => (id, resource),
+            ResourceEvent::Deleted { id } => (id, ClusterState::Undeployed),
+        };
+        let previous = match current {
+            ClusterState::Undeployed => self.cluster_states.remove(&id),
+            _ => self.cluster_states.insert(id, Clone::clone(&current)),
+        };
+
+        let kind = match (previous, &current) {
+            (Some(ClusterState::Deploying), ClusterState::Deployed(_)) => WebhookEventKind::ClusterDeployed,
+            (Some(ClusterState::Deployed(_)), ClusterState::Undeployed) => WebhookEventKind::ClusterUndeployed,
+            _ => return vec![],
+        };
+        vec![WebhookEvent::new(kind, id, to_value(&current))]
+    }
+
+    /// An executor is finished, when the peer reports it as present, absent or failed for the first time.
+    fn detect_executor_states(&mut self, event: ResourceEvent<PeerConfigurationState>) -> Vec<WebhookEvent> {
+        let (id, configuration_state) = match event {
+            ResourceEvent::Created { id, resource } | ResourceEvent::Updated { id, resource } => (id, resource),
+            ResourceEvent::Deleted { id } => {
+                self.executor_states.remove(&id);
+                return vec![];
+            }
+        };
+        let previous_states = self.executor_states.insert(id, executor_states(&configuration_state))
+            .unwrap_or_default();
+
+        configuration_state.executors.into_iter()
+            .filter(|executor| previous_states.get(&executor.id) != Some(&executor.state))
+            .filter_map(|executor| {
+                let (state, error) = match &executor.state {
+                    ParameterStateKind::Present => ("present", None),
+                    ParameterStateKind::Absent => ("absent", None),
+                    ParameterStateKind::Error(error) => ("error", Some(error.to_string())),
+                    ParameterStateKind::WaitingForDependencies(_) => return None,
+                };
+                let data = json!({
+                    "peer_id": id.value(),
+                    "parameter_id": executor.id.0,
+                    "executor": to_value(&executor.value),
+                    "state": state,
+                    "error": error,
+                });
+                Some(WebhookEvent::new(WebhookEventKind::ExecutorFinished, id, data))
+            })
+            .collect()
+    }
+}
+
+fn stored_or_deleted<R: Serialize>(event: ResourceEvent<R>, stored: WebhookEventKind, deleted: WebhookEventKind) -> Vec<WebhookEvent> {
+    let event = match event {
+        ResourceEvent::Created { id, resource } | ResourceEvent::Updated { id, resource } => WebhookEvent::new(stored, id, to_value(&resource)),
+        ResourceEvent::Deleted { id } => WebhookEvent::new(deleted, id, Value::Null),
+    };
+    vec![event]
+}
+
+fn executor_states(configuration_state: &PeerConfigurationState) -> HashMap<ParameterId, ParameterStateKind> {
+    configuration_state.executors.iter()
+        .map(|executor| (executor.id, Clone::clone(&executor.state)))
+        .collect()
+}
+
+fn to_value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|cause| Value::String(format!("<not serializable: {cause}>")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::state::DeployedClusterState;
+    use opendut_types::peer::configuration::{ParameterState, ParameterStateError};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
+    use opendut_types::peer::state::PeerUpState;
+
+    use super::*;
+
+    #[test]
+    fn should_detect_transitions_of_peers_and_clusters() {
+        let mut testee = EventDetector::default();
+        let peer_id = Id::random();
+        let up = PeerState::Up { inner: PeerUpState::Available, remote_host: IpAddr::from([127, 0, 0, 1]) };
+
+        let kinds = |events: Vec<WebhookEvent>| events.into_iter().map(|event| event.kind).collect::<Vec<_>>();
+
+        assert_that!(kinds(testee.detect(ObservedChange::PeerState(ResourceEvent::Created { id: peer_id, resource: PeerState::Down }))), empty());
+        assert_that!(kinds(testee.detect(ObservedChange::PeerState(ResourceEvent::Updated { id: peer_id, resource: Clone::clone(&up) }))), elements_are![eq(WebhookEventKind::PeerUp)]);
+        assert_that!(kinds(testee.detect(ObservedChange::PeerState(ResourceEvent::Updated { id: peer_id, resource: up }))), empty());
+        assert_that!(kinds(testee.detect(ObservedChange::PeerState(ResourceEvent::Updated { id: peer_id, resource: PeerState::Down }))), elements_are![eq(WebhookEventKind::PeerDown)]);
+
+        let cluster_id = Id::random();
+        testee.detect(ObservedChange::ClusterState(ResourceEvent::Created { id: cluster_id, resource: ClusterState::Deploying }));
+        assert_that!(kinds(testee.detect(ObservedChange::ClusterState(ResourceEvent::Deleted { id: cluster_id }))), empty());
+        let failure = ClusterDeploymentFailure { cluster_id: ClusterId(cluster_id.value()), cause: String::from("VPN not reachable.") };
+        let events = testee.detect(ObservedChange::ClusterDeploymentFailed(failure));
+        assert_that!(kinds(Clone::clone(&events)), elements_are![eq(WebhookEventKind::ClusterDeploymentFailed)]);
+        assert_that!(events[0].data["error"], eq(json!("VPN not reachable.")));
+
+        testee.detect(ObservedChange::ClusterState(ResourceEvent::Created { id: cluster_id, resource: ClusterState::Deploying }));
+        let deployed = ClusterState::Deployed(DeployedClusterState::Healthy);
+        assert_that!(kinds(testee.detect(ObservedChange::ClusterState(ResourceEvent::Updated { id: cluster_id, resource: deployed }))), elements_are![eq(WebhookEventKind::ClusterDeployed)]);
+        assert_that!(kinds(testee.detect(ObservedChange::ClusterState(ResourceEvent::Deleted { id: cluster_id }))), elements_are![eq(WebhookEventKind::ClusterUndeployed)]);
+    }
+
+    #[test]
+    fn should_detect_finished_executors_once() {
+        let mut testee = EventDetector::default();
+        let peer_id = Id::random();
+        let executor = |id: ParameterId, state: ParameterStateKind| ParameterState {
+            id,
+            state,
+            value: ExecutorDescriptor { kind: ExecutorKind::Executable, results_url: None },
+        };
+        let executor_a = ParameterId(Uuid::new_v4());
+        let executor_b = ParameterId(Uuid::new_v4());
+
+        let events = testee.detect(ObservedChange::PeerConfigurationState(ResourceEvent::Created { id: peer_id, resource: PeerConfigurationState {
+            executors: vec![
+                executor(executor_a, ParameterStateKind::Present),
+                executor(executor_b, ParameterStateKind::WaitingForDependencies(vec![executor_a])),
+            ],
+            cluster_teardown: None,
+        }}));
+        assert_that!(events.len(), eq(1));
+        assert_that!(events[0].data["state"], eq(json!("present")));
+
+        let events = testee.detect(ObservedChange::PeerConfigurationState(ResourceEvent::Updated { id: peer_id, resource: PeerConfigurationState {
+            executors: vec![
+                executor(executor_a, ParameterStateKind::Present),
+                executor(executor_b, ParameterStateKind::Error(ParameterStateError::CreatingFailed(String::from("Image not found.")))),
+            ],
+            cluster_teardown: None,
+        }}));
+        assert_that!(events.len(), eq(1));
+        assert_that!(events[0].kind, eq(WebhookEventKind::ExecutorFinished));
+        assert_that!(events[0].data["parameter_id"], eq(json!(executor_b.0)));
+        assert_that!(events[0].data["error"], eq(json!("Creating failed: Image not found.")));
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+use tokio::sync::{broadcast, RwLock};
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::configuration::PeerConfigurationState;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::state::PeerState;
+use opendut_types::webhook::{Webhook, WebhookDelivery, WebhookDeliveryOutcome, WebhookId};
+
+use crate::cluster::manager::ClusterDeploymentFailure;
+use crate::resources::manager::{ResourcesManagerRef, SubscriptionLagged};
+use crate::webhook::events::{EventDetector, ObservedChange, WebhookEvent};
+
+pub mod events;
+
+/// Header carrying the hex-encoded HMAC-SHA256 of the payload, keyed with the secret of the webhook, prefixed with `sha256=`.
+pub const SIGNATURE_HEADER: &str = "X-Opendut-Signature";
+/// Header carrying the kind of the event, e.g. `peer-down`.
+pub const EVENT_HEADER: &str = "X-Opendut-Event";
+/// Header carrying the id of the event, which stays the same when the delivery is retried.
+pub const DELIVERY_HEADER: &str = "X-Opendut-Delivery";
+
+pub type WebhookDispatcherRef = Arc<WebhookDispatcher>;
+
+/// Posts events to the [`Webhook`]s subscribed to them and keeps a log of the latest deliveries of each webhook.
+pub struct WebhookDispatcher {
+    resources_manager: ResourcesManagerRef,
+    client: reqwest::Client,
+    options: WebhookOptions,
+    deliveries: RwLock<HashMap<WebhookId, VecDeque<WebhookDelivery>>>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(resources_manager: ResourcesManagerRef, options: WebhookOptions) -> anyhow::Result<WebhookDispatcherRef> {
+        let client = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .build()?;
+
+        Ok(Arc::new(Self {
+            resources_manager,
+            client,
+            options,
+            deliveries: Default::default(),
+        }))
+    }
+
+    /// Watches the resources and the failed deployments for events and dispatches them, until CARL stops.
+    pub fn spawn(self: &Arc<Self>, deployment_failures: broadcast::Receiver<ClusterDeploymentFailure>) {
+        let resources_manager = &self.resources_manager;
+        let mut changes = resources_manager.subscribe::<PeerDescriptor>().map(|event| event.map(ObservedChange::PeerDescriptor))
+            .merge(resources_manager.subscribe::<PeerState>().map(|event| event.map(ObservedChange::PeerState)))
+            .merge(resources_manager.subscribe::<PeerConfigurationState>().map(|event| event.map(ObservedChange::PeerConfigurationState)))
+            .merge(resources_manager.subscribe::<ClusterConfiguration>().map(|event| event.map(ObservedChange::ClusterConfiguration)))
+            .merge(resources_manager.subscribe::<ClusterState>().map(|event| event.map(ObservedChange::ClusterState)))
+            .merge(BroadcastStream::new(deployment_failures).map(|failure| failure
+                .map(ObservedChange::ClusterDeploymentFailed)
+                .map_err(|BroadcastStreamRecvError::Lagged(skipped)| SubscriptionLagged { skipped })
+            ));
+
+        let dispatcher = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut detector = dispatcher.resources_manager.resources(EventDetector::initialize).await;
+
+            while let Some(change) = changes.next().await {
+                match change {
+                    Ok(change) => {
+                        for event in detector.detect(change) {
+                            dispatcher.dispatch(event).await;
+                        }
+                    }
+                    Err(lagged) => {
+                        warn!("Events for webhooks may have been missed: {lagged}");
+                        detector = dispatcher.resources_manager.resources(EventDetector::initialize).await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Delivers the event to all webhooks subscribed to it, retrying failed deliveries in the background.
+    pub async fn dispatch(self: &Arc<Self>, event: WebhookEvent) {
+        let webhooks = self.resources_manager.resources(|resources| {
+            resources.iter::<Webhook>()
+                .filter(|webhook| webhook.subscribes_to(event.kind))
+                .map(|webhook| webhook.id)
+                .collect::<Vec<_>>()
+        }).await;
+
+        let event = Arc::new(event);
+        for webhook_id in webhooks {
+            self.record(WebhookDelivery {
+                webhook: webhook_id,
+                event_id: event.id,
+                event: event.kind,
+                timestamp: event.timestamp,
+                attempts: 0,
+                outcome: WebhookDeliveryOutcome::Pending,
+            }).await;
+
+            let dispatcher = Arc::clone(self);
+            let event = Arc::clone(&event);
+            tokio::spawn(async move {
+                dispatcher.deliver(webhook_id, &event).await;
+            });
+        }
+    }
+
+    async fn deliver(&self, webhook_id: WebhookId, event: &WebhookEvent) {
+        let payload = event.payload();
+
+        for attempt in 1..=self.options.attempts {
+            let Some(webhook) = self.resources_manager.get::<Webhook>(webhook_id).await else {
+                debug!("Stopped delivering event <{}>, since webhook <{webhook_id}> was deleted.", event.id);
+                return;
+            };
+
+            let outcome = match self.post(&webhook, event, &payload).await {
+                Ok(status_code) => {
+                    debug!("Delivered event <{}> '{}' to webhook <{webhook_id}>.", event.id, event.kind);
+                    WebhookDeliveryOutcome::Delivered { status_code }
+                }
+                Err(cause) if attempt < self.options.attempts => {
+                    debug!("Failed to deliver event <{}> to webhook <{webhook_id}> in attempt {attempt}. Retrying.\n  {cause}", event.id);
+                    WebhookDeliveryOutcome::Retrying { cause }
+                }
+                Err(cause) => {
+                    warn!("Failed to deliver event <{}> to webhook <{webhook_id}> after {attempt} attempts.\n  {cause}", event.id);
+                    WebhookDeliveryOutcome::Failed { cause }
+                }
+            };
+            let retrying = matches!(outcome, WebhookDeliveryOutcome::Retrying { .. });
+            self.update(webhook_id, event.id, attempt, outcome).await;
+
+            if !retrying {
+                return;
+            }
+            tokio::time::sleep(self.options.backoff(attempt)).await;
+        }
+    }
+
+    /// Returns the status code, if the endpoint responded with a successful one.
+    async fn post(&self, webhook: &Webhook, event: &WebhookEvent, payload: &[u8]) -> Result<u16, String> {
+        let response = self.client.post(Clone::clone(&webhook.url))
+            .header(CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, format!("sha256={}", sign(&webhook.secret, payload)))
+            .header(EVENT_HEADER, event.kind.to_string())
+            .header(DELIVERY_HEADER, event.id.to_string())
+            .body(payload.to_vec())
+            .send().await
+            .map_err(|cause| cause.to_string())?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(status.as_u16())
+        } else {
+            Err(format!("Endpoint responded with status '{status}'."))
+        }
+    }
+
+    /// Returns the latest deliveries to the webhook, from oldest to latest.
+    pub async fn list_deliveries(&self, webhook_id: WebhookId, limit: Option<u32>) -> Vec<WebhookDelivery> {
+        let deliveries = self.deliveries.read().await;
+        let Some(deliveries) = deliveries.get(&webhook_id) else {
+            return Vec::new();
+        };
+        let skipped = limit.map_or(0, |limit| deliveries.len().saturating_sub(limit as usize));
+        deliveries.iter()
+            .skip(skipped)
+            .cloned()
+            .collect()
+    }
+
+    /// Discards the deliveries of a deleted webhook.
+    pub async fn forget(&self, webhook_id: WebhookId) {
+        self.deliveries.write().await.remove(&webhook_id);
+    }
+
+    async fn record(&self, delivery: WebhookDelivery) {
+        let mut deliveries = self.deliveries.write().await;
+        let deliveries = deliveries.entry(delivery.webhook).or_default();
+        deliveries.push_back(delivery);
+        while deliveries.len() > self.options.log_capacity {
+            deliveries.pop_front();
+        }
+    }
+
+    async fn update(&self, webhook_id: WebhookId, event_id: Uuid, attempts: u32, outcome: WebhookDeliveryOutcome) {
+        let mut deliveries = self.deliveries.write().await;
+        let delivery = deliveries.get_mut(&webhook_id)
+            .and_then(|deliveries| deliveries.iter_mut().rev().find(|delivery| delivery.event_id == event_id));
+        if let Some(delivery) = delivery {
+            delivery.attempts = attempts;
+            delivery.outcome = outcome;
+        }
+    }
+}
+
+/// Computes the hex-encoded HMAC-SHA256 of the payload, with which receivers can verify, that the payload was sent by CARL.
+pub fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC should accept keys of any length.");
+    mac.update(payload);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// Generates the secret of a new webhook.
+pub fn generate_secret() -> String {
+    format!("whsec_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+#[derive(Clone, Debug)]
+pub struct WebhookOptions {
+    /// Maximum number of attempts to deliver an event.
+    pub attempts: u32,
+    pub timeout: Duration,
+    /// Delay after the first failed attempt, which is doubled after each further failed attempt.
+    pub backoff_initial: Duration,
+    pub backoff_max: Duration,
+    /// Number of deliveries kept per webhook.
+    pub log_capacity: usize,
+}
+
+impl WebhookOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let attempts = config.get::<u32>("webhook.delivery.attempts")?;
+        let timeout = Duration::from_millis(config.get::<u64>("webhook.delivery.timeout.ms")?);
+        let backoff_initial = Duration::from_millis(config.get::<u64>("webhook.delivery.backoff.initial.ms")?);
+        let backoff_max = Duration::from_millis(config.get::<u64>("webhook.delivery.backoff.max.ms")?);
+        let log_capacity = config.get::<usize>("webhook.delivery.log.capacity")?;
+
+        Ok(WebhookOptions {
+            attempts,
+            timeout,
+            backoff_initial,
+            backoff_max,
+            log_capacity,
+        })
+    }
+
+    fn backoff(&self, failed_attempts: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(failed_attempts.saturating_sub(1));
+        self.backoff_initial.saturating_mul(factor)
+            .min(self.backoff_max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::net::{IpAddr, SocketAddr, TcpListener};
+    use std::sync::Mutex;
+
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerId;
+    use opendut_types::peer::state::PeerUpState;
+    use opendut_types::webhook::WebhookEventKind;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Receiver {
+        requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+    }
+
+    /// Stand-in for an endpoint, which fails the first request and accepts all further requests.
+    async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+        let mut requests = receiver.requests.lock().unwrap();
+        requests.push((headers, body));
+        if requests.len() == 1 {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::NO_CONTENT
+        }
+    }
+
+    #[tokio::test]
+    async fn should_deliver_signed_events_to_subscribed_webhooks_with_retries() -> anyhow::Result<()> {
+        let receiver = Receiver::default();
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+        let address = listener.local_addr()?;
+        let server = axum::Server::from_tcp(listener)?
+            .serve(axum::Router::new().route("/hook", post(receive)).with_state(Clone::clone(&receiver)).into_make_service());
+        tokio::spawn(server);
+
+        let resources_manager = ResourcesManager::new();
+        let webhook = Webhook {
+            id: WebhookId::random(),
+            url: url::Url::parse(&format!("http://{address}/hook"))?,
+            events: HashSet::from([WebhookEventKind::PeerDown]),
+            secret: generate_secret(),
+            created_by: String::from("admin"),
+        };
+        resources_manager.insert(webhook.id, Clone::clone(&webhook)).await?;
+
+        let testee = WebhookDispatcher::new(Arc::clone(&resources_manager), WebhookOptions {
+            attempts: 3,
+            timeout: Duration::from_secs(5),
+            backoff_initial: Duration::from_millis(10),
+            backoff_max: Duration::from_millis(100),
+            log_capacity: 10,
+        })?;
+        let (_deployment_failures, deployment_failures_receiver) = broadcast::channel(1);
+        testee.spawn(deployment_failures_receiver);
+
+        let peer_id = PeerId::random();
+        resources_manager.insert(peer_id, PeerState::Up { inner: PeerUpState::Available, remote_host: IpAddr::from([127, 0, 0, 1]) }).await?;
+        resources_manager.insert(peer_id, PeerState::Down).await?;
+
+        let deliveries = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let deliveries = testee.list_deliveries(webhook.id, None).await;
+                if deliveries.iter().any(|delivery| matches!(delivery.outcome, WebhookDeliveryOutcome::Delivered { .. })) {
+                    break deliveries;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await?;
+
+        assert_that!(deliveries, elements_are![matches_pattern!(WebhookDelivery {
+            event: eq(WebhookEventKind::PeerDown),
+            attempts: eq(2),
+            outcome: eq(WebhookDeliveryOutcome::Delivered { status_code: 204 }),
+        })]);
+
+        let requests = receiver.requests.lock().unwrap();
+        assert_that!(requests.len(), eq(2));
+        let (headers, body) = &requests[1];
+        let signature = headers.get(SIGNATURE_HEADER).and_then(|value| value.to_str().ok());
+        assert_that!(signature, some(eq(format!("sha256={}", sign(&webhook.secret, body)))));
+        assert_that!(headers.get(DELIVERY_HEADER), eq(requests[0].0.get(DELIVERY_HEADER)));
+
+        let payload = serde_json::from_slice::<serde_json::Value>(body)?;
+        assert_that!(payload["event"], eq(serde_json::json!("peer-down")));
+        assert_that!(payload["resource_id"], eq(serde_json::json!(peer_id.uuid)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_double_backoff_up_to_maximum() {
+        let options = WebhookOptions {
+            attempts: 5,
+            timeout: Duration::from_secs(1),
+            backoff_initial: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(5),
+            log_capacity: 1,
+        };
+        assert_that!(options.backoff(1), eq(Duration::from_secs(1)));
+        assert_that!(options.backoff(3), eq(Duration::from_secs(4)));
+        assert_that!(options.backoff(4), eq(Duration::from_secs(5)));
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::project::ProjectName;
+use opendut_types::service_account::{ApiToken, ApiTokenId, ApiTokenRole, ServiceAccountId};
+
+use crate::commands::api_token::ApiTokenTable;
+use crate::CreateOutputFormat;
+
+/// Issue an API token for a service account. The token is only shown once.
+#[derive(clap::Parser)]
+pub struct CreateApiTokenCli {
+    ///ID of the service account, which authenticates with the token
+    #[arg(long)]
+    service_account_id: Uuid,
+    ///Role granted by the token
+    #[arg(value_enum, long, default_value_t = RoleVariants::Viewer)]
+    role: RoleVariants,
+    ///Projects, whose resources may be accessed with the token. Ignored for the role 'admin'.
+    #[arg(long, num_args = 1.., default_values_t = [String::from(ProjectName::DEFAULT)])]
+    projects: Vec<String>,
+    ///Expiry of the token in RFC 3339 format, e.g. 2024-12-31T00:00:00Z
+    #[arg(long, required_unless_present = "valid_days")]
+    expires_at: Option<DateTime<Utc>>,
+    ///Number of days the token is valid, as an alternative to its expiry
+    #[arg(long, conflicts_with = "expires_at")]
+    valid_days: Option<u64>,
+    ///ApiTokenID
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+#[derive(clap::ValueEnum, Clone)]
+pub enum RoleVariants {
+    Viewer,
+    Operator,
+    Admin,
+}
+
+impl From<RoleVariants> for ApiTokenRole {
+    fn from(value: RoleVariants) -> Self {
+        match value {
+            RoleVariants::Viewer => ApiTokenRole::Viewer,
+            RoleVariants::Operator => ApiTokenRole::Operator,
+            RoleVariants::Admin => ApiTokenRole::Admin,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct IssuedApiTokenOutput {
+    #[serde(flatten)]
+    api_token: ApiTokenTable,
+    secret: String,
+}
+
+impl CreateApiTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let id = ApiTokenId::from(self.id.unwrap_or_else(Uuid::new_v4));
+
+        let expires_at = match (self.expires_at, self.valid_days) {
+            (Some(expires_at), _) => SystemTime::from(expires_at),
+            (None, Some(valid_days)) => SystemTime::now() + Duration::from_secs(valid_days * 24 * 60 * 60),
+            (None, None) => return Err(String::from("Either an expiry or the number of days the API token is valid must be specified.")),
+        };
+        let projects = self.projects.into_iter()
+            .map(ProjectName::try_from)
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(|error| error.to_string())?;
+
+        let api_token = ApiToken {
+            id,
+            service_account: ServiceAccountId::from(self.service_account_id),
+            role: ApiTokenRole::from(self.role),
+            projects,
+            expires_at,
+            secret_hash: String::new(), //set by CARL
+        };
+
+        let issued = carl.service_accounts.issue_api_token(api_token).await
+            .map_err(|error| format!("Could not issue API token <{}>.\n  {}", id, error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully issued API token <{}>. Store it securely, since it cannot be shown again:", id);
+                println!("{}", issued.secret);
+            }
+            CreateOutputFormat::Json => {
+                let output = IssuedApiTokenOutput { api_token: ApiTokenTable::from(issued.api_token), secret: issued.secret };
+                let json = serde_json::to_string(&output).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let output = IssuedApiTokenOutput { api_token: ApiTokenTable::from(issued.api_token), secret: issued.secret };
+                let json = serde_json::to_string_pretty(&output).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
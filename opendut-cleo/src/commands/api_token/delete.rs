@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ApiTokenId;
+
+/// Revoke an API token
+#[derive(clap::Parser)]
+pub struct DeleteApiTokenCli {
+    ///ApiTokenID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteApiTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ApiTokenId::from(self.id);
+        carl.service_accounts.revoke_api_token(id).await
+            .map_err(|error| format!("Could not revoke API token <{}>.\n  {}", id, error))?;
+        println!("Revoked API token <{}>.", id);
+
+        Ok(())
+    }
+}
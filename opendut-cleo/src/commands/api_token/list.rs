@@ -0,0 +1,46 @@
+use cli_table::{print_stdout, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ServiceAccountId;
+
+use crate::commands::api_token::ApiTokenTable;
+use crate::ListOutputFormat;
+
+/// List the API tokens of all or one service account(s)
+#[derive(clap::Parser)]
+pub struct ListApiTokensCli {
+    ///Only list the tokens of the service account with this ID
+    #[arg(long)]
+    service_account_id: Option<Uuid>,
+}
+
+impl ListApiTokensCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let service_account_id = self.service_account_id.map(ServiceAccountId::from);
+        let mut api_tokens = carl.service_accounts.list_api_tokens(service_account_id).await
+            .map_err(|error| format!("Error while listing API tokens: {}", error))?;
+        api_tokens.sort_by_key(|api_token| api_token.expires_at);
+
+        let api_token_table = api_tokens.into_iter()
+            .map(ApiTokenTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(api_token_table.with_title())
+                    .expect("List of API tokens should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&api_token_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&api_token_table).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use cli_table::Table;
+use serde::Serialize;
+
+use opendut_types::service_account::{ApiToken, ApiTokenId, ServiceAccountId};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+#[derive(Table, Debug, Serialize)]
+pub(crate) struct ApiTokenTable {
+    #[table(title = "ApiTokenID")]
+    id: ApiTokenId,
+    #[table(title = "ServiceAccountID")]
+    service_account: ServiceAccountId,
+    #[table(title = "Role")]
+    role: String,
+    #[table(title = "Projects")]
+    projects: String,
+    #[table(title = "Expires At")]
+    expires_at: String,
+    #[table(title = "Expired")]
+    expired: bool,
+}
+
+impl From<ApiToken> for ApiTokenTable {
+    fn from(api_token: ApiToken) -> Self {
+        let mut projects = api_token.projects.iter().map(ToString::to_string).collect::<Vec<_>>();
+        projects.sort();
+
+        ApiTokenTable {
+            id: api_token.id,
+            service_account: api_token.service_account,
+            role: api_token.role.to_string(),
+            projects: projects.join(", "),
+            expires_at: DateTime::<Utc>::from(api_token.expires_at).to_string(),
+            expired: api_token.is_expired_at(SystemTime::now()),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::audit::{AuditEntry, AuditQuery};
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::resources::Id;
+
+use crate::ListOutputFormat;
+
+/// List the audit log of mutating operations
+#[derive(clap::Parser)]
+pub struct ListAuditEntriesCli {
+    ///Only list operations performed by this user
+    #[arg(long)]
+    user: Option<String>,
+    ///Only list operations performed on the resource with this ID
+    #[arg(long)]
+    resource: Option<Uuid>,
+    ///Only list the latest entries up to this number
+    #[arg(long)]
+    limit: Option<u32>,
+}
+
+#[derive(Table, Debug, Serialize)]
+struct AuditEntryTable {
+    #[table(title = "Timestamp")]
+    timestamp: String,
+    #[table(title = "User")]
+    user: String,
+    #[table(title = "Operation")]
+    operation: String,
+    #[table(title = "ResourceID")]
+    resource_id: Id,
+    #[table(title = "Before")]
+    before: String,
+    #[table(title = "After")]
+    after: String,
+}
+
+impl ListAuditEntriesCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let query = AuditQuery {
+            user: self.user,
+            resource_id: self.resource.map(Id::from),
+            limit: self.limit,
+        };
+        let entries = carl.audit.list_audit_entries(query).await
+            .map_err(|error| format!("Error while listing audit entries: {}", error))?;
+
+        let audit_table = entries.into_iter()
+            .map(AuditEntryTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(audit_table.with_title())
+                    .expect("List of audit entries should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&audit_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&audit_table).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<AuditEntry> for AuditEntryTable {
+    fn from(entry: AuditEntry) -> Self {
+        let timestamp = DateTime::<Utc>::from(entry.timestamp);
+
+        AuditEntryTable {
+            timestamp: timestamp.to_string(),
+            user: entry.user,
+            operation: entry.operation,
+            resource_id: entry.resource_id,
+            before: entry.before.unwrap_or_default(),
+            after: entry.after.unwrap_or_default(),
+        }
+    }
+}
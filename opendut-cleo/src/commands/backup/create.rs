@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::CarlClient;
+
+/// Write a backup of all resources in CARL to an archive file
+#[derive(clap::Parser)]
+pub struct CreateBackupCli {
+    ///Path of the archive file to write
+    #[arg()]
+    file: PathBuf,
+}
+
+impl CreateBackupCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let archive = carl.backup.create_backup().await
+            .map_err(|error| format!("Could not create backup.\n  {}", error))?;
+
+        let peer_count = archive.peer_descriptors.len();
+        let cluster_configuration_count = archive.cluster_configurations.len();
+        let cluster_deployment_count = archive.cluster_deployments.len();
+
+        std::fs::write(&self.file, archive.encode())
+            .map_err(|error| format!("Could not write backup to '{}'.\n  {}", self.file.display(), error))?;
+
+        println!("Wrote backup with {peer_count} peer(s), {cluster_configuration_count} cluster configuration(s) and {cluster_deployment_count} cluster deployment(s) to '{}'.", self.file.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::backup::ResourcesArchive;
+use opendut_carl_api::carl::CarlClient;
+
+/// Restore all resources from an archive file into CARL, replacing resources with the same ID
+#[derive(clap::Parser)]
+pub struct RestoreBackupCli {
+    ///Path of the archive file to restore
+    #[arg()]
+    file: PathBuf,
+}
+
+impl RestoreBackupCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let content = std::fs::read(&self.file)
+            .map_err(|error| format!("Could not read backup from '{}'.\n  {}", self.file.display(), error))?;
+        let archive = ResourcesArchive::decode(&content)
+            .map_err(|error| format!("Could not decode backup from '{}'.\n  {}", self.file.display(), error))?;
+
+        carl.backup.restore_backup(archive).await
+            .map_err(|error| format!("Could not restore backup.\n  {}", error))?;
+
+        println!("Restored backup from '{}'.", self.file.display());
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use uuid::Uuid;
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterConfiguration, ClusterId};
 use opendut_types::peer::PeerId;
+use opendut_types::project::ProjectName;
 use opendut_types::topology::{DeviceDescriptor, DeviceName};
 
 use crate::{ClusterConfigurationDevices, CreateOutputFormat};
@@ -26,6 +27,9 @@ pub struct CreateClusterConfigurationCli {
     ///List of devices in cluster
     #[clap(flatten)]
     devices: ClusterConfigurationDevices,
+    ///Project the cluster belongs to
+    #[arg(long, default_value = ProjectName::DEFAULT)]
+    project: String,
 }
 
 impl CreateClusterConfigurationCli {
@@ -48,6 +52,9 @@ impl CreateClusterConfigurationCli {
 
         let leader = PeerId::from(self.leader_id); //TODO: check if peer exists
 
+        let project = ProjectName::try_from(self.project)
+            .map_err(|error| format!("Could not create cluster configuration.\n  {}", error))?;
+
         let all_devices = carl.peers.list_devices().await
             .map_err(|error| format!("Error while listing devices.\n  {}", error))?;
         let checked_devices = check_devices(&all_devices, &self.devices.device_names, &self.devices.device_ids);
@@ -65,11 +72,12 @@ impl CreateClusterConfigurationCli {
         if !errors.is_empty() {
             Err(format!("Could not create cluster configuration:\n  {}", errors.join("\n  ")))?
         }
-        if devices.len() < 2 {
-            Err("Specify at least 2 devices per cluster configuration.".to_string())?
+        let device_selectors = self.devices.device_selectors;
+        if devices.len() < 2 && device_selectors.is_empty() {
+            Err("Specify at least 2 devices or a device selector per cluster configuration.".to_string())?
         }
 
-        let configuration = ClusterConfiguration { id: cluster_id, name: Clone::clone(&cluster_name), leader, devices: device_ids };
+        let configuration = ClusterConfiguration { id: cluster_id, name: Clone::clone(&cluster_name), leader, devices: device_ids, device_selectors: Clone::clone(&device_selectors), project };
         carl.cluster.store_cluster_configuration(configuration.clone()).await
             .map_err(|err| format!("Could not store cluster configuration. Make sure the application is running. Error: {}", err))?;
 
@@ -83,6 +91,12 @@ impl CreateClusterConfigurationCli {
                 for device_name in device_names.iter() {
                     println!("\x09{}", device_name);
                 };
+                if device_selectors.is_empty().not() {
+                    println!("The following device selectors are resolved when the cluster is deployed:");
+                    for device_selector in device_selectors.iter() {
+                        println!("\x09{}", device_selector);
+                    };
+                }
             }
             CreateOutputFormat::Json => {
                 let json = serde_json::to_string(&configuration).unwrap();
@@ -25,6 +25,7 @@ struct ClusterTable {
     leader: PeerId,
     peers: Vec<PeerName>,
     devices: Vec<DeviceName>,
+    device_selectors: Vec<String>,
 }
 
 impl DescribeClusterConfigurationCli {
@@ -64,6 +65,9 @@ impl DescribeClusterConfigurationCli {
             leader: cluster_configuration.leader,
             peers: cluster_peers,
             devices: cluster_devices,
+            device_selectors: cluster_configuration.device_selectors.iter()
+                .map(ToString::to_string)
+                .collect(),
         };
 
         let text = match output {
@@ -74,7 +78,8 @@ impl DescribeClusterConfigurationCli {
                   Leader: {}
                   Peers: [{:?}]
                   Devices: [{:?}]
-            "), table.name, table.id, table.leader, table.peers, table.devices)
+                  Device Selectors: [{:?}]
+            "), table.name, table.id, table.leader, table.peers, table.devices, table.device_selectors)
             }
             DescribeOutputFormat::Json => {
                 serde_json::to_string(&table).unwrap()
@@ -2,6 +2,8 @@ use cli_table::{print_stdout, Table, WithTitle};
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterId, ClusterName};
+use opendut_types::project::ProjectName;
+use opendut_types::ShortName;
 
 use crate::ListOutputFormat;
 
@@ -15,20 +17,26 @@ struct ClusterTable {
     name: ClusterName,
     #[table(title = "ClusterID")]
     id: ClusterId,
+    #[table(title = "Project")]
+    project: ProjectName,
+    #[table(title = "State")]
+    state: &'static str,
 }
 
 impl ListClusterConfigurationsCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let clusters = carl.cluster.list_cluster_configurations().await
+        let clusters = carl.cluster.list_cluster_configurations_with_states().await
             .map_err(|error| format!("Could not list any cluster configurations.\n  {error}"))?;
 
         match output {
             ListOutputFormat::Table => {
                 let cluster_table = clusters.into_iter()
-                    .map(|cluster| {
+                    .map(|(cluster, state)| {
                         ClusterTable {
                             name: cluster.name,
                             id: cluster.id,
+                            project: cluster.project,
+                            state: state.short_name(),
                         }
                     })
                     .collect::<Vec<_>>();
@@ -36,10 +44,12 @@ impl ListClusterConfigurationsCli {
                     .expect("List of cluster configurations should be printable as table.");
             }
             ListOutputFormat::Json => {
+                let clusters = clusters.into_iter().map(|(cluster, _)| cluster).collect::<Vec<_>>();
                 let json = serde_json::to_string(&clusters).unwrap();
                 println!("{}", json);
             }
             ListOutputFormat::PrettyJson => {
+                let clusters = clusters.into_iter().map(|(cluster, _)| cluster).collect::<Vec<_>>();
                 let json = serde_json::to_string_pretty(&clusters).unwrap();
                 println!("{}", json);
             }
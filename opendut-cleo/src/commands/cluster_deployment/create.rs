@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use uuid::Uuid;
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterDeployment, ClusterId};
@@ -15,7 +17,7 @@ impl CreateClusterDeploymentCli {
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
         let id = ClusterId::from(self.id);
 
-        let deployment = ClusterDeployment { id };
+        let deployment = ClusterDeployment { id, devices: HashSet::new(), deployed_by: String::new() };
         carl.cluster.store_cluster_deployment(deployment).await
             .map_err(|error| format!("Could not create cluster deployment for ClusterID: '{}'.\n  {}", id, error))?;
         match output {
@@ -1,6 +1,7 @@
 use cli_table::{print_stdout, Table, WithTitle};
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterId};
+use opendut_types::ShortName;
 use crate::ListOutputFormat;
 
 /// List all cluster deployments
@@ -11,19 +12,22 @@ pub struct ListClusterDeploymentsCli;
 struct ClusterTable {
     #[table(title = "ClusterID")]
     id: ClusterId,
+    #[table(title = "State")]
+    state: &'static str,
 }
 
 impl ListClusterDeploymentsCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let clusters = carl.cluster.list_cluster_deployments().await
+        let clusters = carl.cluster.list_cluster_deployments_with_states().await
             .map_err(|error| format!("Error while listing cluster deployments: {}", error))?;
 
         match output {
             ListOutputFormat::Table => {
                 let cluster_table = clusters.into_iter()
-                    .map(|cluster_deployment| {
+                    .map(|(cluster_deployment, state)| {
                         ClusterTable {
                             id: cluster_deployment.id,
+                            state: state.short_name(),
                         }
                     })
                     .collect::<Vec<_>>();
@@ -31,10 +35,12 @@ impl ListClusterDeploymentsCli {
                     .expect("List of clusters should be printable as table.");
             }
             ListOutputFormat::Json => {
+                let clusters = clusters.into_iter().map(|(cluster_deployment, _)| cluster_deployment).collect::<Vec<_>>();
                 let json = serde_json::to_string(&clusters).unwrap();
                 println!("{}", json);
             }
             ListOutputFormat::PrettyJson => {
+                let clusters = clusters.into_iter().map(|(cluster_deployment, _)| cluster_deployment).collect::<Vec<_>>();
                 let json = serde_json::to_string_pretty(&clusters).unwrap();
                 println!("{}", json);
             }
@@ -34,7 +34,7 @@ impl CreateDeviceCli {
         let peer_id = PeerId::from(self.peer_id);
         let device_id = self.device_id.map(DeviceId::from).unwrap_or(DeviceId::random());
 
-        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        let (mut peer_descriptor, version) = carl.peers.get_peer_descriptor_with_version(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
         let peer_network_interface_names = peer_descriptor.network.interfaces.iter().map(|peer_interface| {
             peer_interface.name.clone()
@@ -100,7 +100,7 @@ impl CreateDeviceCli {
                 }
             }
         }
-        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+        carl.peers.store_peer_descriptor_if_version(Clone::clone(&peer_descriptor), version).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
@@ -14,10 +14,10 @@ impl DeleteDeviceCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
         let device_id = DeviceId::from(self.id);
 
-        let mut peers = carl.peers.list_peer_descriptors().await
+        let mut peers = carl.peers.list_peer_descriptors_with_versions().await
             .map_err(|error| format!("Could not list peers.\n  {}", error))?;
 
-        let peer = peers.iter_mut().find(|peer| {
+        let (peer, version) = peers.iter_mut().find(|(peer, _)| {
             peer.topology.devices
                 .iter()
                 .any(|device| device.id == device_id)
@@ -25,7 +25,7 @@ impl DeleteDeviceCli {
 
         peer.topology.devices.retain(|device| device.id != device_id);
 
-        carl.peers.store_peer_descriptor(Clone::clone(peer)).await
+        carl.peers.store_peer_descriptor_if_version(Clone::clone(peer), *version).await
             .map_err(|error| format!("Failed to delete peer.\n  {}", error))?;
 
         Ok(())
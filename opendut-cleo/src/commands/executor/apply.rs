@@ -36,12 +36,12 @@ impl ApplyContainerExecutorCli {
         let peer_id = executor_configuration.peer_id;
         let executor_descriptor = executor_configuration.executor_descriptor;
 
-        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        let (mut peer_descriptor, version) = carl.peers.get_peer_descriptor_with_version(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
 
         peer_descriptor.executors.executors.push(executor_descriptor);
 
-        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+        carl.peers.store_peer_descriptor_if_version(Clone::clone(&peer_descriptor), version).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
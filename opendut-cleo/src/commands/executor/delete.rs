@@ -19,8 +19,8 @@ impl DeleteContainerExecutorCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
         let id = PeerId::from(self.peer_id);
 
-        let mut peer = carl.peers
-            .get_peer_descriptor(id)
+        let (mut peer, version) = carl.peers
+            .get_peer_descriptor_with_version(id)
             .await
             .map_err(|error| format!("Failed to get peer with the id '{}'.\n  {}", id, error))?;
 
@@ -38,7 +38,7 @@ impl DeleteContainerExecutorCli {
             })
         };
 
-        carl.peers.store_peer_descriptor(peer).await
+        carl.peers.store_peer_descriptor_if_version(peer, version).await
             .map_err(|error| format!("Failed to delete container executor for peer.\n  {}", error))?;
 
         Ok(())
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
 use uuid::Uuid;
@@ -24,6 +25,7 @@ impl GenerateSetupStringCli {
             Ok(setup_string) => {
                 println!("{}", setup_string);
                 eprintln!("Setup-Strings may only be used to set up one host. For setting up multiple hosts, you should create a peer for each host.");
+                eprintln!("This Setup-String expires at {}, unless it is used before.", DateTime::<Utc>::from(created_setup.expires_at));
             }
             Err(_) => {
                 println!("Could not configure setup string...")
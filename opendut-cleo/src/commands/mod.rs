@@ -1,8 +1,16 @@
+pub mod api_token;
+pub mod audit;
+pub mod backup;
 pub mod cluster_configuration;
 pub mod cluster_deployment;
 pub mod device;
 pub mod peer;
+pub mod peer_setup;
 pub mod network_interface;
+pub mod reservation;
+pub mod service_account;
+pub mod webhook;
+pub mod webhook_delivery;
 pub mod executor;
 pub mod decode_setup_string;
 pub mod generate_setup_string;
@@ -23,7 +23,7 @@ impl CreateNetworkInterfaceCli {
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
         let peer_id = PeerId::from(self.peer_id);
 
-        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        let (mut peer_descriptor, version) = carl.peers.get_peer_descriptor_with_version(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
 
         let peer_interface_names = peer_descriptor.network.interfaces
@@ -54,7 +54,7 @@ impl CreateNetworkInterfaceCli {
             );
         }
 
-        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+        carl.peers.store_peer_descriptor_if_version(Clone::clone(&peer_descriptor), version).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
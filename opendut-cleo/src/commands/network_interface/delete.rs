@@ -20,8 +20,8 @@ impl DeleteNetworkInterfaceCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
         let id = PeerId::from(self.peer_id);
 
-        let mut peer = carl.peers
-            .get_peer_descriptor(id)
+        let (mut peer, version) = carl.peers
+            .get_peer_descriptor_with_version(id)
             .await
             .map_err(|error| format!("Failed to get peer with the id '{}'.\n  {}", id, error))?;
 
@@ -43,7 +43,7 @@ impl DeleteNetworkInterfaceCli {
             peer.network.interfaces.retain(|interface| interface.name.name() != name.name())
         };
 
-        carl.peers.store_peer_descriptor(peer).await
+        carl.peers.store_peer_descriptor_if_version(peer, version).await
             .map_err(|error| format!("Failed to delete network interfaces for peer.\n  {}", error))?;
 
         Ok(())
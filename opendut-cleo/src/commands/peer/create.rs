@@ -5,6 +5,7 @@ use crate::{CreateOutputFormat};
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::peer::executor::{ExecutorDescriptors};
+use opendut_types::project::ProjectName;
 use opendut_types::util::net::NetworkInterfaceName;
 
 /// Create a peer
@@ -24,6 +25,9 @@ pub struct CreatePeerCli {
     /// Not removing the bridge could lead to network traffic being misdirected!
     #[arg(long)]
     bridge_name: Option<NetworkInterfaceName>,
+    ///Project the peer belongs to
+    #[arg(long, default_value = ProjectName::DEFAULT)]
+    project: String,
 }
 
 impl CreatePeerCli {
@@ -39,6 +43,9 @@ impl CreatePeerCli {
             .map_err(|error| format!("Could not create peer.\n  {}", error))?;
 
         let bridge_name = self.bridge_name;
+
+        let project = ProjectName::try_from(self.project)
+            .map_err(|error| format!("Could not create peer.\n  {}", error))?;
         
         let descriptor: PeerDescriptor = PeerDescriptor {
             id,
@@ -51,7 +58,8 @@ impl CreatePeerCli {
             topology: Default::default(),
             executors: ExecutorDescriptors {
                 executors: vec![],
-            }
+            },
+            project,
         };
         carl.peers
             .store_peer_descriptor(descriptor.clone())
@@ -3,6 +3,9 @@ use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::configuration::{ParameterStatus, ParameterStatusReport, ParameterValue};
+use opendut_types::peer::executor::ExecutorKind;
+use opendut_types::ShortName;
 
 use crate::DescribeOutputFormat;
 
@@ -23,11 +26,42 @@ impl DescribePeerCli {
                 format!("Failed to retrieve peer descriptor for peer <{}>", peer_id)
             })?;
 
-        render_peer_descriptor(peer_descriptor, output);
+        if let DescribeOutputFormat::Text = output {
+            let status_reports = carl.peers.get_peer_status_reports(peer_id).await
+                .map_err(|error| format!("Failed to retrieve status reports for peer <{}>.\n  {}", peer_id, error))?;
+            let status_text = render_status_reports(&peer_descriptor, &status_reports);
+
+            render_peer_descriptor(peer_descriptor, output);
+            println!("{status_text}");
+        } else {
+            render_peer_descriptor(peer_descriptor, output);
+        }
         Ok(())
     }
 }
 
+fn render_status_reports(peer_descriptor: &PeerDescriptor, status_reports: &[ParameterStatusReport]) -> String {
+    let executors = peer_descriptor.executors.executors.iter()
+        .map(|executor| {
+            let id = executor.parameter_identifier();
+            let name = match &executor.kind {
+                ExecutorKind::Executable => String::from("Executable"),
+                ExecutorKind::Container { image, .. } => format!("Container '{image}'"),
+            };
+            let status = status_reports.iter()
+                .find(|report| report.id == id)
+                .map(|report| match &report.status {
+                    ParameterStatus::Error(error) => format!("{} ({error})", report.status.short_name()),
+                    status => String::from(status.short_name()),
+                })
+                .unwrap_or_else(|| String::from("Unknown"));
+            format!("\n    {name} <{id}>: {status}")
+        })
+        .collect::<String>();
+
+    format!("  Executors:{executors}")
+}
+
 pub fn render_peer_descriptor(peer_descriptor: PeerDescriptor, output: DescribeOutputFormat) {
     let peer_devices = peer_descriptor
         .topology
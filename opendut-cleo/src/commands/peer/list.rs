@@ -5,6 +5,7 @@ use serde::Serialize;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName};
+use opendut_types::project::ProjectName;
 
 use crate::ListOutputFormat;
 
@@ -22,6 +23,8 @@ struct PeerTable {
     status: PeerStatus,
     #[table(title = "Location")]
     location: PeerLocation,
+    #[table(title = "Project")]
+    project: ProjectName,
     #[table(title = "NetworkInterfaces")]
     network_interfaces: String,
 }
@@ -91,6 +94,7 @@ fn filter_connected_peers(
                 name: Clone::clone(&peer.name),
                 id: peer.id,
                 location: Clone::clone(&peer.location.clone().unwrap_or_default()),
+                project: Clone::clone(&peer.project),
                 network_interfaces: interfaces.join(", "),
                 status
             }
@@ -124,7 +128,8 @@ mod test {
             topology: Default::default(),
             executors: ExecutorDescriptors {
                 executors: vec![]
-            }
+            },
+            project: ProjectName::default(),
         }];
         let connected_peers = vec![all_peers[0].id];
         assert_that!(
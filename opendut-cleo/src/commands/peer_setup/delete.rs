@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::setup::PeerSetupNonce;
+
+/// Revoke a setup-string, so that it cannot be used to connect a peer anymore
+#[derive(clap::Parser)]
+pub struct DeletePeerSetupCli {
+    ///Nonce of the setup-string, as shown when listing the setup-strings of a peer
+    #[arg()]
+    nonce: Uuid,
+}
+
+impl DeletePeerSetupCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let nonce = PeerSetupNonce::from(self.nonce);
+        let setup = carl.peers.revoke_peer_setup(nonce).await
+            .map_err(|error| format!("Could not revoke setup-string <{}>.\n  {}", nonce, error))?;
+        println!("Revoked setup-string <{}> of peer <{}>.", nonce, setup.peer_id);
+
+        Ok(())
+    }
+}
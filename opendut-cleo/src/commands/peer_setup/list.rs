@@ -0,0 +1,45 @@
+use cli_table::{print_stdout, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+
+use crate::commands::peer_setup::PeerSetupTable;
+use crate::ListOutputFormat;
+
+/// List the setup-strings issued for a peer
+#[derive(clap::Parser)]
+pub struct ListPeerSetupsCli {
+    ///PeerID
+    #[arg()]
+    peer_id: Uuid,
+}
+
+impl ListPeerSetupsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+        let setups = carl.peers.list_peer_setups(peer_id).await
+            .map_err(|error| format!("Could not list setup-strings of peer <{}>.\n  {}", peer_id, error))?;
+
+        let setup_table = setups.into_iter()
+            .map(PeerSetupTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(setup_table.with_title())
+                    .expect("List of setup-strings should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&setup_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&setup_table).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
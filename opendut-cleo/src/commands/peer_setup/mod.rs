@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use cli_table::Table;
+use serde::Serialize;
+
+use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::{IssuedPeerSetup, IssuedPeerSetupState, PeerSetupNonce};
+
+pub mod delete;
+pub mod list;
+
+#[derive(Table, Debug, Serialize)]
+pub(crate) struct PeerSetupTable {
+    #[table(title = "Nonce")]
+    nonce: PeerSetupNonce,
+    #[table(title = "PeerID")]
+    peer_id: PeerId,
+    #[table(title = "Issued At")]
+    issued_at: String,
+    #[table(title = "Expires At")]
+    expires_at: String,
+    #[table(title = "State")]
+    state: String,
+    #[table(title = "State Since")]
+    state_since: String,
+    #[table(title = "OIDC Client")]
+    client_id: String,
+}
+
+impl From<IssuedPeerSetup> for PeerSetupTable {
+    fn from(setup: IssuedPeerSetup) -> Self {
+        let state_since = match setup.state {
+            IssuedPeerSetupState::Outstanding => String::new(),
+            IssuedPeerSetupState::Consumed { at, .. } | IssuedPeerSetupState::Revoked { at } => DateTime::<Utc>::from(at).to_string(),
+        };
+
+        PeerSetupTable {
+            nonce: setup.nonce,
+            peer_id: setup.peer_id,
+            issued_at: DateTime::<Utc>::from(setup.issued_at).to_string(),
+            expires_at: DateTime::<Utc>::from(setup.expires_at).to_string(),
+            state: setup.state.to_string(),
+            state_since,
+            client_id: setup.client_id.map(|client_id| client_id.value()).unwrap_or_default(),
+        }
+    }
+}
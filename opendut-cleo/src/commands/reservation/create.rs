@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+use opendut_types::reservation::{Reservation, ReservationId};
+use opendut_types::topology::DeviceId;
+
+use crate::commands::reservation::ReservationTable;
+use crate::CreateOutputFormat;
+
+/// Reserve peers and devices for exclusive use during a period of time
+#[derive(clap::Parser)]
+pub struct CreateReservationCli {
+    ///IDs of the peers to reserve, including all of their devices
+    #[arg(long, num_args = 0..)]
+    peer_ids: Vec<Uuid>,
+    ///IDs of the devices to reserve
+    #[arg(long, num_args = 0..)]
+    device_ids: Vec<Uuid>,
+    ///Start of the reservation in RFC 3339 format, e.g. 2024-01-31T08:00:00Z [default: now]
+    #[arg(long)]
+    start: Option<DateTime<Utc>>,
+    ///End of the reservation in RFC 3339 format, e.g. 2024-01-31T17:00:00Z
+    #[arg(long, required_unless_present = "duration_minutes")]
+    end: Option<DateTime<Utc>>,
+    ///Duration of the reservation in minutes, as an alternative to its end
+    #[arg(long, conflicts_with = "end")]
+    duration_minutes: Option<u64>,
+    ///ReservationID
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+impl CreateReservationCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let id = ReservationId::from(self.id.unwrap_or_else(Uuid::new_v4));
+
+        let start = self.start.map(SystemTime::from).unwrap_or_else(SystemTime::now);
+        let end = match (self.end, self.duration_minutes) {
+            (Some(end), _) => SystemTime::from(end),
+            (None, Some(duration_minutes)) => start + Duration::from_secs(duration_minutes * 60),
+            (None, None) => return Err(String::from("Either an end or a duration of the reservation must be specified.")),
+        };
+
+        let reservation = Reservation {
+            id,
+            user: String::new(), //set by CARL to the authenticated user
+            peers: self.peer_ids.into_iter().map(PeerId::from).collect::<HashSet<_>>(),
+            devices: self.device_ids.into_iter().map(DeviceId::from).collect::<HashSet<_>>(),
+            start,
+            end,
+        };
+
+        let reservation = carl.reservations.create_reservation(reservation).await
+            .map_err(|error| format!("Could not create reservation <{}>.\n  {}", id, error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully created reservation <{}> for user '{}'.", id, reservation.user);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&ReservationTable::from(reservation)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&ReservationTable::from(reservation)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
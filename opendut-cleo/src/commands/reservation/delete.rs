@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::reservation::ReservationId;
+
+/// Delete a reservation, releasing its peers and devices
+#[derive(clap::Parser)]
+pub struct DeleteReservationCli {
+    ///ReservationID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteReservationCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ReservationId::from(self.id);
+        carl.reservations.delete_reservation(id).await
+            .map_err(|error| format!("Could not delete reservation <{}>.\n  {}", id, error))?;
+        println!("Deleted reservation <{}>.", id);
+
+        Ok(())
+    }
+}
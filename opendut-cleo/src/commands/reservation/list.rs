@@ -0,0 +1,39 @@
+use cli_table::{print_stdout, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::reservation::ReservationTable;
+use crate::ListOutputFormat;
+
+/// List all current and upcoming reservations
+#[derive(clap::Parser)]
+pub struct ListReservationsCli;
+
+impl ListReservationsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let mut reservations = carl.reservations.list_reservations().await
+            .map_err(|error| format!("Error while listing reservations: {}", error))?;
+        reservations.sort_by_key(|reservation| reservation.start);
+
+        let reservation_table = reservations.into_iter()
+            .map(ReservationTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(reservation_table.with_title())
+                    .expect("List of reservations should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&reservation_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&reservation_table).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
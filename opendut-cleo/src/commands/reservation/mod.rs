@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use cli_table::Table;
+use serde::Serialize;
+
+use opendut_types::reservation::{Reservation, ReservationId};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+#[derive(Table, Debug, Serialize)]
+pub(crate) struct ReservationTable {
+    #[table(title = "ReservationID")]
+    id: ReservationId,
+    #[table(title = "User")]
+    user: String,
+    #[table(title = "Start")]
+    start: String,
+    #[table(title = "End")]
+    end: String,
+    #[table(title = "PeerIDs")]
+    peers: String,
+    #[table(title = "DeviceIDs")]
+    devices: String,
+}
+
+impl From<Reservation> for ReservationTable {
+    fn from(reservation: Reservation) -> Self {
+        let mut peers = reservation.peers.iter().map(ToString::to_string).collect::<Vec<_>>();
+        peers.sort();
+        let mut devices = reservation.devices.iter().map(ToString::to_string).collect::<Vec<_>>();
+        devices.sort();
+
+        ReservationTable {
+            id: reservation.id,
+            user: reservation.user,
+            start: DateTime::<Utc>::from(reservation.start).to_string(),
+            end: DateTime::<Utc>::from(reservation.end).to_string(),
+            peers: peers.join(", "),
+            devices: devices.join(", "),
+        }
+    }
+}
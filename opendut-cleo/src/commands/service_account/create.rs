@@ -0,0 +1,51 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountName};
+
+use crate::commands::service_account::ServiceAccountTable;
+use crate::CreateOutputFormat;
+
+/// Create a service account, which authenticates with API tokens, e.g. in CI pipelines
+#[derive(clap::Parser)]
+pub struct CreateServiceAccountCli {
+    ///Name of the service account
+    #[arg(short, long)]
+    name: String,
+    ///ServiceAccountID
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+impl CreateServiceAccountCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let id = ServiceAccountId::from(self.id.unwrap_or_else(Uuid::new_v4));
+        let name = ServiceAccountName::try_from(self.name)
+            .map_err(|error| error.to_string())?;
+
+        let service_account = ServiceAccount {
+            id,
+            name: Clone::clone(&name),
+            created_by: String::new(), //set by CARL to the authenticated user
+        };
+
+        let service_account = carl.service_accounts.create_service_account(service_account).await
+            .map_err(|error| format!("Could not create service account '{}'.\n  {}", name, error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully created service account '{}' <{}>.", service_account.name, service_account.id);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&ServiceAccountTable::from(service_account)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&ServiceAccountTable::from(service_account)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
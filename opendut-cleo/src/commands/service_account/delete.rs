@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ServiceAccountId;
+
+/// Delete a service account and revoke all of its API tokens
+#[derive(clap::Parser)]
+pub struct DeleteServiceAccountCli {
+    ///ServiceAccountID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteServiceAccountCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ServiceAccountId::from(self.id);
+        let service_account = carl.service_accounts.delete_service_account(id).await
+            .map_err(|error| format!("Could not delete service account <{}>.\n  {}", id, error))?;
+        println!("Deleted service account '{}' <{}> and revoked its API tokens.", service_account.name, id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use cli_table::{print_stdout, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::service_account::ServiceAccountTable;
+use crate::ListOutputFormat;
+
+/// List all service accounts
+#[derive(clap::Parser)]
+pub struct ListServiceAccountsCli;
+
+impl ListServiceAccountsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let mut service_accounts = carl.service_accounts.list_service_accounts().await
+            .map_err(|error| format!("Error while listing service accounts: {}", error))?;
+        service_accounts.sort_by_key(|service_account| service_account.name.to_string());
+
+        let service_account_table = service_accounts.into_iter()
+            .map(ServiceAccountTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(service_account_table.with_title())
+                    .expect("List of service accounts should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&service_account_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&service_account_table).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use cli_table::Table;
+use serde::Serialize;
+
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountName};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+#[derive(Table, Debug, Serialize)]
+pub(crate) struct ServiceAccountTable {
+    #[table(title = "Name")]
+    name: ServiceAccountName,
+    #[table(title = "ServiceAccountID")]
+    id: ServiceAccountId,
+    #[table(title = "Created By")]
+    created_by: String,
+}
+
+impl From<ServiceAccount> for ServiceAccountTable {
+    fn from(service_account: ServiceAccount) -> Self {
+        ServiceAccountTable {
+            name: service_account.name,
+            id: service_account.id,
+            created_by: service_account.created_by,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use serde::Serialize;
+use url::Url;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::webhook::{Webhook, WebhookEventKind, WebhookId};
+
+use crate::commands::webhook::WebhookTable;
+use crate::CreateOutputFormat;
+
+/// Create a webhook, to which CARL posts signed notifications about events. The signing secret is only shown once.
+#[derive(clap::Parser)]
+pub struct CreateWebhookCli {
+    ///URL, to which the notifications are posted
+    #[arg(long)]
+    url: Url,
+    ///Events to notify about, e.g. cluster-deployed or peer-down. All events, if none are given.
+    #[arg(long, num_args = 1..)]
+    events: Vec<WebhookEventKind>,
+    ///WebhookID
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+#[derive(Serialize)]
+struct CreatedWebhookOutput {
+    #[serde(flatten)]
+    webhook: WebhookTable,
+    secret: String,
+}
+
+impl CreateWebhookCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let id = WebhookId::from(self.id.unwrap_or_else(Uuid::new_v4));
+
+        let webhook = Webhook {
+            id,
+            url: self.url,
+            events: self.events.into_iter().collect(),
+            secret: String::new(), //generated by CARL
+            created_by: String::new(), //set by CARL to the authenticated user
+        };
+
+        let mut webhook = carl.webhooks.create_webhook(webhook).await
+            .map_err(|error| format!("Could not create webhook <{}>.\n  {}", id, error))?;
+        let secret = std::mem::take(&mut webhook.secret);
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully created webhook <{}> to '{}'. Store its secret securely, since it cannot be shown again:", webhook.id, webhook.url);
+                println!("{}", secret);
+            }
+            CreateOutputFormat::Json => {
+                let output = CreatedWebhookOutput { webhook: WebhookTable::from(webhook), secret };
+                let json = serde_json::to_string(&output).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let output = CreatedWebhookOutput { webhook: WebhookTable::from(webhook), secret };
+                let json = serde_json::to_string_pretty(&output).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::webhook::WebhookId;
+
+/// Delete a webhook. Pending deliveries to it are abandoned.
+#[derive(clap::Parser)]
+pub struct DeleteWebhookCli {
+    ///WebhookID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteWebhookCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = WebhookId::from(self.id);
+        let webhook = carl.webhooks.delete_webhook(id).await
+            .map_err(|error| format!("Could not delete webhook <{}>.\n  {}", id, error))?;
+        println!("Deleted webhook <{}> to '{}'.", id, webhook.url);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use cli_table::{print_stdout, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::webhook::WebhookTable;
+use crate::ListOutputFormat;
+
+/// List all webhooks
+#[derive(clap::Parser)]
+pub struct ListWebhooksCli;
+
+impl ListWebhooksCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let mut webhooks = carl.webhooks.list_webhooks().await
+            .map_err(|error| format!("Error while listing webhooks: {}", error))?;
+        webhooks.sort_by_key(|webhook| webhook.url.to_string());
+
+        let webhook_table = webhooks.into_iter()
+            .map(WebhookTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(webhook_table.with_title())
+                    .expect("List of webhooks should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&webhook_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&webhook_table).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use cli_table::Table;
+use serde::Serialize;
+
+use opendut_types::webhook::{Webhook, WebhookId};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+#[derive(Table, Debug, Serialize)]
+pub(crate) struct WebhookTable {
+    #[table(title = "WebhookID")]
+    id: WebhookId,
+    #[table(title = "URL")]
+    url: String,
+    #[table(title = "Events")]
+    events: String,
+    #[table(title = "Created By")]
+    created_by: String,
+}
+
+impl From<Webhook> for WebhookTable {
+    fn from(webhook: Webhook) -> Self {
+        let events = if webhook.events.is_empty() {
+            String::from("all")
+        } else {
+            let mut events = webhook.events.iter().map(ToString::to_string).collect::<Vec<_>>();
+            events.sort();
+            events.join(", ")
+        };
+
+        WebhookTable {
+            id: webhook.id,
+            url: webhook.url.to_string(),
+            events,
+            created_by: webhook.created_by,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use cli_table::{print_stdout, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::webhook::WebhookId;
+
+use crate::commands::webhook_delivery::WebhookDeliveryTable;
+use crate::ListOutputFormat;
+
+/// List the latest deliveries to a webhook, ordered from oldest to latest
+#[derive(clap::Parser)]
+pub struct ListWebhookDeliveriesCli {
+    ///ID of the webhook
+    #[arg(long)]
+    webhook_id: Uuid,
+    ///Maximum number of deliveries to list
+    #[arg(long)]
+    limit: Option<u32>,
+}
+
+impl ListWebhookDeliveriesCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let webhook_id = WebhookId::from(self.webhook_id);
+        let deliveries = carl.webhooks.list_webhook_deliveries(webhook_id, self.limit).await
+            .map_err(|error| format!("Error while listing deliveries of webhook <{}>: {}", webhook_id, error))?;
+
+        let delivery_table = deliveries.into_iter()
+            .map(WebhookDeliveryTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                print_stdout(delivery_table.with_title())
+                    .expect("List of webhook deliveries should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&delivery_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&delivery_table).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
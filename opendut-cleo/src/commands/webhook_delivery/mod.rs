@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use cli_table::Table;
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_types::webhook::{WebhookDelivery, WebhookEventKind};
+
+pub mod list;
+
+#[derive(Table, Debug, Serialize)]
+pub(crate) struct WebhookDeliveryTable {
+    #[table(title = "EventID")]
+    event_id: Uuid,
+    #[table(title = "Event")]
+    event: WebhookEventKind,
+    #[table(title = "Timestamp")]
+    timestamp: String,
+    #[table(title = "Attempts")]
+    attempts: u32,
+    #[table(title = "Outcome")]
+    outcome: String,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryTable {
+    fn from(delivery: WebhookDelivery) -> Self {
+        WebhookDeliveryTable {
+            event_id: delivery.event_id,
+            event: delivery.event,
+            timestamp: DateTime::<Utc>::from(delivery.timestamp).to_string(),
+            attempts: delivery.attempts,
+            outcome: delivery.outcome.to_string(),
+        }
+    }
+}
@@ -7,6 +7,7 @@ use clap_complete::Shell;
 use console::Style;
 
 use opendut_carl_api::carl::{CaCertInfo, CarlClient};
+use opendut_types::cluster::DeviceSelector;
 use opendut_types::topology::DeviceName;
 use opendut_util::settings::{FileFormat, load_config, LoadedConfig};
 
@@ -57,6 +58,8 @@ enum Commands {
     },
     GenerateSetupString(commands::generate_setup_string::GenerateSetupStringCli),
     DecodeSetupString(commands::decode_setup_string::DecodeSetupStringCli),
+    CreateBackup(commands::backup::create::CreateBackupCli),
+    RestoreBackup(commands::backup::restore::RestoreBackupCli),
     ///Describe openDuT resource
     Describe {
         ///Name of openDuT resource
@@ -92,11 +95,18 @@ enum Commands {
 
 #[derive(Subcommand)]
 enum ListResource {
+    AuditEntries(commands::audit::list::ListAuditEntriesCli),
     ClusterConfigurations(commands::cluster_configuration::list::ListClusterConfigurationsCli),
     ClusterDeployments(commands::cluster_deployment::list::ListClusterDeploymentsCli),
     Peers(commands::peer::list::ListPeersCli),
+    PeerSetups(commands::peer_setup::list::ListPeerSetupsCli),
     Devices(commands::device::list::ListDevicesCli),
     ContainerExecutor(commands::executor::list::ListContainerExecutorCli),
+    Reservations(commands::reservation::list::ListReservationsCli),
+    ServiceAccounts(commands::service_account::list::ListServiceAccountsCli),
+    ApiTokens(commands::api_token::list::ListApiTokensCli),
+    Webhooks(commands::webhook::list::ListWebhooksCli),
+    WebhookDeliveries(commands::webhook_delivery::list::ListWebhookDeliveriesCli),
 }
 
 #[derive(clap::Args)]
@@ -106,6 +116,9 @@ struct ClusterConfigurationDevices {
     device_names: Vec<DeviceName>,
     #[arg(long, num_args = 0..)]
     device_ids: Vec<String>,
+    ///Selectors for devices, resolved when the cluster is deployed, e.g. "tag=brake-ecu AND location=Lab2"
+    #[arg(long, num_args = 0..)]
+    device_selectors: Vec<DeviceSelector>,
 }
 
 #[derive(ValueEnum, Clone)]
@@ -132,7 +145,11 @@ enum CreateResource {
     Peer(commands::peer::create::CreatePeerCli),
     ContainerExecutor(commands::executor::create::CreateContainerExecutorCli),
     NetworkInterface(commands::network_interface::create::CreateNetworkInterfaceCli),
-    Device(commands::device::create::CreateDeviceCli)
+    Device(commands::device::create::CreateDeviceCli),
+    Reservation(commands::reservation::create::CreateReservationCli),
+    ServiceAccount(commands::service_account::create::CreateServiceAccountCli),
+    ApiToken(commands::api_token::create::CreateApiTokenCli),
+    Webhook(commands::webhook::create::CreateWebhookCli),
 }
 
 #[derive(Subcommand)]
@@ -152,9 +169,14 @@ enum DeleteResource {
     ClusterConfiguration(commands::cluster_configuration::delete::DeleteClusterConfigurationCli),
     ClusterDeployment(commands::cluster_deployment::delete::DeleteClusterDeploymentCli),
     Peer(commands::peer::delete::DeletePeerCli),
+    PeerSetup(commands::peer_setup::delete::DeletePeerSetupCli),
     ContainerExecutor(commands::executor::delete::DeleteContainerExecutorCli),
     NetworkInterface(commands::network_interface::delete::DeleteNetworkInterfaceCli),
     Device(commands::device::delete::DeleteDeviceCli),
+    Reservation(commands::reservation::delete::DeleteReservationCli),
+    ServiceAccount(commands::service_account::delete::DeleteServiceAccountCli),
+    ApiToken(commands::api_token::delete::DeleteApiTokenCli),
+    Webhook(commands::webhook::delete::DeleteWebhookCli),
 }
 
 #[derive(ValueEnum, Clone)]
@@ -212,6 +234,8 @@ async fn execute() -> Result<()> {
     let cleo_config_hide_secrets_override = config::Config::builder()
         .set_override("network.oidc.client.secret", "redacted")
         .map_err(|_error| "Failed to hide cleo secrets.")?
+        .set_override("network.carl.api.token", "redacted")
+        .map_err(|_error| "Failed to hide cleo secrets.")?
         .build()
         .map_err(|_error| "Failed to hide cleo secrets.")?;
 
@@ -232,6 +256,9 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
         Commands::List { resource, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
+                ListResource::AuditEntries(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 ListResource::ClusterConfigurations(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
@@ -241,12 +268,30 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 ListResource::Peers(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::PeerSetups(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 ListResource::ContainerExecutor(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
                 ListResource::Devices(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::Reservations(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::ServiceAccounts(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::ApiTokens(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::Webhooks(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::WebhookDeliveries(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::Apply { resource, output } => {
@@ -278,6 +323,18 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 CreateResource::Device(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                CreateResource::Reservation(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::ServiceAccount(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::ApiToken(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::Webhook(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::GenerateSetupString(implementation) => {
@@ -288,6 +345,14 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
         Commands::DecodeSetupString(implementation) => {
             implementation.execute().await?;
         }
+        Commands::CreateBackup(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::RestoreBackup(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
         Commands::Describe { resource, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
@@ -314,6 +379,9 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 DeleteResource::Peer(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::PeerSetup(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
                 DeleteResource::ContainerExecutor(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
@@ -323,6 +391,18 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 DeleteResource::Device(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::Reservation(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::ServiceAccount(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::ApiToken(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::Webhook(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
             }
         }
         Commands::Find { resource, output } => {
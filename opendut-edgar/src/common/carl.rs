@@ -9,6 +9,7 @@ use tracing::{debug, info, warn};
 use opendut_carl_api::carl::{broker, CaCertInfo, CarlClient};
 use opendut_carl_api::proto::services::peer_messaging_broker;
 use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::PeerSetupNonce;
 use opendut_util::project;
 
 pub async fn connect(settings: &Config) -> anyhow::Result<CarlClient> {
@@ -44,11 +45,12 @@ pub async fn connect(settings: &Config) -> anyhow::Result<CarlClient> {
 
 pub async fn open_stream(
     self_id: PeerId,
+    setup_nonce: PeerSetupNonce,
     remote_address: &IpAddr,
     carl: &mut CarlClient,
 ) -> anyhow::Result<(broker::Downstream, broker::Upstream), broker::error::OpenStream> {
     debug!("Opening peer messaging stream...");
-    let (rx_inbound, tx_outbound) = carl.broker.open_stream(self_id, remote_address).await?;
+    let (rx_inbound, tx_outbound) = carl.broker.open_stream(self_id, setup_nonce, remote_address).await?;
 
     tx_outbound.send(peer_messaging_broker::Upstream {
         message: Some(peer_messaging_broker::upstream::Message::Ping(peer_messaging_broker::Ping {})),
@@ -0,0 +1,94 @@
+use std::ops::Not;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use opendut_types::peer::certificate::PeerCertificate;
+
+use crate::common::settings;
+use crate::fs;
+
+/// Files, in which EDGAR keeps the client certificate, with which it authenticates itself to CARL via mutual TLS.
+#[derive(Clone, Debug)]
+pub struct ClientCertificateFiles {
+    pub certificate: PathBuf,
+    pub key: PathBuf,
+}
+
+impl ClientCertificateFiles {
+
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            certificate: PathBuf::from(config.get_string(settings::key::network::tls::client::certificate)?),
+            key: PathBuf::from(config.get_string(settings::key::network::tls::client::key)?),
+        })
+    }
+
+    /// Whether the files exist and contain the given certificate.
+    pub fn contain(&self, certificate: &PeerCertificate) -> anyhow::Result<bool> {
+        if self.certificate.exists().not() || self.key.exists().not() {
+            return Ok(false);
+        }
+        Ok(fs::read_to_string(&self.certificate)? == certificate.certificate.encode_as_string()
+            && fs::read_to_string(&self.key)? == certificate.key.encode_as_string())
+    }
+
+    /// Replaces the certificate and key. Both are written to temporary files first, to not leave a mismatching pair behind.
+    pub fn write(&self, certificate: &PeerCertificate) -> anyhow::Result<()> {
+        let certificate_file = write_temporary(&self.certificate, &certificate.certificate.encode_as_string(), 0o644)?;
+        let key_file = write_temporary(&self.key, &certificate.key.encode_as_string(), 0o600)?;
+
+        fs::rename(certificate_file, &self.certificate)?;
+        fs::rename(key_file, &self.key)?;
+        Ok(())
+    }
+}
+
+fn write_temporary(path: &Path, content: &str, mode: u32) -> anyhow::Result<PathBuf> {
+    let directory = path.parent()
+        .context(format!("Path '{}' should have a parent directory.", path.display()))?;
+    fs::create_dir_all(directory)?;
+
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".new");
+    let temporary = PathBuf::from(temporary);
+
+    fs::write(&temporary, content)?;
+    fs::set_permissions(&temporary, std::fs::Permissions::from_mode(mode))?;
+    Ok(temporary)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use pem::Pem;
+
+    use opendut_types::peer::certificate::PeerCertificateKey;
+    use opendut_types::util::net::Certificate;
+
+    use super::*;
+
+    #[test]
+    fn should_write_certificate_and_key() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let files = ClientCertificateFiles {
+            certificate: temp.child("tls/client.pem").to_path_buf(),
+            key: temp.child("tls/client.key").to_path_buf(),
+        };
+        let certificate = PeerCertificate {
+            certificate: Certificate(Pem::new("CERTIFICATE", vec![1, 2, 3])),
+            key: PeerCertificateKey(Pem::new("PRIVATE KEY", vec![4, 5, 6])),
+        };
+
+        assert!(files.contain(&certificate)?.not());
+        files.write(&certificate)?;
+        assert!(files.contain(&certificate)?);
+
+        let key_mode = fs::metadata(&files.key)?.permissions().mode();
+        assert_eq!(key_mode & 0o777, 0o600);
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use std::str::FromStr;
 use opendut_types::util::net::NetworkInterfaceName;
 
 pub mod carl;
+pub mod client_certificate;
 pub mod settings;
 
 
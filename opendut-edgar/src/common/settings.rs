@@ -6,6 +6,15 @@ use opendut_util::settings::LoadedConfig;
 pub mod key {
     pub mod peer {
         pub const id: &str = "peer.id";
+        pub const setup_nonce: &str = "peer.setup.nonce";
+    }
+    pub mod network {
+        pub mod tls {
+            pub mod client {
+                pub const certificate: &str = "network.tls.client.certificate";
+                pub const key: &str = "network.tls.client.key";
+            }
+        }
     }
     pub mod vpn {
         pub const table: &str = "vpn";
@@ -146,6 +146,13 @@ impl CanManager {
         self.cannelloni_termination_token.lock().unwrap().store(true, Ordering::Relaxed);
     }
     
+    /// Terminates all cannelloni instances and removes all CAN routes, e.g. when the peer is no longer assigned to a cluster.
+    pub async fn teardown(&self) -> Result<(), Error> {
+        self.terminate_cannelloni_managers().await;
+        self.remove_all_can_routes().await?;
+        Ok(())
+    }
+
     pub async fn setup_remote_routing_client(&self, bridge_name: &NetworkInterfaceName, leader_ip: &IpAddr, leader_port: &Port) -> Result<(), Error> {
 
         self.terminate_cannelloni_managers().await;
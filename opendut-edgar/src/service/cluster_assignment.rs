@@ -1,4 +1,5 @@
 use std::net::{IpAddr, Ipv4Addr};
+use std::ops::Not;
 use std::sync::Arc;
 use tracing::debug;
 
@@ -53,6 +54,111 @@ pub async fn network_interfaces_setup(
     Ok(())
 }
 
+/// Applies a changed assignment of the same cluster, only reconfiguring what differs from the previous assignment.
+#[tracing::instrument(skip(previous_cluster_assignment, cluster_assignment, can_manager, network_interface_manager), level="trace")]
+pub async fn network_interfaces_reconcile(
+    previous_cluster_assignment: &ClusterAssignment,
+    cluster_assignment: &ClusterAssignment,
+    self_id: PeerId,
+    bridge_name: &NetworkInterfaceName,
+    network_interface_manager: NetworkInterfaceManagerRef,
+    can_manager: CanManagerRef,
+) -> Result<(), Error> {
+
+    let previous_local_assignment = previous_cluster_assignment.assignments.iter().find(|assignment| {
+        assignment.peer_id == self_id
+    }).ok_or(Error::LocalPeerAssignmentNotFound { self_id })?;
+
+    let local_peer_assignment = cluster_assignment.assignments.iter().find(|assignment| {
+        assignment.peer_id == self_id
+    }).ok_or(Error::LocalPeerAssignmentNotFound { self_id })?;
+
+    let previous_remote_ips = determine_remote_ips(previous_cluster_assignment, self_id)?;
+    let remote_ips = determine_remote_ips(cluster_assignment, self_id)?;
+
+    if previous_local_assignment.vpn_address != local_peer_assignment.vpn_address || previous_remote_ips != remote_ips {
+        let local_ip = require_ipv4_for_gre(local_peer_assignment.vpn_address)?;
+        let remote_ips = remote_ips.into_iter()
+            .map(require_ipv4_for_gre)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if previous_local_assignment.vpn_address != local_peer_assignment.vpn_address {
+            gre::setup_interfaces(
+                &local_ip,
+                &remote_ips,
+                bridge_name,
+                Arc::clone(&network_interface_manager),
+            ).await
+            .map_err(Error::GreInterfaceSetupFailed)?;
+        } else {
+            gre::reconcile_interfaces(
+                &local_ip,
+                &remote_ips,
+                bridge_name,
+                Arc::clone(&network_interface_manager),
+            ).await
+            .map_err(Error::GreInterfaceSetupFailed)?;
+        }
+    } else {
+        debug!("Remote peers unchanged. Keeping GRE interfaces.");
+    }
+
+    let previous_ethernet_interfaces = get_own_ethernet_interfaces(previous_cluster_assignment, self_id)?;
+    let ethernet_interfaces = get_own_ethernet_interfaces(cluster_assignment, self_id)?;
+
+    let added_ethernet_interfaces = ethernet_interfaces.iter()
+        .filter(|interface| previous_ethernet_interfaces.contains(interface).not())
+        .cloned()
+        .collect::<Vec<_>>();
+    let removed_ethernet_interfaces = previous_ethernet_interfaces.iter()
+        .filter(|interface| ethernet_interfaces.contains(interface).not())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    remove_device_interfaces_from_bridge(&removed_ethernet_interfaces, Arc::clone(&network_interface_manager)).await
+        .map_err(Error::RemoveDeviceInterfaceFromBridgeFailed)?;
+    join_device_interfaces_to_bridge(&added_ethernet_interfaces, bridge_name, Arc::clone(&network_interface_manager)).await
+        .map_err(Error::JoinDeviceInterfaceToBridgeFailed)?;
+
+    let previous_can_setup = (
+        get_own_can_interfaces(previous_cluster_assignment, self_id)?,
+        previous_cluster_assignment.leader,
+        determine_remote_assignments(previous_cluster_assignment, self_id)?,
+    );
+    let can_setup = (
+        get_own_can_interfaces(cluster_assignment, self_id)?,
+        cluster_assignment.leader,
+        determine_remote_assignments(cluster_assignment, self_id)?,
+    );
+
+    if previous_can_setup != can_setup {
+        setup_can(cluster_assignment, self_id, can_manager).await?;
+    } else {
+        debug!("CAN setup unchanged. Keeping CAN routing.");
+    }
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(can_manager, network_interface_manager), level="trace")]
+pub async fn network_interfaces_teardown(
+    bridge_name: &NetworkInterfaceName,
+    network_interface_manager: NetworkInterfaceManagerRef,
+    can_manager: CanManagerRef,
+) -> Result<(), Error> {
+
+    gre::remove_existing_interfaces(Arc::clone(&network_interface_manager)).await
+        .map_err(Error::GreInterfaceTeardownFailed)?;
+
+    bridge::recreate(bridge_name, Arc::clone(&network_interface_manager)).await //removes all device interfaces from the bridge
+        .map_err(Error::BridgeRecreationFailed)?;
+
+    can_manager.teardown().await
+        .map_err(Error::CanRoutingTeardownFailed)?;
+
+    Ok(())
+}
+
 pub async fn setup_can(
     cluster_assignment: &ClusterAssignment,
     self_id: PeerId,
@@ -183,6 +289,18 @@ async fn join_device_interfaces_to_bridge(
     Ok(())
 }
 
+async fn remove_device_interfaces_from_bridge(
+    device_interfaces: &Vec<NetworkInterfaceDescriptor>,
+    network_interface_manager: NetworkInterfaceManagerRef
+) -> Result<(), network_interface::manager::Error> {
+    for interface in device_interfaces {
+        let interface = network_interface_manager.try_find_interface(&interface.name).await?;
+        network_interface_manager.remove_interface_from_bridge(&interface).await?;
+        debug!("Removed device interface {interface} from bridge.");
+    }
+    Ok(())
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("(Re-)Creating the bridge failed: {0}")]
@@ -201,4 +319,10 @@ pub enum Error {
     RemoteCanRoutingSetupFailed(crate::service::can_manager::Error),
     #[error("Joining device interface to bridge failed: {0}")]
     JoinDeviceInterfaceToBridgeFailed(network_interface::manager::Error),
+    #[error("Removing device interface from bridge failed: {0}")]
+    RemoveDeviceInterfaceFromBridgeFailed(network_interface::manager::Error),
+    #[error("GRE interface teardown failed: {0}")]
+    GreInterfaceTeardownFailed(gre::Error),
+    #[error("CAN routing teardown failed: {0}")]
+    CanRoutingTeardownFailed(crate::service::can_manager::Error),
 }
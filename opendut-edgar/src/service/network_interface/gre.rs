@@ -1,11 +1,12 @@
 use std::net::Ipv4Addr;
+use std::ops::Not;
 use std::sync::Arc;
 use tracing::debug;
 
 use opendut_types::util::net::NetworkInterfaceName;
 
 use crate::service::network_interface;
-use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+use crate::service::network_interface::manager::{Interface, NetworkInterfaceManagerRef};
 
 const GRE_INTERFACE_NAME_PREFIX: &str = "gre-opendut";
 
@@ -33,11 +34,52 @@ pub async fn setup_interfaces(
     Ok(())
 }
 
-async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<(), Error> {
+/// Creates GRE interfaces for added remote IPs and removes those of removed remote IPs.
+/// The interfaces to the remaining remote IPs are kept, so their connections are not interrupted.
+pub async fn reconcile_interfaces(
+    local_ip: &Ipv4Addr,
+    remote_ips: &[Ipv4Addr],
+    bridge_name: &NetworkInterfaceName,
+    network_interface_manager: NetworkInterfaceManagerRef,
+) -> Result<(), Error> {
 
-    let interfaces_to_remove = network_interface_manager.list_interfaces().await?
-        .into_iter()
-        .filter(|interface| interface.name.name().starts_with(GRE_INTERFACE_NAME_PREFIX));
+    let mut kept_remote_ips = Vec::new();
+    let mut used_interface_names = Vec::new();
+
+    for interface in list_existing_interfaces(Arc::clone(&network_interface_manager)).await? {
+        let remote_ip = network_interface_manager.get_gretap_v4_remote_ip(&interface).await?;
+        match remote_ip {
+            Some(remote_ip) if remote_ips.contains(&remote_ip) && kept_remote_ips.contains(&remote_ip).not() => {
+                debug!("Keeping GRE interface '{interface}' to {remote_ip}.");
+                kept_remote_ips.push(remote_ip);
+                used_interface_names.push(interface.name);
+            }
+            _ => {
+                network_interface_manager.delete_interface(&interface).await?;
+                debug!("Removed GRE interface '{interface}'.");
+            }
+        }
+    }
+
+    let added_remote_ips = remote_ips.iter()
+        .filter(|remote_ip| kept_remote_ips.contains(remote_ip).not());
+
+    let mut interface_indices = (0..).filter(|interface_index| {
+        used_interface_names.iter().any(|name| name.name() == interface_name(*interface_index)).not()
+    });
+
+    for remote_ip in added_remote_ips {
+        let interface_index = interface_indices.next()
+            .ok_or_else(|| Error::Other { message: String::from("No free index for GRE interface.") })?;
+        create_interface(local_ip, remote_ip, interface_index, bridge_name, Arc::clone(&network_interface_manager)).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<(), Error> {
+
+    let interfaces_to_remove = list_existing_interfaces(Arc::clone(&network_interface_manager)).await?;
 
     for interface in interfaces_to_remove {
         network_interface_manager.delete_interface(&interface).await?;
@@ -46,6 +88,18 @@ async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceM
     Ok(())
 }
 
+async fn list_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<Vec<Interface>, Error> {
+    let interfaces = network_interface_manager.list_interfaces().await?
+        .into_iter()
+        .filter(|interface| interface.name.name().starts_with(GRE_INTERFACE_NAME_PREFIX))
+        .collect();
+    Ok(interfaces)
+}
+
+fn interface_name(interface_index: usize) -> String {
+    format!("{}{}", GRE_INTERFACE_NAME_PREFIX, interface_index)
+}
+
 async fn create_interface(
     local_ip: &Ipv4Addr,
     remote_ip: &Ipv4Addr,
@@ -54,7 +108,7 @@ async fn create_interface(
     network_interface_manager: NetworkInterfaceManagerRef,
 ) -> Result<(), Error> {
 
-    let interface_name = NetworkInterfaceName::try_from(format!("{}{}", GRE_INTERFACE_NAME_PREFIX, interface_index))
+    let interface_name = NetworkInterfaceName::try_from(interface_name(interface_index))
         .map_err(|cause| Error::Other { message: format!("Error while constructing GRE interface name: {cause}") })?;
 
     let gre_interface = network_interface_manager.create_gretap_v4_interface(&interface_name, local_ip, remote_ip).await?;
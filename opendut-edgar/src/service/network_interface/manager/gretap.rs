@@ -4,7 +4,7 @@ use std::net::Ipv4Addr;
 use netlink_packet_route::link::{InfoData, InfoKind, LinkAttribute, LinkInfo};
 use netlink_packet_utils::{Emitable, Parseable};
 use netlink_packet_utils::byteorder::{ByteOrder, NativeEndian, WriteBytesExt};
-use netlink_packet_utils::nla::NlaBuffer;
+use netlink_packet_utils::nla::{Nla, NlaBuffer};
 use rtnetlink::LinkAddRequest;
 
 pub trait Gretap {
@@ -12,46 +12,72 @@ pub trait Gretap {
 }
 impl Gretap for LinkAddRequest {
     fn gretap_v4(mut self, name: impl Into<String>, local_ip: &Ipv4Addr, remote_ip: &Ipv4Addr) -> Self {
-
-        // Byte-values extracted from WireShark via nlmon-interface
-        // and command `ip link add name <NAME> type gretap local <LOCAL_IP> remote <REMOTE_IP>`.
-        // Compare with implementation of ip-command: https://github.com/shemminger/iproute2/blob/040325f543a1f7e6bb336355c136984e9bbe00d6/ip/link_gre.c#L394
-        let attributes = [
-            InfoGreTap::IKey(0),
-            InfoGreTap::OKey(0),
-            InfoGreTap::IFlags(0),
-            InfoGreTap::OFlags(0),
-            InfoGreTap::Local(u32::from_le_bytes(local_ip.octets())),
-            InfoGreTap::Remote(u32::from_le_bytes(remote_ip.octets())),
-            InfoGreTap::Pmtudisc(1),
-            InfoGreTap::Tos(0),
-            InfoGreTap::Ttl(0),
-            InfoGreTap::FwMark(0),
-            InfoGreTap::EncapType(0),
-            InfoGreTap::EncapFlags(0),
-            InfoGreTap::EncapSPort(0),
-            InfoGreTap::EncapDPort(0),
-        ];
-
-        let attributes = attributes.map(|attribute| {
-            let mut buffer = vec![0u8; attribute.buffer_len()];
-            attribute.emit(&mut buffer);
-            let buffer = NlaBuffer::new(&buffer);
-            netlink_packet_route::link::InfoGreTap::parse(&buffer)
-                .expect("GRE attribute should be parseable from constant") //if not, this is a bug in how we specify the attribute
-        });
-
         self.message_mut().attributes.extend(vec![
             LinkAttribute::IfName(name.into()),
             LinkAttribute::LinkInfo(vec![
                 LinkInfo::Kind(InfoKind::GreTap),
-                LinkInfo::Data(InfoData::GreTap(attributes.to_vec())),
+                LinkInfo::Data(InfoData::GreTap(gretap_v4_attributes(local_ip, remote_ip))),
             ]),
         ]);
         self
     }
 }
 
+fn gretap_v4_attributes(local_ip: &Ipv4Addr, remote_ip: &Ipv4Addr) -> Vec<netlink_packet_route::link::InfoGreTap> {
+
+    // Byte-values extracted from WireShark via nlmon-interface
+    // and command `ip link add name <NAME> type gretap local <LOCAL_IP> remote <REMOTE_IP>`.
+    // Compare with implementation of ip-command: https://github.com/shemminger/iproute2/blob/040325f543a1f7e6bb336355c136984e9bbe00d6/ip/link_gre.c#L394
+    let attributes = [
+        InfoGreTap::IKey(0),
+        InfoGreTap::OKey(0),
+        InfoGreTap::IFlags(0),
+        InfoGreTap::OFlags(0),
+        InfoGreTap::Local(u32::from_le_bytes(local_ip.octets())),
+        InfoGreTap::Remote(u32::from_le_bytes(remote_ip.octets())),
+        InfoGreTap::Pmtudisc(1),
+        InfoGreTap::Tos(0),
+        InfoGreTap::Ttl(0),
+        InfoGreTap::FwMark(0),
+        InfoGreTap::EncapType(0),
+        InfoGreTap::EncapFlags(0),
+        InfoGreTap::EncapSPort(0),
+        InfoGreTap::EncapDPort(0),
+    ];
+
+    let attributes = attributes.map(|attribute| {
+        let mut buffer = vec![0u8; attribute.buffer_len()];
+        attribute.emit(&mut buffer);
+        let buffer = NlaBuffer::new(&buffer);
+        netlink_packet_route::link::InfoGreTap::parse(&buffer)
+            .expect("GRE attribute should be parseable from constant") //if not, this is a bug in how we specify the attribute
+    });
+
+    attributes.to_vec()
+}
+
+/// Extracts the remote IP from the attributes of a GRE interface, as created by [`Gretap::gretap_v4`].
+pub fn gretap_v4_remote_ip(attributes: &[LinkAttribute]) -> Option<Ipv4Addr> {
+    attributes.iter()
+        .filter_map(|attribute| match attribute {
+            LinkAttribute::LinkInfo(infos) => Some(infos),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|info| match info {
+            LinkInfo::Data(InfoData::GreTap(attributes)) => Some(attributes),
+            _ => None,
+        })
+        .flatten()
+        .find(|attribute| attribute.kind() == InfoGreTap::Remote(0).kind())
+        .and_then(|attribute| {
+            let mut buffer = vec![0u8; attribute.value_len()];
+            attribute.emit_value(&mut buffer);
+            <[u8; 4]>::try_from(buffer).ok()
+        })
+        .map(Ipv4Addr::from)
+}
+
 #[allow(dead_code)]
 enum InfoGreTap { // https://elixir.bootlin.com/linux/v6.5.3/source/include/uapi/linux/if_tunnel.h#L117
     Unspec,
@@ -173,3 +199,27 @@ impl netlink_packet_utils::nla::Nla for InfoGreTap {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_extract_the_remote_ip_from_the_attributes_of_a_gre_interface() {
+        let local_ip = Ipv4Addr::new(192, 168, 0, 1);
+        let remote_ip = Ipv4Addr::new(192, 168, 0, 2);
+
+        let attributes = vec![
+            LinkAttribute::IfName(String::from("gre-opendut0")),
+            LinkAttribute::LinkInfo(vec![
+                LinkInfo::Kind(InfoKind::GreTap),
+                LinkInfo::Data(InfoData::GreTap(gretap_v4_attributes(&local_ip, &remote_ip))),
+            ]),
+        ];
+
+        assert_that!(gretap_v4_remote_ip(&attributes), some(eq(remote_ip)));
+        assert_that!(gretap_v4_remote_ip(&attributes[..1]), none());
+    }
+}
@@ -95,6 +95,12 @@ impl NetworkInterfaceManager {
         Ok(interface)
     }
 
+    /// Returns the remote IP of a GRE interface, as created via [`Self::create_gretap_v4_interface`].
+    pub async fn get_gretap_v4_remote_ip(&self, interface: &Interface) -> Result<Option<Ipv4Addr>, Error> {
+        let attributes = self.get_attributes(interface).await?;
+        Ok(gretap::gretap_v4_remote_ip(&attributes))
+    }
+
     pub async fn set_interface_up(&self, interface: &Interface) -> Result<(), Error> {
         self.handle
             .link()
@@ -132,6 +138,16 @@ impl NetworkInterfaceManager {
         Ok(())
     }
 
+    pub async fn remove_interface_from_bridge(&self, interface: &Interface) -> Result<(), Error> {
+        self.handle
+            .link()
+            .set(interface.index)
+            .nocontroller()
+            .execute().await
+            .map_err(|cause| Error::RemoveInterfaceFromBridge { interface: interface.clone(), cause })?;
+        Ok(())
+    }
+
     pub async fn delete_interface(&self, interface: &Interface) -> Result<(), Error> {
         self.handle
             .link()
@@ -192,6 +208,8 @@ pub enum Error {
     SetInterfaceUp { interface: Interface, cause: rtnetlink::Error },
     #[error("Failure while joining interface {interface} to bridge {bridge}: {cause}")]
     JoinInterfaceToBridge { interface: Interface, bridge: Interface, cause: rtnetlink::Error },
+    #[error("Failure while removing interface {interface} from its bridge: {cause}")]
+    RemoveInterfaceFromBridge { interface: Interface, cause: rtnetlink::Error },
     #[error("Failure while creating virtual CAN interface '{name}': {cause}")]
     VCanInterfaceCreation { name: NetworkInterfaceName, cause: String},
     #[error("Failure while invoking command line program '{command}': {cause}")]
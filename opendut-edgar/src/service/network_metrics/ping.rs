@@ -6,30 +6,29 @@ use tokio::time::sleep;
 use tracing::{error, trace};
 use opendut_types::cluster::PeerClusterAssignment;
 
+/// Pings the given peers in the given interval, until the returned future is dropped.
 pub async fn cluster_ping(peers: Vec<PeerClusterAssignment>, ping_interval_ms: Duration) {
     let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
     let rtt = meter.f64_gauge("round_trip_time").init();
 
     let rtt_mutex = Arc::new(Mutex::new(rtt));
 
-    tokio::spawn(async move {
-        let data = [1, 2, 3, 4];
-        let options = ping_rs::PingOptions { ttl: 128, dont_fragment: true };
-        loop {
-            sleep(ping_interval_ms).await;
-            let timeout = Duration::from_secs(1); //TODO make configurable
-            for peer in peers.clone() {
-                let remote_address = peer.vpn_address;
-                let result = ping_rs::send_ping(&remote_address, timeout, &data, Some(&options));
-                match result {
-                    Ok(reply) => {
-                        rtt_mutex.lock().await
-                            .record(reply.rtt as f64, &[KeyValue::new("peer_ip_address", remote_address.to_string())]);
-                        trace!("Reply from {}: bytes={} time={}ms TTL={}", reply.address, data.len(), reply.rtt, options.ttl)
-                    },
-                    Err(cause) => error!("Error while pinging peer {peer_id} with IP {peer_ip}: {cause:?}", peer_id=peer.peer_id, peer_ip=remote_address)
-                }
+    let data = [1, 2, 3, 4];
+    let options = ping_rs::PingOptions { ttl: 128, dont_fragment: true };
+    loop {
+        sleep(ping_interval_ms).await;
+        let timeout = Duration::from_secs(1); //TODO make configurable
+        for peer in peers.clone() {
+            let remote_address = peer.vpn_address;
+            let result = ping_rs::send_ping(&remote_address, timeout, &data, Some(&options));
+            match result {
+                Ok(reply) => {
+                    rtt_mutex.lock().await
+                        .record(reply.rtt as f64, &[KeyValue::new("peer_ip_address", remote_address.to_string())]);
+                    trace!("Reply from {}: bytes={} time={}ms TTL={}", reply.address, data.len(), reply.rtt, options.ttl)
+                },
+                Err(cause) => error!("Error while pinging peer {peer_id} with IP {peer_ip}: {cause:?}", peer_id=peer.peer_id, peer_ip=remote_address)
             }
         }
-    });
+    }
 }
\ No newline at end of file
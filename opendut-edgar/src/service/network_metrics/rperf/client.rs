@@ -13,6 +13,7 @@ use opendut_types::cluster::PeerClusterAssignment;
 use crate::service::network_metrics::rperf::{RperfError, RperfRunError};
 use crate::service::network_metrics::rperf::RperfRunError::RperfClientError;
 
+/// Runs an rperf client for each of the given peers. The clients are terminated when the returned future is dropped.
 pub async fn launch_rperf_clients(peers: Vec<PeerClusterAssignment>, target_bandwidth_kbit_per_second: u64, rperf_backoff_max_elapsed_time_ms: Duration) {
 
     let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
@@ -23,20 +24,21 @@ pub async fn launch_rperf_clients(peers: Vec<PeerClusterAssignment>, target_band
     let megabits_second_send_mutex = Arc::new(Mutex::new(megabits_second_send));
     let megabits_second_receive_mutex = Arc::new(Mutex::new(megabits_second_receive));
 
-    for peer in peers.clone() {
+    let clients = peers.into_iter().map(|peer| {
         let megabits_second_send_mutex = megabits_second_send_mutex.clone();
         let megabits_second_receive_mutex = megabits_second_receive_mutex.clone();
-        tokio::spawn(async move {
-            exponential_backoff_launch_rperf_client(
+        async move {
+            let _ = exponential_backoff_launch_rperf_client(
                 &peer,
                 target_bandwidth_kbit_per_second,
                 rperf_backoff_max_elapsed_time_ms,
                 megabits_second_send_mutex,
                 megabits_second_receive_mutex
             ).await
-                .inspect_err(|cause| error!("Failed to start rperf client for peer {peer_id}: {cause}", peer_id=peer.peer_id))
-        });
-    }
+                .inspect_err(|cause| error!("Failed to start rperf client for peer {peer_id}: {cause}", peer_id=peer.peer_id));
+        }
+    });
+    futures::future::join_all(clients).await;
 }
 
 pub async fn exponential_backoff_launch_rperf_client(
@@ -75,6 +77,7 @@ pub async fn launch_rperf_client(
         .arg(format!("{target_bandwidth_kbit_per_second}k")) //the k suffix signifies the entered bandwidth is to be read in kilobits
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true)
         .spawn();
 
     match rperf_client {
@@ -0,0 +1,97 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use axum::routing::get;
+use tracing::{error, info};
+
+use opendut_util::telemetry::metrics::{PrometheusRegistry, prometheus_registry};
+
+pub struct PrometheusEndpointOptions {
+    pub address: SocketAddr,
+}
+impl PrometheusEndpointOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let host = config.get_string("opentelemetry.metrics.prometheus.bind.host")?;
+        let port = config.get_int("opentelemetry.metrics.prometheus.bind.port")?;
+        let address = SocketAddr::from_str(&format!("{host}:{port}"))?;
+
+        Ok(Self { address })
+    }
+}
+
+/// Serves the metrics for scraping by Prometheus at `/metrics`, if exposing them for Prometheus is enabled.
+pub fn spawn_endpoint(options: PrometheusEndpointOptions) -> anyhow::Result<()> {
+    let Some(registry) = prometheus_registry() else {
+        return Ok(());
+    };
+    serve(registry, options.address)?;
+    Ok(())
+}
+
+/// Returns the address the endpoint is bound to, which differs from the given one, if its port is 0.
+fn serve(registry: PrometheusRegistry, address: SocketAddr) -> anyhow::Result<SocketAddr> {
+    let router = Router::new()
+        .route("/metrics", get(move || render_metrics(Clone::clone(&registry))));
+
+    let server = axum::Server::try_bind(&address)?
+        .serve(router.into_make_service());
+    let address = server.local_addr();
+
+    info!("Serving metrics for Prometheus at http://{address}/metrics");
+
+    tokio::spawn(async move {
+        if let Err(cause) = server.await {
+            error!("Endpoint for Prometheus metrics failed: {cause}");
+        }
+    });
+    Ok(address)
+}
+
+/// Gathering the metrics invokes the callbacks of observable instruments, which may block, so it runs on a blocking thread.
+async fn render_metrics(registry: PrometheusRegistry) -> Response {
+    match tokio::task::spawn_blocking(move || registry.render()).await {
+        Ok(Ok(metrics)) => {
+            ([(header::CONTENT_TYPE, PrometheusRegistry::CONTENT_TYPE)], metrics).into_response()
+        }
+        Ok(Err(cause)) => {
+            error!("Failed to render metrics for Prometheus: {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(cause) => {
+            error!("Task rendering metrics for Prometheus failed: {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_serve_registered_gauges_in_the_prometheus_format() -> anyhow::Result<()> {
+        let registry = PrometheusRegistry::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(registry.exporter(true)?)
+            .build();
+        let _gauge = meter_provider.meter("test")
+            .u64_observable_gauge("edgar_test_gauge")
+            .with_callback(|observer| observer.observe(42, &[]))
+            .init();
+
+        let address = serve(registry, SocketAddr::from(([127, 0, 0, 1], 0)))?;
+        let response = reqwest::get(format!("http://{address}/metrics")).await?;
+
+        assert_that!(response.status(), eq(reqwest::StatusCode::OK));
+        assert_that!(response.text().await?, contains_substring("edgar_test_gauge{otel_scope_name=\"test\"} 42"));
+
+        Ok(())
+    }
+}
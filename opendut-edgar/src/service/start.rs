@@ -9,31 +9,37 @@ use config::Config;
 use opentelemetry::propagation::text_map_propagator::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::sync::mpsc::Sender;
+use tokio::task::AbortHandle;
 use tokio::time::sleep;
 use tonic::Code;
 use tracing::{debug, error, info, Span, trace, warn};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use opendut_carl_api::proto::services::peer_messaging_broker;
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerCertificate, ApplyPeerConfiguration, ReportPeerConfigurationState, TracingContext};
 use opendut_carl_api::proto::services::peer_messaging_broker::downstream::Message;
 use opendut_types::cluster::{ClusterAssignment, PeerClusterAssignment};
-use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+use opendut_types::peer::configuration::{ClusterTeardownState, PeerConfiguration, PeerConfiguration2, PeerConfigurationState};
 use opendut_types::peer::PeerId;
+use opendut_types::peer::certificate::PeerCertificate;
+use opendut_types::peer::setup::PeerSetupNonce;
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_util::telemetry;
 use opendut_util::telemetry::logging::LoggingConfig;
-use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
+use opendut_util::telemetry::opentelemetry_types::{Opentelemetry, Prometheus};
 use opendut_util::project;
 use opendut_util::settings::LoadedConfig;
 
 use crate::common::{carl, settings};
+use crate::common::client_certificate::ClientCertificateFiles;
 use crate::service::test_execution::executor_manager::{ExecutorManager, ExecutorManagerRef};
 use crate::service::{cluster_assignment, vpn};
 use crate::service::can_manager::{CanManager, CanManagerRef};
 use crate::service::cluster_assignment::Error;
 use crate::service::network_metrics;
 use crate::service::network_interface::manager::{NetworkInterfaceManager, NetworkInterfaceManagerRef};
+use crate::service::prometheus;
+use crate::service::prometheus::PrometheusEndpointOptions;
 
 const BANNER: &str = r"
                          _____     _______
@@ -69,13 +75,14 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow:
 
     let logging_config = LoggingConfig::load(&settings.config)?;
     let opentelemetry = Opentelemetry::load(&settings.config, service_instance_id).await?;
+    let prometheus = Prometheus::load(&settings.config)?;
     
-    let mut shutdown = telemetry::initialize_with_config(logging_config, opentelemetry.clone()).await?;
+    let mut shutdown = telemetry::initialize_with_config(logging_config, opentelemetry, prometheus).await?;
 
-    if let (Opentelemetry::Enabled { cpu_collection_interval_ms, .. },
-        Some(meter_providers, ..)) = (opentelemetry, &shutdown.meter_providers) {
-        telemetry::metrics::initialize_metrics_collection(cpu_collection_interval_ms, meter_providers);
+    if let Some(meter_providers) = &shutdown.meter_providers {
+        telemetry::metrics::initialize_metrics_collection(meter_providers);
     }
+    prometheus::spawn_endpoint(PrometheusEndpointOptions::load(&settings.config)?)?;
 
     create(self_id, settings).await?;
 
@@ -94,6 +101,9 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
 
     let network_interface_management_enabled = settings.config.get::<bool>("network.interface.management.enabled")?;
 
+    let setup_nonce = settings.config.get::<PeerSetupNonce>(settings::key::peer::setup_nonce)
+        .context("Failed to read the nonce of the Setup-String from configuration.\n\nRun `edgar setup` with a new Setup-String before launching the service.")?;
+
     let remote_address = vpn::retrieve_remote_host(&settings).await?;
     
     let ping_interval = Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.ping.interval.ms")?);
@@ -109,13 +119,17 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
         ping_interval,
         target_bandwidth_kbit_per_second,
         rperf_backoff_max_elapsed_time,
+        metrics_task: Default::default(),
+        cluster_assignment: Default::default(),
     };
 
+    let client_certificate_files = ClientCertificateFiles::load(&settings.config)?;
+
     let timeout_duration = Duration::from_millis(settings.config.get::<u64>("carl.disconnect.timeout.ms")?);
 
     let mut carl = carl::connect(&settings.config).await?;
 
-    let (mut rx_inbound, tx_outbound) = carl::open_stream(self_id, &remote_address, &mut carl).await?;
+    let (mut rx_inbound, tx_outbound) = carl::open_stream(self_id, setup_nonce, &remote_address, &mut carl).await?;
 
     loop {
         let received = tokio::time::timeout(
@@ -129,6 +143,7 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
                     handle_stream_message(
                         message,
                         &setup_cluster_info,
+                        &client_certificate_files,
                         &tx_outbound,
                     ).await?
                 }
@@ -178,6 +193,7 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
 async fn handle_stream_message(
     message: peer_messaging_broker::Downstream,
     setup_cluster_info: &SetupClusterInfo,
+    client_certificate_files: &ClientCertificateFiles,
     tx_outbound: &Sender<peer_messaging_broker::Upstream>,
 ) -> anyhow::Result<()> {
 
@@ -197,7 +213,8 @@ async fn handle_stream_message(
                     tx_outbound.send(message).await
                         .inspect_err(|cause| debug!("Failed to send ping to CARL: {cause}"));
             }
-            Message::ApplyPeerConfiguration(message) => { apply_peer_configuration(message, context, setup_cluster_info).await? }
+            Message::ApplyPeerConfiguration(message) => { apply_peer_configuration(message, context, setup_cluster_info, tx_outbound).await? }
+            Message::ApplyPeerCertificate(message) => { apply_peer_certificate(message, client_certificate_files) }
         }
     } else {
         ignore(message)
@@ -207,7 +224,12 @@ async fn handle_stream_message(
 }
 
 #[tracing::instrument(skip_all, level="trace")]
-async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Option<TracingContext>, setup_cluster_info: &SetupClusterInfo) -> anyhow::Result<()> {
+async fn apply_peer_configuration(
+    message: ApplyPeerConfiguration,
+    context: Option<TracingContext>,
+    setup_cluster_info: &SetupClusterInfo,
+    tx_outbound: &Sender<peer_messaging_broker::Upstream>,
+) -> anyhow::Result<()> {
 
     match message.clone() {
         ApplyPeerConfiguration {
@@ -226,20 +248,42 @@ async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Opti
                     match PeerConfiguration2::try_from(configuration2) {
                         Err(error) => error!("Illegal PeerConfiguration2: {error}"),
                         Ok(configuration2) => {
-                            let _ = setup_cluster(
+                            let previous_cluster_assignment = setup_cluster_info.cluster_assignment.lock().unwrap().take();
+
+                            let result = setup_cluster(
+                                &previous_cluster_assignment,
                                 &configuration.cluster_assignment,
                                 setup_cluster_info,
                                 configuration.network.bridge_name,
                             ).await;
-
-                            let mut executor_manager = setup_cluster_info.executor_manager.lock().unwrap();
-                            executor_manager.terminate_executors();
-                            executor_manager.create_new_executors(configuration2.executors);
+                            let cluster_teardown = match (&previous_cluster_assignment, &configuration.cluster_assignment) {
+                                (Some(previous_cluster_assignment), None) => Some(match &result {
+                                    Ok(()) => ClusterTeardownState::Succeeded { cluster_id: previous_cluster_assignment.id },
+                                    Err(cause) => ClusterTeardownState::Failed { cluster_id: previous_cluster_assignment.id, cause: cause.to_string() },
+                                }),
+                                _ => None,
+                            };
+                            *setup_cluster_info.cluster_assignment.lock().unwrap() = match result {
+                                Ok(()) => Clone::clone(&configuration.cluster_assignment),
+                                Err(_) => Clone::clone(&previous_cluster_assignment), //retry with the next configuration
+                            };
+
+                            let executor_states = {
+                                let mut executor_manager = setup_cluster_info.executor_manager.lock().unwrap();
+                                executor_manager.terminate_executors();
+                                executor_manager.create_new_executors(configuration2.executors)
+                            };
 
                             setup_cluster_metrics(
+                                &previous_cluster_assignment,
                                 &configuration.cluster_assignment,
                                 setup_cluster_info,
                             )?;
+
+                            report_peer_configuration_state(
+                                PeerConfigurationState { executors: executor_states, cluster_teardown },
+                                tx_outbound,
+                            ).await;
                         }
                     }
                 }
@@ -250,6 +294,30 @@ async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Opti
     Ok(())
 }
 
+/// Stores the renewed client certificate, which is used when connecting to CARL the next time.
+fn apply_peer_certificate(message: ApplyPeerCertificate, client_certificate_files: &ClientCertificateFiles) {
+    match message.certificate.map(PeerCertificate::try_from) {
+        Some(Ok(certificate)) => match client_certificate_files.write(&certificate) {
+            Ok(()) => info!("Stored renewed client certificate at '{}'.", client_certificate_files.certificate.display()),
+            Err(cause) => error!("Failed to store renewed client certificate:\n  {cause}"),
+        },
+        Some(Err(cause)) => error!("Illegal PeerCertificate: {cause}"),
+        None => warn!("Ignoring renewal of client certificate, which contains no certificate."),
+    }
+}
+
+async fn report_peer_configuration_state(state: PeerConfigurationState, tx_outbound: &Sender<peer_messaging_broker::Upstream>) {
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::ReportPeerConfigurationState(ReportPeerConfigurationState {
+            state: Some(state.into()),
+        })),
+        context: None,
+    };
+    let _ignore_error =
+        tx_outbound.send(message).await
+            .inspect_err(|cause| warn!("Failed to report state of peer configuration to CARL: {cause}"));
+}
+
 struct SetupClusterInfo {
     self_id: PeerId,
     network_interface_management_enabled: bool,
@@ -259,9 +327,13 @@ struct SetupClusterInfo {
     ping_interval: Duration,
     target_bandwidth_kbit_per_second: u64,
     rperf_backoff_max_elapsed_time: Duration,
+    metrics_task: std::sync::Mutex<Option<AbortHandle>>,
+    /// The cluster assignment, which was last set up successfully, to only reconcile changes to it.
+    cluster_assignment: std::sync::Mutex<Option<ClusterAssignment>>,
 }
 #[tracing::instrument(skip_all)]
 async fn setup_cluster(
+    previous_cluster_assignment: &Option<ClusterAssignment>,
     cluster_assignment: &Option<ClusterAssignment>,
     info: &SetupClusterInfo,
     bridge_name: NetworkInterfaceName,
@@ -273,32 +345,87 @@ async fn setup_cluster(
             info!("Was assigned to cluster <{}>", cluster_assignment.id);
 
             if info.network_interface_management_enabled {
-                cluster_assignment::network_interfaces_setup(
-                    cluster_assignment,
-                    info.self_id,
+                match previous_cluster_assignment {
+                    Some(previous_cluster_assignment) if previous_cluster_assignment.id == cluster_assignment.id => {
+                        debug!("Reconciling changes to assignment of cluster <{}>.", cluster_assignment.id);
+                        cluster_assignment::network_interfaces_reconcile(
+                            previous_cluster_assignment,
+                            cluster_assignment,
+                            info.self_id,
+                            &bridge_name,
+                            Arc::clone(&info.network_interface_manager),
+                            Arc::clone(&info.can_manager)
+                        ).await
+                            .inspect_err(|error| {
+                                error!("Failed to reconcile network interfaces: {error}")
+                            })?;
+                    }
+                    _ => {
+                        cluster_assignment::network_interfaces_setup(
+                            cluster_assignment,
+                            info.self_id,
+                            &bridge_name,
+                            Arc::clone(&info.network_interface_manager),
+                            Arc::clone(&info.can_manager)
+                        ).await
+                            .inspect_err(|error| {
+                                error!("Failed to configure network interfaces: {error}")
+                            })?;
+                    }
+                }
+            } else {
+                debug!("Skipping changes to network interfaces after receiving ClusterAssignment, as this is disabled via configuration.");
+            }
+        }
+        None => {
+            debug!("No ClusterAssignment in peer configuration.");
+
+            let Some(previous_cluster_assignment) = previous_cluster_assignment else {
+                debug!("Was not assigned to a cluster before. Nothing to tear down.");
+                return Ok(());
+            };
+            info!("Was removed from cluster <{}>. Tearing down its setup.", previous_cluster_assignment.id);
+
+            if info.network_interface_management_enabled {
+                cluster_assignment::network_interfaces_teardown(
                     &bridge_name,
                     Arc::clone(&info.network_interface_manager),
                     Arc::clone(&info.can_manager)
                 ).await
                     .inspect_err(|error| {
-                        error!("Failed to configure network interfaces: {error}")
+                        error!("Failed to tear down network interfaces: {error}")
                     })?;
             } else {
-                debug!("Skipping changes to network interfaces after receiving ClusterAssignment, as this is disabled via configuration.");
+                debug!("Skipping changes to network interfaces after receiving no ClusterAssignment, as this is disabled via configuration.");
             }
         }
-        None => {
-            debug!("No ClusterAssignment in peer configuration.");
-            //TODO teardown cluster, if configuration changed
-        }
     }
     Ok(())
 }
 
 fn setup_cluster_metrics(
+    previous_cluster_assignment: &Option<ClusterAssignment>,
     cluster_assignment: &Option<ClusterAssignment>,
     setup_cluster_info: &SetupClusterInfo,
-) -> anyhow::Result<()> { //TODO make idempotent
+) -> anyhow::Result<()> {
+    let member_addresses = |cluster_assignment: &Option<ClusterAssignment>| cluster_assignment.as_ref()
+        .map(|cluster_assignment| {
+            let mut addresses = cluster_assignment.assignments.iter().map(|assignment| assignment.vpn_address).collect::<Vec<_>>();
+            addresses.sort();
+            addresses
+        });
+
+    let metrics_running = setup_cluster_info.metrics_task.lock().unwrap().is_some();
+    if metrics_running && member_addresses(previous_cluster_assignment) == member_addresses(cluster_assignment) {
+        debug!("Peers of cluster assignment unchanged. Keeping metrics running.");
+        return Ok(());
+    }
+
+    if let Some(previous_metrics_task) = setup_cluster_info.metrics_task.lock().unwrap().take() {
+        debug!("Stopping metrics of previous cluster assignment.");
+        previous_metrics_task.abort();
+    }
+
     match cluster_assignment {
         None => {}
         Some(cluster_assignment) => {
@@ -314,15 +441,20 @@ fn setup_cluster_metrics(
             let target_bandwidth_kbit_per_second = setup_cluster_info.target_bandwidth_kbit_per_second;
             let rperf_backoff_max_elapsed_time_ms = setup_cluster_info.rperf_backoff_max_elapsed_time;
 
-            tokio::spawn(async move {
-                network_metrics::ping::cluster_ping(peers.clone(), ping_interval_ms).await;
+            let metrics_task = tokio::spawn(async move {
+                let ping = network_metrics::ping::cluster_ping(peers.clone(), ping_interval_ms);
 
-                if project::is_running_in_development().not() {
-                    let _ = network_metrics::rperf::server::exponential_backoff_launch_rperf_server(rperf_backoff_max_elapsed_time_ms).await //ignore errors during startup of rperf server, as we do not want to crash EDGAR for this
-                        .inspect_err(|cause| error!("Failed to start rperf server:\n  {cause}"));
-                    network_metrics::rperf::client::launch_rperf_clients(peers, target_bandwidth_kbit_per_second, rperf_backoff_max_elapsed_time_ms).await;
-                }
+                let rperf = async {
+                    if project::is_running_in_development().not() {
+                        let _ = network_metrics::rperf::server::exponential_backoff_launch_rperf_server(rperf_backoff_max_elapsed_time_ms).await //ignore errors during startup of rperf server, as we do not want to crash EDGAR for this
+                            .inspect_err(|cause| error!("Failed to start rperf server:\n  {cause}"));
+                        network_metrics::rperf::client::launch_rperf_clients(peers, target_bandwidth_kbit_per_second, rperf_backoff_max_elapsed_time_ms).await;
+                    }
+                };
+
+                tokio::join!(ping, rperf);
             });
+            *setup_cluster_info.metrics_task.lock().unwrap() = Some(metrics_task.abort_handle());
         }
     }
     Ok(())
@@ -1,6 +1,9 @@
+use std::collections::HashSet;
+use std::ops::Not;
 use std::sync::{Arc, Mutex};
 
-use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorKind}};
+use opendut_types::peer::configuration::{Parameter, ParameterState, ParameterStateError, ParameterStateKind, ParameterTarget};
+use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
 use tokio::sync::watch::{self, Sender};
 use tracing::warn;
 
@@ -19,54 +22,78 @@ impl ExecutorManager {
         }))
     }
 
-    pub fn create_new_executors(&mut self, executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>) {
+    /// Starts the executors with target `Present` and returns the resulting state of each executor parameter.
+    pub fn create_new_executors(&mut self, executors: Vec<Parameter<ExecutorDescriptor>>) -> Vec<ParameterState<ExecutorDescriptor>> {
 
-        let executors = executors.into_iter()
-            .filter_map(|executor| { //TODO properly handle Present vs. Absent
-                if matches!(executor.target, peer::configuration::ParameterTarget::Present) {
-                    Some(executor.value)
-                } else {
-                    None
-                }
-            });
+        let present_ids = executors.iter()
+            .filter(|executor| matches!(executor.target, ParameterTarget::Present))
+            .map(|executor| executor.id)
+            .collect::<HashSet<_>>();
 
-        for executor in executors {
+        executors.into_iter()
+            .map(|executor| {
+                let state = match executor.target {
+                    ParameterTarget::Absent => ParameterStateKind::Absent, //previously running executors were terminated already
+                    ParameterTarget::Present => {
+                        let incomplete_dependencies = executor.dependencies.iter()
+                            .filter(|dependency| present_ids.contains(dependency).not())
+                            .copied()
+                            .collect::<Vec<_>>();
 
-            let (tx, rx) = watch::channel(false);
+                        if incomplete_dependencies.is_empty() {
+                            self.create_executor(Clone::clone(&executor.value))
+                        } else {
+                            ParameterStateKind::WaitingForDependencies(incomplete_dependencies)
+                        }
+                    }
+                };
+                ParameterState { id: executor.id, state, value: executor.value }
+            })
+            .collect()
+    }
 
-            let ExecutorDescriptor {kind, results_url} = executor;
+    fn create_executor(&mut self, executor: ExecutorDescriptor) -> ParameterStateKind {
 
-            match kind {
-                ExecutorKind::Executable => warn!("Executing Executable not yet implemented."),
-                ExecutorKind::Container {
-                    engine,
+        let ExecutorDescriptor {kind, results_url} = executor;
+
+        match kind {
+            ExecutorKind::Executable => {
+                let message = String::from("Executing Executable not yet implemented.");
+                warn!("{message}");
+                ParameterStateKind::Error(ParameterStateError::CreatingFailed(message))
+            }
+            ExecutorKind::Container {
+                engine,
+                name,
+                image,
+                volumes,
+                devices,
+                envs,
+                ports,
+                command,
+                args,
+            } => {
+                let (tx, rx) = watch::channel(false);
+
+                let container_config = ContainerConfiguration{
                     name,
+                    engine,
                     image,
-                    volumes,
-                    devices,
-                    envs,
-                    ports,
                     command,
                     args,
-                } => {
-                    let container_config = ContainerConfiguration{
-                        name,
-                        engine,
-                        image,
-                        command,
-                        args,
-                        envs,
-                        results_url,
-                        ports,
-                        devices,
-                        volumes,
-                    };
-                    tokio::spawn(async move {
-                        ContainerManager::new(container_config, rx).start().await;
-                    });
-                }
+                    envs,
+                    results_url,
+                    ports,
+                    devices,
+                    volumes,
+                };
+                tokio::spawn(async move {
+                    ContainerManager::new(container_config, rx).start().await;
+                });
+                self.tx_termination_channels.push(tx);
+
+                ParameterStateKind::Present
             }
-            self.tx_termination_channels.push(tx);
         }
     }
 
@@ -25,6 +25,12 @@ pub fn default_carl_ca_certificate_path() -> PathBuf {
 pub fn default_checksum_carl_ca_certificate_file() -> PathBuf {
     PathBuf::from("/etc/opendut/tls/.ca.pem.checksum")
 }
+pub fn default_client_certificate_path() -> PathBuf {
+    PathBuf::from("/etc/opendut/tls/edgar-client.pem")
+}
+pub fn default_client_key_path() -> PathBuf {
+    PathBuf::from("/etc/opendut/tls/edgar-client.key")
+}
 pub fn default_os_cert_store_ca_certificate_path() -> PathBuf {
     PathBuf::from("/usr/local/share/ca-certificates/opendut-ca.crt")
 }
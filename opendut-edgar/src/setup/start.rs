@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 use std::env;
 use std::sync::Arc;
+use std::time::SystemTime;
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use tracing::info;
 use url::Url;
 
@@ -11,7 +12,7 @@ use opendut_types::util::net::NetworkInterfaceName;
 use opendut_types::vpn::netbird::SetupKey;
 use opendut_types::vpn::VpnPeerConfiguration;
 use opendut_util::telemetry;
-use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
+use opendut_util::telemetry::opentelemetry_types::{Opentelemetry, Prometheus};
 
 use crate::service::network_interface::manager::NetworkInterfaceManager;
 use crate::setup::{Leader, runner, tasks, User};
@@ -26,6 +27,10 @@ pub async fn managed(run_mode: RunMode, no_confirm: bool, setup_string: String,
     let peer_setup = PeerSetup::decode(&setup_string)
         .context("Failed to decode Setup-String.")?;
 
+    if peer_setup.is_expired_at(SystemTime::now()) {
+        bail!("The Setup-String expired. Please generate a new Setup-String for peer <{}>.", peer_setup.id);
+    }
+
     let service_user = determine_service_user_name();
     info!("Using service user '{}'.", service_user.name);
 
@@ -34,10 +39,12 @@ pub async fn managed(run_mode: RunMode, no_confirm: bool, setup_string: String,
 
     let mut tasks: Vec<Box<dyn Task>> = vec![
         Box::new(tasks::WriteCaCertificate::with_certificate(peer_setup.ca)),
+        Box::new(tasks::WriteClientCertificate::with_certificate(peer_setup.certificate)),
         Box::new(tasks::CheckCommandLinePrograms),
         Box::new(tasks::WriteConfiguration::with_override(
             write_configuration::ConfigOverride {
                 peer_id: peer_setup.id,
+                setup_nonce: peer_setup.nonce,
                 carl_url: peer_setup.carl,
                 auth_config: peer_setup.auth_config,
             }),
@@ -130,8 +137,9 @@ pub async fn init_logging() -> anyhow::Result<()> {
         file_logging,
     };
     let opentelemetry_config = Opentelemetry::Disabled;
-    
-    let _ = telemetry::initialize_with_config(logging_config, opentelemetry_config).await?;
+    let prometheus_config = Prometheus::Disabled;
+
+    let _ = telemetry::initialize_with_config(logging_config, opentelemetry_config, prometheus_config).await?;
 
     Ok(())
 }
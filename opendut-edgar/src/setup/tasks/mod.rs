@@ -39,4 +39,7 @@ pub use create_kernel_module_load_rule::CreateKernelModuleLoadRule;
 pub mod write_ca_certificate;
 pub use write_ca_certificate::WriteCaCertificate;
 
+mod write_client_certificate;
+pub use write_client_certificate::WriteClientCertificate;
+
 pub mod copy_rperf;
@@ -0,0 +1,42 @@
+use opendut_types::peer::certificate::PeerCertificate;
+
+use crate::common::client_certificate::ClientCertificateFiles;
+use crate::setup::constants;
+use crate::setup::task::{Success, Task, TaskFulfilled};
+
+pub struct WriteClientCertificate {
+    pub certificate: PeerCertificate,
+    pub files: ClientCertificateFiles,
+}
+
+impl Task for WriteClientCertificate {
+
+    fn description(&self) -> String {
+        String::from("Write Client Certificate")
+    }
+
+    fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+        if self.files.contain(&self.certificate)? {
+            Ok(TaskFulfilled::Yes)
+        } else {
+            Ok(TaskFulfilled::No)
+        }
+    }
+
+    fn execute(&self) -> anyhow::Result<Success> {
+        self.files.write(&self.certificate)?;
+        Ok(Success::default())
+    }
+}
+
+impl WriteClientCertificate {
+    pub fn with_certificate(certificate: PeerCertificate) -> Self {
+        Self {
+            certificate,
+            files: ClientCertificateFiles {
+                certificate: constants::default_client_certificate_path(),
+                key: constants::default_client_key_path(),
+            },
+        }
+    }
+}
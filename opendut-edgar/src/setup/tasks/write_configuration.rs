@@ -8,6 +8,7 @@ use tracing::{debug, error, info};
 use url::Url;
 
 use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::PeerSetupNonce;
 use opendut_types::util::net::AuthConfig;
 
 use crate::common::settings;
@@ -16,6 +17,7 @@ use crate::setup::task::{Success, Task, TaskFulfilled};
 
 pub struct ConfigOverride {
     pub peer_id: PeerId,
+    pub setup_nonce: PeerSetupNonce,
     pub carl_url: Url,
     pub auth_config: AuthConfig,
 }
@@ -52,6 +54,12 @@ impl Task for WriteConfiguration {
             }
             new_settings["peer"]["id"] = toml_edit::value(peer_id);
 
+            if new_settings["peer"].get("setup").is_none() {
+                new_settings["peer"]["setup"] = toml_edit::table();
+                new_settings["peer"]["setup"].as_table_mut().unwrap().set_dotted(true);
+            }
+            new_settings["peer"]["setup"]["nonce"] = toml_edit::value(self.config_override.setup_nonce.to_string());
+
             if new_settings.get("network").and_then(|network| network.get("carl")).is_none() {
                 new_settings["network"] = toml_edit::table();
                 new_settings["network"]["carl"] = toml_edit::table();
@@ -207,6 +215,7 @@ mod tests {
         assert_that!(file_content, eq(indoc!(r#"
             [peer]
             id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+            setup.nonce = "5a5ac8b3-0a8c-4e0f-8f5b-1b8e8e4c3d7a"
 
             [network]
             carl.host = "example.com"
@@ -242,6 +251,7 @@ mod tests {
         assert_that!(file_content, eq(indoc!(r#"
             [peer]
             id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+            setup.nonce = "5a5ac8b3-0a8c-4e0f-8f5b-1b8e8e4c3d7a"
 
             [network]
             carl.host = "example.com"
@@ -337,6 +347,7 @@ mod tests {
         config_file.write_str(&format!(indoc!(r#"
             [peer]
             id = "{}"
+            setup.nonce = "{}"
 
             [network.carl]
             host = "{}"
@@ -350,7 +361,7 @@ mod tests {
             id = "{}"
             secret = "{}"
             scopes = "{}"
-        "#), fixture.peer_id, HOST, PORT, OIDC_ENABLED, ISSUER_URL, CLIENT_ID, CLIENT_SECRET, SCOPES))?;
+        "#), fixture.peer_id, fixture.setup_nonce, HOST, PORT, OIDC_ENABLED, ISSUER_URL, CLIENT_ID, CLIENT_SECRET, SCOPES))?;
 
         let file_content = fs::read_to_string(&config_file)?;
         assert!(predicate::str::is_empty().not().eval(&file_content));
@@ -373,6 +384,7 @@ mod tests {
             config_merge_suggestion_file: fixture.config_merge_suggestion_file.to_path_buf(),
             config_override: ConfigOverride {
                 peer_id: fixture.peer_id,
+                setup_nonce: fixture.setup_nonce,
                 carl_url: Url::parse("https://example.com:1234").unwrap(),
                 auth_config: AuthConfig::Enabled {
                     issuer_url: Url::parse("https://test.com:1234").unwrap(),
@@ -393,6 +405,7 @@ mod tests {
             config_merge_suggestion_file: fixture.config_merge_suggestion_file.to_path_buf(),
             config_override: ConfigOverride {
                 peer_id: fixture.peer_id,
+                setup_nonce: fixture.setup_nonce,
                 carl_url: Url::parse("https://example.com:1234").unwrap(),
                 auth_config: AuthConfig::Disabled,
             },
@@ -404,6 +417,7 @@ mod tests {
         config_file_to_write_to: ChildPath,
         config_merge_suggestion_file: ChildPath,
         peer_id: PeerId,
+        setup_nonce: PeerSetupNonce,
     }
     #[fixture]
     fn fixture() -> Fixture {
@@ -415,11 +429,14 @@ mod tests {
 
         let peer_id = PeerId::from(uuid!("dc72f6d9-d700-455f-8c31-9f15438e7503"));
 
+        let setup_nonce = PeerSetupNonce::from(uuid!("5a5ac8b3-0a8c-4e0f-8f5b-1b8e8e4c3d7a"));
+
         Fixture {
             _temp_dir: temp_dir,
             config_file_to_write_to,
             config_merge_suggestion_file,
             peer_id,
+            setup_nonce,
         }
     }
 }
@@ -1,6 +1,7 @@
 use leptos::*;
 use leptos_router::use_params_map;
 use opendut_types::cluster::{ClusterId};
+use opendut_types::project::ProjectName;
 
 use crate::app::{ExpectGlobals, use_app_globals};
 use crate::clusters::configurator::components::{DeviceSelection, DeviceSelector, LeaderSelection};
@@ -53,6 +54,8 @@ pub fn ClusterConfigurator() -> impl IntoView {
                 name: UserInputValue::Left(UserInputError::from("Enter a valid cluster name.")),
                 devices: DeviceSelection::Left(String::from("Select at least two devices.")),
                 leader: LeaderSelection::Left(String::from("Select a leader.")),
+                device_selectors: Vec::new(),
+                project: ProjectName::default(),
             });
 
             create_local_resource(|| {}, move |_| { // TODO: maybe a action suits better here
@@ -63,6 +66,8 @@ pub fn ClusterConfigurator() -> impl IntoView {
                             user_configuration.name = UserInputValue::Right(configuration.name.value());
                             user_configuration.devices = DeviceSelection::Right(configuration.devices);
                             user_configuration.leader = LeaderSelection::Right(configuration.leader);
+                            user_configuration.device_selectors = configuration.device_selectors;
+                            user_configuration.project = configuration.project;
                         });
                     }
                 }
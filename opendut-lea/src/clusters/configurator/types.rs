@@ -1,4 +1,5 @@
-use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName, DeviceSelector};
+use opendut_types::project::ProjectName;
 
 use crate::clusters::configurator::components::{DeviceSelection, LeaderSelection};
 use crate::components::UserInputValue;
@@ -22,6 +23,10 @@ pub struct UserClusterConfiguration {
     pub name: UserInputValue,
     pub devices: DeviceSelection,
     pub leader: LeaderSelection,
+    /// Device selectors are not editable yet, but kept when saving a loaded configuration.
+    pub device_selectors: Vec<DeviceSelector>,
+    /// The project is not editable yet, but kept when saving a loaded configuration.
+    pub project: ProjectName,
 }
 
 impl UserClusterConfiguration {
@@ -49,6 +54,8 @@ impl TryFrom<UserClusterConfiguration> for ClusterConfiguration {
             name,
             leader,
             devices,
+            device_selectors: configuration.device_selectors,
+            project: configuration.project,
         })
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use leptos::*;
 use leptos::html::Div;
 use leptos_use::on_click_outside;
@@ -39,7 +41,7 @@ pub fn ClustersOverview() -> impl IntoView {
             let mut carl = globals.expect_client();
             let id = Clone::clone(id);
             async move {
-                match carl.cluster.store_cluster_deployment(ClusterDeployment { id }).await {
+                match carl.cluster.store_cluster_deployment(ClusterDeployment { id, devices: HashSet::new(), deployed_by: String::new() }).await {
                     Ok(_) => {
                         toaster.toast(Toast::builder()
                             .simple("Successfully stored cluster deployment!")
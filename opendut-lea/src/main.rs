@@ -22,6 +22,7 @@ mod nav;
 mod user;
 mod about;
 mod cleo;
+mod reservations;
 
 fn main() {
 
@@ -70,6 +70,10 @@ pub fn Navbar() -> impl IntoView {
                                         <i class="fa-solid fa-microchip fa-lg pr-1" />
                                         <span class="ml-2 is-size-6">"Peers"</span>
                                     </a>
+                                    <a class="dut-nav-flyout-item" href="/reservations">
+                                        <i class="fa-solid fa-calendar-check fa-lg pr-1" />
+                                        <span class="ml-2 is-size-6">"Reservations"</span>
+                                    </a>
                                     <a class="dut-nav-flyout-item" href="/cleo">
                                         <i class="fa-solid fa-terminal fa-lg pr-1" />
                                         <span class="ml-2 is-size-6">"CLEO"</span>
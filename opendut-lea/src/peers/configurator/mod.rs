@@ -3,6 +3,7 @@ use leptos_router::use_params_map;
 
 use opendut_types::peer::executor::ExecutorDescriptor;
 use opendut_types::peer::PeerId;
+use opendut_types::project::ProjectName;
 
 use crate::app::{ExpectGlobals, use_app_globals};
 use crate::components::{BasePageContainer, Breadcrumb, Initialized, UserInputError, UserInputValue};
@@ -59,6 +60,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                 },
                 is_new: true,
                 executors: Vec::new(),
+                project: ProjectName::default(),
             });
 
             let peer_configuration_resource = create_local_resource(|| {}, move |_| {
@@ -68,6 +70,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                         peer_configuration.update(|user_configuration| {
                             user_configuration.name = UserInputValue::Right(configuration.name.value());
                             user_configuration.is_new = false;
+                            user_configuration.project = configuration.project;
                             user_configuration.location = UserInputValue::Right(configuration.location.unwrap_or_default().value());
                             user_configuration.devices = configuration.topology.devices.into_iter().map(|device| {
                                 create_rw_signal(UserDeviceConfiguration {
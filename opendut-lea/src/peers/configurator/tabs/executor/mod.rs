@@ -1,6 +1,9 @@
-use leptos::{component, create_memo, create_rw_signal, create_slice, IntoView, RwSignal, SignalUpdate, SignalWith, SignalWithUntracked, view};
+use leptos::{component, create_local_resource, create_memo, create_rw_signal, create_slice, IntoView, RwSignal, SignalGet, SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked, view};
+use opendut_types::peer::configuration::ParameterStatus;
 use opendut_types::peer::executor::container::Engine;
+use opendut_types::ShortName;
 
+use crate::app::{ExpectGlobals, use_app_globals};
 use crate::components::UserInputValue;
 use crate::peers::configurator::tabs::executor::executor_panel::ExecutorPanel;
 use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserPeerConfiguration, UserPeerExecutor};
@@ -12,6 +15,56 @@ pub fn ExecutorTab(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl
     view! {
         <div>
             <ExecutorTable peer_configuration />
+            <ExecutorStatus peer_configuration />
+        </div>
+    }
+}
+
+#[component]
+fn ExecutorStatus(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl IntoView {
+
+    let globals = use_app_globals();
+
+    let status_reports = create_local_resource(|| {}, move |_| {
+        let mut carl = globals.expect_client();
+        let peer_id = peer_configuration.get_untracked().id;
+        async move {
+            carl.peers.get_peer_status_reports(peer_id).await
+                .unwrap_or_default()
+        }
+    });
+
+    let rows = move || {
+        status_reports.get().unwrap_or_default().into_iter()
+            .map(|report| {
+                let status = match &report.status {
+                    ParameterStatus::Error(error) => format!("{}: {error}", report.status.short_name()),
+                    status => String::from(status.short_name()),
+                };
+                view! {
+                    <tr>
+                        <td>{ report.id.to_string() }</td>
+                        <td>{ status }</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div class="mt-5">
+            <label class="label">Status reported by peer</label>
+            <table class="table is-fullwidth">
+                <thead>
+                    <tr>
+                        <th>Parameter</th>
+                        <th>Status</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { rows }
+                </tbody>
+            </table>
         </div>
     }
 }
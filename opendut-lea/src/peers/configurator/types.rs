@@ -1,6 +1,7 @@
 use leptos::{RwSignal, SignalGetUntracked};
 
 use opendut_types::peer::executor::ExecutorDescriptor;
+use opendut_types::project::ProjectName;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ExecutorDescriptors, ResultsUrl};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
@@ -46,6 +47,8 @@ pub struct UserPeerConfiguration {
     pub devices: Vec<RwSignal<UserDeviceConfiguration>>,
     pub network: UserPeerNetwork,
     pub executors: Vec<RwSignal<UserPeerExecutor>>,
+    /// The project is not editable yet, but kept when saving a loaded peer.
+    pub project: ProjectName,
     pub is_new: bool,
 }
 
@@ -159,6 +162,7 @@ impl TryFrom<UserPeerConfiguration> for PeerDescriptor {
             executors: ExecutorDescriptors {
                 executors
             },
+            project: configuration.project,
         })
     }
 }
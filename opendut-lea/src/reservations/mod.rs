@@ -0,0 +1,3 @@
+pub use overview::ReservationsOverview;
+
+mod overview;
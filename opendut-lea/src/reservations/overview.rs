@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use leptos::*;
+
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::reservation::{Reservation, ReservationId};
+
+use crate::app::{ExpectGlobals, use_app_globals};
+use crate::components::{BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, Initialized, SimpleButton, Toast, use_toaster};
+
+#[component(transparent)]
+pub fn ReservationsOverview() -> impl IntoView {
+
+    #[component]
+    fn inner() -> impl IntoView {
+
+        let globals = use_app_globals();
+
+        let reservations: Resource<(), Vec<Reservation>> = create_local_resource(|| {}, move |_| {
+            let mut carl = globals.expect_client();
+            async move {
+                carl.reservations.list_reservations().await
+                    .expect("Failed to request the list of reservations.")
+            }
+        });
+
+        let registered_peers: Resource<(), Vec<PeerDescriptor>> = create_local_resource(|| {}, move |_| {
+            let mut carl = globals.expect_client();
+            async move {
+                carl.peers.list_peer_descriptors().await
+                    .expect("Failed to request the list of peers.")
+            }
+        });
+
+        let selected_peer = create_rw_signal(Option::<PeerId>::None);
+        let duration_hours = create_rw_signal(1_i64);
+
+        let create_reservation = create_action(move |(peer_id, duration_hours): &(PeerId, i64)| {
+            let toaster = use_toaster();
+            let mut carl = globals.expect_client();
+            let start = Utc::now();
+            let reservation = Reservation {
+                id: ReservationId::random(),
+                user: String::new(),
+                peers: HashSet::from([*peer_id]),
+                devices: HashSet::new(),
+                start: SystemTime::from(start),
+                end: SystemTime::from(start + Duration::hours(*duration_hours)),
+            };
+            async move {
+                match carl.reservations.create_reservation(reservation).await {
+                    Ok(_) => {
+                        toaster.toast(Toast::builder()
+                            .simple("Successfully created reservation!")
+                            .success()
+                        );
+                    }
+                    Err(error) => {
+                        toaster.toast(Toast::builder()
+                            .simple(format!("Failed to create reservation: {error}"))
+                            .error()
+                        );
+                    }
+                }
+                reservations.refetch();
+            }
+        });
+
+        let delete_reservation = create_action(move |id: &ReservationId| {
+            let toaster = use_toaster();
+            let mut carl = globals.expect_client();
+            let id = Clone::clone(id);
+            async move {
+                match carl.reservations.delete_reservation(id).await {
+                    Ok(_) => {
+                        toaster.toast(Toast::builder()
+                            .simple("Successfully deleted reservation!")
+                            .success()
+                        );
+                    }
+                    Err(error) => {
+                        toaster.toast(Toast::builder()
+                            .simple(format!("Failed to delete reservation: {error}"))
+                            .error()
+                        );
+                    }
+                }
+                reservations.refetch();
+            }
+        });
+
+        let peer_name = move |peer_id: &PeerId| {
+            registered_peers.get()
+                .and_then(|peers| peers.into_iter().find(|peer| peer.id == *peer_id))
+                .map(|peer| peer.name.to_string())
+                .unwrap_or_else(|| peer_id.to_string())
+        };
+
+        let peer_options = move || {
+            registered_peers.get().unwrap_or_default().into_iter()
+                .map(|peer| {
+                    view! {
+                        <option value=peer.id.to_string()>{ peer.name.to_string() }</option>
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let reservation_rows = move || {
+            let mut reservations = reservations.get().unwrap_or_default();
+            reservations.sort_by_key(|reservation| reservation.start);
+
+            reservations.into_iter().map(|reservation| {
+                let reservation_id = reservation.id;
+                let mut claimed = reservation.peers.iter().map(peer_name).collect::<Vec<_>>();
+                claimed.extend(reservation.devices.iter().map(ToString::to_string));
+                claimed.sort();
+
+                view! {
+                    <tr>
+                        <td class="is-vcentered">{ claimed.join(", ") }</td>
+                        <td class="is-vcentered">{ reservation.user }</td>
+                        <td class="is-vcentered">{ format_time(reservation.start) }</td>
+                        <td class="is-vcentered">{ format_time(reservation.end) }</td>
+                        <td class="is-vcentered">
+                            <div class="is-pulled-right">
+                                <IconButton
+                                    icon=FontAwesomeIcon::TrashCan
+                                    color=ButtonColor::White
+                                    size=ButtonSize::Normal
+                                    state=ButtonState::Enabled
+                                    label="Delete Reservation"
+                                    on_action=move || delete_reservation.dispatch(reservation_id)
+                                />
+                            </div>
+                        </td>
+                    </tr>
+                }
+            }).collect::<Vec<_>>()
+        };
+
+        let create_button_state = MaybeSignal::derive(move || {
+            if selected_peer.get().is_some() && duration_hours.get() > 0 {
+                ButtonState::Enabled
+            } else {
+                ButtonState::Disabled
+            }
+        });
+
+        let breadcrumbs = vec![
+            Breadcrumb::new("Dashboard", "/"),
+            Breadcrumb::new("Reservations", "/reservations")
+        ];
+
+        view! {
+            <BasePageContainer
+                title="Reservations"
+                breadcrumbs=breadcrumbs
+                controls=view! {
+                    <IconButton
+                        icon=FontAwesomeIcon::ArrowsRotate
+                        color=ButtonColor::Light
+                        size=ButtonSize::Normal
+                        state=ButtonState::Enabled
+                        label="Refresh table of reservations"
+                        on_action=move || reservations.refetch()
+                    />
+                }
+            >
+                <div class="field is-grouped">
+                    <div class="control">
+                        <div class="select">
+                            <select
+                                aria-label="Peer to reserve"
+                                on:change=move |event| {
+                                    let value = event_target_value(&event);
+                                    selected_peer.set(PeerId::try_from(value.as_str()).ok());
+                                }
+                            >
+                                <option value="">"Select a peer"</option>
+                                { peer_options }
+                            </select>
+                        </div>
+                    </div>
+                    <div class="control">
+                        <input
+                            class="input"
+                            type="number"
+                            min="1"
+                            aria-label="Duration in hours"
+                            prop:value=move || duration_hours.get()
+                            on:input=move |event| {
+                                duration_hours.set(event_target_value(&event).parse().unwrap_or(0));
+                            }
+                        />
+                    </div>
+                    <div class="control">
+                        <SimpleButton
+                            text="Reserve"
+                            color=ButtonColor::Info
+                            state=create_button_state
+                            on_action=move || {
+                                if let Some(peer_id) = selected_peer.get() {
+                                    create_reservation.dispatch((peer_id, duration_hours.get()));
+                                }
+                            }
+                        />
+                    </div>
+                </div>
+                <div class="mt-4">
+                    <Transition
+                        fallback=move || view! { <p>"Loading..."</p> }
+                    >
+                        <table class="table is-hoverable is-fullwidth">
+                            <thead>
+                                <tr>
+                                    <th>"Reserved"</th>
+                                    <th>"User"</th>
+                                    <th>"Start"</th>
+                                    <th>"End"</th>
+                                    <th class="is-narrow">"Action"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { reservation_rows }
+                            </tbody>
+                        </table>
+                    </Transition>
+                </div>
+            </BasePageContainer>
+        }
+    }
+
+    view! {
+        <Initialized>
+            <Inner />
+        </Initialized>
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M").to_string()
+}
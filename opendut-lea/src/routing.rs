@@ -63,6 +63,7 @@ mod routes {
     use crate::error::ErrorPage;
     use crate::licenses::LicensesOverview;
     use crate::peers::{PeerConfigurator, PeersOverview};
+    use crate::reservations::ReservationsOverview;
     use crate::routing::NotFound;
     use crate::user::UserOverview;
     use crate::about::AboutOverview;
@@ -79,6 +80,7 @@ mod routes {
                         <Route path="/clusters/:id/configure/:tab" view=|| view! { <ClusterConfigurator /> } />
                         <Route path="/peers" view=|| view! { <PeersOverview /> } />
                         <Route path="/peers/:id/configure/:tab" view=|| view! { <PeerConfigurator /> } />
+                        <Route path="/reservations" view=|| view! { <ReservationsOverview /> } />
                         <Route path="/cleo" view=|| view! { <CleoSetup /> } />
                         <Route path="/user" view=|| view! { <UserOverview /> } />
                         <Route path="/licenses" view=|| view! { <LicensesOverview /> } />
@@ -9,12 +9,16 @@ fn main() -> Result<()> {
         "proto/opendut/types/peer/configuration.proto",
         "proto/opendut/types/peer/executor/executor.proto",
         "proto/opendut/types/peer/executor/container.proto",
+        "proto/opendut/types/project/project.proto",
+        "proto/opendut/types/reservation/reservation.proto",
+        "proto/opendut/types/service_account/service_account.proto",
         "proto/opendut/types/topology/device.proto",
         "proto/opendut/types/topology/topology.proto",
         "proto/opendut/types/util/metadata.proto",
         "proto/opendut/types/util/net.proto",
         "proto/opendut/types/util/uuid.proto",
         "proto/opendut/types/vpn/vpn.proto",
+        "proto/opendut/types/webhook/webhook.proto",
         "proto/opendut/types/cleo/cleo.proto"
     ];
 
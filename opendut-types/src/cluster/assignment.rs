@@ -19,3 +19,10 @@ pub struct PeerClusterAssignment {
     pub can_server_port: Port,
     pub device_interfaces: Vec<NetworkInterfaceDescriptor>,
 }
+
+/// The CAN server ports held by the members of a deployed cluster.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClusterPortAllocation {
+    pub id: ClusterId,
+    pub can_server_ports: Vec<Port>,
+}
@@ -6,15 +6,19 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub use assignment::*;
+pub use selector::*;
 
 use crate::peer::PeerId;
+use crate::project::ProjectName;
 use crate::topology::DeviceId;
 
 mod assignment;
+mod selector;
 pub mod state;
 
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct ClusterId(pub Uuid);
 
@@ -64,6 +68,7 @@ impl fmt::Display for ClusterId {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClusterName(pub(crate) String);
 
 impl ClusterName {
@@ -144,11 +149,17 @@ impl fmt::Display for ClusterName {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClusterConfiguration {
     pub id: ClusterId,
     pub name: ClusterName,
     pub leader: PeerId,
     pub devices: HashSet<DeviceId>,
+    /// Selectors, which are resolved to further devices, when the cluster is deployed.
+    pub device_selectors: Vec<DeviceSelector>,
+    /// The project, which the cluster belongs to. All of its devices must belong to the same project.
+    #[serde(default)]
+    pub project: ProjectName,
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
@@ -160,8 +171,13 @@ pub enum IllegalClusterConfiguration {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClusterDeployment {
     pub id: ClusterId,
+    /// The devices of the cluster, as resolved by CARL from the configured devices and device selectors when deploying.
+    pub devices: HashSet<DeviceId>,
+    /// The user, who deployed the cluster. Set by CARL.
+    pub deployed_by: String,
 }
 
 
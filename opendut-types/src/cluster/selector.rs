@@ -0,0 +1,158 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::peer::{PeerDescriptor, PeerLocation};
+use crate::topology::{DeviceDescriptor, DeviceTag};
+
+/// Selects all devices, which fulfill each of its conditions.
+///
+/// Written as conditions joined by `AND`, e.g. `tag=brake-ecu AND location=Lab2`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DeviceSelector {
+    pub conditions: Vec<DeviceSelectorCondition>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DeviceSelectorCondition {
+    /// The device carries the tag.
+    Tag(DeviceTag),
+    /// The device is attached to a peer at the location.
+    Location(PeerLocation),
+}
+
+impl DeviceSelector {
+    const CONDITION_SEPARATOR: &'static str = " AND ";
+
+    pub fn matches(&self, device: &DeviceDescriptor, peer: &PeerDescriptor) -> bool {
+        self.conditions.iter().all(|condition| match condition {
+            DeviceSelectorCondition::Tag(tag) => device.tags.contains(tag),
+            DeviceSelectorCondition::Location(location) => peer.location.as_ref() == Some(location),
+        })
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalDeviceSelector {
+    #[error("Device selector '{value}' contains no conditions.")]
+    Empty { value: String },
+    #[error("Condition '{condition}' of device selector '{value}' is not of the form 'key=value'.")]
+    MalformedCondition { value: String, condition: String },
+    #[error("Condition '{condition}' of device selector '{value}' uses unknown key '{key}'. Expected 'tag' or 'location'.")]
+    UnknownKey { value: String, condition: String, key: String },
+    #[error("Condition '{condition}' of device selector '{value}' is invalid: {cause}")]
+    InvalidValue { value: String, condition: String, cause: String },
+}
+
+impl TryFrom<&str> for DeviceSelector {
+    type Error = IllegalDeviceSelector;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.trim().is_empty() {
+            return Err(IllegalDeviceSelector::Empty { value: value.to_owned() });
+        }
+
+        let conditions = value.split(Self::CONDITION_SEPARATOR)
+            .map(str::trim)
+            .map(|condition| {
+                let (key, condition_value) = condition.split_once('=')
+                    .ok_or_else(|| IllegalDeviceSelector::MalformedCondition { value: value.to_owned(), condition: condition.to_owned() })?;
+                let invalid_value = |cause: String| IllegalDeviceSelector::InvalidValue { value: value.to_owned(), condition: condition.to_owned(), cause };
+
+                match key.trim() {
+                    "tag" => DeviceTag::try_from(condition_value.trim())
+                        .map(DeviceSelectorCondition::Tag)
+                        .map_err(|cause| invalid_value(cause.to_string())),
+                    "location" => PeerLocation::try_from(condition_value.trim())
+                        .map(DeviceSelectorCondition::Location)
+                        .map_err(|cause| invalid_value(cause.to_string())),
+                    key => Err(IllegalDeviceSelector::UnknownKey { value: value.to_owned(), condition: condition.to_owned(), key: key.to_owned() }),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { conditions })
+    }
+}
+
+impl TryFrom<String> for DeviceSelector {
+    type Error = IllegalDeviceSelector;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        DeviceSelector::try_from(value.as_str())
+    }
+}
+
+impl FromStr for DeviceSelector {
+    type Err = IllegalDeviceSelector;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        DeviceSelector::try_from(value)
+    }
+}
+
+impl fmt::Display for DeviceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let conditions = self.conditions.iter()
+            .map(|condition| match condition {
+                DeviceSelectorCondition::Tag(tag) => format!("tag={tag}"),
+                DeviceSelectorCondition::Location(location) => format!("location={location}"),
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", conditions.join(Self::CONDITION_SEPARATOR))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Not;
+
+    use googletest::prelude::*;
+
+    use crate::peer::{PeerId, PeerName, PeerNetworkDescriptor};
+    use crate::peer::executor::ExecutorDescriptors;
+    use crate::project::ProjectName;
+    use crate::topology::{DeviceId, DeviceName, Topology};
+    use crate::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+
+    use super::*;
+
+    #[test]
+    fn should_parse_and_match_devices_by_tag_and_location() -> Result<()> {
+        let selector = DeviceSelector::try_from("tag=brake-ecu AND location=Lab2").expect("Selector should be valid.");
+        assert_that!(selector.to_string(), eq("tag=brake-ecu AND location=Lab2"));
+
+        let device = |tag: &str| DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from("Device").unwrap(),
+            description: None,
+            interface: NetworkInterfaceDescriptor {
+                name: NetworkInterfaceName::try_from("eth0").unwrap(),
+                configuration: NetworkInterfaceConfiguration::Ethernet,
+            },
+            tags: vec![DeviceTag::try_from(tag).unwrap()],
+        };
+        let peer = |location: &str| PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("Peer").unwrap(),
+            location: PeerLocation::try_from(location).ok(),
+            network: PeerNetworkDescriptor::default(),
+            topology: Topology::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            project: ProjectName::default(),
+        };
+
+        assert!(selector.matches(&device("brake-ecu"), &peer("Lab2")));
+        assert!(selector.matches(&device("brake-ecu"), &peer("Lab1")).not());
+        assert!(selector.matches(&device("steering-ecu"), &peer("Lab2")).not());
+
+        assert_that!(DeviceSelector::try_from(""), err(matches_pattern!(IllegalDeviceSelector::Empty { .. })));
+        assert_that!(DeviceSelector::try_from("tag"), err(matches_pattern!(IllegalDeviceSelector::MalformedCondition { .. })));
+        assert_that!(DeviceSelector::try_from("color=red"), err(matches_pattern!(IllegalDeviceSelector::UnknownKey { key: eq("color") })));
+
+        Ok(())
+    }
+}
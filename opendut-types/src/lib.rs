@@ -2,9 +2,13 @@ use uuid::{Uuid, uuid};
 
 pub mod cluster;
 pub mod peer;
+pub mod project;
 pub mod proto;
+pub mod reservation;
+pub mod service_account;
 pub mod topology;
 pub mod vpn;
+pub mod webhook;
 pub mod util;
 pub mod resources;
 pub mod cleo;
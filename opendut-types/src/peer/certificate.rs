@@ -0,0 +1,69 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+use crate::util::net::Certificate;
+
+/// Serial number of a client certificate, which CARL issued for a peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PeerCertificateId(pub Uuid);
+
+impl PeerCertificateId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PeerCertificateId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for PeerCertificateId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Client certificate and private key, with which EDGAR authenticates itself via mutual TLS, when it opens the peer messaging stream.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerCertificate {
+    pub certificate: Certificate,
+    pub key: PeerCertificateKey,
+}
+
+/// PKCS#8-encoded private key of a [`PeerCertificate`].
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerCertificateKey(pub pem::Pem);
+
+impl PeerCertificateKey {
+    pub fn encode_as_string(&self) -> String {
+        let encode_config = pem::EncodeConfig::default()
+            .set_line_ending(pem::LineEnding::LF);
+
+        pem::encode_config(&self.0, encode_config)
+    }
+}
+
+impl fmt::Debug for PeerCertificateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("PeerCertificateKey([redacted])")
+    }
+}
+
+/// CARL's record of a client certificate issued for a peer. Certificates without such a record are rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IssuedPeerCertificate {
+    pub id: PeerCertificateId,
+    pub peer_id: PeerId,
+    /// Hex-encoded SHA-256 hash of the DER-encoded certificate.
+    pub fingerprint: String,
+    pub issued_at: SystemTime,
+    pub expires_at: SystemTime,
+}
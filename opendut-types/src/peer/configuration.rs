@@ -1,11 +1,12 @@
 use std::any::Any;
+use std::fmt::{Display, Formatter};
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::cluster::ClusterAssignment;
-use crate::OPENDUT_UUID_NAMESPACE;
+use crate::cluster::{ClusterAssignment, ClusterId};
+use crate::{OPENDUT_UUID_NAMESPACE, ShortName};
 use crate::peer::executor::{ExecutorDescriptor, ExecutorKind};
 use crate::util::net::NetworkInterfaceName;
 
@@ -47,15 +48,129 @@ pub struct Parameter<V: ParameterValue> {
     pub value: V,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ParameterId(pub Uuid);
 
+impl Display for ParameterId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParameterTarget {
     Present,
     Absent,
 }
 
+/// Feedback from a peer, how far it has applied its [`PeerConfiguration2`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerConfigurationState {
+    pub executors: Vec<ParameterState<ExecutorDescriptor>>,
+    /// Outcome of tearing down the cluster, which the peer was removed from with its last [`PeerConfiguration`].
+    /// `None`, if the peer did not leave a cluster.
+    pub cluster_teardown: Option<ClusterTeardownState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClusterTeardownState {
+    Succeeded { cluster_id: ClusterId },
+    Failed { cluster_id: ClusterId, cause: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterState<V: ParameterValue> {
+    pub id: ParameterId,
+    pub state: ParameterStateKind,
+    pub value: V,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParameterStateKind {
+    Present,
+    Absent,
+    WaitingForDependencies(Vec<ParameterId>),
+    Error(ParameterStateError),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterStateError {
+    CreatingFailed(String),
+    RemovingFailed(String),
+}
+
+impl Display for ParameterStateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterStateError::CreatingFailed(message) => write!(f, "Creating failed: {message}"),
+            ParameterStateError::RemovingFailed(message) => write!(f, "Removing failed: {message}"),
+        }
+    }
+}
+
+/// Status of a parameter, as presented to users.
+/// Combines the target, which CARL requested from the peer, with the state reported back by the peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterStatusReport {
+    pub id: ParameterId,
+    pub status: ParameterStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterStatus {
+    Present,
+    Creating,
+    Absent,
+    Removing,
+    WaitingForDependencies(Vec<ParameterId>),
+    Error(ParameterStateError),
+}
+
+impl ParameterStatus {
+    /// Determines the status of a parameter from its target and the state reported by the peer, if any.
+    pub fn determine(target: ParameterTarget, reported_state: Option<&ParameterStateKind>) -> Self {
+        match (target, reported_state) {
+            (_, Some(ParameterStateKind::WaitingForDependencies(dependencies))) => ParameterStatus::WaitingForDependencies(Clone::clone(dependencies)),
+            (_, Some(ParameterStateKind::Error(error))) => ParameterStatus::Error(Clone::clone(error)),
+            (ParameterTarget::Present, Some(ParameterStateKind::Present)) => ParameterStatus::Present,
+            (ParameterTarget::Present, Some(ParameterStateKind::Absent) | None) => ParameterStatus::Creating,
+            (ParameterTarget::Absent, Some(ParameterStateKind::Absent)) => ParameterStatus::Absent,
+            (ParameterTarget::Absent, Some(ParameterStateKind::Present) | None) => ParameterStatus::Removing,
+        }
+    }
+}
+
+impl ShortName for ParameterStatus {
+    fn short_name(&self) -> &'static str {
+        match self {
+            ParameterStatus::Present => "Present",
+            ParameterStatus::Creating => "Creating",
+            ParameterStatus::Absent => "Absent",
+            ParameterStatus::Removing => "Removing",
+            ParameterStatus::WaitingForDependencies(_) => "WaitingForDependencies",
+            ParameterStatus::Error(_) => "Error",
+        }
+    }
+}
+
+impl PeerConfiguration2 {
+    /// Creates a status report for each parameter of this configuration, based on the state reported by the peer.
+    pub fn status_reports(&self, state: &PeerConfigurationState) -> Vec<ParameterStatusReport> {
+        self.executors.iter()
+            .map(|parameter| {
+                let reported_state = state.executors.iter()
+                    .find(|executor_state| executor_state.id == parameter.id)
+                    .map(|executor_state| &executor_state.state);
+
+                ParameterStatusReport {
+                    id: parameter.id,
+                    status: ParameterStatus::determine(parameter.target, reported_state),
+                }
+            })
+            .collect()
+    }
+}
+
 pub trait ParameterValue: Any + Hash {
     /// Unique identifier, which is ideally stable, too.
     /// A naive implementation for a `self` implementing `Hash` could look like this:
@@ -99,6 +214,8 @@ impl ParameterValue for ExecutorDescriptor {
 
 #[cfg(test)]
 mod tests {
+    use crate::peer::executor::ResultsUrl;
+
     use super::*;
 
     #[test]
@@ -118,4 +235,46 @@ mod tests {
         assert_eq!(executor_target.value, value);
         assert_eq!(executor_target.target, target);
     }
+
+    #[test]
+    fn create_status_reports_from_reported_peer_configuration_state() {
+        let mut peer_configuration = PeerConfiguration2::default();
+
+        let executor_present = ExecutorDescriptor {
+            kind: ExecutorKind::Executable,
+            results_url: None
+        };
+        let executor_absent = ExecutorDescriptor {
+            kind: ExecutorKind::Executable,
+            results_url: Some(ResultsUrl::try_from("https://example.com/results").unwrap()),
+        };
+        peer_configuration.insert_executor(executor_present.clone(), ParameterTarget::Present);
+        peer_configuration.insert_executor(executor_absent.clone(), ParameterTarget::Absent);
+
+        let id_present = executor_present.parameter_identifier();
+        let id_absent = executor_absent.parameter_identifier();
+
+        assert_eq!(
+            peer_configuration.status_reports(&PeerConfigurationState::default()),
+            vec![
+                ParameterStatusReport { id: id_present, status: ParameterStatus::Creating },
+                ParameterStatusReport { id: id_absent, status: ParameterStatus::Removing },
+            ]
+        );
+
+        let state = PeerConfigurationState {
+            executors: vec![
+                ParameterState { id: id_present, state: ParameterStateKind::Error(ParameterStateError::CreatingFailed(String::from("Image not found."))), value: executor_present },
+                ParameterState { id: id_absent, state: ParameterStateKind::Absent, value: executor_absent },
+            ],
+            cluster_teardown: None,
+        };
+        assert_eq!(
+            peer_configuration.status_reports(&state),
+            vec![
+                ParameterStatusReport { id: id_present, status: ParameterStatus::Error(ParameterStateError::CreatingFailed(String::from("Image not found."))) },
+                ParameterStatusReport { id: id_absent, status: ParameterStatus::Absent },
+            ]
+        );
+    }
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum Engine {
     Docker,
@@ -35,6 +36,7 @@ impl CommandName for Engine {
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ContainerName {
     #[default]
@@ -116,6 +118,7 @@ impl fmt::Display for ContainerName {
 
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContainerEnvironmentVariable {
     name: String,
     value: String,
@@ -164,6 +167,7 @@ where
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContainerImage(String);
 
 impl ContainerImage {
@@ -232,6 +236,7 @@ impl fmt::Display for ContainerImage {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContainerVolume(String);
 
 impl ContainerVolume {
@@ -287,6 +292,7 @@ impl fmt::Display for ContainerVolume {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContainerDevice(String);
 
 impl ContainerDevice {
@@ -342,6 +348,7 @@ impl fmt::Display for ContainerDevice {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContainerPortSpec(String);
 
 impl ContainerPortSpec{
@@ -397,6 +404,7 @@ impl fmt::Display for ContainerPortSpec {
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ContainerCommand {
     #[default]
@@ -460,6 +468,7 @@ impl fmt::Display for ContainerCommand {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContainerCommandArgument(String);
 
 impl ContainerCommandArgument {
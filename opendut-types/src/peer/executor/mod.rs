@@ -9,11 +9,13 @@ use crate::peer::executor::container::{Engine, ContainerName, ContainerImage, Co
 pub mod container;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ExecutorDescriptors {
     pub executors: Vec<ExecutorDescriptor>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct ExecutorDescriptor {
     #[serde(flatten)]
@@ -22,6 +24,7 @@ pub struct ExecutorDescriptor {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub enum ExecutorKind {
     Executable,
@@ -48,6 +51,7 @@ pub enum ExecutorKind {
 
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ResultsUrl(Url);
 
 impl ResultsUrl {
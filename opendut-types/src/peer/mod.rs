@@ -1,5 +1,6 @@
 use std::fmt;
 use std::ops::Not;
+use std::time::SystemTime;
 
 use base64::Engine;
 use base64::prelude::BASE64_URL_SAFE;
@@ -7,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
+use crate::peer::certificate::PeerCertificate;
 use crate::peer::executor::ExecutorDescriptors;
+use crate::peer::setup::PeerSetupNonce;
+use crate::project::ProjectName;
 use crate::topology::Topology;
 use crate::util::net::{Certificate, NetworkInterfaceDescriptor, AuthConfig, NetworkInterfaceName};
 use crate::vpn::VpnPeerConfiguration;
@@ -15,8 +19,11 @@ use crate::vpn::VpnPeerConfiguration;
 pub mod state;
 pub mod executor;
 pub mod configuration;
+pub mod setup;
+pub mod certificate;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct PeerId { pub uuid: Uuid }
 
@@ -63,6 +70,7 @@ impl fmt::Display for PeerId {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PeerName(pub(crate) String);
 
 impl PeerName {
@@ -149,6 +157,7 @@ impl fmt::Display for PeerName {
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PeerLocation(pub(crate) String);
 
 impl PeerLocation {
@@ -218,6 +227,7 @@ impl fmt::Display for PeerLocation {
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PeerNetworkDescriptor {
     pub interfaces: Vec<NetworkInterfaceDescriptor>,
     pub bridge_name: Option<NetworkInterfaceName>,
@@ -230,6 +240,7 @@ impl PeerNetworkDescriptor {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PeerDescriptor {
     pub id: PeerId,
     pub name: PeerName,
@@ -237,6 +248,9 @@ pub struct PeerDescriptor {
     pub network: PeerNetworkDescriptor,
     pub topology: Topology,
     pub executors: ExecutorDescriptors,
+    /// The project, which the peer and its devices belong to.
+    #[serde(default)]
+    pub project: ProjectName,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -246,9 +260,20 @@ pub struct PeerSetup {
     pub ca: Certificate,
     pub auth_config: AuthConfig,
     pub vpn: VpnPeerConfiguration,
+    /// Marks the setup-string as used, when EDGAR first connects to CARL.
+    pub nonce: PeerSetupNonce,
+    pub issued_at: SystemTime,
+    /// After this time, the setup-string cannot be used to enrol the peer anymore.
+    pub expires_at: SystemTime,
+    /// Client certificate for authenticating the peer via mutual TLS.
+    pub certificate: PeerCertificate,
 }
 
 impl PeerSetup {
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at <= time
+    }
+
     pub fn encode(&self) -> Result<String, PeerSetupEncodeError> {
         let json = serde_json::to_string(self).map_err(|cause| PeerSetupEncodeError {
             details: format!("Serialization failed due to: {}", cause),
@@ -307,6 +332,8 @@ pub struct PeerSetupDecodeError {
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
+    use std::time::Duration;
+
     use googletest::prelude::*;
     use pem::Pem;
     use uuid::Uuid;
@@ -314,6 +341,7 @@ mod tests {
     use crate::vpn::netbird::SetupKey;
 
     use super::*;
+    use crate::peer::certificate::PeerCertificateKey;
     use crate::util::net::{ClientId, ClientSecret, OAuthScope};
 
     #[test]
@@ -332,10 +360,17 @@ mod tests {
                 management_url: Url::parse("https://netbird.opendut.local/api")?,
                 setup_key: SetupKey::from(Uuid::parse_str("d79c202f-bbbf-4997-844e-678f27606e1c")?),
             },
+            nonce: PeerSetupNonce::try_from("5a5ac8b3-0a8c-4e0f-8f5b-1b8e8e4c3d7a").unwrap(),
+            issued_at: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_086_400),
+            certificate: PeerCertificate {
+                certificate: Certificate(Pem::new("CERTIFICATE", vec![])),
+                key: PeerCertificateKey(Pem::new("PRIVATE KEY", vec![])),
+            },
         };
 
         let encoded = setup.encode()?;
-        assert_that!(encoded, eq("F8sBABwHzrk8VikIvG691CJ7W1Stsq0oZqoIe8UvyoH_PRFpnb4ullM3wV7g8UFoTRxt-svCaEwMLLSAA7DMXeWhPNSyiLPbPh7iH2lyQsi01GXIkGU-g5HSILFKIJFpVniZZ0XMabK49sS7TWmybMacHztb1vGBJFsITgY9fIGLFdBn_YygcXHdu6CJx17_kdGTXHZEVbbx5_p3wJh2xZP1U-w47d2wcUQUC-fE_u3xqINxDkbE8MD44QUiPdzFwtIZcYdvd7jxsT3zrsw9ho2cozsAmNJrznkEXI_FHmRUPZj8BvjFuCsrVnFuCovbd3TFQ-Q-yZRQJVKBGx9MkngEYwo4H0rlnXCFzKgC"));
+        assert_that!(encoded, eq("FyEDAJwHtnO0lSEVBNODzkMxy5lZ1R7JnQfyhar-CHvwOTqXqLJah6WrbxWFTd0ESyAMscvkd4vWoi4qjOYESzTwSLpcss3GioI9ooEl7qbnp0cs6B8frAaAsbn62glFWmGwNrCPzrC3WUprBylpQJB03gLmbQoXVVlMhttVdatpRbCrbHAw4MMXvgsK4_N4T5gX43drCOl2Xc6CkcIHLzuCIhr49Ty7wShvyku2h7TRYY93F40IYsExUWdj7MtcOAYFwO5F51s_DKROuqj0dMJ0JbdjPvlhe8rvgeiRX2AEomsEUMb-cD_uAQf7ck0yKPZcfkL8RG_Mi346rirK21e5KS_A0PbijKvMOVeGvm_ZhVDYtF11bWOaYgX9q3tGfQiIKSbpsqdJnTAUU9nVmGlzV7oSxA9tQkipYJauSLNLkcuM8E-zcjzIEmxrmlt_2Kf9QSU1_4GbC8xI7ZrQz0G4DS4NmdSfq_N4HSgMTujCgSHyz2yK82DtFeMH"));
 
         let decoded = PeerSetup::decode(&encoded)?;
         assert_that!(decoded, eq(setup));
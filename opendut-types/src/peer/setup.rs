@@ -0,0 +1,89 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+use crate::util::net::ClientId;
+
+/// Unique value embedded into each setup-string, by which CARL tracks whether the setup-string was already used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PeerSetupNonce(pub Uuid);
+
+impl PeerSetupNonce {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PeerSetupNonce {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal PeerSetupNonce: {value}")]
+pub struct IllegalPeerSetupNonce {
+    pub value: String,
+}
+
+impl TryFrom<&str> for PeerSetupNonce {
+    type Error = IllegalPeerSetupNonce;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalPeerSetupNonce { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for PeerSetupNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// CARL's record of a setup-string handed out for a peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IssuedPeerSetup {
+    pub nonce: PeerSetupNonce,
+    pub peer_id: PeerId,
+    pub issued_at: SystemTime,
+    pub expires_at: SystemTime,
+    pub state: IssuedPeerSetupState,
+    /// OIDC client registered for the peer along with the setup-string. Only this client may open the peer's messaging stream.
+    /// Not set, if authentication was disabled when the setup-string was issued.
+    pub client_id: Option<ClientId>,
+}
+
+impl IssuedPeerSetup {
+    /// Whether the setup-string may not be used anymore to enrol its peer at the given time.
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at <= time
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IssuedPeerSetupState {
+    /// Not yet used by EDGAR.
+    Outstanding,
+    /// Used by EDGAR when it first connected to CARL.
+    /// Bound to the client certificate EDGAR connected with, so that nobody else can reuse the setup-string.
+    /// Hex-encoded SHA-256 hash of the DER-encoded certificate, which follows renewals of the certificate.
+    Consumed { at: SystemTime, certificate_fingerprint: String },
+    /// Revoked by an administrator. EDGAR cannot connect with it anymore.
+    Revoked { at: SystemTime },
+}
+
+impl fmt::Display for IssuedPeerSetupState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssuedPeerSetupState::Outstanding => write!(f, "outstanding"),
+            IssuedPeerSetupState::Consumed { .. } => write!(f, "consumed"),
+            IssuedPeerSetupState::Revoked { .. } => write!(f, "revoked"),
+        }
+    }
+}
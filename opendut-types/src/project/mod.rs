@@ -0,0 +1,95 @@
+use std::fmt;
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+
+/// Name of a project, which isolates the peers, devices and clusters of a team from those of other teams.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProjectName(pub(crate) String);
+
+impl ProjectName {
+
+    pub const MIN_LENGTH: usize = 2;
+    pub const MAX_LENGTH: usize = 64;
+
+    /// Name of the project, which resources belong to, if no project was specified.
+    pub const DEFAULT: &'static str = "default";
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+impl Default for ProjectName {
+    fn default() -> Self {
+        Self(String::from(Self::DEFAULT))
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalProjectName {
+    #[error("Project name '{value}' is too short. Expected at least {expected} characters, got {actual}.")]
+    TooShort { value: String, expected: usize, actual: usize },
+    #[error("Project name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Project name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+    #[error("Project name '{value}' contains invalid start or end characters.")]
+    InvalidStartEndCharacter { value: String },
+}
+
+impl From<ProjectName> for String {
+    fn from(value: ProjectName) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for ProjectName {
+
+    type Error = IllegalProjectName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalProjectName::TooShort {
+                value,
+                expected: Self::MIN_LENGTH,
+                actual: length,
+            })
+        }
+        else if length > Self::MAX_LENGTH {
+            Err(IllegalProjectName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        }
+        else if crate::util::invalid_start_and_end_of_a_name(&value) {
+            Err(IllegalProjectName::InvalidStartEndCharacter { value })
+        }
+        else if value.chars().any(|c| crate::util::valid_characters_in_name(&c).not()) {
+            Err(IllegalProjectName::InvalidCharacter {
+                value
+            })
+        }
+        else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ProjectName {
+
+    type Error = IllegalProjectName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ProjectName::try_from(value.to_owned())
+    }
+}
+
+impl fmt::Display for ProjectName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -59,6 +59,10 @@ impl From<crate::cluster::ClusterConfiguration> for ClusterConfiguration {
             devices: configuration.devices.into_iter()
                         .map(DeviceId::from)
                         .collect(),
+            device_selectors: configuration.device_selectors.into_iter()
+                        .map(DeviceSelector::from)
+                        .collect(),
+            project: Some(configuration.project.into()),
         }
     }
 }
@@ -81,6 +85,12 @@ impl TryFrom<ClusterConfiguration> for crate::cluster::ClusterConfiguration {
             .ok_or(ErrorBuilder::field_not_set("leader"))?
             .try_into()?;
 
+        // Configurations stored before projects were introduced belong to the default project.
+        let project = configuration.project
+            .map(crate::project::ProjectName::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(Self {
             id: cluster_id,
             name: cluster_name,
@@ -88,6 +98,54 @@ impl TryFrom<ClusterConfiguration> for crate::cluster::ClusterConfiguration {
             devices: configuration.devices.into_iter()
                         .map(DeviceId::try_into)
                         .collect::<Result<_, _>>()?,
+            device_selectors: configuration.device_selectors.into_iter()
+                        .map(DeviceSelector::try_into)
+                        .collect::<Result<_, _>>()?,
+            project,
+        })
+    }
+}
+
+impl From<crate::cluster::DeviceSelector> for DeviceSelector {
+    fn from(selector: crate::cluster::DeviceSelector) -> Self {
+        Self {
+            conditions: selector.conditions.into_iter()
+                .map(|condition| {
+                    let condition = match condition {
+                        crate::cluster::DeviceSelectorCondition::Tag(tag) => device_selector_condition::Condition::Tag(tag.into()),
+                        crate::cluster::DeviceSelectorCondition::Location(location) => device_selector_condition::Condition::Location(location.into()),
+                    };
+                    DeviceSelectorCondition { condition: Some(condition) }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<DeviceSelector> for crate::cluster::DeviceSelector {
+    type Error = ConversionError;
+
+    fn try_from(selector: DeviceSelector) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<DeviceSelector, crate::cluster::DeviceSelector>;
+
+        let conditions = selector.conditions.into_iter()
+            .map(|condition| {
+                let condition = condition.condition
+                    .ok_or(ErrorBuilder::field_not_set("condition"))?;
+                let condition = match condition {
+                    device_selector_condition::Condition::Tag(tag) => crate::cluster::DeviceSelectorCondition::Tag(tag.try_into()?),
+                    device_selector_condition::Condition::Location(location) => crate::cluster::DeviceSelectorCondition::Location(location.try_into()?),
+                };
+                Ok(condition)
+            })
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+
+        if conditions.is_empty() {
+            return Err(ErrorBuilder::message("Device selector contains no conditions."));
+        }
+
+        Ok(Self {
+            conditions,
         })
     }
 }
@@ -96,6 +154,10 @@ impl From<crate::cluster::ClusterDeployment> for ClusterDeployment {
     fn from(deployment: crate::cluster::ClusterDeployment) -> Self {
         Self {
             id: Some(deployment.id.into()),
+            devices: deployment.devices.into_iter()
+                .map(DeviceId::from)
+                .collect(),
+            deployed_by: deployment.deployed_by,
         }
     }
 }
@@ -112,6 +174,10 @@ impl TryFrom<ClusterDeployment> for crate::cluster::ClusterDeployment {
 
         Ok(Self {
             id: cluster_id,
+            devices: deployment.devices.into_iter()
+                .map(DeviceId::try_into)
+                .collect::<Result<_, _>>()?,
+            deployed_by: deployment.deployed_by,
         })
     }
 }
@@ -262,6 +328,36 @@ impl TryFrom<PeerClusterAssignment> for crate::cluster::PeerClusterAssignment {
     }
 }
 
+impl From<crate::cluster::ClusterPortAllocation> for ClusterPortAllocation {
+    fn from(allocation: crate::cluster::ClusterPortAllocation) -> Self {
+        Self {
+            id: Some(allocation.id.into()),
+            can_server_ports: allocation.can_server_ports.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<ClusterPortAllocation> for crate::cluster::ClusterPortAllocation {
+    type Error = ConversionError;
+
+    fn try_from(allocation: ClusterPortAllocation) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ClusterPortAllocation, crate::cluster::ClusterPortAllocation>;
+
+        let cluster_id: crate::cluster::ClusterId = allocation.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let can_server_ports = allocation.can_server_ports.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            id: cluster_id,
+            can_server_ports,
+        })
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod test {
@@ -319,3 +415,21 @@ mod test {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn A_DeviceSelector_without_conditions_should_be_rejected() -> Result<()> {
+
+        let proto = DeviceSelector { conditions: vec![] };
+
+        assert_that!(crate::cluster::DeviceSelector::try_from(proto), err(anything()));
+
+        Ok(())
+    }
+}
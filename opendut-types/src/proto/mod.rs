@@ -1,8 +1,12 @@
 pub mod cluster;
 pub mod peer;
+pub mod project;
+pub mod reservation;
+pub mod service_account;
 pub mod topology;
 pub mod util;
 pub mod vpn;
+pub mod webhook;
 pub mod cleo;
 
 use std::marker::PhantomData;
@@ -152,3 +152,208 @@ impl From<peer_configuration_parameter::Target> for crate::peer::configuration::
         }
     }
 }
+
+
+impl From<crate::peer::configuration::PeerConfigurationState> for PeerState {
+    fn from(value: crate::peer::configuration::PeerConfigurationState) -> Self {
+        Self {
+            executors: value.executors.into_iter().map(PeerConfigurationParameterStateExecutor::from).collect(),
+            cluster_teardown: value.cluster_teardown.map(ClusterTeardownState::from),
+        }
+    }
+}
+impl TryFrom<PeerState> for crate::peer::configuration::PeerConfigurationState {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerState) -> Result<Self, Self::Error> {
+        Ok(crate::peer::configuration::PeerConfigurationState {
+            executors: value.executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            cluster_teardown: value.cluster_teardown.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<crate::peer::configuration::ClusterTeardownState> for ClusterTeardownState {
+    fn from(value: crate::peer::configuration::ClusterTeardownState) -> Self {
+        match value {
+            crate::peer::configuration::ClusterTeardownState::Succeeded { cluster_id } => Self {
+                cluster_id: Some(cluster_id.into()),
+                result: Some(cluster_teardown_state::Result::Succeeded(ClusterTeardownStateSucceeded {})),
+            },
+            crate::peer::configuration::ClusterTeardownState::Failed { cluster_id, cause } => Self {
+                cluster_id: Some(cluster_id.into()),
+                result: Some(cluster_teardown_state::Result::Failed(ClusterTeardownStateFailed { cause })),
+            },
+        }
+    }
+}
+impl TryFrom<ClusterTeardownState> for crate::peer::configuration::ClusterTeardownState {
+    type Error = ConversionError;
+
+    fn try_from(value: ClusterTeardownState) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ClusterTeardownState, crate::peer::configuration::ClusterTeardownState>;
+
+        let cluster_id: crate::cluster::ClusterId = value.cluster_id
+            .ok_or(ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+
+        let state = match value.result.ok_or(ErrorBuilder::field_not_set("result"))? {
+            cluster_teardown_state::Result::Succeeded(_) => crate::peer::configuration::ClusterTeardownState::Succeeded { cluster_id },
+            cluster_teardown_state::Result::Failed(ClusterTeardownStateFailed { cause }) => crate::peer::configuration::ClusterTeardownState::Failed { cluster_id, cause },
+        };
+        Ok(state)
+    }
+}
+
+impl From<crate::peer::configuration::ParameterState<crate::peer::executor::ExecutorDescriptor>> for PeerConfigurationParameterStateExecutor {
+    fn from(value: crate::peer::configuration::ParameterState<crate::peer::executor::ExecutorDescriptor>) -> Self {
+        Self {
+            state: Some(PeerConfigurationParameterState {
+                id: Some(value.id.into()),
+                state: Some(value.state.into()),
+            }),
+            executor: Some(value.value.into()),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterStateExecutor> for crate::peer::configuration::ParameterState<crate::peer::executor::ExecutorDescriptor> {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterStateExecutor) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConfigurationParameterStateExecutor, crate::peer::configuration::ParameterState<crate::peer::executor::ExecutorDescriptor>>;
+
+        let state = value.state
+            .ok_or(ErrorBuilder::field_not_set("state"))?;
+
+        let executor: crate::peer::executor::ExecutorDescriptor = value.executor
+            .ok_or(ErrorBuilder::field_not_set("executor"))?
+            .try_into()?;
+
+        Ok(Self {
+            id: state.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+            state: state.state.ok_or(ErrorBuilder::field_not_set("state"))?.try_into()?,
+            value: executor,
+        })
+    }
+}
+
+impl From<crate::peer::configuration::ParameterStateKind> for peer_configuration_parameter_state::State {
+    fn from(value: crate::peer::configuration::ParameterStateKind) -> Self {
+        match value {
+            crate::peer::configuration::ParameterStateKind::Present => peer_configuration_parameter_state::State::Present(PeerConfigurationParameterTargetPresent {}),
+            crate::peer::configuration::ParameterStateKind::Absent => peer_configuration_parameter_state::State::Absent(PeerConfigurationParameterTargetAbsent {}),
+            crate::peer::configuration::ParameterStateKind::WaitingForDependencies(dependencies) => peer_configuration_parameter_state::State::WaitingForDependencies(dependencies.into()),
+            crate::peer::configuration::ParameterStateKind::Error(error) => peer_configuration_parameter_state::State::Error(error.into()),
+        }
+    }
+}
+impl TryFrom<peer_configuration_parameter_state::State> for crate::peer::configuration::ParameterStateKind {
+    type Error = ConversionError;
+
+    fn try_from(value: peer_configuration_parameter_state::State) -> Result<Self, ConversionError> {
+        let state = match value {
+            peer_configuration_parameter_state::State::Present(_) => crate::peer::configuration::ParameterStateKind::Present,
+            peer_configuration_parameter_state::State::Absent(_) => crate::peer::configuration::ParameterStateKind::Absent,
+            peer_configuration_parameter_state::State::WaitingForDependencies(waiting) => crate::peer::configuration::ParameterStateKind::WaitingForDependencies(waiting.try_into()?),
+            peer_configuration_parameter_state::State::Error(error) => crate::peer::configuration::ParameterStateKind::Error(error.try_into()?),
+        };
+        Ok(state)
+    }
+}
+
+impl From<crate::peer::configuration::ParameterStatusReport> for PeerConfigurationParameterStatusReport {
+    fn from(value: crate::peer::configuration::ParameterStatusReport) -> Self {
+        use crate::peer::configuration::ParameterStatus;
+
+        let state = match value.status {
+            ParameterStatus::Present => peer_configuration_parameter_status_report::State::Present(PeerConfigurationParameterTargetPresent {}),
+            ParameterStatus::Creating => peer_configuration_parameter_status_report::State::Creating(PeerConfigurationParameterTargetCreating {}),
+            ParameterStatus::Absent => peer_configuration_parameter_status_report::State::Absent(PeerConfigurationParameterTargetAbsent {}),
+            ParameterStatus::Removing => peer_configuration_parameter_status_report::State::Removing(PeerConfigurationParameterTargetRemoving {}),
+            ParameterStatus::WaitingForDependencies(dependencies) => peer_configuration_parameter_status_report::State::WaitingForDependencies(dependencies.into()),
+            ParameterStatus::Error(error) => peer_configuration_parameter_status_report::State::Error(error.into()),
+        };
+        Self {
+            id: Some(value.id.into()),
+            state: Some(state),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterStatusReport> for crate::peer::configuration::ParameterStatusReport {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterStatusReport) -> Result<Self, Self::Error> {
+        use crate::peer::configuration::ParameterStatus;
+        type ErrorBuilder = ConversionErrorBuilder<PeerConfigurationParameterStatusReport, crate::peer::configuration::ParameterStatusReport>;
+
+        let status = match value.state.ok_or(ErrorBuilder::field_not_set("state"))? {
+            peer_configuration_parameter_status_report::State::Present(_) => ParameterStatus::Present,
+            peer_configuration_parameter_status_report::State::Creating(_) => ParameterStatus::Creating,
+            peer_configuration_parameter_status_report::State::Absent(_) => ParameterStatus::Absent,
+            peer_configuration_parameter_status_report::State::Removing(_) => ParameterStatus::Removing,
+            peer_configuration_parameter_status_report::State::WaitingForDependencies(waiting) => ParameterStatus::WaitingForDependencies(waiting.try_into()?),
+            peer_configuration_parameter_status_report::State::Error(error) => ParameterStatus::Error(error.try_into()?),
+        };
+
+        Ok(Self {
+            id: value.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+            status,
+        })
+    }
+}
+
+impl From<Vec<crate::peer::configuration::ParameterId>> for PeerConfigurationParameterTargetWaitingForDependencies {
+    fn from(value: Vec<crate::peer::configuration::ParameterId>) -> Self {
+        Self {
+            incomplete_dependencies: value.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterTargetWaitingForDependencies> for Vec<crate::peer::configuration::ParameterId> {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterTargetWaitingForDependencies) -> Result<Self, Self::Error> {
+        value.incomplete_dependencies.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+impl From<crate::peer::configuration::ParameterStateError> for PeerConfigurationParameterTargetError {
+    fn from(value: crate::peer::configuration::ParameterStateError) -> Self {
+        let error = match value {
+            crate::peer::configuration::ParameterStateError::CreatingFailed(message) => {
+                peer_configuration_parameter_target_error::Error::CreatingFailed(PeerConfigurationParameterTargetErrorCreatingFailed {
+                    error: Some(peer_configuration_parameter_target_error_creating_failed::Error::Unclassified(UnclassifiedError { message })),
+                })
+            }
+            crate::peer::configuration::ParameterStateError::RemovingFailed(message) => {
+                peer_configuration_parameter_target_error::Error::RemovingFailed(PeerConfigurationParameterTargetErrorRemovingFailed {
+                    error: Some(peer_configuration_parameter_target_error_removing_failed::Error::Unclassified(UnclassifiedError { message })),
+                })
+            }
+        };
+        Self {
+            error: Some(error),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterTargetError> for crate::peer::configuration::ParameterStateError {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterTargetError) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConfigurationParameterTargetError, crate::peer::configuration::ParameterStateError>;
+
+        let error = match value.error.ok_or(ErrorBuilder::field_not_set("error"))? {
+            peer_configuration_parameter_target_error::Error::CreatingFailed(failure) => {
+                let peer_configuration_parameter_target_error_creating_failed::Error::Unclassified(UnclassifiedError { message }) = failure.error
+                    .ok_or(ErrorBuilder::field_not_set("creating_failed"))?;
+                crate::peer::configuration::ParameterStateError::CreatingFailed(message)
+            }
+            peer_configuration_parameter_target_error::Error::RemovingFailed(failure) => {
+                let peer_configuration_parameter_target_error_removing_failed::Error::Unclassified(UnclassifiedError { message }) = failure.error
+                    .ok_or(ErrorBuilder::field_not_set("removing_failed"))?;
+                crate::peer::configuration::ParameterStateError::RemovingFailed(message)
+            }
+        };
+        Ok(error)
+    }
+}
@@ -1,5 +1,8 @@
+use std::time::{Duration, SystemTime};
+
 use crate::proto;
 use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::reservation::millis_since_epoch;
 use crate::proto::vpn::VpnPeerConfig;
 
 use super::util::{NetworkInterfaceDescriptor, NetworkInterfaceName};
@@ -122,6 +125,7 @@ impl From<crate::peer::PeerDescriptor> for PeerDescriptor {
             network: Some(value.network.into()),
             topology: Some(value.topology.into()),
             executors: Some(value.executors.into()),
+            project: Some(value.project.into()),
         }
     }
 }
@@ -155,7 +159,13 @@ impl TryFrom<PeerDescriptor> for crate::peer::PeerDescriptor {
         let executors = value.executors
             .ok_or(ErrorBuilder::field_not_set("executors"))?
             .try_into()?;
-        
+
+        // Peers stored before projects were introduced belong to the default project.
+        let project = value.project
+            .map(crate::project::ProjectName::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(crate::peer::PeerDescriptor {
             id,
             name,
@@ -163,6 +173,7 @@ impl TryFrom<PeerDescriptor> for crate::peer::PeerDescriptor {
             network,
             topology,
             executors,
+            project,
         })
     }
 }
@@ -175,6 +186,10 @@ impl From<crate::peer::PeerSetup> for PeerSetup {
             ca: Some(value.ca.into()),
             vpn: Some(value.vpn.into()),
             auth_config: Some(value.auth_config.into()),
+            nonce: Some(value.nonce.into()),
+            issued_at: millis_since_epoch(value.issued_at),
+            expires_at: millis_since_epoch(value.expires_at),
+            certificate: Some(value.certificate.into()),
         }
     }
 }
@@ -206,12 +221,206 @@ impl TryFrom<PeerSetup> for crate::peer::PeerSetup {
             .ok_or(ErrorBuilder::field_not_set("auth_config"))?
             .try_into()?;
 
+        let nonce = value.nonce
+            .ok_or(ErrorBuilder::field_not_set("nonce"))?
+            .try_into()?;
+
+        let certificate = value.certificate
+            .ok_or(ErrorBuilder::field_not_set("certificate"))?
+            .try_into()?;
+
         Ok(Self {
             id,
             carl,
             ca,
             auth_config,
             vpn,
+            nonce,
+            issued_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.issued_at),
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.expires_at),
+            certificate,
+        })
+    }
+}
+
+impl From<crate::peer::setup::PeerSetupNonce> for PeerSetupNonce {
+    fn from(value: crate::peer::setup::PeerSetupNonce) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<PeerSetupNonce> for crate::peer::setup::PeerSetupNonce {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerSetupNonce) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerSetupNonce, crate::peer::setup::PeerSetupNonce>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::setup::IssuedPeerSetup> for IssuedPeerSetup {
+    fn from(value: crate::peer::setup::IssuedPeerSetup) -> Self {
+        Self {
+            nonce: Some(value.nonce.into()),
+            peer_id: Some(value.peer_id.into()),
+            issued_at: millis_since_epoch(value.issued_at),
+            expires_at: millis_since_epoch(value.expires_at),
+            state: Some(value.state.into()),
+            client_id: value.client_id.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<IssuedPeerSetup> for crate::peer::setup::IssuedPeerSetup {
+    type Error = ConversionError;
+
+    fn try_from(value: IssuedPeerSetup) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<IssuedPeerSetup, crate::peer::setup::IssuedPeerSetup>;
+
+        let nonce = value.nonce
+            .ok_or(ErrorBuilder::field_not_set("nonce"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let state = value.state
+            .ok_or(ErrorBuilder::field_not_set("state"))?
+            .try_into()?;
+        let client_id = value.client_id
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        Ok(Self {
+            nonce,
+            peer_id,
+            issued_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.issued_at),
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.expires_at),
+            state,
+            client_id,
+        })
+    }
+}
+
+impl From<crate::peer::setup::IssuedPeerSetupState> for IssuedPeerSetupState {
+    fn from(value: crate::peer::setup::IssuedPeerSetupState) -> Self {
+        let inner = match value {
+            crate::peer::setup::IssuedPeerSetupState::Outstanding => {
+                issued_peer_setup_state::Inner::Outstanding(IssuedPeerSetupStateOutstanding {})
+            }
+            crate::peer::setup::IssuedPeerSetupState::Consumed { at, certificate_fingerprint } => {
+                issued_peer_setup_state::Inner::Consumed(IssuedPeerSetupStateConsumed { at: millis_since_epoch(at), certificate_fingerprint })
+            }
+            crate::peer::setup::IssuedPeerSetupState::Revoked { at } => {
+                issued_peer_setup_state::Inner::Revoked(IssuedPeerSetupStateRevoked { at: millis_since_epoch(at) })
+            }
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<IssuedPeerSetupState> for crate::peer::setup::IssuedPeerSetupState {
+    type Error = ConversionError;
+
+    fn try_from(value: IssuedPeerSetupState) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<IssuedPeerSetupState, crate::peer::setup::IssuedPeerSetupState>;
+
+        let state = match value.inner.ok_or(ErrorBuilder::field_not_set("inner"))? {
+            issued_peer_setup_state::Inner::Outstanding(_) => {
+                crate::peer::setup::IssuedPeerSetupState::Outstanding
+            }
+            issued_peer_setup_state::Inner::Consumed(IssuedPeerSetupStateConsumed { at, certificate_fingerprint }) => {
+                crate::peer::setup::IssuedPeerSetupState::Consumed { at: SystemTime::UNIX_EPOCH + Duration::from_millis(at), certificate_fingerprint }
+            }
+            issued_peer_setup_state::Inner::Revoked(IssuedPeerSetupStateRevoked { at }) => {
+                crate::peer::setup::IssuedPeerSetupState::Revoked { at: SystemTime::UNIX_EPOCH + Duration::from_millis(at) }
+            }
+        };
+        Ok(state)
+    }
+}
+
+impl From<crate::peer::certificate::PeerCertificateId> for PeerCertificateId {
+    fn from(value: crate::peer::certificate::PeerCertificateId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<PeerCertificateId> for crate::peer::certificate::PeerCertificateId {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerCertificateId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerCertificateId, crate::peer::certificate::PeerCertificateId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::certificate::PeerCertificate> for PeerCertificate {
+    fn from(value: crate::peer::certificate::PeerCertificate) -> Self {
+        Self {
+            certificate: Some(value.certificate.into()),
+            key: Vec::from(value.key.0.contents()),
+        }
+    }
+}
+
+impl TryFrom<PeerCertificate> for crate::peer::certificate::PeerCertificate {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerCertificate) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerCertificate, crate::peer::certificate::PeerCertificate>;
+
+        let certificate = value.certificate
+            .ok_or(ErrorBuilder::field_not_set("certificate"))?
+            .try_into()?;
+
+        Ok(Self {
+            certificate,
+            key: crate::peer::certificate::PeerCertificateKey(pem::Pem::new("PRIVATE KEY", value.key)),
+        })
+    }
+}
+
+impl From<crate::peer::certificate::IssuedPeerCertificate> for IssuedPeerCertificate {
+    fn from(value: crate::peer::certificate::IssuedPeerCertificate) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            fingerprint: value.fingerprint,
+            issued_at: millis_since_epoch(value.issued_at),
+            expires_at: millis_since_epoch(value.expires_at),
+        }
+    }
+}
+
+impl TryFrom<IssuedPeerCertificate> for crate::peer::certificate::IssuedPeerCertificate {
+    type Error = ConversionError;
+
+    fn try_from(value: IssuedPeerCertificate) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<IssuedPeerCertificate, crate::peer::certificate::IssuedPeerCertificate>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            peer_id,
+            fingerprint: value.fingerprint,
+            issued_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.issued_at),
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.expires_at),
         })
     }
 }
@@ -0,0 +1,22 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.project.rs"));
+
+impl From<crate::project::ProjectName> for ProjectName {
+    fn from(value: crate::project::ProjectName) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<ProjectName> for crate::project::ProjectName {
+    type Error = ConversionError;
+
+    fn try_from(value: ProjectName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ProjectName, crate::project::ProjectName>;
+
+        crate::project::ProjectName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
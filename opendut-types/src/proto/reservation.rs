@@ -0,0 +1,76 @@
+use std::time::{Duration, SystemTime};
+
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::peer::PeerId;
+use crate::proto::topology::DeviceId;
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.reservation.rs"));
+
+impl From<crate::reservation::ReservationId> for ReservationId {
+    fn from(value: crate::reservation::ReservationId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ReservationId> for crate::reservation::ReservationId {
+    type Error = ConversionError;
+
+    fn try_from(value: ReservationId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ReservationId, crate::reservation::ReservationId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::reservation::Reservation> for Reservation {
+    fn from(reservation: crate::reservation::Reservation) -> Self {
+        Self {
+            id: Some(reservation.id.into()),
+            user: reservation.user,
+            peers: reservation.peers.into_iter()
+                .map(PeerId::from)
+                .collect(),
+            devices: reservation.devices.into_iter()
+                .map(DeviceId::from)
+                .collect(),
+            start: millis_since_epoch(reservation.start),
+            end: millis_since_epoch(reservation.end),
+        }
+    }
+}
+
+impl TryFrom<Reservation> for crate::reservation::Reservation {
+    type Error = ConversionError;
+
+    fn try_from(reservation: Reservation) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<Reservation, crate::reservation::Reservation>;
+
+        let id: crate::reservation::ReservationId = reservation.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            user: reservation.user,
+            peers: reservation.peers.into_iter()
+                .map(PeerId::try_into)
+                .collect::<Result<_, _>>()?,
+            devices: reservation.devices.into_iter()
+                .map(DeviceId::try_into)
+                .collect::<Result<_, _>>()?,
+            start: SystemTime::UNIX_EPOCH + Duration::from_millis(reservation.start),
+            end: SystemTime::UNIX_EPOCH + Duration::from_millis(reservation.end),
+        })
+    }
+}
+
+pub(crate) fn millis_since_epoch(time: SystemTime) -> u64 {
+    let millis = time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    u64::try_from(millis).unwrap_or(u64::MAX)
+}
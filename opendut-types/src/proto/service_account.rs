@@ -0,0 +1,173 @@
+use std::time::{Duration, SystemTime};
+
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::project::ProjectName;
+use crate::proto::reservation::millis_since_epoch;
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.service_account.rs"));
+
+impl From<crate::service_account::ServiceAccountId> for ServiceAccountId {
+    fn from(value: crate::service_account::ServiceAccountId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ServiceAccountId> for crate::service_account::ServiceAccountId {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccountId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccountId, crate::service_account::ServiceAccountId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::service_account::ServiceAccountName> for ServiceAccountName {
+    fn from(value: crate::service_account::ServiceAccountName) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<ServiceAccountName> for crate::service_account::ServiceAccountName {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccountName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccountName, crate::service_account::ServiceAccountName>;
+
+        crate::service_account::ServiceAccountName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::service_account::ServiceAccount> for ServiceAccount {
+    fn from(value: crate::service_account::ServiceAccount) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            created_by: value.created_by,
+        }
+    }
+}
+
+impl TryFrom<ServiceAccount> for crate::service_account::ServiceAccount {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccount) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccount, crate::service_account::ServiceAccount>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let name = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            name,
+            created_by: value.created_by,
+        })
+    }
+}
+
+impl From<crate::service_account::ApiTokenId> for ApiTokenId {
+    fn from(value: crate::service_account::ApiTokenId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ApiTokenId> for crate::service_account::ApiTokenId {
+    type Error = ConversionError;
+
+    fn try_from(value: ApiTokenId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ApiTokenId, crate::service_account::ApiTokenId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::service_account::ApiTokenRole> for ApiTokenRole {
+    fn from(value: crate::service_account::ApiTokenRole) -> Self {
+        let inner = match value {
+            crate::service_account::ApiTokenRole::Viewer => api_token_role::Inner::Viewer(ApiTokenRoleViewer {}),
+            crate::service_account::ApiTokenRole::Operator => api_token_role::Inner::Operator(ApiTokenRoleOperator {}),
+            crate::service_account::ApiTokenRole::Admin => api_token_role::Inner::Admin(ApiTokenRoleAdmin {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ApiTokenRole> for crate::service_account::ApiTokenRole {
+    type Error = ConversionError;
+
+    fn try_from(value: ApiTokenRole) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ApiTokenRole, crate::service_account::ApiTokenRole>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            api_token_role::Inner::Viewer(_) => crate::service_account::ApiTokenRole::Viewer,
+            api_token_role::Inner::Operator(_) => crate::service_account::ApiTokenRole::Operator,
+            api_token_role::Inner::Admin(_) => crate::service_account::ApiTokenRole::Admin,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::service_account::ApiToken> for ApiToken {
+    fn from(value: crate::service_account::ApiToken) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            service_account: Some(value.service_account.into()),
+            role: Some(value.role.into()),
+            projects: value.projects.into_iter()
+                .map(ProjectName::from)
+                .collect(),
+            expires_at: millis_since_epoch(value.expires_at),
+            secret_hash: value.secret_hash,
+        }
+    }
+}
+
+impl TryFrom<ApiToken> for crate::service_account::ApiToken {
+    type Error = ConversionError;
+
+    fn try_from(value: ApiToken) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ApiToken, crate::service_account::ApiToken>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let service_account = value.service_account
+            .ok_or(ErrorBuilder::field_not_set("service_account"))?
+            .try_into()?;
+        let role = value.role
+            .ok_or(ErrorBuilder::field_not_set("role"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            service_account,
+            role,
+            projects: value.projects.into_iter()
+                .map(ProjectName::try_into)
+                .collect::<Result<_, _>>()?,
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.expires_at),
+            secret_hash: value.secret_hash,
+        })
+    }
+}
@@ -20,6 +20,18 @@ impl From<Uuid> for uuid::Uuid {
     }
 }
 
+impl From<crate::resources::Id> for Uuid {
+    fn from(value: crate::resources::Id) -> Self {
+        Self::from(value.value())
+    }
+}
+
+impl From<Uuid> for crate::resources::Id {
+    fn from(value: Uuid) -> Self {
+        Self::from(uuid::Uuid::from(value))
+    }
+}
+
 impl From<String> for Hostname {
     fn from(value: String) -> Self {
         Self { value }
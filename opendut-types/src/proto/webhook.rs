@@ -0,0 +1,196 @@
+use std::time::{Duration, SystemTime};
+
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::reservation::millis_since_epoch;
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.webhook.rs"));
+
+impl From<crate::webhook::WebhookId> for WebhookId {
+    fn from(value: crate::webhook::WebhookId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<WebhookId> for crate::webhook::WebhookId {
+    type Error = ConversionError;
+
+    fn try_from(value: WebhookId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WebhookId, crate::webhook::WebhookId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::webhook::WebhookEventKind> for WebhookEventKind {
+    fn from(value: crate::webhook::WebhookEventKind) -> Self {
+        use crate::webhook::WebhookEventKind as Kind;
+
+        let inner = match value {
+            Kind::PeerStored => webhook_event_kind::Inner::PeerStored(WebhookEventKindPeerStored {}),
+            Kind::PeerDeleted => webhook_event_kind::Inner::PeerDeleted(WebhookEventKindPeerDeleted {}),
+            Kind::PeerUp => webhook_event_kind::Inner::PeerUp(WebhookEventKindPeerUp {}),
+            Kind::PeerDown => webhook_event_kind::Inner::PeerDown(WebhookEventKindPeerDown {}),
+            Kind::ClusterConfigurationStored => webhook_event_kind::Inner::ClusterConfigurationStored(WebhookEventKindClusterConfigurationStored {}),
+            Kind::ClusterConfigurationDeleted => webhook_event_kind::Inner::ClusterConfigurationDeleted(WebhookEventKindClusterConfigurationDeleted {}),
+            Kind::ClusterDeployed => webhook_event_kind::Inner::ClusterDeployed(WebhookEventKindClusterDeployed {}),
+            Kind::ClusterDeploymentFailed => webhook_event_kind::Inner::ClusterDeploymentFailed(WebhookEventKindClusterDeploymentFailed {}),
+            Kind::ClusterUndeployed => webhook_event_kind::Inner::ClusterUndeployed(WebhookEventKindClusterUndeployed {}),
+            Kind::ExecutorFinished => webhook_event_kind::Inner::ExecutorFinished(WebhookEventKindExecutorFinished {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<WebhookEventKind> for crate::webhook::WebhookEventKind {
+    type Error = ConversionError;
+
+    fn try_from(value: WebhookEventKind) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WebhookEventKind, crate::webhook::WebhookEventKind>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            webhook_event_kind::Inner::PeerStored(_) => Self::PeerStored,
+            webhook_event_kind::Inner::PeerDeleted(_) => Self::PeerDeleted,
+            webhook_event_kind::Inner::PeerUp(_) => Self::PeerUp,
+            webhook_event_kind::Inner::PeerDown(_) => Self::PeerDown,
+            webhook_event_kind::Inner::ClusterConfigurationStored(_) => Self::ClusterConfigurationStored,
+            webhook_event_kind::Inner::ClusterConfigurationDeleted(_) => Self::ClusterConfigurationDeleted,
+            webhook_event_kind::Inner::ClusterDeployed(_) => Self::ClusterDeployed,
+            webhook_event_kind::Inner::ClusterDeploymentFailed(_) => Self::ClusterDeploymentFailed,
+            webhook_event_kind::Inner::ClusterUndeployed(_) => Self::ClusterUndeployed,
+            webhook_event_kind::Inner::ExecutorFinished(_) => Self::ExecutorFinished,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::webhook::Webhook> for Webhook {
+    fn from(value: crate::webhook::Webhook) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            url: Some(value.url.into()),
+            events: value.events.into_iter()
+                .map(WebhookEventKind::from)
+                .collect(),
+            secret: value.secret,
+            created_by: value.created_by,
+        }
+    }
+}
+
+impl TryFrom<Webhook> for crate::webhook::Webhook {
+    type Error = ConversionError;
+
+    fn try_from(value: Webhook) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<Webhook, crate::webhook::Webhook>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let url = value.url
+            .ok_or(ErrorBuilder::field_not_set("url"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            url,
+            events: value.events.into_iter()
+                .map(WebhookEventKind::try_into)
+                .collect::<Result<_, _>>()?,
+            secret: value.secret,
+            created_by: value.created_by,
+        })
+    }
+}
+
+impl From<crate::webhook::WebhookDeliveryOutcome> for WebhookDeliveryOutcome {
+    fn from(value: crate::webhook::WebhookDeliveryOutcome) -> Self {
+        use crate::webhook::WebhookDeliveryOutcome as Outcome;
+
+        let inner = match value {
+            Outcome::Pending => webhook_delivery_outcome::Inner::Pending(WebhookDeliveryOutcomePending {}),
+            Outcome::Retrying { cause } => webhook_delivery_outcome::Inner::Retrying(WebhookDeliveryOutcomeRetrying { cause }),
+            Outcome::Delivered { status_code } => webhook_delivery_outcome::Inner::Delivered(WebhookDeliveryOutcomeDelivered { status_code: u32::from(status_code) }),
+            Outcome::Failed { cause } => webhook_delivery_outcome::Inner::Failed(WebhookDeliveryOutcomeFailed { cause }),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<WebhookDeliveryOutcome> for crate::webhook::WebhookDeliveryOutcome {
+    type Error = ConversionError;
+
+    fn try_from(value: WebhookDeliveryOutcome) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WebhookDeliveryOutcome, crate::webhook::WebhookDeliveryOutcome>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            webhook_delivery_outcome::Inner::Pending(_) => Self::Pending,
+            webhook_delivery_outcome::Inner::Retrying(WebhookDeliveryOutcomeRetrying { cause }) => Self::Retrying { cause },
+            webhook_delivery_outcome::Inner::Delivered(WebhookDeliveryOutcomeDelivered { status_code }) => {
+                let status_code = u16::try_from(status_code)
+                    .map_err(|_| ErrorBuilder::message(format!("Status code {status_code} is out of range.")))?;
+                Self::Delivered { status_code }
+            }
+            webhook_delivery_outcome::Inner::Failed(WebhookDeliveryOutcomeFailed { cause }) => Self::Failed { cause },
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::webhook::WebhookDelivery> for WebhookDelivery {
+    fn from(value: crate::webhook::WebhookDelivery) -> Self {
+        Self {
+            webhook: Some(value.webhook.into()),
+            event_id: Some(value.event_id.into()),
+            event: Some(value.event.into()),
+            timestamp: millis_since_epoch(value.timestamp),
+            attempts: value.attempts,
+            outcome: Some(value.outcome.into()),
+        }
+    }
+}
+
+impl TryFrom<WebhookDelivery> for crate::webhook::WebhookDelivery {
+    type Error = ConversionError;
+
+    fn try_from(value: WebhookDelivery) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WebhookDelivery, crate::webhook::WebhookDelivery>;
+
+        let webhook = value.webhook
+            .ok_or(ErrorBuilder::field_not_set("webhook"))?
+            .try_into()?;
+        let event_id = value.event_id
+            .ok_or(ErrorBuilder::field_not_set("event_id"))?
+            .into();
+        let event = value.event
+            .ok_or(ErrorBuilder::field_not_set("event"))?
+            .try_into()?;
+        let outcome = value.outcome
+            .ok_or(ErrorBuilder::field_not_set("outcome"))?
+            .try_into()?;
+
+        Ok(Self {
+            webhook,
+            event_id,
+            event,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(value.timestamp),
+            attempts: value.attempts,
+            outcome,
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Not;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+use crate::topology::DeviceId;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReservationId(pub Uuid);
+
+impl ReservationId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ReservationId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ReservationId: {value}")]
+pub struct IllegalReservationId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ReservationId {
+    type Error = IllegalReservationId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalReservationId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for ReservationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Exclusive use of peers and devices by a user for a period of time.
+///
+/// Reserving a peer reserves all of its devices.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    pub id: ReservationId,
+    pub user: String,
+    pub peers: HashSet<PeerId>,
+    pub devices: HashSet<DeviceId>,
+    pub start: SystemTime,
+    pub end: SystemTime,
+}
+
+impl Reservation {
+    /// Whether the reservation is in effect at the given time.
+    pub fn is_active_at(&self, time: SystemTime) -> bool {
+        self.start <= time && time < self.end
+    }
+
+    /// Whether the reservation ended at or before the given time.
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.end <= time
+    }
+
+    /// Whether both reservations claim a peer or device during a common period of time.
+    ///
+    /// The devices are resolved to their peers via `device_peers`, so that a reserved device conflicts with a reservation of its peer.
+    pub fn conflicts_with(&self, other: &Reservation, device_peers: &HashMap<DeviceId, PeerId>) -> bool {
+        let overlapping_time = self.start < other.end && other.start < self.end;
+        let overlapping_peers = self.peers.is_disjoint(&other.peers).not();
+        let overlapping_devices = self.devices.is_disjoint(&other.devices).not();
+        let overlapping_peers_of_devices = self.claims_peer_of_any(&other.devices, device_peers)
+            || other.claims_peer_of_any(&self.devices, device_peers);
+
+        overlapping_time && (overlapping_peers || overlapping_devices || overlapping_peers_of_devices)
+    }
+
+    fn claims_peer_of_any(&self, devices: &HashSet<DeviceId>, device_peers: &HashMap<DeviceId, PeerId>) -> bool {
+        devices.iter()
+            .filter_map(|device_id| device_peers.get(device_id))
+            .any(|peer_id| self.peers.contains(peer_id))
+    }
+
+    /// Whether the reservation claims any of the given peers or devices.
+    pub fn claims_any(&self, peers: &HashSet<PeerId>, devices: &HashSet<DeviceId>) -> bool {
+        self.peers.is_disjoint(peers).not()
+            || self.devices.is_disjoint(devices).not()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn reservations_should_only_conflict_when_overlapping_in_time_and_resources() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        let peer = PeerId::random();
+        let device = DeviceId::random();
+
+        let reservation = Reservation {
+            id: ReservationId::random(),
+            user: String::from("alice"),
+            peers: HashSet::from([peer]),
+            devices: HashSet::new(),
+            start: now,
+            end: now + hour,
+        };
+
+        let overlapping = Reservation {
+            id: ReservationId::random(),
+            user: String::from("bob"),
+            peers: HashSet::from([peer]),
+            devices: HashSet::from([device]),
+            start: now + hour / 2,
+            end: now + hour * 2,
+        };
+        let device_peers = HashMap::new();
+        assert_that!(reservation.conflicts_with(&overlapping, &device_peers), eq(true));
+
+        let subsequent = Reservation {
+            start: now + hour,
+            ..Clone::clone(&overlapping)
+        };
+        assert_that!(reservation.conflicts_with(&subsequent, &device_peers), eq(false));
+
+        let other_resources = Reservation {
+            peers: HashSet::new(),
+            ..Clone::clone(&overlapping)
+        };
+        assert_that!(reservation.conflicts_with(&other_resources, &device_peers), eq(false));
+
+        let device_of_reserved_peer = Reservation {
+            peers: HashSet::new(),
+            devices: HashSet::from([device]),
+            ..Clone::clone(&overlapping)
+        };
+        assert_that!(reservation.conflicts_with(&device_of_reserved_peer, &device_peers), eq(false));
+        let device_peers = HashMap::from([(device, peer)]);
+        assert_that!(reservation.conflicts_with(&device_of_reserved_peer, &device_peers), eq(true));
+        assert_that!(device_of_reserved_peer.conflicts_with(&reservation, &device_peers), eq(true));
+
+        assert_that!(reservation.is_active_at(now + hour / 2), eq(true));
+        assert_that!(reservation.is_active_at(now + hour), eq(false));
+        assert_that!(reservation.is_expired_at(now + hour), eq(true));
+    }
+}
@@ -47,3 +47,30 @@ impl From<Uuid> for Id {
         }
     }
 }
+
+/// Version of a stored resource, which increases with every change of the resource.
+#[derive(Copy, Debug, Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Version(pub u64);
+
+impl Version {
+    /// Version of a resource, which does not exist.
+    pub const NONE: Version = Version(0);
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for Version {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Version> for u64 {
+    fn from(value: Version) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Not;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::project::ProjectName;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ServiceAccountId(pub Uuid);
+
+impl ServiceAccountId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ServiceAccountId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ServiceAccountId: {value}")]
+pub struct IllegalServiceAccountId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ServiceAccountId {
+    type Error = IllegalServiceAccountId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalServiceAccountId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for ServiceAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ServiceAccountName(pub(crate) String);
+
+impl ServiceAccountName {
+
+    pub const MIN_LENGTH: usize = 4;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalServiceAccountName {
+    #[error("Service account name '{value}' is too short. Expected at least {expected} characters, got {actual}.")]
+    TooShort { value: String, expected: usize, actual: usize },
+    #[error("Service account name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Service account name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+    #[error("Service account name '{value}' contains invalid start or end characters.")]
+    InvalidStartEndCharacter { value: String },
+}
+
+impl From<ServiceAccountName> for String {
+    fn from(value: ServiceAccountName) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for ServiceAccountName {
+
+    type Error = IllegalServiceAccountName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalServiceAccountName::TooShort {
+                value,
+                expected: Self::MIN_LENGTH,
+                actual: length,
+            })
+        }
+        else if length > Self::MAX_LENGTH {
+            Err(IllegalServiceAccountName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        }
+        else if crate::util::invalid_start_and_end_of_a_name(&value) {
+            Err(IllegalServiceAccountName::InvalidStartEndCharacter { value })
+        }
+        else if value.chars().any(|c| crate::util::valid_characters_in_name(&c).not()) {
+            Err(IllegalServiceAccountName::InvalidCharacter {
+                value
+            })
+        }
+        else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ServiceAccountName {
+
+    type Error = IllegalServiceAccountName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ServiceAccountName::try_from(value.to_owned())
+    }
+}
+
+impl fmt::Display for ServiceAccountName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Technical identity, e.g. of a CI pipeline, which authenticates against CARL with [`ApiToken`]s instead of OIDC.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ServiceAccount {
+    pub id: ServiceAccountId,
+    pub name: ServiceAccountName,
+    /// The user, who created the service account. Set by CARL.
+    pub created_by: String,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiTokenId(pub Uuid);
+
+impl ApiTokenId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ApiTokenId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ApiTokenId: {value}")]
+pub struct IllegalApiTokenId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ApiTokenId {
+    type Error = IllegalApiTokenId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalApiTokenId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for ApiTokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Level of access granted by an [`ApiToken`]. Each role includes the permissions of the roles below it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ApiTokenRole {
+    Viewer,
+    Operator,
+    Admin,
+}
+
+impl fmt::Display for ApiTokenRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiTokenRole::Viewer => write!(f, "viewer"),
+            ApiTokenRole::Operator => write!(f, "operator"),
+            ApiTokenRole::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// Long-lived, revocable credential of a [`ServiceAccount`].
+///
+/// CARL only stores a hash of the token's secret, which is handed out once when the token is issued.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApiToken {
+    pub id: ApiTokenId,
+    pub service_account: ServiceAccountId,
+    pub role: ApiTokenRole,
+    /// Projects, whose resources may be accessed with the token. Ignored for the [`ApiTokenRole::Admin`], which may access all projects.
+    pub projects: HashSet<ProjectName>,
+    pub expires_at: SystemTime,
+    /// Hex-encoded SHA-256 hash of the token's secret.
+    pub secret_hash: String,
+}
+
+impl ApiToken {
+    /// Whether the token may not be used anymore at the given time.
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at <= time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_reject_illegal_service_account_names() {
+        assert_that!(ServiceAccountName::try_from("ci-pipeline"), ok(anything()));
+        assert_that!(ServiceAccountName::try_from("ci"), err(matches_pattern!(IllegalServiceAccountName::TooShort { .. })));
+        assert_that!(ServiceAccountName::try_from("-ci-pipeline"), err(matches_pattern!(IllegalServiceAccountName::InvalidStartEndCharacter { .. })));
+        assert_that!(ServiceAccountName::try_from("ci pipeline"), err(matches_pattern!(IllegalServiceAccountName::InvalidCharacter { .. })));
+    }
+}
@@ -6,6 +6,7 @@ use crate::util::net::NetworkInterfaceDescriptor;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Topology {
     pub devices: Vec<DeviceDescriptor>,
 }
@@ -17,6 +18,7 @@ impl Topology {
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct DeviceId(pub uuid::Uuid);
 
@@ -47,6 +49,7 @@ impl From<uuid::Uuid> for DeviceId {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DeviceName(pub(crate) String);
 
 impl DeviceName {
@@ -139,6 +142,7 @@ impl fmt::Display for DeviceName {
 }
 
 #[derive(Clone, Debug, Eq, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DeviceDescription(pub(crate) String);
 
 impl DeviceDescription {
@@ -199,6 +203,7 @@ impl fmt::Display for DeviceDescription {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DeviceTag(pub(crate) String);
 
 impl DeviceTag {
@@ -259,6 +264,7 @@ impl fmt::Display for DeviceTag {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DeviceDescriptor {
     pub id: DeviceId,
     pub name: DeviceName,
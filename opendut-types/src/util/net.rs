@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct NetworkInterfaceName { name: String }
 impl NetworkInterfaceName {
@@ -77,6 +78,7 @@ impl FromStr for Certificate {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CanSamplePoint {
     sample_point_times_1000: u32
 }
@@ -126,6 +128,7 @@ pub enum CanSamplePointError {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum NetworkInterfaceConfiguration {
     Ethernet,
     Can {
@@ -154,6 +157,7 @@ impl fmt::Display for NetworkInterfaceConfiguration {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NetworkInterfaceDescriptor {
     pub name: NetworkInterfaceName,
     pub configuration: NetworkInterfaceConfiguration,
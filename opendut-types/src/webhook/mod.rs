@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WebhookId(pub Uuid);
+
+impl WebhookId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for WebhookId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal WebhookId: {value}")]
+pub struct IllegalWebhookId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for WebhookId {
+    type Error = IllegalWebhookId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalWebhookId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for WebhookId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Kinds of events in CARL, which are sent to [`Webhook`]s.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookEventKind {
+    /// A peer descriptor was created or updated.
+    PeerStored,
+    PeerDeleted,
+    /// A peer connected to CARL.
+    PeerUp,
+    /// A peer lost its connection to CARL.
+    PeerDown,
+    /// A cluster configuration was created or updated.
+    ClusterConfigurationStored,
+    ClusterConfigurationDeleted,
+    ClusterDeployed,
+    ClusterDeploymentFailed,
+    ClusterUndeployed,
+    /// A peer reported, that it finished starting, stopping or failed to start or stop one of its executors.
+    ExecutorFinished,
+}
+
+impl WebhookEventKind {
+    pub const ALL: [WebhookEventKind; 10] = [
+        WebhookEventKind::PeerStored,
+        WebhookEventKind::PeerDeleted,
+        WebhookEventKind::PeerUp,
+        WebhookEventKind::PeerDown,
+        WebhookEventKind::ClusterConfigurationStored,
+        WebhookEventKind::ClusterConfigurationDeleted,
+        WebhookEventKind::ClusterDeployed,
+        WebhookEventKind::ClusterDeploymentFailed,
+        WebhookEventKind::ClusterUndeployed,
+        WebhookEventKind::ExecutorFinished,
+    ];
+}
+
+impl fmt::Display for WebhookEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WebhookEventKind::PeerStored => "peer-stored",
+            WebhookEventKind::PeerDeleted => "peer-deleted",
+            WebhookEventKind::PeerUp => "peer-up",
+            WebhookEventKind::PeerDown => "peer-down",
+            WebhookEventKind::ClusterConfigurationStored => "cluster-configuration-stored",
+            WebhookEventKind::ClusterConfigurationDeleted => "cluster-configuration-deleted",
+            WebhookEventKind::ClusterDeployed => "cluster-deployed",
+            WebhookEventKind::ClusterDeploymentFailed => "cluster-deployment-failed",
+            WebhookEventKind::ClusterUndeployed => "cluster-undeployed",
+            WebhookEventKind::ExecutorFinished => "executor-finished",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Unknown webhook event '{value}'.")]
+pub struct IllegalWebhookEventKind {
+    pub value: String,
+}
+
+impl FromStr for WebhookEventKind {
+    type Err = IllegalWebhookEventKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        WebhookEventKind::ALL.into_iter()
+            .find(|kind| kind.to_string() == value)
+            .ok_or_else(|| IllegalWebhookEventKind { value: String::from(value) })
+    }
+}
+
+/// Endpoint, to which CARL posts a signed JSON payload for each event it subscribed to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub url: Url,
+    /// Events sent to the endpoint. An empty set subscribes to all events.
+    pub events: HashSet<WebhookEventKind>,
+    /// Key for signing the payloads. Generated by CARL and only handed out once, when the webhook is created.
+    pub secret: String,
+    /// The user, who created the webhook. Set by CARL.
+    pub created_by: String,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event: WebhookEventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Attempt of CARL to deliver an event to a [`Webhook`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebhookDelivery {
+    pub webhook: WebhookId,
+    /// Id of the event, which is the same for all webhooks receiving the event.
+    pub event_id: Uuid,
+    pub event: WebhookEventKind,
+    /// Time the event occurred.
+    pub timestamp: SystemTime,
+    pub attempts: u32,
+    pub outcome: WebhookDeliveryOutcome,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WebhookDeliveryOutcome {
+    /// Not attempted yet.
+    Pending,
+    /// The last attempt failed, but the delivery will be attempted again.
+    Retrying { cause: String },
+    /// The endpoint responded with a successful status code.
+    Delivered { status_code: u16 },
+    /// All attempts failed.
+    Failed { cause: String },
+}
+
+impl fmt::Display for WebhookDeliveryOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookDeliveryOutcome::Pending => write!(f, "Pending"),
+            WebhookDeliveryOutcome::Retrying { cause } => write!(f, "Retrying: {cause}"),
+            WebhookDeliveryOutcome::Delivered { status_code } => write!(f, "Delivered ({status_code})"),
+            WebhookDeliveryOutcome::Failed { cause } => write!(f, "Failed: {cause}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_the_displayed_names_of_event_kinds() {
+        for kind in WebhookEventKind::ALL {
+            assert_that!(WebhookEventKind::from_str(&kind.to_string()), ok(eq(kind)));
+            assert_that!(serde_json::to_value(kind).unwrap(), eq(serde_json::Value::String(kind.to_string())));
+        }
+        assert_that!(WebhookEventKind::from_str("peer-exploded"), err(anything()));
+    }
+}
@@ -13,6 +13,7 @@ use crate::confidential::client::ConfidentialClient;
 pub struct TonicAuthenticationService {
     inner: Channel,
     confidential_client: Option<Arc<ConfidentialClient>>,
+    api_token: Option<String>,
 }
 
 impl TonicAuthenticationService {
@@ -23,6 +24,19 @@ impl TonicAuthenticationService {
         TonicAuthenticationService {
             inner,
             confidential_client,
+            api_token: None,
+        }
+    }
+
+    /// Authenticates with the long-lived API token of a service account instead of an OIDC access token.
+    pub fn with_api_token(
+        inner: Channel,
+        api_token: String,
+    ) -> Self {
+        TonicAuthenticationService {
+            inner,
+            confidential_client: None,
+            api_token: Some(api_token),
         }
     }
 }
@@ -44,8 +58,15 @@ impl Service<Request<BoxBody>> for TonicAuthenticationService {
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
         let confidential_client = self.confidential_client.clone();
+        let api_token = self.api_token.clone();
 
         Box::pin(async move {
+            if let Some(api_token) = api_token {
+                let bearer_header = HeaderValue::from_str(&format!("Bearer {api_token}"))?;
+                request.headers_mut().insert("Authorization", bearer_header);
+                return Ok(inner.call(request).await?);
+            }
+
             let token_result = confidential_client.as_ref()
                 .map(|manager| manager.get_token());
 
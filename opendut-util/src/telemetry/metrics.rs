@@ -1,46 +1,134 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::{MeterProvider, MetricsError};
-use opentelemetry_otlp::WithExportConfig;
-use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_otlp::{MetricsExporterBuilder, WithExportConfig};
+use opentelemetry_prometheus::PrometheusExporter;
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::metrics::reader::{DefaultAggregationSelector, DefaultTemporalitySelector};
 use opentelemetry_sdk::{Resource, runtime};
+use prometheus::TextEncoder;
 use simple_moving_average::{SMA, SumTreeSMA};
 use sysinfo::{Pid, System};
 use tokio::sync::Mutex;
 use tokio::time::sleep;
+use tracing::warn;
 use opendut_auth::confidential::blocking::client::{ConfClientArcMutex, ConfidentialClientRef};
 use crate::telemetry::DEFAULT_METER_NAME;
 use crate::telemetry::opentelemetry_types::Endpoint;
 
-pub fn init_metrics(telemetry_interceptor: ConfClientArcMutex<Option<ConfidentialClientRef>>, endpoint: &Endpoint, service_name: impl Into<String>, service_instance_id: impl Into<String>, metrics_interval: Duration) -> Result<SdkMeterProvider, MetricsError> {
-    opentelemetry_otlp::new_pipeline()
-        .metrics(runtime::Tokio)
-        .with_exporter(
+/// Pushes the metrics to an OpenTelemetry collector.
+#[derive(Clone)]
+pub struct OtlpMetricsExport {
+    pub telemetry_interceptor: ConfClientArcMutex<Option<ConfidentialClientRef>>,
+    pub endpoint: Endpoint,
+    pub interval: Duration,
+}
+
+pub fn init_metrics(otlp_export: Option<OtlpMetricsExport>, prometheus_exporter: Option<PrometheusExporter>, resource: Resource) -> Result<SdkMeterProvider, MetricsError> {
+    let mut meter_provider = SdkMeterProvider::builder()
+        .with_resource(resource);
+
+    if let Some(otlp_export) = otlp_export {
+        let exporter = MetricsExporterBuilder::from(
             opentelemetry_otlp::new_exporter()
                 .tonic()
-                .with_interceptor(telemetry_interceptor)
-                .with_endpoint(Clone::clone(&endpoint.url))
-        )
-        .with_resource(Resource::new(vec![
-            KeyValue::new(
-                opentelemetry_semantic_conventions::resource::SERVICE_NAME,
-                service_name.into()
-            ),
-            KeyValue::new(
-                opentelemetry_semantic_conventions::resource::SERVICE_INSTANCE_ID,
-                service_instance_id.into()
-            ),
-        ]))
-        .with_period(metrics_interval)
-        .build()
+                .with_interceptor(otlp_export.telemetry_interceptor)
+                .with_endpoint(otlp_export.endpoint.url)
+        ).build_metrics_exporter(
+            Box::new(DefaultTemporalitySelector::new()),
+            Box::new(DefaultAggregationSelector::new()),
+        )?;
+
+        let reader = PeriodicReader::builder(exporter, runtime::Tokio)
+            .with_interval(otlp_export.interval)
+            .build();
+        meter_provider = meter_provider.with_reader(reader);
+    }
+
+    if let Some(prometheus_exporter) = prometheus_exporter {
+        meter_provider = meter_provider.with_reader(prometheus_exporter);
+    }
+
+    Ok(meter_provider.build())
+}
+
+pub(crate) fn service_resource(service_name: impl Into<String>, service_instance_id: impl Into<String>) -> Resource {
+    Resource::new(vec![
+        KeyValue::new(
+            opentelemetry_semantic_conventions::resource::SERVICE_NAME,
+            service_name.into()
+        ),
+        KeyValue::new(
+            opentelemetry_semantic_conventions::resource::SERVICE_INSTANCE_ID,
+            service_instance_id.into()
+        ),
+    ])
+}
+
+static PROMETHEUS_REGISTRY: OnceLock<PrometheusRegistry> = OnceLock::new();
+
+/// Returns the registry, from which Prometheus scrapes the metrics, if exposing them for Prometheus is enabled.
+pub fn prometheus_registry() -> Option<PrometheusRegistry> {
+    PROMETHEUS_REGISTRY.get().cloned()
+}
+
+/// Registry of all metrics, which are exposed for scraping by Prometheus.
+/// Holds one Prometheus registry per meter provider, since each exporter has to be registered separately.
+#[derive(Clone, Default)]
+pub struct PrometheusRegistry {
+    registries: Arc<std::sync::Mutex<Vec<prometheus::Registry>>>,
+}
+
+impl PrometheusRegistry {
+    /// Value of the `Content-Type` header for the text-exposition format.
+    pub const CONTENT_TYPE: &'static str = prometheus::TEXT_FORMAT;
+
+    /// Creates an exporter, which collects the metrics of a meter provider into this registry.
+    /// Only one exporter of a registry should report the `target_info` and `otel_scope_info` metrics, since they would be duplicates otherwise.
+    pub fn exporter(&self, with_info: bool) -> Result<PrometheusExporter, MetricsError> {
+        let registry = prometheus::Registry::new();
+
+        let exporter = opentelemetry_prometheus::exporter()
+            .with_registry(Clone::clone(&registry));
+
+        let exporter = if with_info {
+            exporter
+        } else {
+            exporter
+                .without_target_info()
+                .without_scope_info()
+        };
+        let exporter = exporter.build()?;
+
+        self.registries.lock().expect("Lock of Prometheus registries should not be poisoned.")
+            .push(registry);
+
+        Ok(exporter)
+    }
+
+    pub(crate) fn install(self) {
+        if PROMETHEUS_REGISTRY.set(self).is_err() {
+            warn!("Registry for Prometheus metrics was already initialized. Ignoring new registry.");
+        }
+    }
+
+    /// Renders the current values of all metrics in the Prometheus text-exposition format.
+    pub fn render(&self) -> Result<String, prometheus::Error> {
+        let metric_families = self.registries.lock().expect("Lock of Prometheus registries should not be poisoned.")
+            .iter()
+            .flat_map(|registry| registry.gather())
+            .collect::<Vec<_>>();
+
+        TextEncoder::new().encode_to_string(&metric_families)
+    }
 }
 
 pub fn initialize_metrics_collection(
-    cpu_collection_interval_ms: Duration,
     meter_providers: &NamedMeterProviders,
 ) {
     let (default_meter_provider, cpu_meter_provider) = meter_providers;
+    let cpu_collection_interval_ms = cpu_meter_provider.kind.collection_interval;
     let default_meter = default_meter_provider.meter_provider.meter(DEFAULT_METER_NAME);
 
     let process_ram_used = default_meter.u64_observable_gauge("process_ram_used").init();
@@ -94,5 +182,7 @@ pub type NamedMeterProviders = (NamedMeterProvider<NamedMeterProviderKindDefault
 pub trait NamedMeterProviderKind {}
 pub struct NamedMeterProviderKindDefault;
 impl NamedMeterProviderKind for NamedMeterProviderKindDefault {}
-pub struct NamedMeterProviderKindCpu;
+pub struct NamedMeterProviderKindCpu {
+    pub collection_interval: Duration,
+}
 impl NamedMeterProviderKind for NamedMeterProviderKindCpu {}
@@ -14,6 +14,7 @@ use opentelemetry::trace::TraceError;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_sdk::logs::Logger;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::Resource;
 use tokio::sync::Mutex;
 use tracing::error;
 use tracing_subscriber::filter::Directive;
@@ -23,8 +24,8 @@ use tracing_subscriber::util::SubscriberInitExt;
 use opendut_auth::confidential::blocking::client::{AuthError, ConfClientArcMutex};
 use opendut_auth::confidential::error::ConfidentialClientError;
 use crate::telemetry::logging::{LoggingConfig, LoggingConfigError};
-use crate::telemetry::metrics::{NamedMeterProvider, NamedMeterProviderKindCpu, NamedMeterProviderKindDefault, NamedMeterProviders};
-use crate::telemetry::opentelemetry_types::{Opentelemetry, OpentelemetryConfigError};
+use crate::telemetry::metrics::{NamedMeterProvider, NamedMeterProviderKindCpu, NamedMeterProviderKindDefault, NamedMeterProviders, OtlpMetricsExport, PrometheusRegistry};
+use crate::telemetry::opentelemetry_types::{Opentelemetry, OpentelemetryConfigError, Prometheus};
 
 pub const DEFAULT_METER_NAME: &str = "opendut_meter";
 
@@ -53,6 +54,7 @@ pub enum Error {
 pub async fn initialize_with_config(
     logging_config: LoggingConfig,
     opentelemetry_config: Opentelemetry,
+    prometheus_config: Prometheus,
 ) -> Result<ShutdownHandle, Error> {
 
     global::set_text_map_propagator(TraceContextPropagator::new());
@@ -86,54 +88,74 @@ pub async fn initialize_with_config(
             None
         };
 
-    let (tracer, logger, logger_layer, meter_providers) =
+    let (tracer, logger, logger_layer, otlp_metrics_export, resource) =
         if let Opentelemetry::Enabled {
             collector_endpoint,
             service_name, 
             service_instance_id, 
             metrics_interval_ms, 
-            cpu_collection_interval_ms, 
-            confidential_client, ..} = opentelemetry_config {
+            confidential_client, ..} = &opentelemetry_config {
 
-            let confidential_client = ConfClientArcMutex(Arc::new(Mutex::new(confidential_client)));
+            let confidential_client = ConfClientArcMutex(Arc::new(Mutex::new(Clone::clone(confidential_client))));
 
-            let tracer = traces::init_tracer(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize tracer.");
+            let tracer = traces::init_tracer(confidential_client.clone(), collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize tracer.");
             
-            let logger = logging::init_logger(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize logs.");
+            let logger = logging::init_logger(confidential_client.clone(), collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize logs.");
 
             let logger_provider: GlobalLoggerProvider = logger_provider();
             let logger_layer = OpenTelemetryTracingBridge::new(&logger_provider);
 
-            let default_meter_provider = NamedMeterProvider {
-                kind: NamedMeterProviderKindDefault,
-                meter_provider: metrics::init_metrics(
-                    confidential_client.clone(),
-                    &collector_endpoint,
-                    service_name.clone(),
-                    service_instance_id.clone(),
-                    metrics_interval_ms
-                ).expect("Failed to initialize default metrics.")
+            let otlp_metrics_export = OtlpMetricsExport {
+                telemetry_interceptor: confidential_client,
+                endpoint: Clone::clone(collector_endpoint),
+                interval: *metrics_interval_ms,
             };
+            let resource = metrics::service_resource(service_name.clone(), service_instance_id.clone());
 
-            let cpu_meter_provider = NamedMeterProvider {
-                kind: NamedMeterProviderKindCpu,
-                meter_provider: metrics::init_metrics(
-                    confidential_client,
-                    &collector_endpoint,
-                    service_name,
-                    service_instance_id,
-                    cpu_collection_interval_ms
-                ).expect("Failed to initialize CPU metrics.")
-            };
+            (Some(tracer), Some(logger), Some(logger_layer), Some(otlp_metrics_export), resource)
+    } else {
+        (None, None, None, None, Resource::default())
+    };
 
-            global::set_meter_provider(default_meter_provider.meter_provider.clone());
-            let meter_providers: NamedMeterProviders = (default_meter_provider, cpu_meter_provider);
+    let prometheus_registry = match prometheus_config {
+        Prometheus::Enabled { .. } => Some(PrometheusRegistry::default()),
+        Prometheus::Disabled => None,
+    };
 
-            (Some(tracer), Some(logger), Some(logger_layer), Some(meter_providers))
-    } else {
-        (None, None, None, None)
+    let cpu_collection_interval = match (&opentelemetry_config, &prometheus_config) {
+        (Opentelemetry::Enabled { cpu_collection_interval_ms, .. }, _)
+        | (Opentelemetry::Disabled, Prometheus::Enabled { cpu_collection_interval_ms }) => Some(*cpu_collection_interval_ms),
+        (Opentelemetry::Disabled, Prometheus::Disabled) => None,
     };
 
+    let meter_providers = cpu_collection_interval.map(|cpu_collection_interval| {
+        let default_meter_provider = NamedMeterProvider {
+            kind: NamedMeterProviderKindDefault,
+            meter_provider: metrics::init_metrics(
+                Clone::clone(&otlp_metrics_export),
+                prometheus_registry.as_ref().map(|registry| registry.exporter(true)).transpose().expect("Failed to initialize Prometheus exporter for default metrics."),
+                Clone::clone(&resource),
+            ).expect("Failed to initialize default metrics.")
+        };
+
+        let cpu_meter_provider = NamedMeterProvider {
+            kind: NamedMeterProviderKindCpu { collection_interval: cpu_collection_interval },
+            meter_provider: metrics::init_metrics(
+                otlp_metrics_export.map(|export| OtlpMetricsExport { interval: cpu_collection_interval, ..export }),
+                prometheus_registry.as_ref().map(|registry| registry.exporter(false)).transpose().expect("Failed to initialize Prometheus exporter for CPU metrics."),
+                resource,
+            ).expect("Failed to initialize CPU metrics.")
+        };
+
+        global::set_meter_provider(default_meter_provider.meter_provider.clone());
+        let meter_providers: NamedMeterProviders = (default_meter_provider, cpu_meter_provider);
+        meter_providers
+    });
+
+    if let Some(prometheus_registry) = prometheus_registry {
+        prometheus_registry.install();
+    }
+
     tracing_subscriber::registry()
         .with(stdout_logging_layer)
         .with(tracing_filter)
@@ -146,7 +168,7 @@ pub async fn initialize_with_config(
 }
 
 pub async fn initialize_with_defaults() -> Result<ShutdownHandle, Error> {
-    initialize_with_config(LoggingConfig::default(), Opentelemetry::Disabled).await
+    initialize_with_config(LoggingConfig::default(), Opentelemetry::Disabled, Prometheus::Disabled).await
 }
 
 #[must_use]
@@ -68,33 +68,8 @@ impl Opentelemetry {
                 Duration::from_millis(interval_u64)
             };
 
-            let cpu_collection_interval_ms = {
-                let field = String::from("opentelemetry.metrics.cpu.collection.interval.ms");
+            let cpu_collection_interval_ms = load_cpu_collection_interval(config)?;
 
-                let interval_i64 = config.get_int(&field)
-                    .map_err(|cause| OpentelemetryConfigError::ValueParseError {
-                        field: field.clone(),
-                        cause: format!("{:?}", cause)
-                    })?;
-
-                let interval_u64 = u64::try_from(interval_i64)
-                    .map_err(|cause| OpentelemetryConfigError::ValueParseError {
-                        field: field.clone(),
-                        cause: format!("{:?}", cause)
-                    })?;
-                let interval = Duration::from_millis(interval_u64);
-
-                if interval < sysinfo::MINIMUM_CPU_UPDATE_INTERVAL {
-                    return Err(OpentelemetryConfigError::InvalidValueError {
-                        field,
-                        message: format!(
-                            "Provided configuration value needs to be higher than the minimum CPU update interval of {} ms.",
-                            sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.as_millis()
-                        )
-                    });
-                }
-                interval
-            };
             let confidential_client = ConfidentialClient::from_settings(config).await
                 .map_err(|cause| OpentelemetryConfigError::ConfidentialClientError{
                     message: String::from("Could not create AuthenticationManager"),
@@ -115,6 +90,62 @@ impl Opentelemetry {
     }
 }
 
+/// Exposes the metrics in the Prometheus text-exposition format for scraping, independent of whether they are pushed to a collector.
+#[derive(Default, Clone)]
+pub enum Prometheus {
+    Enabled {
+        cpu_collection_interval_ms: Duration,
+    },
+    #[default]
+    Disabled,
+}
+
+impl Prometheus {
+    pub fn load(config: &config::Config) -> Result<Self, OpentelemetryConfigError> {
+        let field = String::from("opentelemetry.metrics.prometheus.enabled");
+        let prometheus_enabled = config.get_bool(&field)
+            .map_err(|cause| OpentelemetryConfigError::ValueParseError {
+                field,
+                cause: format!("{:?}", cause)
+            })?;
+
+        if prometheus_enabled {
+            let cpu_collection_interval_ms = load_cpu_collection_interval(config)?;
+            Ok(Prometheus::Enabled { cpu_collection_interval_ms })
+        } else {
+            Ok(Prometheus::Disabled)
+        }
+    }
+}
+
+fn load_cpu_collection_interval(config: &config::Config) -> Result<Duration, OpentelemetryConfigError> {
+    let field = String::from("opentelemetry.metrics.cpu.collection.interval.ms");
+
+    let interval_i64 = config.get_int(&field)
+        .map_err(|cause| OpentelemetryConfigError::ValueParseError {
+            field: field.clone(),
+            cause: format!("{:?}", cause)
+        })?;
+
+    let interval_u64 = u64::try_from(interval_i64)
+        .map_err(|cause| OpentelemetryConfigError::ValueParseError {
+            field: field.clone(),
+            cause: format!("{:?}", cause)
+        })?;
+    let interval = Duration::from_millis(interval_u64);
+
+    if interval < sysinfo::MINIMUM_CPU_UPDATE_INTERVAL {
+        return Err(OpentelemetryConfigError::InvalidValueError {
+            field,
+            message: format!(
+                "Provided configuration value needs to be higher than the minimum CPU update interval of {} ms.",
+                sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.as_millis()
+            )
+        });
+    }
+    Ok(interval)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum OpentelemetryConfigError {
     #[error("Failed to parse configuration from field: '{field}'. Cause: {cause}")]
@@ -21,6 +21,7 @@ mod tests;
 pub trait Client {
     async fn create_netbird_group(&self, name: netbird::GroupName, peers: Vec<netbird::PeerId>) -> Result<netbird::Group, RequestError>;
     async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> Result<netbird::Group, GetGroupError>;
+    async fn update_netbird_group(&self, group: netbird::Group, peers: Vec<netbird::PeerId>) -> Result<netbird::Group, RequestError>;
     async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> Result<(), RequestError>;
     #[allow(unused)] //Currently unused, but expected to be needed again
     async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> Result<netbird::Peer, RequestError>;
@@ -112,7 +113,7 @@ impl Client for DefaultClient {
             }
         };
 
-        let request = post_json_request(url, body)?;
+        let request = json_request(Method::POST, url, body)?;
 
         let response = self.requester.handle(request).await?
             .error_for_status().map_err(RequestError::IllegalStatus)?;
@@ -145,6 +146,35 @@ impl Client for DefaultClient {
         }
     }
 
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn update_netbird_group(&self, group: netbird::Group, peers: Vec<netbird::PeerId>) -> Result<netbird::Group, RequestError> {
+
+        let url = routes::group(Clone::clone(&self.netbird_url), &group.id);
+
+        let body = {
+            #[derive(Serialize)]
+            struct UpdateGroup {
+                name: netbird::GroupName,
+                peers: Vec<netbird::PeerId>,
+            }
+
+            UpdateGroup {
+                name: group.name,
+                peers,
+            }
+        };
+
+        let request = json_request(Method::PUT, url, body)?;
+
+        let response = self.requester.handle(request).await?
+            .error_for_status().map_err(RequestError::IllegalStatus)?;
+
+        let result = response.json().await
+            .map_err(RequestError::JsonDeserialization)?;
+
+        Ok(result)
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> Result<(), RequestError> {
         let url = routes::group(Clone::clone(&self.netbird_url), group_id);
@@ -211,7 +241,7 @@ impl Client for DefaultClient {
             }
         };
 
-        let request = post_json_request(url, body)?;
+        let request = json_request(Method::POST, url, body)?;
 
         let response = self.requester.handle(request).await?;
         response.error_for_status()
@@ -282,7 +312,7 @@ impl Client for DefaultClient {
             }
         };
 
-        let request = post_json_request(url, body)
+        let request = json_request(Method::POST, url, body)
             .map_err(|cause| CreateSetupKeyError::RequestFailure { peer_id, cause })?;
 
         let response = self.requester.handle(request).await
@@ -296,8 +326,8 @@ impl Client for DefaultClient {
     }
 }
 
-fn post_json_request(url: Url, body: impl Serialize) -> Result<Request, RequestError> {
-    let mut request = Request::new(Method::POST, url);
+fn json_request(method: Method, url: Url, body: impl Serialize) -> Result<Request, RequestError> {
+    let mut request = Request::new(method, url);
 
     request.headers_mut()
         .insert(header::CONTENT_TYPE, DefaultClient::APPLICATION_JSON.parse().unwrap());
@@ -102,6 +102,75 @@ async fn create_group(fixture: Fixture) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[tokio::test]
+async fn update_group(fixture: Fixture) -> anyhow::Result<()> {
+    let requester = fixture.requester(|fixture, request| {
+        assert_that!(request.method(), eq(&Method::PUT));
+        assert_that!(request.url().path(), eq("/api/groups/ch8i4ug6lnn4g9hqv7m0"));
+
+        let request = request.body().unwrap().as_bytes().unwrap();
+        let request: serde_json::Value = serde_json::from_slice(request).unwrap();
+
+        let expectation = json!({
+            "name": fixture.cluster_netbird_group_name(),
+            "peers": [
+                fixture.netbird_peer_id(),
+            ]
+        });
+
+        assert_that!(request, eq(expectation));
+
+        let response = http::Response::builder()
+            .body(
+                json!({
+                    "id": "ch8i4ug6lnn4g9hqv7m0",
+                    "name": fixture.cluster_netbird_group_name(),
+                    "peers_count": 1,
+                    "issued": "api",
+                    "peers": [
+                        {
+                            "id": "chacbco6lnnbn6cg5s90",
+                            "name": "stage-host-1",
+                        }
+                    ]
+                }).to_string()
+            ).unwrap();
+
+        Ok(Response::from(response))
+    });
+
+    let client = DefaultClient::create(fixture.base_url(), None, None, Some(Box::new(requester)), TIMEOUT, RETRIES)?;
+
+    let group = netbird::Group {
+        id: fixture.netbird_group_id(),
+        name: fixture.cluster_netbird_group_name(),
+        peers_count: 0,
+        peers: vec![],
+    };
+    let result = client.update_netbird_group(group, vec![fixture.netbird_peer_id()]).await?;
+
+    assert_that!(
+        result,
+        matches_pattern!(
+            netbird::Group {
+                id: eq(fixture.netbird_group_id()),
+                name: eq(fixture.cluster_netbird_group_name()),
+                peers_count: eq(1),
+                peers: elements_are!(
+                    matches_pattern!(
+                        netbird::GroupPeerInfo {
+                            id: eq(fixture.netbird_peer_id()),
+                            name: anything()
+                        }
+                    )
+                ),
+            }
+        )
+    );
+    Ok(())
+}
+
 #[rstest]
 #[tokio::test]
 async fn find_group(fixture: Fixture) -> anyhow::Result<()> {
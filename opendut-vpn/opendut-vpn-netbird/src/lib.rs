@@ -12,7 +12,7 @@ pub use netbird::Token as NetbirdToken;
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerId;
 use opendut_types::vpn::VpnPeerConfiguration;
-use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, UpdateClusterError, VpnManagementClient};
 
 use crate::client::{Client, DefaultClient};
 use crate::netbird::error::{CreateClientError, CreateSetupKeyError, GetGroupError, GetRulesError, RequestError};
@@ -66,6 +66,22 @@ impl NetbirdManagementClient {
     }
 }
 
+impl NetbirdManagementClient {
+
+    /// Looks up the NetBird peers in the self-groups of the given peers.
+    async fn resolve_netbird_peers(&self, peers: &[PeerId]) -> Result<Vec<netbird::PeerId>, (PeerId, Box<dyn std::error::Error>)> {
+        let mut netbird_peers = vec![];
+        for peer_id in peers {
+            let group = self.inner.get_netbird_group(&(*peer_id).into()).await
+                .map_err(|error| (*peer_id, error.into()))?;
+            let peer = group.peers.into_iter().next()
+                .ok_or((*peer_id, anyhow!("Self-Group does not contain expected peer!").into()))?;
+            netbird_peers.push(peer.id);
+        }
+        Ok(netbird_peers)
+    }
+}
+
 #[async_trait]
 impl VpnManagementClient for NetbirdManagementClient {
 
@@ -82,17 +98,8 @@ impl VpnManagementClient for NetbirdManagementClient {
             }
         };
 
-        let netbird_peers: Vec<netbird::PeerId> = {
-            let mut netbird_peers = vec![];
-            for peer_id in peers {
-                let group = self.inner.get_netbird_group(&(*peer_id).into()).await
-                    .map_err(|error| CreateClusterError::PeerResolutionFailure { peer_id: *peer_id, cluster_id, error: error.into() })?;
-                let peer = group.peers.into_iter().next()
-                    .ok_or(CreateClusterError::PeerResolutionFailure { peer_id: *peer_id, cluster_id, error: anyhow!("Self-Group does not contain expected peer!").into() })?;
-                netbird_peers.push(peer.id);
-            }
-            netbird_peers
-        };
+        let netbird_peers = self.resolve_netbird_peers(peers).await
+            .map_err(|(peer_id, error)| CreateClusterError::PeerResolutionFailure { peer_id, cluster_id, error })?;
 
         let group = self.inner.create_netbird_group(cluster_id.into(), netbird_peers).await
             .map_err(|error| CreateClusterError::CreationFailure { cluster_id, error: error.into() })?;
@@ -103,6 +110,21 @@ impl VpnManagementClient for NetbirdManagementClient {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn update_cluster(&self, cluster_id: ClusterId, peers: &[PeerId]) -> Result<(), UpdateClusterError> {
+
+        let group = self.inner.get_netbird_group(&cluster_id.into()).await
+            .map_err(|error| UpdateClusterError::UpdateFailure { cluster_id, error: error.into() })?;
+
+        let netbird_peers = self.resolve_netbird_peers(peers).await
+            .map_err(|(peer_id, error)| UpdateClusterError::PeerResolutionFailure { peer_id, cluster_id, error })?;
+
+        self.inner.update_netbird_group(group, netbird_peers).await
+            .map_err(|error| UpdateClusterError::UpdateFailure { cluster_id, error: error.into() })?;
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     async fn delete_cluster(&self, cluster_id: ClusterId) -> Result<(), DeleteClusterError> {
         let rule_name = netbird::RuleName::Cluster(cluster_id);
@@ -350,6 +372,60 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_should_update_a_cluster_by_replacing_the_peers_of_its_netbird_group() -> Result<()> {
+
+        let cluster_id = ClusterId::from(uuid!("6a6510a9-031b-4834-a4f7-454cc401fe13"));
+        let peer_a_id = PeerId::from(uuid!("d61bed7b-2fec-4a5b-a937-d6a791cb5ff9"));
+        let peer_a_group_name = netbird::GroupName::from(peer_a_id);
+        let cluster_group_name = netbird::GroupName::from(cluster_id);
+        let peer_a_group = netbird::Group {
+            id: netbird::GroupId::from("peer-a-group"),
+            name: Clone::clone(&peer_a_group_name),
+            peers_count: 0,
+            peers: vec![GroupPeerInfo { id: netbird::PeerId::from("peer-a"), name: String::from("peer-a")}],
+        };
+        let cluster_group = netbird::Group {
+            id: netbird::GroupId::from("cluster-group"),
+            name: Clone::clone(&cluster_group_name),
+            peers_count: 0,
+            peers: vec![GroupPeerInfo { id: netbird::PeerId::from("peer-b"), name: String::from("peer-b")}],
+        };
+
+        let fixture = Fixture::setup(|mock_client| {
+            mock_client.expect_get_netbird_group()
+                .returning({
+                    let cluster_group = Clone::clone(&cluster_group);
+                    move |group_name| {
+                        if group_name == &peer_a_group_name {
+                            Ok(Clone::clone(&peer_a_group))
+                        }
+                        else if group_name == &cluster_group_name {
+                            Ok(Clone::clone(&cluster_group))
+                        }
+                        else {
+                            Err(GetGroupError::GroupNotFound { group_name: group_name.to_owned() })
+                        }
+                    }
+                });
+            mock_client.expect_update_netbird_group()
+                .times(1)
+                .withf({
+                    let cluster_group = Clone::clone(&cluster_group);
+                    move |actual_group, actual_peers| {
+                        actual_group == &cluster_group && actual_peers == &vec![netbird::PeerId::from("peer-a")]
+                    }
+                })
+                .returning(move |group, _| Ok(group));
+            mock_client.expect_delete_netbird_group().never();
+            mock_client.expect_create_netbird_group().never();
+        });
+
+        assert_that!(fixture.testee.update_cluster(cluster_id, &[peer_a_id]).await, ok(anything()));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn A_NetbirdManagementClient_should_delete_the_peer_when_creating_a_peer_configuration() -> Result<()> {
 
@@ -450,6 +526,7 @@ mod test {
         impl Client for MockClient {
             async fn create_netbird_group(&self, name: netbird::GroupName, peers: Vec<netbird::PeerId>) -> std::result::Result<netbird::Group, RequestError>;
             async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> std::result::Result<netbird::Group, GetGroupError>;
+            async fn update_netbird_group(&self, group: netbird::Group, peers: Vec<netbird::PeerId>) -> std::result::Result<netbird::Group, RequestError>;
             async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> std::result::Result<(), RequestError>;
             async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> std::result::Result<netbird::Peer, RequestError>;
             async fn delete_netbird_peer(&self, peer_id: &netbird::PeerId) -> std::result::Result<(), RequestError>;
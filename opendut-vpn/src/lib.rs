@@ -9,6 +9,9 @@ pub trait VpnManagementClient {
 
     async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId]) -> Result<(), CreateClusterError>;
 
+    /// Replaces the peers of an existing cluster. Peers, which stay in the cluster, keep their connections.
+    async fn update_cluster(&self, cluster_id: ClusterId, peers: &[PeerId]) -> Result<(), UpdateClusterError>;
+
     async fn delete_cluster(&self, cluster_id: ClusterId) -> Result<(), DeleteClusterError>;
 
     async fn create_peer(&self, peer_id: PeerId) -> Result<(), CreatePeerError>;
@@ -38,6 +41,21 @@ pub enum CreateClusterError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum UpdateClusterError {
+    #[error("Peer <{peer_id}> of cluster <{cluster_id}> could not be resolved:\n  {error}")]
+    PeerResolutionFailure {
+        peer_id: PeerId,
+        cluster_id: ClusterId,
+        error: Box<dyn std::error::Error>,
+    },
+    #[error("An error occurred while updating cluster <{cluster_id}>:\n  {error}")]
+    UpdateFailure {
+        cluster_id: ClusterId,
+        error: Box<dyn std::error::Error>
+    },
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum DeleteClusterError {
     #[error("No cluster <{cluster_id}> could be found: {message}")]
@@ -1,10 +1,15 @@
 use std::time::Duration;
 
+use assert_fs::TempDir;
 use config::Config;
 use googletest::prelude::*;
 use tracing::info;
 
-use opendut_types::peer::PeerId;
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerNetworkDescriptor};
+use opendut_types::peer::executor::ExecutorDescriptors;
+use opendut_types::project::ProjectName;
+use opendut_types::topology::Topology;
+use opendut_edgar::common::client_certificate::ClientCertificateFiles;
 use opendut_util::telemetry;
 
 use crate::util;
@@ -28,32 +33,57 @@ async fn register_edgar_carl() -> Result<()> {
         .set_override("network.tls.certificate", "resources/development/tls/insecure-development-carl.pem")?
         .set_override("network.tls.key", "resources/development/tls/insecure-development-carl.key")?
         .build()?;
-    let carl_settings = opendut_carl::settings::load_with_overrides(carl_config_override)?;
     let _ = tokio::spawn(async {
-        opendut_carl::create(carl_settings).await
+        opendut_carl::create(carl_config_override).await
             .expect("CARL crashed")
     });
 
     let peer_id = PeerId::random();
-    let settings_overrides = Config::builder()
+    let settings_overrides = || Config::builder()
         .set_override(opendut_edgar::common::settings::key::peer::id, peer_id.to_string())?
         .set_override("network.carl.host", "localhost")?
         .set_override("network.carl.port", carl_port)?
         .set_override("network.connect.retries", 100)?
-        .set_override("network.oidc.enabled", false)?
-        .build()?;
-    let edgar_config = opendut_edgar::common::settings::load_with_overrides(settings_overrides).unwrap();
+        .set_override("network.oidc.enabled", false);
+    let assert_channel_config = opendut_edgar::common::settings::load_with_overrides(settings_overrides()?.build()?).unwrap();
+
+    let mut carl_client = opendut_edgar::common::carl::connect(&assert_channel_config.config).await
+        .expect("Failed to connect to CARL for state checks");
 
-    let assert_channel_config = edgar_config.clone();
+    carl_client.peers.store_peer_descriptor(PeerDescriptor {
+        id: peer_id,
+        name: PeerName::try_from("RegisterTestPeer")?,
+        location: None,
+        network: PeerNetworkDescriptor {
+            interfaces: vec![],
+            bridge_name: None,
+        },
+        topology: Topology { devices: vec![] },
+        executors: ExecutorDescriptors { executors: vec![] },
+        project: ProjectName::default(),
+    }).await?;
+    let peer_setup = carl_client.peers.create_peer_setup(peer_id, String::from("register-test")).await?;
+
+    let temp = TempDir::new()?;
+    let client_certificate_files = ClientCertificateFiles {
+        certificate: temp.path().join("edgar-client.pem"),
+        key: temp.path().join("edgar-client.key"),
+    };
+    client_certificate_files.write(&peer_setup.certificate).unwrap();
+
+    let edgar_config = opendut_edgar::common::settings::load_with_overrides(
+        settings_overrides()?
+            .set_override(opendut_edgar::common::settings::key::peer::setup_nonce, peer_setup.nonce.to_string())?
+            .set_override(opendut_edgar::common::settings::key::network::tls::client::certificate, client_certificate_files.certificate.to_string_lossy().to_string())?
+            .set_override(opendut_edgar::common::settings::key::network::tls::client::key, client_certificate_files.key.to_string_lossy().to_string())?
+            .build()?
+    ).unwrap();
 
     let _ = tokio::spawn(async move {
         opendut_edgar::service::start::create(peer_id, edgar_config).await
             .expect("EDGAR crashed")
     });
 
-    let mut carl_client = opendut_edgar::common::carl::connect(&assert_channel_config.config).await
-        .expect("Failed to connect to CARL for state checks");
-
     let retries = 5;
     let interval = Duration::from_millis(500);
     for retries_left in (0..retries).rev() {